@@ -1,7 +1,9 @@
 // Criterion.rs Benchmark: 기본 DB CRUD 성능 측정
 // Task 1.2: SQLite 쿼리 벤치마킹 (Phase 1, Week 1-2)
+// 연결 풀: 호출마다 연결 생성 vs 공유 풀, 동시 조회 (읽기 전용 연결 수별)
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use judgify_desktop::database::{Database, PoolConfig};
 use rusqlite::Connection;
 use uuid::Uuid;
 use chrono::Utc;
@@ -137,10 +139,69 @@ fn bench_save_judgment(c: &mut Criterion) {
     });
 }
 
+/// 서비스마다 Database::open (연결 + 스키마/시드/마이그레이션 확인) vs 공유 연결 풀
+fn bench_open_per_call_vs_shared_pool(c: &mut Criterion) {
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("bench.db");
+    let shared = Database::open(db_path.clone()).expect("Failed to open database");
+
+    let mut group = c.benchmark_group("db_connection");
+    group.sample_size(20);
+
+    group.bench_function("open_per_call", |b| {
+        b.iter(|| {
+            let db = Database::open(db_path.clone()).expect("Failed to open database");
+            black_box(db.get_all_workflows().expect("Failed to query workflows"));
+        })
+    });
+
+    group.bench_function("shared_pool", |b| {
+        b.iter(|| {
+            let db = shared.clone();
+            black_box(db.get_all_workflows().expect("Failed to query workflows"));
+        })
+    });
+
+    group.finish();
+}
+
+/// 4개 스레드 동시 조회: 단일 연결(읽기 연결 0개) vs 읽기 전용 연결 4개
+fn bench_concurrent_reads(c: &mut Criterion) {
+    let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+    let db_path = temp_dir.path().join("bench.db");
+    drop(Database::open(db_path.clone()).expect("Failed to open database"));
+
+    let mut group = c.benchmark_group("db_concurrent_reads");
+    group.sample_size(20);
+
+    for readers in [0, 4] {
+        let config = PoolConfig { readers, ..PoolConfig::default() };
+        let db = Database::open_with_config(db_path.clone(), config).expect("Failed to open database");
+
+        group.bench_with_input(BenchmarkId::new("readers", readers), &db, |b, db| {
+            b.iter(|| {
+                std::thread::scope(|scope| {
+                    for _ in 0..4 {
+                        scope.spawn(|| {
+                            for _ in 0..25 {
+                                black_box(db.get_judgment_history(None, 100).expect("Failed to query judgments"));
+                            }
+                        });
+                    }
+                });
+            })
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     db_benches,
     bench_save_workflow,
     bench_get_workflow,
-    bench_save_judgment
+    bench_save_judgment,
+    bench_open_per_call_vs_shared_pool,
+    bench_concurrent_reads
 );
 criterion_main!(db_benches);
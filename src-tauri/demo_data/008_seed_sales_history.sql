@@ -625,55 +625,3 @@ INSERT INTO sales_order_dtl (so_no, seq, item_cd, qty, unit_price, amount, alloc
 ('SO-2024-08-008', 1, 'FG-005', 3000, 5880, 101430000, 3000, 17250),
 ('SO-2024-08-008', 2, 'FG-006', 3000, 5880, 101430000, 3000, 17250),
 ('SO-2024-08-008', 3, 'FG-001', 2000, 5880, 67620000, 2000, 11500);
-
--- ========================================
--- 월별 판매 집계 VIEW (시연용 차트 데이터)
--- ========================================
-
-CREATE VIEW IF NOT EXISTS v_monthly_sales AS
-SELECT
-    strftime('%Y-%m', s.order_date) as year_month,
-    c.cust_nm as customer_name,
-    i.item_nm as product_name,
-    i.item_cd as item_cd,
-    SUM(d.qty) as total_qty,
-    SUM(d.amount) as total_amount
-FROM sales_order s
-JOIN sales_order_dtl d ON s.so_no = d.so_no
-JOIN customer_mst c ON s.cust_cd = c.cust_cd
-JOIN item_mst i ON d.item_cd = i.item_cd
-WHERE s.status IN ('COMPLETED', 'SHIPPED')
-GROUP BY strftime('%Y-%m', s.order_date), c.cust_nm, i.item_nm, i.item_cd
-ORDER BY year_month DESC, total_amount DESC;
-
--- 품목별 월간 판매 집계 VIEW
-CREATE VIEW IF NOT EXISTS v_product_monthly_sales AS
-SELECT
-    strftime('%Y-%m', s.order_date) as year_month,
-    i.item_cd,
-    i.item_nm as product_name,
-    SUM(d.qty) as total_qty,
-    SUM(d.amount) as total_amount,
-    COUNT(DISTINCT s.so_no) as order_count
-FROM sales_order s
-JOIN sales_order_dtl d ON s.so_no = d.so_no
-JOIN item_mst i ON d.item_cd = i.item_cd
-WHERE s.status IN ('COMPLETED', 'SHIPPED')
-GROUP BY strftime('%Y-%m', s.order_date), i.item_cd, i.item_nm
-ORDER BY year_month DESC, total_amount DESC;
-
--- 고객별 판매 집계 VIEW
-CREATE VIEW IF NOT EXISTS v_customer_sales AS
-SELECT
-    c.cust_cd,
-    c.cust_nm as customer_name,
-    c.cust_type,
-    COUNT(DISTINCT s.so_no) as order_count,
-    SUM(d.qty) as total_qty,
-    SUM(d.amount) as total_amount
-FROM sales_order s
-JOIN sales_order_dtl d ON s.so_no = d.so_no
-JOIN customer_mst c ON s.cust_cd = c.cust_cd
-WHERE s.status IN ('COMPLETED', 'SHIPPED')
-GROUP BY c.cust_cd, c.cust_nm, c.cust_type
-ORDER BY total_amount DESC;
@@ -120,23 +120,3 @@ INSERT INTO sensor_log (equip_cd, param_cd, batch_lot_no, recorded_at, value, is
 -- 12월 5일 (오늘)
 ('EQ-MIX-01', 'PARAM-MIX-TEMP', 'BATCH-251205-001', '2025-12-05 08:00:00', 22.7, 0, NULL),
 ('EQ-MIX-01', 'PARAM-MIX-TEMP', 'BATCH-251205-001', '2025-12-05 09:00:00', 23.4, 0, NULL);
-
--- ========================================
--- 4. 일별 평균 온도 데이터 뷰 (편의용)
--- ========================================
--- 뷰 삭제 후 재생성 (존재하면)
-DROP VIEW IF EXISTS v_daily_avg_temperature;
-
-CREATE VIEW v_daily_avg_temperature AS
-SELECT
-    DATE(recorded_at) as date,
-    equip_cd,
-    param_cd,
-    ROUND(AVG(value), 2) as avg_temp,
-    ROUND(MIN(value), 2) as min_temp,
-    ROUND(MAX(value), 2) as max_temp,
-    COUNT(*) as reading_count
-FROM sensor_log
-WHERE param_cd LIKE '%TEMP%'
-GROUP BY DATE(recorded_at), equip_cd, param_cd
-ORDER BY date DESC;
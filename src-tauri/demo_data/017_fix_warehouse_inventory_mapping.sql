@@ -41,21 +41,3 @@ INSERT OR REPLACE INTO warehouse_mst (warehouse_id, warehouse_nm, warehouse_type
 ('FG-WH03', '완제품창고 C', 'FG', '물류센터 C구역', 0, 5),
 ('FG-WH04', '완제품창고 D', 'FG', '물류센터 D구역', 0, 5),
 ('FG-COLD01', '완제품 냉장창고', 'COLD', '물류센터 냉장구역', 0, 5);
-
--- ========================================
--- 확인용 뷰: 창고별 재고 현황
--- ========================================
-DROP VIEW IF EXISTS v_warehouse_inventory_summary;
-
-CREATE VIEW v_warehouse_inventory_summary AS
-SELECT
-    i.location as warehouse_id,
-    COALESCE(w.warehouse_nm, i.location) as warehouse_nm,
-    COALESCE(w.warehouse_type, 'UNKNOWN') as warehouse_type,
-    SUM(i.qty) as total_qty,
-    COUNT(DISTINCT i.item_cd) as item_count
-FROM inventory i
-LEFT JOIN warehouse_mst w ON i.location = w.warehouse_id
-GROUP BY i.location
-ORDER BY total_qty DESC;
-
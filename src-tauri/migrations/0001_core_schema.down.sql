@@ -0,0 +1,29 @@
+-- ========================================
+-- 0001_core_schema.down.sql
+-- 핵심 스키마 되돌리기 (생성 역순으로 삭제, 인덱스/트리거는 테이블과 함께 삭제됨)
+-- ========================================
+
+DROP TABLE IF EXISTS workflow_executions;
+DROP TABLE IF EXISTS workflow_schedules;
+DROP TABLE IF EXISTS approval_requests;
+DROP TABLE IF EXISTS mes_uploads;
+DROP TABLE IF EXISTS mes_data_logs_fts;
+DROP TABLE IF EXISTS mes_data_logs;
+DROP TABLE IF EXISTS lot_holds;
+DROP TABLE IF EXISTS ccp_corrective_action_checks;
+DROP TABLE IF EXISTS ccp_corrective_actions;
+DROP TABLE IF EXISTS ccp_risk_policies;
+DROP TABLE IF EXISTS ccp_judgments;
+DROP TABLE IF EXISTS ccp_sensors;
+DROP TABLE IF EXISTS ccp_doc_versions;
+DROP TABLE IF EXISTS ccp_docs_fts;
+DROP TABLE IF EXISTS ccp_docs;
+DROP TABLE IF EXISTS token_usage;
+DROP TABLE IF EXISTS prompt_ab_variants;
+DROP TABLE IF EXISTS prompt_templates;
+DROP TABLE IF EXISTS feedbacks;
+DROP TABLE IF EXISTS rule_evaluation_reports;
+DROP TABLE IF EXISTS training_sample_embeddings;
+DROP TABLE IF EXISTS training_samples;
+DROP TABLE IF EXISTS workflows;
+DROP TABLE IF EXISTS judgments;
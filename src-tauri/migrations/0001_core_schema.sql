@@ -0,0 +1,476 @@
+-- ========================================
+-- 0001_core_schema.sql
+-- 판단/워크플로우/학습/CCP/MES 업로드/승인/스케줄 핵심 스키마
+-- ========================================
+
+CREATE TABLE IF NOT EXISTS judgments (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    input_data TEXT NOT NULL,
+    result INTEGER NOT NULL,
+    confidence REAL NOT NULL,
+    method_used TEXT NOT NULL,
+    explanation TEXT,
+    created_at TEXT NOT NULL,
+    template_id TEXT,
+    template_version INTEGER,
+    latency_ms INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS workflows (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    definition TEXT NOT NULL,
+    rule_expression TEXT,
+    version INTEGER DEFAULT 1,
+    is_active INTEGER DEFAULT 1,
+    created_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS training_samples (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    input_data TEXT NOT NULL,
+    expected_result INTEGER NOT NULL,
+    actual_result INTEGER,
+    accuracy REAL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (workflow_id) REFERENCES workflows(id)
+);
+
+-- 훈련 샘플 임베딩 (유사도 기반 Few-shot 검색, 모델별 저장)
+CREATE TABLE IF NOT EXISTS training_sample_embeddings (
+    sample_id TEXT NOT NULL,
+    model TEXT NOT NULL,
+    workflow_id TEXT NOT NULL,
+    dimensions INTEGER NOT NULL,
+    embedding BLOB NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (sample_id, model),
+    FOREIGN KEY (sample_id) REFERENCES training_samples(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_training_embeddings_workflow
+  ON training_sample_embeddings(workflow_id, model);
+
+-- 추출 Rule 홀드아웃 평가 리포트 (승격 여부와 Rule 버전 함께 기록)
+CREATE TABLE IF NOT EXISTS rule_evaluation_reports (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    workflow_version INTEGER NOT NULL,
+    rule_expression TEXT,
+    promoted INTEGER NOT NULL,
+    f1_score REAL,
+    min_f1 REAL NOT NULL,
+    report_json TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (workflow_id) REFERENCES workflows(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rule_evaluation_reports_workflow
+  ON rule_evaluation_reports(workflow_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS feedbacks (
+    id TEXT PRIMARY KEY,
+    judgment_id TEXT NOT NULL,
+    feedback_type TEXT NOT NULL,
+    value INTEGER NOT NULL,
+    comment TEXT,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (judgment_id) REFERENCES judgments(id)
+);
+
+CREATE TABLE IF NOT EXISTS prompt_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    template_type TEXT NOT NULL,
+    content TEXT NOT NULL,
+    variables TEXT NOT NULL,
+    version INTEGER DEFAULT 1,
+    is_active INTEGER DEFAULT 1,
+    token_limit INTEGER,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- 프롬프트 A/B 테스트 변형 (template_type별 트래픽 가중치)
+CREATE TABLE IF NOT EXISTS prompt_ab_variants (
+    template_id TEXT PRIMARY KEY,
+    template_type TEXT NOT NULL,
+    weight REAL NOT NULL DEFAULT 1.0 CHECK(weight >= 0),
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (template_id) REFERENCES prompt_templates(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_prompt_ab_variants_type
+  ON prompt_ab_variants(template_type);
+
+CREATE TABLE IF NOT EXISTS token_usage (
+    id TEXT PRIMARY KEY,
+    judgment_id TEXT NOT NULL,
+    service TEXT NOT NULL,
+    tokens_used INTEGER NOT NULL,
+    cost_usd REAL NOT NULL,
+    complexity TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (judgment_id) REFERENCES judgments(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_judgments_workflow ON judgments(workflow_id);
+CREATE INDEX IF NOT EXISTS idx_judgments_created ON judgments(created_at);
+CREATE INDEX IF NOT EXISTS idx_training_workflow ON training_samples(workflow_id);
+
+-- Composite indexes for performance optimization (Task 2.2)
+-- 1. Judgment workflow + time index (Dashboard getJudgmentHistory optimization)
+CREATE INDEX IF NOT EXISTS idx_judgments_workflow_created
+  ON judgments(workflow_id, created_at DESC);
+
+-- 2. TrainingSample search index (Learning Service optimization)
+CREATE INDEX IF NOT EXISTS idx_training_workflow_accuracy
+  ON training_samples(workflow_id, accuracy DESC, created_at DESC);
+
+-- 3. Feedback + Judgment JOIN index (complex query optimization)
+CREATE INDEX IF NOT EXISTS idx_feedbacks_judgment_type
+  ON feedbacks(judgment_id, feedback_type, value);
+
+-- 4. Feedback covering index (optimized retrieval with all columns)
+CREATE INDEX IF NOT EXISTS idx_feedbacks_covering
+  ON feedbacks(judgment_id, feedback_type, value, created_at);
+
+-- 5. PromptTemplate type + active index (template selection optimization)
+CREATE INDEX IF NOT EXISTS idx_templates_type_active
+  ON prompt_templates(template_type, is_active, version DESC);
+
+-- 6. Token Usage indexes (MCP cost tracking optimization)
+CREATE INDEX IF NOT EXISTS idx_token_usage_created
+  ON token_usage(created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_token_usage_service_created
+  ON token_usage(service, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_token_usage_judgment
+  ON token_usage(judgment_id);
+
+-- ============================================================
+-- CCP 데모용 테이블 (RAG + 룰베이스 판단)
+-- ============================================================
+
+-- CCP 정책 문서 테이블
+CREATE TABLE IF NOT EXISTS ccp_docs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    company_id TEXT NOT NULL,
+    ccp_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    section_type TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    doc_version_id TEXT
+);
+
+-- FTS5 전문검색 인덱스 (BM25 알고리즘)
+CREATE VIRTUAL TABLE IF NOT EXISTS ccp_docs_fts
+USING fts5(title, content, tokenize='porter unicode61');
+
+CREATE INDEX IF NOT EXISTS idx_ccp_docs_company
+ON ccp_docs(company_id, ccp_id);
+
+-- FTS5 자동 동기화 트리거 (수동 rebuild_fts5_index 불필요)
+CREATE TRIGGER IF NOT EXISTS ccp_docs_ai AFTER INSERT ON ccp_docs
+BEGIN
+    INSERT INTO ccp_docs_fts(rowid, title, content) VALUES (new.id, new.title, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS ccp_docs_ad AFTER DELETE ON ccp_docs
+BEGIN
+    DELETE FROM ccp_docs_fts WHERE rowid = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS ccp_docs_au AFTER UPDATE ON ccp_docs
+BEGIN
+    UPDATE ccp_docs_fts SET title = new.title, content = new.content WHERE rowid = new.id;
+END;
+
+-- CCP 문서 개정 이력 (HACCP 계획서/SOP 버전 관리)
+-- effective_to가 NULL이면 현행 개정본
+CREATE TABLE IF NOT EXISTS ccp_doc_versions (
+    id TEXT PRIMARY KEY,
+    company_id TEXT NOT NULL,
+    ccp_id TEXT NOT NULL,
+    doc_key TEXT NOT NULL,
+    title TEXT NOT NULL,
+    revision INTEGER NOT NULL,
+    source_format TEXT NOT NULL CHECK(source_format IN ('markdown', 'text', 'csv')),
+    source_name TEXT,
+    checksum TEXT NOT NULL,
+    section_count INTEGER NOT NULL,
+    effective_from TEXT NOT NULL,
+    effective_to TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(company_id, ccp_id, doc_key, revision)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ccp_doc_versions_doc
+ON ccp_doc_versions(company_id, ccp_id, doc_key, revision DESC);
+
+-- CCP 센서 로그 테이블
+CREATE TABLE IF NOT EXISTS ccp_sensors (
+    log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    company_id TEXT NOT NULL,
+    ccp_id TEXT NOT NULL,
+    log_date TEXT NOT NULL,
+    measured_value REAL NOT NULL,
+    result TEXT NOT NULL CHECK(result IN ('OK', 'NG')),
+    operator_name TEXT,
+    action_taken TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_ccp_sensors_date
+ON ccp_sensors(company_id, ccp_id, log_date);
+
+-- CCP 판단 결과 테이블
+CREATE TABLE IF NOT EXISTS ccp_judgments (
+    id TEXT PRIMARY KEY,
+    company_id TEXT NOT NULL,
+    ccp_id TEXT NOT NULL,
+    period_from TEXT NOT NULL,
+    period_to TEXT NOT NULL,
+    total_logs INTEGER NOT NULL,
+    ng_count INTEGER NOT NULL,
+    ng_rate REAL NOT NULL,
+    avg_value REAL NOT NULL,
+    risk_level TEXT NOT NULL CHECK(risk_level IN ('LOW', 'MEDIUM', 'HIGH')),
+    rule_reason TEXT,
+    llm_summary TEXT,
+    evidence_docs TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_ccp_judgments_company
+ON ccp_judgments(company_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_ccp_judgments_ccp
+ON ccp_judgments(company_id, ccp_id, created_at DESC);
+
+-- CCP 한계기준/위험도 정책 (회사/CCP별, 없으면 기본 정책)
+CREATE TABLE IF NOT EXISTS ccp_risk_policies (
+    company_id TEXT NOT NULL,
+    ccp_id TEXT NOT NULL,
+    high_ng_rate REAL NOT NULL,
+    medium_ng_rate REAL NOT NULL,
+    max_consecutive_failures INTEGER,
+    breach_is_high INTEGER NOT NULL DEFAULT 1,
+    min_temp REAL,
+    max_temp REAL,
+    min_time_sec INTEGER,
+    max_time_sec INTEGER,
+    max_sensitivity_fe REAL,
+    max_sensitivity_sus REAL,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (company_id, ccp_id)
+);
+
+-- HACCP 시정조치 (CCP 점검 FAIL/DEVIATION 시 자동 발행, 검증 승인 후 종결)
+CREATE TABLE IF NOT EXISTS ccp_corrective_actions (
+    id TEXT PRIMARY KEY,
+    company_id TEXT NOT NULL,
+    ccp_id TEXT NOT NULL,
+    trigger_source TEXT NOT NULL CHECK(trigger_source IN ('judgment', 'check')),
+    trigger_ref TEXT NOT NULL,
+    trigger_result TEXT NOT NULL CHECK(trigger_result IN ('FAIL', 'DEVIATION')),
+    affected_lots TEXT NOT NULL,
+    deviation_summary TEXT NOT NULL,
+    hold_decision TEXT NOT NULL DEFAULT 'PENDING'
+        CHECK(hold_decision IN ('PENDING', 'HOLD', 'RELEASE', 'REWORK', 'DISPOSE')),
+    root_cause TEXT,
+    action_taken TEXT,
+    status TEXT NOT NULL DEFAULT 'open'
+        CHECK(status IN ('open', 'verification_pending', 'verified', 'closed')),
+    approval_request_id TEXT,
+    verified_by TEXT,
+    verified_at TEXT,
+    verification_comment TEXT,
+    closed_by TEXT,
+    closed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_ccp_corrective_actions_status
+ON ccp_corrective_actions(company_id, status, created_at DESC);
+
+-- 시정조치 대상 점검 (점검 1건은 시정조치 1건에만 연결)
+CREATE TABLE IF NOT EXISTS ccp_corrective_action_checks (
+    check_id INTEGER PRIMARY KEY,
+    action_id TEXT NOT NULL REFERENCES ccp_corrective_actions(id) ON DELETE CASCADE,
+    batch_lot_no TEXT NOT NULL,
+    judged_result TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ccp_corrective_action_checks_action
+ON ccp_corrective_action_checks(action_id);
+
+-- LOT 출하 보류 (판단 NG / 시정조치 보류 결정, QC 합격 + 승인 후 해제)
+CREATE TABLE IF NOT EXISTS lot_holds (
+    id TEXT PRIMARY KEY,
+    lot_no TEXT NOT NULL,
+    lot_type TEXT NOT NULL CHECK(lot_type IN ('BATCH', 'FG')),
+    status TEXT NOT NULL DEFAULT 'HOLD' CHECK(status IN ('HOLD', 'RELEASE_PENDING', 'RELEASED')),
+    reason TEXT NOT NULL,
+    source_type TEXT NOT NULL CHECK(source_type IN ('judgment', 'corrective_action', 'manual')),
+    source_ref TEXT,
+    held_by TEXT NOT NULL,
+    held_at TEXT NOT NULL DEFAULT (datetime('now')),
+    approval_request_id TEXT,
+    qc_ref TEXT,
+    released_by TEXT,
+    released_at TEXT,
+    release_comment TEXT,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- LOT당 해제되지 않은 보류는 1건
+CREATE UNIQUE INDEX IF NOT EXISTS idx_lot_holds_active
+ON lot_holds(lot_no) WHERE status != 'RELEASED';
+
+CREATE INDEX IF NOT EXISTS idx_lot_holds_status
+ON lot_holds(status, held_at DESC);
+
+-- ============================================================
+-- MES/ERP RAG 테이블 (Phase 8: Generic CSV Upload & Query)
+-- ============================================================
+
+-- MES 데이터 로그 테이블
+CREATE TABLE IF NOT EXISTS mes_data_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    row_index INTEGER NOT NULL,
+    raw_json TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    upload_id TEXT
+);
+
+-- FTS5 전문검색 인덱스 (BM25 알고리즘)
+CREATE VIRTUAL TABLE IF NOT EXISTS mes_data_logs_fts
+USING fts5(content, tokenize='porter unicode61');
+
+-- FTS5 자동 동기화 트리거
+CREATE TRIGGER IF NOT EXISTS mes_data_logs_ai AFTER INSERT ON mes_data_logs
+BEGIN
+    INSERT INTO mes_data_logs_fts(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS mes_data_logs_ad AFTER DELETE ON mes_data_logs
+BEGIN
+    DELETE FROM mes_data_logs_fts WHERE rowid = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS mes_data_logs_au AFTER UPDATE ON mes_data_logs
+BEGIN
+    UPDATE mes_data_logs_fts SET content = new.content WHERE rowid = new.id;
+END;
+
+CREATE INDEX IF NOT EXISTS idx_mes_data_logs_session
+ON mes_data_logs(session_id, created_at DESC);
+
+-- MES 업로드 기록 (업로드별 컬럼 스키마/인코딩/진행 상태)
+CREATE TABLE IF NOT EXISTS mes_uploads (
+    id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    source_path TEXT,
+    encoding TEXT NOT NULL,
+    source_format TEXT NOT NULL DEFAULT 'csv',
+    sheet_name TEXT,
+    row_count INTEGER NOT NULL DEFAULT 0,
+    schema_json TEXT NOT NULL DEFAULT '[]',
+    status TEXT NOT NULL DEFAULT 'IN_PROGRESS' CHECK (status IN ('IN_PROGRESS', 'COMPLETED', 'FAILED')),
+    error TEXT,
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    completed_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_mes_uploads_session
+ON mes_uploads(session_id, started_at DESC);
+
+-- ============================================================
+-- Workflow 승인 요청 테이블 (Phase 9: APPROVAL Node)
+-- ============================================================
+
+CREATE TABLE IF NOT EXISTS approval_requests (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    workflow_name TEXT NOT NULL,
+    step_id TEXT NOT NULL,
+    step_name TEXT NOT NULL,
+    approval_type TEXT NOT NULL CHECK(approval_type IN ('manual', 'conditional')),
+    status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'approved', 'rejected', 'expired')),
+    approvers TEXT NOT NULL,
+    input_data TEXT NOT NULL,
+    condition TEXT,
+    timeout_minutes INTEGER DEFAULT 60,
+    decided_by TEXT,
+    decided_at TEXT,
+    comment TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    expires_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_approval_requests_status
+ON approval_requests(status, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_approval_requests_workflow
+ON approval_requests(workflow_id, status);
+
+-- ============================================================
+-- Workflow 스케줄러 테이블 (Phase 9: Cron-based Scheduler)
+-- ============================================================
+
+CREATE TABLE IF NOT EXISTS workflow_schedules (
+    id TEXT PRIMARY KEY,
+    workflow_id TEXT NOT NULL,
+    workflow_name TEXT NOT NULL,
+    cron_expression TEXT NOT NULL,
+    timezone TEXT NOT NULL DEFAULT 'Asia/Seoul',
+    is_active INTEGER NOT NULL DEFAULT 1,
+    input_data TEXT NOT NULL DEFAULT '{}',
+    last_run_at TEXT,
+    next_run_at TEXT,
+    run_count INTEGER NOT NULL DEFAULT 0,
+    last_status TEXT CHECK(last_status IN ('success', 'failed', 'running')),
+    last_error TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_schedules_active
+ON workflow_schedules(is_active, next_run_at);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_schedules_workflow
+ON workflow_schedules(workflow_id);
+
+-- Workflow 실행 이력 테이블 (Phase 9: Execution History)
+CREATE TABLE IF NOT EXISTS workflow_executions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    workflow_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    steps_executed TEXT NOT NULL,
+    final_result TEXT NOT NULL,
+    execution_time_ms INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_executions_workflow
+ON workflow_executions(workflow_id, created_at DESC);
+
+-- 프롬프트 템플릿 버전 / CCP 문서 개정 / MES 업로드 연결 인덱스
+CREATE INDEX IF NOT EXISTS idx_judgments_template
+ON judgments(template_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_ccp_docs_version
+ON ccp_docs(doc_version_id);
+
+CREATE INDEX IF NOT EXISTS idx_mes_data_logs_upload
+ON mes_data_logs(upload_id);
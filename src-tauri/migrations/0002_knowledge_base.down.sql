@@ -0,0 +1,7 @@
+-- ========================================
+-- 0002_knowledge_base.down.sql
+-- 지식베이스 되돌리기 (생성 역순으로 삭제, 인덱스/트리거는 테이블과 함께 삭제됨)
+-- ========================================
+
+DROP TABLE IF EXISTS knowledge_base_fts;
+DROP TABLE IF EXISTS knowledge_base;
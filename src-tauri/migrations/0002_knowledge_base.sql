@@ -1,5 +1,5 @@
 -- ========================================
--- 001_knowledge_base.sql
+-- 0002_knowledge_base.sql
 -- RAG용 지식베이스 테이블 (FTS5 전문검색)
 -- 퓨어웰 음료㈜ 기업정보 및 SOP 저장
 -- ========================================
@@ -53,5 +53,4 @@ CREATE INDEX IF NOT EXISTS idx_knowledge_base_category ON knowledge_base(categor
 CREATE INDEX IF NOT EXISTS idx_knowledge_base_source ON knowledge_base(source_file);
 CREATE INDEX IF NOT EXISTS idx_knowledge_base_created ON knowledge_base(created_at DESC);
 
--- 참고: workflows, workflow_executions 테이블은 sqlite.rs의 init_schema()에서 생성됨
--- 중복 생성을 피하기 위해 여기서는 제외
+-- 참고: workflows, workflow_executions 테이블은 0001_core_schema.sql에서 생성됨
@@ -0,0 +1,26 @@
+-- ========================================
+-- 0003_erp_schema.down.sql
+-- ERP 스키마 되돌리기 (생성 역순으로 삭제, 인덱스/트리거는 테이블과 함께 삭제됨)
+-- ========================================
+
+DROP TABLE IF EXISTS outbound_dtl;
+DROP TABLE IF EXISTS outbound;
+DROP TABLE IF EXISTS sales_order_dtl;
+DROP TABLE IF EXISTS sales_order;
+DROP TABLE IF EXISTS inventory;
+DROP TABLE IF EXISTS qc_test;
+DROP TABLE IF EXISTS fg_lot;
+DROP TABLE IF EXISTS filling_lot;
+DROP TABLE IF EXISTS process_result;
+DROP TABLE IF EXISTS material_issue;
+DROP TABLE IF EXISTS batch_lot;
+DROP TABLE IF EXISTS production_order;
+DROP TABLE IF EXISTS inbound_dtl;
+DROP TABLE IF EXISTS inbound;
+DROP TABLE IF EXISTS purchase_order_dtl;
+DROP TABLE IF EXISTS purchase_order;
+DROP TABLE IF EXISTS bom_dtl;
+DROP TABLE IF EXISTS bom_mst;
+DROP TABLE IF EXISTS customer_mst;
+DROP TABLE IF EXISTS vendor_mst;
+DROP TABLE IF EXISTS item_mst;
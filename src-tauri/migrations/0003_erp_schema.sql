@@ -1,5 +1,5 @@
 -- ========================================
--- 002_erp_schema.sql
+-- 0003_erp_schema.sql
 -- 퓨어웰 음료㈜ ERP 스키마
 -- 18개 테이블: 마스터 + 구매/입고 + 생산 + 품질 + 판매/출고
 -- ========================================
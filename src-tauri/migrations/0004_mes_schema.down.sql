@@ -0,0 +1,21 @@
+-- ========================================
+-- 0004_mes_schema.down.sql
+-- MES 스키마 되돌리기 (생성 역순으로 삭제, 인덱스/트리거는 테이블과 함께 삭제됨)
+-- ========================================
+
+DROP TABLE IF EXISTS alarm_event;
+DROP TABLE IF EXISTS downtime_event;
+DROP TABLE IF EXISTS checklist_result;
+DROP TABLE IF EXISTS ccp_check_log;
+DROP TABLE IF EXISTS sensor_log;
+DROP TABLE IF EXISTS operation_param_log;
+DROP TABLE IF EXISTS operation_param_target;
+DROP TABLE IF EXISTS operation_exec;
+DROP TABLE IF EXISTS mes_work_order;
+DROP TABLE IF EXISTS param_mst;
+DROP TABLE IF EXISTS reason_code_mst;
+DROP TABLE IF EXISTS operator_mst;
+DROP TABLE IF EXISTS shift_mst;
+DROP TABLE IF EXISTS operation_mst;
+DROP TABLE IF EXISTS equipment_mst;
+DROP TABLE IF EXISTS line_mst;
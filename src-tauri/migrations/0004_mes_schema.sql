@@ -1,5 +1,5 @@
 -- ========================================
--- 003_mes_schema.sql
+-- 0004_mes_schema.sql
 -- 퓨어웰 음료㈜ MES 스키마
 -- 15개 테이블: 마스터 + 작업실행 + 센서/CCP + 이벤트
 -- ========================================
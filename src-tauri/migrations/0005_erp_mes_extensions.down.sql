@@ -0,0 +1,11 @@
+-- ========================================
+-- 0005_erp_mes_extensions.down.sql
+-- 추가 ERP/MES 테이블 되돌리기 (생성 역순으로 삭제, 인덱스/트리거는 테이블과 함께 삭제됨)
+-- ========================================
+
+DROP TABLE IF EXISTS inventory_movement;
+DROP TABLE IF EXISTS warehouse_mst;
+DROP TABLE IF EXISTS material_input_log;
+DROP TABLE IF EXISTS process_param_log;
+DROP TABLE IF EXISTS metal_detection_log;
+DROP TABLE IF EXISTS qc_inspection;
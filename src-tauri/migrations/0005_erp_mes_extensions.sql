@@ -1,5 +1,5 @@
 -- ========================================
--- 010_additional_erp_mes.sql
+-- 0005_erp_mes_extensions.sql
 -- 추가 ERP/MES 테이블 (사용자 요청 기반)
 -- ========================================
 
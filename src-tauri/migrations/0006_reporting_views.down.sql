@@ -0,0 +1,13 @@
+-- ========================================
+-- 0006_reporting_views.down.sql
+-- 조회용 뷰 / LOT 출하 보류 가드 되돌리기
+-- ========================================
+
+DROP TRIGGER IF EXISTS trg_outbound_dtl_lot_hold_update;
+DROP TRIGGER IF EXISTS trg_outbound_dtl_lot_hold;
+DROP VIEW IF EXISTS v_lot_hold_status;
+DROP VIEW IF EXISTS v_warehouse_inventory_summary;
+DROP VIEW IF EXISTS v_daily_avg_temperature;
+DROP VIEW IF EXISTS v_customer_sales;
+DROP VIEW IF EXISTS v_product_monthly_sales;
+DROP VIEW IF EXISTS v_monthly_sales;
@@ -0,0 +1,115 @@
+-- ========================================
+-- 0006_reporting_views.sql
+-- 차트/조회용 뷰 + LOT 출하 보류 가드
+-- (기존 008/016/017 시드 파일과 init_schema에 있던 뷰/트리거를 스키마로 분리)
+-- ========================================
+
+-- ========================================
+-- 월별 판매 집계 VIEW (시연용 차트 데이터)
+-- ========================================
+CREATE VIEW IF NOT EXISTS v_monthly_sales AS
+SELECT
+    strftime('%Y-%m', s.order_date) as year_month,
+    c.cust_nm as customer_name,
+    i.item_nm as product_name,
+    i.item_cd as item_cd,
+    SUM(d.qty) as total_qty,
+    SUM(d.amount) as total_amount
+FROM sales_order s
+JOIN sales_order_dtl d ON s.so_no = d.so_no
+JOIN customer_mst c ON s.cust_cd = c.cust_cd
+JOIN item_mst i ON d.item_cd = i.item_cd
+WHERE s.status IN ('COMPLETED', 'SHIPPED')
+GROUP BY strftime('%Y-%m', s.order_date), c.cust_nm, i.item_nm, i.item_cd
+ORDER BY year_month DESC, total_amount DESC;
+
+-- 품목별 월간 판매 집계 VIEW
+CREATE VIEW IF NOT EXISTS v_product_monthly_sales AS
+SELECT
+    strftime('%Y-%m', s.order_date) as year_month,
+    i.item_cd,
+    i.item_nm as product_name,
+    SUM(d.qty) as total_qty,
+    SUM(d.amount) as total_amount,
+    COUNT(DISTINCT s.so_no) as order_count
+FROM sales_order s
+JOIN sales_order_dtl d ON s.so_no = d.so_no
+JOIN item_mst i ON d.item_cd = i.item_cd
+WHERE s.status IN ('COMPLETED', 'SHIPPED')
+GROUP BY strftime('%Y-%m', s.order_date), i.item_cd, i.item_nm
+ORDER BY year_month DESC, total_amount DESC;
+
+-- 고객별 판매 집계 VIEW
+CREATE VIEW IF NOT EXISTS v_customer_sales AS
+SELECT
+    c.cust_cd,
+    c.cust_nm as customer_name,
+    c.cust_type,
+    COUNT(DISTINCT s.so_no) as order_count,
+    SUM(d.qty) as total_qty,
+    SUM(d.amount) as total_amount
+FROM sales_order s
+JOIN sales_order_dtl d ON s.so_no = d.so_no
+JOIN customer_mst c ON s.cust_cd = c.cust_cd
+WHERE s.status IN ('COMPLETED', 'SHIPPED')
+GROUP BY c.cust_cd, c.cust_nm, c.cust_type
+ORDER BY total_amount DESC;
+
+-- ========================================
+-- 일별 평균 온도 VIEW (온도 변화 추이 차트)
+-- ========================================
+CREATE VIEW IF NOT EXISTS v_daily_avg_temperature AS
+SELECT
+    DATE(recorded_at) as date,
+    equip_cd,
+    param_cd,
+    ROUND(AVG(value), 2) as avg_temp,
+    ROUND(MIN(value), 2) as min_temp,
+    ROUND(MAX(value), 2) as max_temp,
+    COUNT(*) as reading_count
+FROM sensor_log
+WHERE param_cd LIKE '%TEMP%'
+GROUP BY DATE(recorded_at), equip_cd, param_cd
+ORDER BY date DESC;
+
+-- ========================================
+-- 창고별 재고 현황 VIEW (창고별 재고 비율 차트)
+-- ========================================
+CREATE VIEW IF NOT EXISTS v_warehouse_inventory_summary AS
+SELECT
+    i.location as warehouse_id,
+    COALESCE(w.warehouse_nm, i.location) as warehouse_nm,
+    COALESCE(w.warehouse_type, 'UNKNOWN') as warehouse_type,
+    SUM(i.qty) as total_qty,
+    COUNT(DISTINCT i.item_cd) as item_count
+FROM inventory i
+LEFT JOIN warehouse_mst w ON i.location = w.warehouse_id
+GROUP BY i.location
+ORDER BY total_qty DESC;
+
+-- ========================================
+-- 보류 중인 완제품 LOT (완제품 LOT 보류 + 상위 배치 LOT 보류)와 출고 등록 차단 트리거
+-- ========================================
+CREATE VIEW IF NOT EXISTS v_lot_hold_status AS
+SELECT f.fg_lot_no, f.fg_item_cd, fl.batch_lot_no,
+       h.id AS hold_id, h.lot_no AS held_lot_no, h.lot_type, h.status AS hold_status,
+       h.reason, h.held_at
+FROM fg_lot f
+JOIN filling_lot fl ON fl.filling_lot_no = f.filling_lot_no
+JOIN lot_holds h ON h.status != 'RELEASED'
+   AND ((h.lot_type = 'FG' AND h.lot_no = f.fg_lot_no)
+     OR (h.lot_type = 'BATCH' AND h.lot_no = fl.batch_lot_no));
+
+CREATE TRIGGER IF NOT EXISTS trg_outbound_dtl_lot_hold
+BEFORE INSERT ON outbound_dtl
+WHEN EXISTS (SELECT 1 FROM v_lot_hold_status WHERE fg_lot_no = NEW.fg_lot_no)
+BEGIN
+    SELECT RAISE(ABORT, '출하 보류 중인 LOT은 출고할 수 없습니다');
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_outbound_dtl_lot_hold_update
+BEFORE UPDATE OF fg_lot_no ON outbound_dtl
+WHEN EXISTS (SELECT 1 FROM v_lot_hold_status WHERE fg_lot_no = NEW.fg_lot_no)
+BEGIN
+    SELECT RAISE(ABORT, '출하 보류 중인 LOT은 출고할 수 없습니다');
+END;
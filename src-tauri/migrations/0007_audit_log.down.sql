@@ -0,0 +1,8 @@
+-- ========================================
+-- 0007_audit_log.down.sql
+-- 감사 로그 되돌리기 (기록된 감사 항목도 함께 삭제됨)
+-- ========================================
+
+DROP TRIGGER IF EXISTS trg_audit_log_no_delete;
+DROP TRIGGER IF EXISTS trg_audit_log_no_update;
+DROP TABLE IF EXISTS audit_log;
@@ -0,0 +1,38 @@
+-- ========================================
+-- 0007_audit_log.sql
+-- 규제 기록 감사 로그 (CCP 판단, 승인, 룰 변경, 워크플로우 수정)
+-- ========================================
+
+-- 추가 전용 + 해시 체인: hash = SHA-256(이전 hash + 항목 내용)
+-- prev_hash UNIQUE로 같은 위치에 두 항목이 이어지는 분기를 막는다
+CREATE TABLE IF NOT EXISTS audit_log (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    occurred_at TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    action TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    before_json TEXT,
+    after_json TEXT,
+    prev_hash TEXT NOT NULL UNIQUE,
+    hash TEXT NOT NULL UNIQUE
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity
+ON audit_log(entity_type, entity_id, seq);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_occurred
+ON audit_log(occurred_at);
+
+-- 기록된 항목은 수정/삭제 불가
+CREATE TRIGGER IF NOT EXISTS trg_audit_log_no_update
+BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, '감사 로그는 수정할 수 없습니다');
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_audit_log_no_delete
+BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, '감사 로그는 삭제할 수 없습니다');
+END;
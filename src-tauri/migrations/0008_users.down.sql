@@ -0,0 +1,6 @@
+-- ========================================
+-- 0008_users.down.sql
+-- 로컬 사용자 계정 되돌리기
+-- ========================================
+
+DROP TABLE IF EXISTS users;
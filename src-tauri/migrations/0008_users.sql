@@ -0,0 +1,23 @@
+-- ========================================
+-- 0008_users.sql
+-- 로컬 사용자 계정 + 역할 (operator / qa / supervisor / admin)
+-- ========================================
+
+-- password_hash: Argon2id PHC 문자열
+-- failed_attempts / locked_until: 연속 로그인 실패 시 일시 잠금
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT PRIMARY KEY,
+    username TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    display_name TEXT NOT NULL,
+    role TEXT NOT NULL CHECK(role IN ('operator', 'qa', 'supervisor', 'admin')),
+    password_hash TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 1,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    locked_until TEXT,
+    last_login_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_users_role
+ON users(role, is_active);
@@ -7,6 +7,7 @@ pub mod frequency_analyzer;
 pub mod llm_pattern_discoverer;
 pub mod rule_integrator;
 pub mod decision_tree_converter;
+pub mod rule_evaluator;
 
 // 공통 데이터 구조
 
@@ -19,7 +20,7 @@ pub struct ExtractedRule {
     /// 신뢰도 점수 (0.0 ~ 1.0)
     pub confidence: f64,
 
-    /// 추출 방법 ("frequency" | "llm" | "integrated")
+    /// 추출 방법 ("frequency" | "llm" | "decision_tree" | "integrated")
     pub method: String,
 
     /// 피처 중요도 (선택적)
@@ -48,6 +49,79 @@ pub struct FeedbackData {
     pub judgment_id: String,
 }
 
+/// 교차검증 점수가 매겨진 후보 Rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatedRule {
+    pub rule: ExtractedRule,
+
+    /// k-fold 교차검증 점수 (0.0 ~ 1.0, 해당 fold에서 재추출되지 않으면 0점)
+    pub cv_score: f64,
+
+    /// 같은 Rule이 재추출된 fold 수
+    pub folds_rediscovered: usize,
+
+    /// 학습에 사용하지 않은 테스트 세트 평가 결과 (테스트 세트가 없으면 None)
+    #[serde(default)]
+    pub holdout: Option<RuleMetrics>,
+}
+
+/// 혼동 행렬 기반 Rule 평가 지표 (positive = 합격)
+///
+/// 평가 오류가 난 샘플은 오답으로 간주하여 FN/FP에 포함한다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleMetrics {
+    pub true_positive: usize,
+    pub false_positive: usize,
+    pub true_negative: usize,
+    pub false_negative: usize,
+
+    /// 평가 오류 샘플 수 (변수 누락, 문법 오류 등)
+    pub errors: usize,
+
+    pub accuracy: f64,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+}
+
+impl RuleMetrics {
+    /// 혼동 행렬로부터 지표 계산 (분모가 0이면 0.0)
+    pub fn from_counts(
+        true_positive: usize,
+        false_positive: usize,
+        true_negative: usize,
+        false_negative: usize,
+        errors: usize,
+    ) -> Self {
+        let ratio = |num: usize, den: usize| if den == 0 { 0.0 } else { num as f64 / den as f64 };
+
+        let total = true_positive + false_positive + true_negative + false_negative;
+        let precision = ratio(true_positive, true_positive + false_positive);
+        let recall = ratio(true_positive, true_positive + false_negative);
+        let f1 = if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        };
+
+        RuleMetrics {
+            true_positive,
+            false_positive,
+            true_negative,
+            false_negative,
+            errors,
+            accuracy: ratio(true_positive + true_negative, total),
+            precision,
+            recall,
+            f1,
+        }
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.true_positive + self.false_positive + self.true_negative + self.false_negative
+    }
+}
+
 /// 알고리즘 실행 결과
 #[derive(Debug)]
 pub struct AlgorithmResult {
@@ -84,6 +158,15 @@ impl ExtractedRule {
     }
 }
 
+/// Rule 표현식 정규화 (공백 정리, 소문자) - 알고리즘간 동일 Rule 비교용
+pub fn normalize_expression(expression: &str) -> String {
+    expression
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,4 +198,20 @@ mod tests {
 
         assert_eq!(rule.support_ratio(), 0.8);
     }
+
+    #[test]
+    fn test_rule_metrics_from_counts() {
+        let metrics = RuleMetrics::from_counts(8, 2, 6, 4, 1);
+
+        assert_eq!(metrics.sample_count(), 20);
+        assert!((metrics.accuracy - 0.7).abs() < 1e-9);
+        assert!((metrics.precision - 0.8).abs() < 1e-9);
+        assert!((metrics.recall - 8.0 / 12.0).abs() < 1e-9);
+        assert!((metrics.f1 - 2.0 * 0.8 * (8.0 / 12.0) / (0.8 + 8.0 / 12.0)).abs() < 1e-9);
+
+        // 합격 예측이 하나도 없으면 precision/recall/F1 모두 0
+        let never_pass = RuleMetrics::from_counts(0, 0, 5, 5, 0);
+        assert_eq!(never_pass.f1, 0.0);
+        assert_eq!(never_pass.accuracy, 0.5);
+    }
 }
@@ -0,0 +1,243 @@
+// algorithms/rule_evaluator.rs - 추출 Rule 검증 (k-fold 교차검증)
+
+use super::{normalize_expression, ExtractedRule, FeedbackData, RuleMetrics};
+use crate::services::rule_engine::RuleEngine;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde_json::Value;
+
+/// Rule 평가기
+///
+/// 알고리즘이 스스로 보고하는 신뢰도 대신, 학습에 사용되지 않은 샘플에서
+/// Rule의 실제 판단 정확도를 측정한다. 라벨은 `FeedbackData::is_positive` (합격 여부)
+pub struct RuleEvaluator {
+    /// fold 수 (기본: 5, 샘플이 적으면 샘플 수로 축소)
+    pub folds: usize,
+
+    /// 셔플 시드 (같은 데이터는 항상 같은 분할 → 재현 가능한 점수)
+    pub seed: u64,
+}
+
+/// k-fold 분할 중 하나
+#[derive(Debug, Clone)]
+pub struct Fold {
+    pub train: Vec<FeedbackData>,
+    pub test: Vec<FeedbackData>,
+}
+
+impl Default for RuleEvaluator {
+    fn default() -> Self {
+        RuleEvaluator { folds: 5, seed: 42 }
+    }
+}
+
+impl RuleEvaluator {
+    pub fn new(folds: usize) -> Self {
+        RuleEvaluator {
+            folds,
+            ..Self::default()
+        }
+    }
+
+    /// 입력 데이터에 Rule 적용 (JSON 파싱/평가 오류시 None)
+    pub fn predict(expression: &str, input_json: &str) -> Option<bool> {
+        let input: Value = serde_json::from_str(input_json).ok()?;
+        RuleEngine::evaluate_expression(expression, &input).ok()
+    }
+
+    /// 라벨 대비 정확도 (평가 오류는 오답 처리)
+    pub fn accuracy(expression: &str, samples: &[FeedbackData]) -> f64 {
+        Self::evaluate(expression, samples).accuracy
+    }
+
+    /// 혼동 행렬 및 precision/recall/F1 계산
+    pub fn evaluate(expression: &str, samples: &[FeedbackData]) -> RuleMetrics {
+        let (mut tp, mut fp, mut tn, mut fn_, mut errors) = (0, 0, 0, 0, 0);
+
+        for sample in samples {
+            // 평가 오류는 라벨과 반대로 예측한 것으로 처리
+            let predicted = Self::predict(expression, &sample.input_json).unwrap_or_else(|| {
+                errors += 1;
+                !sample.is_positive
+            });
+
+            match (predicted, sample.is_positive) {
+                (true, true) => tp += 1,
+                (true, false) => fp += 1,
+                (false, false) => tn += 1,
+                (false, true) => fn_ += 1,
+            }
+        }
+
+        RuleMetrics::from_counts(tp, fp, tn, fn_, errors)
+    }
+
+    /// 학습/테스트 분할 (라벨별 층화, 테스트 비율만큼 각 라벨에서 추출)
+    ///
+    /// 샘플이 너무 적어 테스트 세트가 비면 홀드아웃 평가는 불가
+    pub fn holdout_split(&self, samples: &[FeedbackData], test_ratio: f64) -> Fold {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut split = Fold {
+            train: Vec::new(),
+            test: Vec::new(),
+        };
+
+        for label in [true, false] {
+            let mut group: Vec<FeedbackData> = samples
+                .iter()
+                .filter(|s| s.is_positive == label)
+                .cloned()
+                .collect();
+            group.shuffle(&mut rng);
+
+            let test_count = (group.len() as f64 * test_ratio).round() as usize;
+            let train = group.split_off(test_count.min(group.len()));
+            split.test.extend(group);
+            split.train.extend(train);
+        }
+
+        split
+    }
+
+    /// 셔플 후 k개 fold로 분할 (각 샘플은 정확히 한 fold의 test에 포함)
+    pub fn split_folds(&self, samples: &[FeedbackData]) -> Vec<Fold> {
+        let k = self.folds.min(samples.len());
+        if k < 2 {
+            return Vec::new();
+        }
+
+        let mut shuffled = samples.to_vec();
+        shuffled.shuffle(&mut StdRng::seed_from_u64(self.seed));
+
+        (0..k)
+            .map(|fold| {
+                let (test, train): (Vec<_>, Vec<_>) = shuffled
+                    .iter()
+                    .enumerate()
+                    .partition(|(i, _)| i % k == fold);
+                Fold {
+                    train: train.into_iter().map(|(_, s)| s.clone()).collect(),
+                    test: test.into_iter().map(|(_, s)| s.clone()).collect(),
+                }
+            })
+            .collect()
+    }
+
+    /// fold 점수: 학습 fold에서 같은 Rule이 재추출되면 검증 fold 정확도, 아니면 None (0점 처리)
+    ///
+    /// 전체 데이터에서만 우연히 나오는 (과적합된) Rule은 점수가 낮아진다.
+    pub fn fold_score(expression: &str, fold_rules: &[ExtractedRule], test: &[FeedbackData]) -> Option<f64> {
+        let normalized = normalize_expression(expression);
+        fold_rules
+            .iter()
+            .any(|r| normalize_expression(&r.expression) == normalized)
+            .then(|| Self::accuracy(expression, test))
+    }
+
+    /// 알고리즘 holdout 정확도: 학습 fold에서 추출한 최고 신뢰도 Rule의 검증 fold 정확도
+    pub fn holdout_accuracy(fold_rules: &[ExtractedRule], test: &[FeedbackData]) -> f64 {
+        fold_rules
+            .iter()
+            .max_by(|a, b| a.confidence.total_cmp(&b.confidence))
+            .map(|rule| Self::accuracy(&rule.expression, test))
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(positive: bool, temp: f64) -> FeedbackData {
+        FeedbackData {
+            input_json: format!(r#"{{"temperature": {}}}"#, temp),
+            is_positive: positive,
+            judgment_result: positive,
+            judgment_id: uuid::Uuid::new_v4().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_accuracy_counts_errors_as_wrong() {
+        let samples = vec![sample(true, 90.0), sample(false, 70.0), sample(true, 86.0), sample(false, 88.0)];
+
+        assert_eq!(RuleEvaluator::accuracy("temperature > 85", &samples), 0.75);
+        // 존재하지 않는 변수 → 평가 오류 → 전부 오답
+        assert_eq!(RuleEvaluator::accuracy("pressure > 1", &samples), 0.0);
+    }
+
+    #[test]
+    fn test_split_folds_covers_each_sample_once() {
+        let samples: Vec<_> = (0..11).map(|i| sample(i % 2 == 0, i as f64)).collect();
+        let folds = RuleEvaluator::new(5).split_folds(&samples);
+
+        assert_eq!(folds.len(), 5);
+        let mut test_ids: Vec<_> = folds
+            .iter()
+            .flat_map(|f| f.test.iter().map(|s| s.judgment_id.clone()))
+            .collect();
+        test_ids.sort();
+        test_ids.dedup();
+        assert_eq!(test_ids.len(), 11);
+        assert!(folds.iter().all(|f| f.train.len() + f.test.len() == 11));
+
+        // 샘플 1개면 교차검증 불가
+        assert!(RuleEvaluator::new(5).split_folds(&samples[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_fold_score_requires_rediscovery() {
+        let test = vec![sample(true, 90.0), sample(false, 70.0)];
+        let fold_rules = vec![ExtractedRule::new("temperature  > 85".to_string(), 0.8, "frequency".to_string())];
+
+        assert_eq!(RuleEvaluator::fold_score("temperature > 85", &fold_rules, &test), Some(1.0));
+        assert_eq!(RuleEvaluator::fold_score("temperature > 60", &fold_rules, &test), None);
+        assert_eq!(RuleEvaluator::holdout_accuracy(&fold_rules, &test), 1.0);
+        assert_eq!(RuleEvaluator::holdout_accuracy(&[], &test), 0.0);
+    }
+
+    #[test]
+    fn test_evaluate_confusion_matrix() {
+        let samples = vec![
+            sample(true, 90.0),  // TP
+            sample(true, 80.0),  // FN
+            sample(false, 88.0), // FP
+            sample(false, 70.0), // TN
+            FeedbackData {
+                input_json: r#"{"pressure": 1}"#.to_string(),
+                is_positive: true,
+                judgment_result: true,
+                judgment_id: "err".to_string(),
+            }, // 평가 오류 → FN
+        ];
+
+        let metrics = RuleEvaluator::evaluate("temperature > 85", &samples);
+
+        assert_eq!(
+            (metrics.true_positive, metrics.false_positive, metrics.true_negative, metrics.false_negative),
+            (1, 1, 1, 2)
+        );
+        assert_eq!(metrics.errors, 1);
+        assert!((metrics.precision - 0.5).abs() < 1e-9);
+        assert!((metrics.recall - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_holdout_split_is_stratified() {
+        let samples: Vec<_> = (0..10)
+            .map(|i| sample(true, 90.0 + i as f64))
+            .chain((0..5).map(|i| sample(false, 70.0 + i as f64)))
+            .collect();
+
+        let split = RuleEvaluator::default().holdout_split(&samples, 0.2);
+
+        assert_eq!(split.test.len(), 3);
+        assert_eq!(split.test.iter().filter(|s| s.is_positive).count(), 2);
+        assert_eq!(split.train.len(), 12);
+        assert!(split
+            .test
+            .iter()
+            .all(|t| split.train.iter().all(|s| s.judgment_id != t.judgment_id)));
+    }
+}
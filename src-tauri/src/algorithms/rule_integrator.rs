@@ -1,6 +1,6 @@
 // algorithms/rule_integrator.rs - Rule 통합 로직
 
-use super::ExtractedRule;
+use super::{normalize_expression, ExtractedRule, ValidatedRule};
 use std::collections::HashMap;
 
 /// Rule 통합기
@@ -89,12 +89,7 @@ impl RuleIntegrator {
     ///
     /// 공백 정리, 일관된 형식 적용
     fn normalize_expression(&self, expression: &str) -> String {
-        expression
-            .trim()
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .join(" ")
-            .to_lowercase()
+        normalize_expression(expression)
     }
 
     /// 동일 표현식끼리 그룹화
@@ -176,6 +171,63 @@ impl RuleIntegrator {
     ) -> anyhow::Result<Option<ExtractedRule>> {
         self.integrate_rules(vec![rules1, rules2])
     }
+
+    /// 교차검증 점수 기준으로 최적 Rule 선택
+    ///
+    /// 알고리즘 자체 신뢰도 대신 검증 점수를 최종 신뢰도로 사용한다.
+    /// 여러 알고리즘이 같은 Rule을 제안하면 최고 점수에 일치 보너스를 더하고,
+    /// 동점이면 제안한 알고리즘 가중치 합이 큰 Rule을 선택
+    pub fn select_validated(&self, candidates: &[ValidatedRule]) -> Option<ExtractedRule> {
+        let mut groups: HashMap<String, Vec<&ValidatedRule>> = HashMap::new();
+        for candidate in candidates {
+            groups
+                .entry(self.normalize_expression(&candidate.rule.expression))
+                .or_default()
+                .push(candidate);
+        }
+
+        groups
+            .into_iter()
+            .filter_map(|(normalized, group)| {
+                let best = group
+                    .iter()
+                    .max_by(|a, b| a.cv_score.total_cmp(&b.cv_score))?;
+
+                let mut methods: Vec<&str> = group.iter().map(|c| c.rule.method.as_str()).collect();
+                methods.sort_unstable();
+                methods.dedup();
+
+                let mut score = best.cv_score;
+                if methods.len() >= 2 {
+                    score = (score + self.agreement_bonus).min(1.0);
+                }
+
+                let weight: f64 = methods
+                    .iter()
+                    .map(|m| self.weights.get(*m).copied().unwrap_or(0.5))
+                    .sum();
+
+                // 원래 표현식 유지 (소문자 정규화시 변수명이 달라질 수 있음)
+                let mut rule = ExtractedRule::new(
+                    best.rule.expression.trim().to_string(),
+                    score,
+                    "integrated".to_string(),
+                );
+                rule.support_count = best.rule.support_count;
+                rule.total_count = best.rule.total_count;
+                rule.feature_importance = best.rule.feature_importance.clone();
+
+                Some((rule, weight, normalized))
+            })
+            .filter(|(rule, _, _)| rule.confidence >= self.min_confidence)
+            .max_by(|a, b| {
+                a.0.confidence
+                    .total_cmp(&b.0.confidence)
+                    .then(a.1.total_cmp(&b.1))
+                    .then(b.2.cmp(&a.2))
+            })
+            .map(|(rule, _, _)| rule)
+    }
 }
 
 #[cfg(test)]
@@ -300,4 +352,49 @@ mod tests {
         // (0.8 * 0.5 + 0.8 * 0.5) / 1.0 + 0.05 = 0.85
         assert!((integrated.confidence - 0.85).abs() < 0.001);
     }
+
+    fn validated(expression: &str, confidence: f64, method: &str, cv_score: f64) -> ValidatedRule {
+        ValidatedRule {
+            rule: ExtractedRule::new(expression.to_string(), confidence, method.to_string()),
+            cv_score,
+            folds_rediscovered: 0,
+            holdout: None,
+        }
+    }
+
+    #[test]
+    fn test_select_validated_ignores_self_reported_confidence() {
+        let integrator = RuleIntegrator::new();
+
+        let candidates = vec![
+            validated("Temperature > 85", 0.95, "llm", 0.72),
+            validated("vibration < 50", 0.60, "decision_tree", 0.88),
+        ];
+
+        let best = integrator.select_validated(&candidates).unwrap();
+
+        assert_eq!(best.expression, "vibration < 50");
+        assert!((best.confidence - 0.88).abs() < 1e-9);
+        assert_eq!(best.method, "integrated");
+    }
+
+    #[test]
+    fn test_select_validated_agreement_and_threshold() {
+        let integrator = RuleIntegrator::new();
+
+        let candidates = vec![
+            validated("Temperature > 85", 0.8, "frequency", 0.80),
+            validated("temperature  >  85", 0.9, "decision_tree", 0.76),
+            validated("vibration < 50", 0.9, "llm", 0.50),
+        ];
+
+        let best = integrator.select_validated(&candidates).unwrap();
+        assert_eq!(best.expression, "Temperature > 85"); // 원래 표현식 유지
+        assert!((best.confidence - 0.85).abs() < 1e-9);
+
+        // 최소 신뢰도 미달 후보만 있으면 선택 없음
+        assert!(integrator
+            .select_validated(&[validated("vibration < 50", 0.9, "llm", 0.50)])
+            .is_none());
+    }
 }
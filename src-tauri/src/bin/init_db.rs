@@ -1,142 +1,116 @@
-use judgify_desktop::database::Database;
+// DB 초기화 / 마이그레이션 CLI
+//
+// 사용법: init_db [--dry-run] [--status] [--down <버전>] [--demo]
+//   (옵션 없음)   미적용 스키마 마이그레이션 실행
+//   --dry-run     실행할 마이그레이션만 출력 (DB 변경 없음, --down과 함께 사용 가능)
+//   --status      마이그레이션별 적용 상태 / 체크섬 확인
+//   --down <버전> 해당 버전까지 되돌리기
+//   --demo        마이그레이션 후 퓨어웰 음료㈜ 데모 데이터 적재
+
+use judgify_desktop::database::{demo_data, migrations, Database, MigrationReport};
 use rusqlite::Connection;
-use std::path::PathBuf;
 
-fn main() {
-    println!("초기화 중: 퓨어웰 음료㈜ 데이터베이스...");
-
-    match Database::new() {
-        Ok(_db) => {
-            println!("✅ 데이터베이스 기본 스키마 생성 완료!");
-
-            // 새로운 마이그레이션 적용 (ERP/MES/RAG)
-            match apply_migrations() {
-                Ok(()) => {
-                    println!("✅ 마이그레이션 001-014 실행 완료");
-                    println!("📁 위치: %APPDATA%\\Judgify\\judgify.db");
-                    println!("✅ 퓨어웰 음료㈜ 시드 데이터 삽입 완료");
-                    println!("✅ 추가 ERP/MES 테이블 및 시드 데이터 삽입 완료");
-                }
-                Err(e) => {
-                    eprintln!("⚠️  마이그레이션 적용 실패: {}", e);
-                }
+struct Options {
+    dry_run: bool,
+    status: bool,
+    down: Option<u32>,
+    demo: bool,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options { dry_run: false, status: false, down: None, demo: false };
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => options.dry_run = true,
+            "--status" => options.status = true,
+            "--demo" => options.demo = true,
+            "--down" => {
+                let version = args.next().ok_or("--down에는 대상 버전이 필요합니다")?;
+                options.down = Some(version.parse().map_err(|_| format!("잘못된 버전: {}", version))?);
             }
+            other => return Err(format!("알 수 없는 옵션: {}", other)),
         }
+    }
+    Ok(options)
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
         Err(e) => {
-            eprintln!("❌ 데이터베이스 초기화 실패: {}", e);
-            std::process::exit(1);
+            eprintln!("❌ {}", e);
+            eprintln!("사용법: init_db [--dry-run] [--status] [--down <버전>] [--demo]");
+            std::process::exit(2);
         }
+    };
+
+    if let Err(e) = run(&options) {
+        eprintln!("❌ 데이터베이스 초기화 실패: {}", e);
+        std::process::exit(1);
     }
 }
 
-fn apply_migrations() -> rusqlite::Result<()> {
-    // Get database path
-    let app_data = std::env::var("APPDATA")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|e| rusqlite::Error::InvalidPath(PathBuf::from(e.to_string())))?;
-
-    let db_path = PathBuf::from(app_data).join("Judgify").join("judgify.db");
+fn run(options: &Options) -> rusqlite::Result<()> {
+    let db_path = Database::get_db_path()?;
+    println!("📁 위치: {}", db_path.display());
+    // Database::open은 마이그레이션을 자동 적용하므로 직접 연결
     let conn = Connection::open(&db_path)?;
 
-    // 마이그레이션 추적 테이블 생성
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS _migrations (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            name TEXT NOT NULL UNIQUE,
-            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
-        )",
-        [],
-    )?;
-
-    // 마이그레이션 파일 목록 (순서대로 실행)
-    let migration_files = [
-        "migrations/001_knowledge_base.sql",
-        "migrations/002_erp_schema.sql",
-        "migrations/003_mes_schema.sql",
-        "migrations/004_seed_knowledge.sql",
-        "migrations/005_seed_erp_master.sql",
-        "migrations/006_seed_erp_transaction.sql",
-        "migrations/007_seed_mes.sql",
-        "migrations/008_seed_sales_history.sql",
-        "migrations/009_seed_2025_sales.sql",
-        "migrations/010_additional_erp_mes.sql",
-        "migrations/011_seed_additional.sql",
-        "migrations/012_seed_erp_extended.sql",
-        "migrations/013_seed_mes_extended.sql",
-        "migrations/014_seed_mes_complete.sql",
-    ];
-
-    let mut applied_count = 0;
-    let mut skipped_count = 0;
-
-    for file in &migration_files {
-        // 이미 적용된 마이그레이션인지 확인
-        let already_applied: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM _migrations WHERE name = ?",
-            [file],
-            |row| row.get(0),
-        ).unwrap_or(0);
-
-        if already_applied > 0 {
-            println!("📄 스킵: {} (이미 적용됨)", file);
-            skipped_count += 1;
-            continue;
-        }
-
-        println!("📄 실행 중: {}", file);
-        match std::fs::read_to_string(file) {
-            Ok(sql) => {
-                match conn.execute_batch(&sql) {
-                    Ok(()) => {
-                        // 마이그레이션 적용 기록
-                        conn.execute(
-                            "INSERT INTO _migrations (name) VALUES (?)",
-                            [file],
-                        )?;
-                        println!("   ✅ 완료");
-                        applied_count += 1;
-                    }
-                    Err(e) => {
-                        eprintln!("   ❌ 실패: {}", e);
-                        return Err(e);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("   ❌ 파일 읽기 실패: {}", e);
-                return Err(rusqlite::Error::InvalidPath(PathBuf::from(format!("파일 읽기 실패: {}", e))));
-            }
+    if options.status {
+        for status in migrations::status(&conn)? {
+            let state = match (&status.applied_at, status.checksum_matches) {
+                (None, _) => "미적용".to_string(),
+                (Some(at), Some(true)) => format!("적용됨 ({})", at),
+                (Some(at), _) => format!("⚠️  적용 후 파일 수정됨 ({})", at),
+            };
+            println!("  {:04} {:<32} {}", status.version, status.name, state);
         }
+        return Ok(());
     }
 
-    println!("\n📊 마이그레이션 결과: {}개 적용, {}개 스킵", applied_count, skipped_count);
-
-    // FTS5 인덱스 rebuild (knowledge_base)
-    println!("\n📊 FTS5 인덱스 재구축 중...");
-
-    // Step 1: 기존 FTS 데이터 삭제
-    conn.execute_batch("DELETE FROM knowledge_base_fts")?;
-
-    // Step 2: knowledge_base에서 FTS 인덱스 데이터 복사
-    conn.execute_batch(r#"
-        INSERT INTO knowledge_base_fts (rowid, title, content, tags)
-        SELECT rowid, title, content, tags FROM knowledge_base
-    "#)?;
-
-    let kb_fts_count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM knowledge_base_fts",
-        [],
-        |row| row.get(0),
-    )?;
-
-    println!("✅ Knowledge Base FTS5 rebuild 완료: {}건", kb_fts_count);
+    if let Some(target) = options.down {
+        let report = migrations::migrate_down(&conn, target, options.dry_run)?;
+        print_report(&report);
+        return Ok(());
+    }
 
-    // 결과 요약 출력
-    print_summary(&conn)?;
+    let report = if options.dry_run {
+        migrations::migrate_up(&conn, true)?
+    } else {
+        migrations::apply_migrations(&conn)?
+    };
+    print_report(&report);
+
+    if options.demo && !options.dry_run {
+        println!("초기화 중: 퓨어웰 음료㈜ 데모 데이터...");
+        let demo = demo_data::load_demo_data(&conn)?;
+        println!(
+            "✅ 데모 데이터: {}개 적재, {}개 기존, {}개 중복",
+            demo.loaded.len(),
+            demo.already_loaded.len(),
+            demo.conflicts.len()
+        );
+        print_summary(&conn)?;
+    }
 
     Ok(())
 }
 
+fn print_report(report: &MigrationReport) {
+    let prefix = if report.dry_run { "(dry-run) " } else { "" };
+    for step in &report.steps {
+        println!("📄 {}{:?} {:04} {}", prefix, step.direction, step.version, step.name);
+    }
+    println!(
+        "📊 {}스키마 버전 v{} → v{} ({}개)",
+        prefix,
+        report.from_version,
+        report.to_version,
+        report.steps.len()
+    );
+}
+
 fn print_summary(conn: &Connection) -> rusqlite::Result<()> {
     println!("\n========================================");
     println!("📊 데이터베이스 초기화 완료 요약");
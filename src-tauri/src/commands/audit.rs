@@ -0,0 +1,40 @@
+use crate::database::Database;
+use crate::services::audit_log::{AuditEntry, AuditExportReport, AuditLogStore, AuditQuery, AuditVerification};
+use std::path::PathBuf;
+use tauri::State;
+
+/// Tauri command: 감사 로그 조회 (최신순, 대상/작업자/기간 필터)
+#[tauri::command]
+pub async fn list_audit_log(
+    query: Option<AuditQuery>,
+    database: State<'_, Database>,
+) -> Result<Vec<AuditEntry>, String> {
+    AuditLogStore::from_database(database.inner().clone())
+        .list(&query.unwrap_or_default())
+        .map_err(|e| format!("감사 로그 조회 실패: {}", e))
+}
+
+/// Tauri command: 감사 로그 해시 체인 검증 (변조/삭제 항목 보고)
+#[tauri::command]
+pub async fn verify_audit_log(database: State<'_, Database>) -> Result<AuditVerification, String> {
+    let store = AuditLogStore::from_database(database.inner().clone());
+
+    tokio::task::spawn_blocking(move || store.verify())
+        .await
+        .map_err(|e| format!("감사 로그 검증 작업 실패: {}", e))?
+        .map_err(|e| format!("감사 로그 검증 실패: {}", e))
+}
+
+/// Tauri command: 감사인 제출용 CSV 내보내기 (내보낸 시점의 검증 결과 포함)
+#[tauri::command]
+pub async fn export_audit_log(
+    export_path: String,
+    database: State<'_, Database>,
+) -> Result<AuditExportReport, String> {
+    let store = AuditLogStore::from_database(database.inner().clone());
+
+    tokio::task::spawn_blocking(move || store.export_csv(&PathBuf::from(export_path)))
+        .await
+        .map_err(|e| format!("감사 로그 내보내기 작업 실패: {}", e))?
+        .map_err(|e| format!("감사 로그 내보내기 실패: {}", e))
+}
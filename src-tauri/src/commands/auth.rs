@@ -0,0 +1,132 @@
+use crate::database::Database;
+use crate::services::auth::{self, NewUser, Permission, UserAccount, UserStore, UserUpdate};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+/// 로그인 상태 (사용자가 없으면 첫 관리자 생성 화면 표시)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthStatus {
+    pub has_users: bool,
+    pub user: Option<UserAccount>,
+}
+
+#[tauri::command]
+pub async fn get_auth_status(database: State<'_, Database>) -> Result<AuthStatus, String> {
+    let has_users = UserStore::from_database(database.inner().clone())
+        .has_users()
+        .map_err(|e| format!("사용자 조회 실패: {}", e))?;
+
+    Ok(AuthStatus {
+        has_users,
+        user: auth::session().current_user(),
+    })
+}
+
+/// Tauri command: 첫 관리자 생성 (사용자가 없을 때만) 후 로그인
+#[tauri::command]
+pub async fn create_initial_admin(
+    username: String,
+    display_name: String,
+    password: String,
+    database: State<'_, Database>,
+) -> Result<UserAccount, String> {
+    let user = UserStore::from_database(database.inner().clone())
+        .create_initial_admin(&username, &display_name, &password)
+        .map_err(|e| format!("관리자 생성 실패: {}", e))?;
+
+    auth::session().login(user.clone());
+    Ok(user)
+}
+
+#[tauri::command]
+pub async fn login(
+    username: String,
+    password: String,
+    database: State<'_, Database>,
+) -> Result<UserAccount, String> {
+    let store = UserStore::from_database(database.inner().clone());
+
+    // Argon2 검증은 CPU 작업이라 블로킹 스레드에서 실행
+    let user = tokio::task::spawn_blocking(move || store.authenticate(&username, &password))
+        .await
+        .map_err(|e| format!("로그인 작업 실패: {}", e))?
+        .map_err(|e| e.to_string())?;
+
+    auth::session().login(user.clone());
+    Ok(user)
+}
+
+#[tauri::command]
+pub async fn logout() -> Result<(), String> {
+    auth::session().logout();
+    Ok(())
+}
+
+/// Tauri command: 본인 비밀번호 변경 (현재 비밀번호 확인)
+#[tauri::command]
+pub async fn change_password(
+    current_password: String,
+    new_password: String,
+    database: State<'_, Database>,
+) -> Result<(), String> {
+    let user = auth::session()
+        .current_user()
+        .ok_or_else(|| "로그인이 필요합니다".to_string())?;
+    let store = UserStore::from_database(database.inner().clone());
+
+    tokio::task::spawn_blocking(move || {
+        store.authenticate(&user.username, &current_password)?;
+        store.set_password(&user.username, &user.id, &new_password)
+    })
+    .await
+    .map_err(|e| format!("비밀번호 변경 작업 실패: {}", e))?
+    .map_err(|e| format!("비밀번호 변경 실패: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_users(database: State<'_, Database>) -> Result<Vec<UserAccount>, String> {
+    auth::require(Permission::ManageUsers).map_err(|e| e.to_string())?;
+
+    UserStore::from_database(database.inner().clone())
+        .list_users()
+        .map_err(|e| format!("사용자 목록 조회 실패: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_user(user: NewUser, database: State<'_, Database>) -> Result<UserAccount, String> {
+    let admin = auth::require(Permission::ManageUsers).map_err(|e| e.to_string())?;
+
+    UserStore::from_database(database.inner().clone())
+        .create_user(&admin.username, &user)
+        .map_err(|e| format!("사용자 생성 실패: {}", e))
+}
+
+/// Tauri command: 사용자 이름/역할/활성 여부 변경 (마지막 관리자는 강등/비활성화 불가)
+#[tauri::command]
+pub async fn update_user(
+    user_id: String,
+    update: UserUpdate,
+    database: State<'_, Database>,
+) -> Result<UserAccount, String> {
+    let admin = auth::require(Permission::ManageUsers).map_err(|e| e.to_string())?;
+
+    let user = UserStore::from_database(database.inner().clone())
+        .update_user(&admin.username, &user_id, &update)
+        .map_err(|e| format!("사용자 수정 실패: {}", e))?;
+
+    auth::session().refresh(&user);
+    Ok(user)
+}
+
+#[tauri::command]
+pub async fn reset_user_password(
+    user_id: String,
+    new_password: String,
+    database: State<'_, Database>,
+) -> Result<(), String> {
+    let admin = auth::require(Permission::ManageUsers).map_err(|e| e.to_string())?;
+
+    UserStore::from_database(database.inner().clone())
+        .set_password(&admin.username, &user_id, &new_password)
+        .map_err(|e| format!("비밀번호 초기화 실패: {}", e))
+}
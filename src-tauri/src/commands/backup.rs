@@ -1,61 +1,69 @@
-use crate::database::BackupManager;
+use crate::database::backup_crypto::{self, BackupKey};
+use crate::database::backup_policy::{self, BackupPolicy, BackupStatus};
+use crate::database::{BackupManager, BackupManifest, BackupTrigger, Database, RestorePreview, RestoreReport};
+use crate::services::auth::{self, Permission};
 use std::path::PathBuf;
 
+/// 앱이 사용하는 DB 경로 (자동 백업 스케줄러와 같은 백업 디렉토리를 공유)
+fn db_path() -> Result<PathBuf, String> {
+    Database::get_db_path().map_err(|e| format!("Failed to resolve database path: {}", e))
+}
+
+fn backup_manager() -> Result<BackupManager, String> {
+    BackupManager::new(db_path()?)
+        .map_err(|e| format!("Failed to initialize backup manager: {}", e))
+}
+
+/// 수동 백업 (passphrase가 있으면 암호로, 없으면 정책의 keyring 암호화 설정을 따름)
 #[tauri::command]
-pub async fn create_backup(app_handle: tauri::AppHandle) -> Result<String, String> {
-    let db_path = app_handle
-        .path_resolver()
-        .app_data_dir()
-        .ok_or_else(|| "Failed to get app data directory".to_string())?
-        .join("judgify.db");
-
-    let manager = BackupManager::new(db_path)
-        .map_err(|e| format!("Failed to initialize backup manager: {}", e))?;
-
-    let backup_path = manager
-        .create_backup()
+pub async fn create_backup(passphrase: Option<String>) -> Result<String, String> {
+    let db_path = db_path()?;
+    let key = passphrase.filter(|p| !p.is_empty()).map(BackupKey::Passphrase);
+
+    // 백업 + GFS 보존 정책 + 보조 위치 복사
+    let report = tokio::task::spawn_blocking(move || backup_policy::run_backup_with_key(&db_path, BackupTrigger::Manual, key))
+        .await
+        .map_err(|e| format!("Failed to run backup task: {}", e))?
         .map_err(|e| format!("Failed to create backup: {}", e))?;
 
-    // 오래된 백업 자동 정리 (최근 10개만 유지)
-    manager
-        .cleanup_old_backups(10)
-        .map_err(|e| format!("Failed to cleanup old backups: {}", e))?;
+    if let Some(error) = report.secondary_error {
+        eprintln!("⚠️  보조 백업 위치 복사 실패: {}", error);
+    }
 
-    Ok(backup_path.to_string_lossy().to_string())
+    Ok(report.backup_path.to_string_lossy().to_string())
 }
 
+/// 복구 미리보기 (백업 검증 + 생성 시각/행 수/적용될 마이그레이션, 현재 DB는 변경하지 않음)
 #[tauri::command]
-pub async fn restore_backup(
-    app_handle: tauri::AppHandle,
-    backup_path: String,
-) -> Result<String, String> {
-    let db_path = app_handle
-        .path_resolver()
-        .app_data_dir()
-        .ok_or_else(|| "Failed to get app data directory".to_string())?
-        .join("judgify.db");
-
-    let manager = BackupManager::new(db_path)
-        .map_err(|e| format!("Failed to initialize backup manager: {}", e))?;
-
-    let backup_path_buf = PathBuf::from(backup_path);
-    manager
-        .restore_from_backup(&backup_path_buf)
-        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+pub async fn preview_restore(backup_path: String, passphrase: Option<String>) -> Result<RestorePreview, String> {
+    let manager = backup_manager()?;
 
-    Ok("Backup restored successfully".to_string())
+    tokio::task::spawn_blocking(move || manager.preview_restore(&PathBuf::from(backup_path), passphrase.as_deref()))
+        .await
+        .map_err(|e| format!("Failed to run restore preview task: {}", e))?
+        .map_err(|e| format!("Failed to preview backup: {}", e))
 }
 
+/// 백업 복구 (검증 후 열린 연결을 닫고 교체, 재시작 없이 적용)
+///
+/// 암호화 아카이브: 암호 기반이면 passphrase 필요, keyring 기반이면 이 PC의 키를 사용
 #[tauri::command]
-pub async fn list_backups(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
-    let db_path = app_handle
-        .path_resolver()
-        .app_data_dir()
-        .ok_or_else(|| "Failed to get app data directory".to_string())?
-        .join("judgify.db");
+pub async fn restore_backup(backup_path: String, passphrase: Option<String>) -> Result<RestoreReport, String> {
+    auth::require(Permission::RestoreBackups).map_err(|e| e.to_string())?;
+    let db_path = db_path()?;
+
+    // 복구 전 현재 DB 백업 (정책 before_restore, 실패 시 복구 중단) 후 복구
+    tokio::task::spawn_blocking(move || {
+        backup_policy::restore_with_pre_backup(&db_path, &PathBuf::from(backup_path), passphrase.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Failed to run restore task: {}", e))?
+    .map_err(|e| format!("Failed to restore backup: {:#}", e))
+}
 
-    let manager = BackupManager::new(db_path)
-        .map_err(|e| format!("Failed to initialize backup manager: {}", e))?;
+#[tauri::command]
+pub async fn list_backups() -> Result<Vec<String>, String> {
+    let manager = backup_manager()?;
 
     let backups = manager
         .list_backups()
@@ -70,15 +78,8 @@ pub async fn list_backups(app_handle: tauri::AppHandle) -> Result<Vec<String>, S
 }
 
 #[tauri::command]
-pub async fn get_backup_info(app_handle: tauri::AppHandle) -> Result<serde_json::Value, String> {
-    let db_path = app_handle
-        .path_resolver()
-        .app_data_dir()
-        .ok_or_else(|| "Failed to get app data directory".to_string())?
-        .join("judgify.db");
-
-    let manager = BackupManager::new(db_path)
-        .map_err(|e| format!("Failed to initialize backup manager: {}", e))?;
+pub async fn get_backup_info() -> Result<serde_json::Value, String> {
+    let manager = backup_manager()?;
 
     let backups = manager
         .list_backups()
@@ -94,3 +95,50 @@ pub async fn get_backup_info(app_handle: tauri::AppHandle) -> Result<serde_json:
         "total_size_mb": (total_size as f64) / (1024.0 * 1024.0),
     }))
 }
+
+#[tauri::command]
+pub async fn get_backup_manifest(backup_path: String) -> Result<Option<BackupManifest>, String> {
+    let manager = backup_manager()?;
+
+    manager
+        .read_manifest(&PathBuf::from(backup_path))
+        .map_err(|e| format!("Failed to read backup manifest: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_backup_policy() -> Result<BackupPolicy, String> {
+    let manager = backup_manager()?;
+
+    BackupPolicy::load(manager.backup_dir())
+        .map_err(|e| format!("Failed to load backup policy: {}", e))
+}
+
+/// 자동 백업 정책 저장 (보조 위치는 저장 전 쓰기 가능 여부 확인)
+#[tauri::command]
+pub async fn update_backup_policy(policy: BackupPolicy) -> Result<BackupPolicy, String> {
+    auth::require(Permission::ManageBackupPolicy).map_err(|e| e.to_string())?;
+    let manager = backup_manager()?;
+
+    if let Some(secondary_dir) = &policy.secondary_dir {
+        BackupManager::with_backup_dir(db_path()?, secondary_dir.clone())
+            .map_err(|e| format!("Secondary backup folder is not writable: {}", e))?;
+    }
+    if policy.encrypt_with_keyring {
+        backup_crypto::ensure_keyring_key()
+            .map_err(|e| format!("Failed to prepare backup encryption key: {}", e))?;
+    }
+
+    policy
+        .save(manager.backup_dir())
+        .map_err(|e| format!("Failed to save backup policy: {}", e))?;
+
+    Ok(policy)
+}
+
+#[tauri::command]
+pub async fn get_backup_status() -> Result<BackupStatus, String> {
+    let manager = backup_manager()?;
+
+    BackupStatus::load(manager.backup_dir())
+        .map_err(|e| format!("Failed to load backup status: {}", e))
+}
@@ -1,5 +1,13 @@
 use crate::services::ccp_service::CcpService;
-use crate::database::{CcpDocWithScore, CcpJudgmentRequest, CcpJudgmentResponse};
+use tauri::State;
+use crate::services::ccp_doc_ingestion::CcpDocStore;
+use crate::services::ccp_policy::{CcpPolicyStore, CcpRiskPolicy};
+use crate::services::ccp_corrective_action::{CorrectiveAction, CorrectiveActionStore, CorrectiveActionUpdate};
+use crate::services::auth::{self, Permission};
+use crate::database::{
+    CcpDocImportRequest, Database, CcpDocImportResult, CcpDocVersion, CcpDocWithScore, CcpJudgmentRequest,
+    CcpJudgmentResponse,
+};
 
 /// Tauri command: CCP 문서 검색 (FTS5 BM25)
 ///
@@ -18,6 +26,7 @@ pub async fn search_ccp_docs(
     ccp_id: Option<String>,
     query: String,
     top_k: usize,
+    database: State<'_, Database>,
 ) -> Result<Vec<CcpDocWithScore>, String> {
     println!("🔍 [IPC] search_ccp_docs called!");
     println!("   company_id: {}", company_id);
@@ -25,7 +34,7 @@ pub async fn search_ccp_docs(
     println!("   query: {}", query);
     println!("   top_k: {}", top_k);
 
-    let service = CcpService::new()
+    let service = CcpService::from_database(database.inner().clone())
         .map_err(|e| format!("Service 초기화 실패: {}", e))?;
 
     let results = service.search_ccp_docs(
@@ -49,11 +58,10 @@ pub async fn search_ccp_docs(
 /// console.log('FTS5 인덱스:', debugInfo.fts_index_count);
 /// ```
 #[tauri::command]
-pub async fn debug_ccp_database() -> Result<serde_json::Value, String> {
-    use crate::database::Database;
-
-    let db = Database::new()
-        .map_err(|e| format!("DB 초기화 실패: {}", e))?;
+pub async fn debug_ccp_database(
+    database: State<'_, Database>,
+) -> Result<serde_json::Value, String> {
+    let db = database.inner().clone();
 
     let db_conn = db.get_connection();
     let conn = db_conn.lock()
@@ -127,13 +135,12 @@ pub async fn debug_ccp_database() -> Result<serde_json::Value, String> {
 /// console.log('Rebuild 완료:', result.message);
 /// ```
 #[tauri::command]
-pub async fn rebuild_fts5_index() -> Result<serde_json::Value, String> {
-    use crate::database::Database;
-
+pub async fn rebuild_fts5_index(
+    database: State<'_, Database>,
+) -> Result<serde_json::Value, String> {
     println!("🔄 [FTS5] Rebuild 시작...");
 
-    let db = Database::new()
-        .map_err(|e| format!("DB 초기화 실패: {}", e))?;
+    let db = database.inner().clone();
 
     let db_conn = db.get_connection();
     let conn = db_conn.lock()
@@ -169,6 +176,9 @@ pub async fn rebuild_fts5_index() -> Result<serde_json::Value, String> {
 
 /// Tauri command: CCP 상태 판단 (하이브리드)
 ///
+/// ccp_id가 MES CCP 유형(PASTEURIZATION | METAL_DETECTION | COOLING)이면
+/// ccp_check_log를 한계기준으로 재판정하고 LOT별 결과(lot_results)를 함께 반환
+///
 /// Frontend 사용 예시:
 /// ```typescript
 /// const result = await invoke('judge_ccp_status', {
@@ -186,11 +196,219 @@ pub async fn rebuild_fts5_index() -> Result<serde_json::Value, String> {
 #[tauri::command]
 pub async fn judge_ccp_status(
     request: CcpJudgmentRequest,
+    database: State<'_, Database>,
 ) -> Result<CcpJudgmentResponse, String> {
-    let service = CcpService::new()
+    let service = CcpService::from_database(database.inner().clone())
         .map_err(|e| format!("Service 초기화 실패: {}", e))?;
 
     service.judge_ccp_status(request)
         .await
         .map_err(|e| format!("판단 실패: {}", e))
 }
+
+/// Tauri command: HACCP 계획서/SOP 가져오기 (Markdown, 텍스트, 섹션 CSV)
+///
+/// 같은 문서(doc_key)를 다시 가져오면 새 개정본으로 저장되고 FTS5 인덱스는 트리거로 자동 갱신
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// const result = await invoke('import_ccp_document', {
+///   request: {
+///     company_id: 'COMP_A',
+///     ccp_id: 'CCP-01',
+///     title: '열처리 HACCP 계획',
+///     file_path: 'C:/docs/ccp01_plan.md',
+///     effective_from: '2025-12-01'
+///   }
+/// });
+/// console.log('개정:', result.version.revision, '섹션:', result.sections.length);
+/// ```
+#[tauri::command]
+pub async fn import_ccp_document(
+    request: CcpDocImportRequest,
+) -> Result<CcpDocImportResult, String> {
+    let store = CcpDocStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.import_document(&request)
+        .map_err(|e| format!("문서 가져오기 실패: {}", e))
+}
+
+/// Tauri command: CCP 문서 개정 이력 조회
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// const versions = await invoke('list_ccp_doc_versions', {
+///   companyId: 'COMP_A',
+///   ccpId: 'CCP-01'
+/// });
+/// ```
+#[tauri::command]
+pub async fn list_ccp_doc_versions(
+    company_id: String,
+    ccp_id: Option<String>,
+) -> Result<Vec<CcpDocVersion>, String> {
+    let store = CcpDocStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.list_versions(&company_id, ccp_id.as_deref())
+        .map_err(|e| format!("개정 이력 조회 실패: {}", e))
+}
+
+/// Tauri command: CCP 위험도 정책 조회 (저장된 정책이 없으면 기본 정책, updated_at = null)
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// const policy = await invoke('get_ccp_risk_policy', { companyId: 'COMP_A', ccpId: 'CCP-01' });
+/// ```
+#[tauri::command]
+pub async fn get_ccp_risk_policy(
+    company_id: String,
+    ccp_id: String,
+) -> Result<CcpRiskPolicy, String> {
+    let store = CcpPolicyStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.effective(&company_id, &ccp_id)
+        .map_err(|e| format!("정책 조회 실패: {}", e))
+}
+
+/// Tauri command: 회사의 저장된 CCP 위험도 정책 목록
+#[tauri::command]
+pub async fn list_ccp_risk_policies(
+    company_id: String,
+) -> Result<Vec<CcpRiskPolicy>, String> {
+    let store = CcpPolicyStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.list(&company_id)
+        .map_err(|e| format!("정책 목록 조회 실패: {}", e))
+}
+
+/// Tauri command: CCP 위험도 정책 저장 (한계기준, NG 비율 임계값, 연속 NG 규칙)
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// await invoke('save_ccp_risk_policy', {
+///   policy: {
+///     company_id: 'COMP_A',
+///     ccp_id: 'CCP-01',
+///     high_ng_rate: 0.05,
+///     medium_ng_rate: 0.02,
+///     max_consecutive_failures: 3,
+///     breach_is_high: true,
+///     limits: { min_temp: 75.0, max_temp: 95.0 }
+///   }
+/// });
+/// ```
+#[tauri::command]
+pub async fn save_ccp_risk_policy(
+    policy: CcpRiskPolicy,
+) -> Result<CcpRiskPolicy, String> {
+    let store = CcpPolicyStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.save(&policy)
+        .map_err(|e| format!("정책 저장 실패: {}", e))
+}
+
+/// Tauri command: CCP 위험도 정책 삭제 (이후 기본 정책 적용)
+#[tauri::command]
+pub async fn delete_ccp_risk_policy(
+    company_id: String,
+    ccp_id: String,
+) -> Result<bool, String> {
+    let store = CcpPolicyStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.delete(&company_id, &ccp_id)
+        .map_err(|e| format!("정책 삭제 실패: {}", e))
+}
+
+/// Tauri command: HACCP 시정조치 목록 (status: open | verification_pending | verified | closed)
+#[tauri::command]
+pub async fn list_corrective_actions(
+    company_id: String,
+    status: Option<String>,
+) -> Result<Vec<CorrectiveAction>, String> {
+    let store = CorrectiveActionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.list(&company_id, status.as_deref())
+        .map_err(|e| format!("시정조치 조회 실패: {}", e))
+}
+
+/// Tauri command: HACCP 시정조치 상세 (검증 승인 결과 반영 후 반환)
+#[tauri::command]
+pub async fn get_corrective_action(id: String) -> Result<CorrectiveAction, String> {
+    let store = CorrectiveActionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.sync_verification(&id)
+        .map_err(|e| format!("시정조치 조회 실패: {}", e))
+}
+
+/// Tauri command: MES CCP 점검 재판정 후 FAIL/DEVIATION 점검에 대한 시정조치 발행
+///
+/// 새로 이탈한 점검이 없으면 null 반환
+#[tauri::command]
+pub async fn open_corrective_actions_for_checks(
+    company_id: String,
+    ccp_type: String,
+    period_from: String,
+    period_to: String,
+    batch_lot_no: Option<String>,
+) -> Result<Option<CorrectiveAction>, String> {
+    let store = CorrectiveActionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.scan_checks(&company_id, &ccp_type, &period_from, &period_to, batch_lot_no.as_deref())
+        .map_err(|e| format!("시정조치 발행 실패: {}", e))
+}
+
+/// Tauri command: 시정조치 보류 결정/원인/조치 내용 기록 (open 상태에서만)
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// await invoke('update_corrective_action', {
+///   id: 'ca-...',
+///   update: { hold_decision: 'HOLD', root_cause: '증기 밸브 고착', action_taken: '밸브 교체 후 재살균' }
+/// });
+/// ```
+#[tauri::command]
+pub async fn update_corrective_action(
+    id: String,
+    update: CorrectiveActionUpdate,
+) -> Result<CorrectiveAction, String> {
+    let store = CorrectiveActionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.update(&id, &update)
+        .map_err(|e| format!("시정조치 수정 실패: {}", e))
+}
+
+/// Tauri command: 시정조치 검증 승인 요청 (승인/거부는 process_approval로 처리)
+#[tauri::command]
+pub async fn request_corrective_action_verification(
+    id: String,
+    approvers: String,
+    timeout_minutes: Option<i64>,
+) -> Result<CorrectiveAction, String> {
+    let store = CorrectiveActionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.request_verification(&id, &approvers, timeout_minutes)
+        .map_err(|e| format!("검증 요청 실패: {}", e))
+}
+
+/// Tauri command: 시정조치 종결 (검증이 기록되지 않았으면 실패, 종결자는 로그인 사용자)
+#[tauri::command]
+pub async fn close_corrective_action(id: String) -> Result<CorrectiveAction, String> {
+    let user = auth::require(Permission::CloseCorrectiveActions).map_err(|e| e.to_string())?;
+
+    let store = CorrectiveActionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.close(&id, &user.username)
+        .map_err(|e| format!("시정조치 종결 실패: {}", e))
+}
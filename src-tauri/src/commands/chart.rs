@@ -4,6 +4,7 @@
 
 use crate::database::Database;
 use crate::services::chart_service::{ChartResponse, ChartService, LLMChartPlan};
+use crate::services::spc_service::{SpcAnalysis, SpcRequest, SpcService};
 use serde::{Deserialize, Serialize};
 
 /// 차트 생성 요청
@@ -119,6 +120,44 @@ pub fn get_chart_examples() -> Vec<String> {
     ]
 }
 
+/// Tauri command: SPC 관리도 분석 (관리한계, Cpk, Nelson 규칙 위반)
+///
+/// ```typescript
+/// const analysis = await invoke('analyze_spc', {
+///   request: { source: 'sensor_log', param_cd: 'PARAM-PAST-TEMP', equip_cd: null,
+///              from: '2024-11-01', to: '2024-11-30', subgroup_size: 5 }
+/// });
+/// ```
+#[tauri::command]
+pub async fn analyze_spc(request: SpcRequest) -> Result<SpcAnalysis, String> {
+    println!("📈 [IPC] analyze_spc called: {}", request.source.measure_name());
+
+    tokio::task::spawn_blocking(move || {
+        SpcService::new()
+            .and_then(|service| service.analyze(&request))
+            .map_err(|e| format!("SPC 분석 실패: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Task 실행 실패: {}", e))?
+}
+
+/// Tauri command: SPC 관리도 차트 생성 (LLM 호출 없음)
+#[tauri::command]
+pub async fn generate_spc_chart(request: SpcRequest) -> Result<GenerateChartResponse, String> {
+    Ok(match analyze_spc(request).await {
+        Ok(analysis) => GenerateChartResponse {
+            success: true,
+            chart: Some(ChartService::spc_chart(&analysis)),
+            error: None,
+        },
+        Err(e) => GenerateChartResponse {
+            success: false,
+            chart: None,
+            error: Some(e),
+        },
+    })
+}
+
 /// 동기 헬퍼 함수: DB 쿼리 실행 (spawn_blocking용)
 fn execute_chart_query(plan: &LLMChartPlan) -> Result<ChartResponse, String> {
     let chart_service = ChartService::new()
@@ -96,8 +96,10 @@ pub async fn send_chat_message(
     let session_id = if let Some(sid) = request.session_id {
         sid
     } else {
+        // 로그인 사용자의 세션으로 생성
+        let user = crate::services::auth::session().current_user();
         let session = service
-            .create_session(None)
+            .create_session(user.as_ref().map(|u| u.id.as_str()))
             .await
             .map_err(|e| e.to_string())?;
         session.id
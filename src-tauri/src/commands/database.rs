@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::State;
 use crate::database::Database;
+use crate::services::auth::{self, Permission};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TableInfo {
@@ -242,6 +243,7 @@ pub async fn execute_custom_query(
     query: String,
 ) -> Result<QueryResult, String> {
     println!("📊 사용자 정의 쿼리 실행");
+    auth::require(Permission::RunCustomQueries).map_err(|e| e.to_string())?;
 
     // 읽기 전용 쿼리만 허용 (SELECT로 시작)
     let trimmed_query = query.trim().to_uppercase();
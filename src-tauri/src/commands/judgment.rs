@@ -1,5 +1,7 @@
+use crate::database::Database;
 use crate::services::judgment_engine::{JudgmentEngine, JudgmentInput, JudgmentResult};
 use serde::{Deserialize, Serialize};
+use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecuteJudgmentRequest {
@@ -10,9 +12,10 @@ pub struct ExecuteJudgmentRequest {
 #[tauri::command]
 pub async fn execute_judgment(
     request: ExecuteJudgmentRequest,
+    database: State<'_, Database>,
 ) -> Result<JudgmentResult, String> {
     println!("⚖️ [IPC] execute_judgment called! workflow_id: {:?}", request.workflow_id);
-    let engine = JudgmentEngine::new().map_err(|e| e.to_string())?;
+    let engine = JudgmentEngine::from_database(database.inner().clone()).map_err(|e| e.to_string())?;
 
     let input = JudgmentInput {
         workflow_id: request.workflow_id,
@@ -26,9 +29,10 @@ pub async fn execute_judgment(
 pub async fn get_judgment_history(
     workflow_id: Option<String>,
     limit: Option<u32>,
+    database: State<'_, Database>,
 ) -> Result<Vec<JudgmentResult>, String> {
     println!("📊 [IPC] get_judgment_history called! workflow_id: {:?}, limit: {:?}", workflow_id, limit);
-    let engine = JudgmentEngine::new().map_err(|e| e.to_string())?;
+    let engine = JudgmentEngine::from_database(database.inner().clone()).map_err(|e| e.to_string())?;
     engine.get_history(workflow_id, limit.unwrap_or(50))
         .await
         .map_err(|e| e.to_string())
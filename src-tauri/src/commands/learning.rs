@@ -1,5 +1,7 @@
-use crate::services::learning_service::LearningService;
+use crate::database::{Database, RuleEvaluationRecord};
+use crate::services::learning_service::{LearningService, RuleExtractionReport};
 use serde::{Deserialize, Serialize};
+use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SaveFeedbackRequest {
@@ -27,8 +29,11 @@ pub struct TrainingSample {
 }
 
 #[tauri::command]
-pub async fn save_feedback(request: SaveFeedbackRequest) -> Result<(), String> {
-    let service = LearningService::new().map_err(|e| e.to_string())?;
+pub async fn save_feedback(
+    request: SaveFeedbackRequest,
+    database: State<'_, Database>,
+) -> Result<(), String> {
+    let service = LearningService::from_database(database.inner().clone()).map_err(|e| e.to_string())?;
 
     service
         .save_feedback(
@@ -37,6 +42,7 @@ pub async fn save_feedback(request: SaveFeedbackRequest) -> Result<(), String> {
             request.value,
             request.comment,
         )
+        .await
         .map_err(|e| e.to_string())?;
 
     Ok(())
@@ -45,8 +51,9 @@ pub async fn save_feedback(request: SaveFeedbackRequest) -> Result<(), String> {
 #[tauri::command]
 pub async fn get_few_shot_samples(
     request: FewShotSamplesRequest,
+    database: State<'_, Database>,
 ) -> Result<Vec<TrainingSample>, String> {
-    let service = LearningService::new().map_err(|e| e.to_string())?;
+    let service = LearningService::from_database(database.inner().clone()).map_err(|e| e.to_string())?;
 
     let samples = service
         .get_few_shot_samples(request.workflow_id, request.limit)
@@ -67,8 +74,17 @@ pub async fn get_few_shot_samples(
 }
 
 #[tauri::command]
-pub async fn extract_rules(workflow_id: String) -> Result<String, String> {
-    let service = LearningService::new().map_err(|e| e.to_string())?;
+pub async fn extract_rules(
+    workflow_id: String,
+    min_f1: Option<f64>,
+    database: State<'_, Database>,
+) -> Result<RuleExtractionReport, String> {
+    let mut service = LearningService::from_database(database.inner().clone()).map_err(|e| e.to_string())?;
+    if let Some(min_f1) = min_f1 {
+        service = service
+            .with_min_promotion_f1(min_f1)
+            .map_err(|e| e.to_string())?;
+    }
 
     let result = service
         .extract_rules(workflow_id)
@@ -77,3 +93,16 @@ pub async fn extract_rules(workflow_id: String) -> Result<String, String> {
 
     Ok(result)
 }
+
+#[tauri::command]
+pub async fn get_rule_evaluation_reports(
+    workflow_id: String,
+    limit: Option<u32>,
+    database: State<'_, Database>,
+) -> Result<Vec<RuleEvaluationRecord>, String> {
+    let service = LearningService::from_database(database.inner().clone()).map_err(|e| e.to_string())?;
+
+    service
+        .get_rule_evaluation_reports(&workflow_id, limit.unwrap_or(20))
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,106 @@
+//! LOT 출하 보류/해제 Tauri 커맨드
+//!
+//! 판단 NG / CCP 시정조치로 보류된 LOT 조회, 수동 보류, 해제 승인 요청, LOT 추적
+
+use crate::services::lot_disposition::{LotDispositionStore, LotHold, LotType, OutboundCheck};
+use crate::services::traceability::{TraceDirection, TraceReport, TraceabilityService};
+
+/// Tauri command: LOT 보류 목록 (status: HOLD | RELEASE_PENDING | RELEASED, 없으면 전체)
+#[tauri::command]
+pub async fn list_lot_holds(status: Option<String>) -> Result<Vec<LotHold>, String> {
+    let store = LotDispositionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.list(status.as_deref())
+        .map_err(|e| format!("LOT 보류 조회 실패: {}", e))
+}
+
+/// Tauri command: LOT 보류 상세 (해제 승인 결과 반영 후 반환)
+#[tauri::command]
+pub async fn get_lot_hold(id: String) -> Result<LotHold, String> {
+    let store = LotDispositionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.sync_release(&id)
+        .map_err(|e| format!("LOT 보류 조회 실패: {}", e))
+}
+
+/// Tauri command: LOT별 보류 이력
+#[tauri::command]
+pub async fn get_lot_hold_history(lot_no: String) -> Result<Vec<LotHold>, String> {
+    let store = LotDispositionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.history(&lot_no)
+        .map_err(|e| format!("LOT 보류 이력 조회 실패: {}", e))
+}
+
+/// Tauri command: LOT 수동 보류 (lot_type: BATCH | FG, 없으면 자동 판별)
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// await invoke('place_lot_hold', {
+///   lotNo: 'FG-20241105-001', lotType: 'FG', reason: '고객 클레임 조사', heldBy: 'qa01'
+/// });
+/// ```
+#[tauri::command]
+pub async fn place_lot_hold(
+    lot_no: String,
+    lot_type: Option<String>,
+    reason: String,
+    held_by: String,
+) -> Result<LotHold, String> {
+    let lot_type = match lot_type.as_deref() {
+        Some(value) => Some(LotType::parse(value).ok_or_else(|| format!("지원하지 않는 LOT 종류: {}", value))?),
+        None => None,
+    };
+    let store = LotDispositionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.place_hold(&lot_no, lot_type, &reason, "manual", None, &held_by)
+        .map_err(|e| format!("LOT 보류 실패: {}", e))
+}
+
+/// Tauri command: 완제품 LOT 출고 가능 여부 (보류 중이면 차단 보류 목록 포함)
+#[tauri::command]
+pub async fn check_lot_outbound(fg_lot_no: String) -> Result<OutboundCheck, String> {
+    let store = LotDispositionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.check_outbound(&fg_lot_no)
+        .map_err(|e| format!("출고 가능 여부 조회 실패: {}", e))
+}
+
+/// Tauri command: LOT 보류 해제 승인 요청 (보류 이후 QC 합격 필요, 승인/거부는 process_approval)
+#[tauri::command]
+pub async fn request_lot_release(
+    id: String,
+    approvers: String,
+    timeout_minutes: Option<i64>,
+) -> Result<LotHold, String> {
+    let store = LotDispositionStore::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    store.request_release(&id, &approvers, timeout_minutes)
+        .map_err(|e| format!("해제 요청 실패: {}", e))
+}
+
+/// Tauri command: LOT 추적 (direction: forward | backward, 리콜 모의훈련용)
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// // 원료 LOT이 들어간 완제품 LOT과 출고처
+/// await invoke('trace_lot', { lotNo: 'RM001-240805-01', direction: 'forward' });
+/// // 완제품 LOT에 투입된 원료 LOT과 입고처
+/// await invoke('trace_lot', { lotNo: 'FG-20241105-001', direction: 'backward' });
+/// ```
+#[tauri::command]
+pub async fn trace_lot(lot_no: String, direction: String) -> Result<TraceReport, String> {
+    let direction = TraceDirection::parse(&direction)
+        .ok_or_else(|| format!("지원하지 않는 추적 방향: {}", direction))?;
+    let service = TraceabilityService::new()
+        .map_err(|e| format!("Service 초기화 실패: {}", e))?;
+
+    service.trace(&lot_no, direction)
+        .map_err(|e| format!("LOT 추적 실패: {}", e))
+}
@@ -1,5 +1,10 @@
+use crate::database::Database;
 use crate::services::mes_data_service::MesDataService;
+use crate::services::mes_import::{self, SheetSummary};
+use crate::services::mes_ingest::{MesIngestService, MesUpload};
+use crate::services::mes_query::{MesQueryService, QueryOutcome, QueryPlan};
 use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
 
 /// MES/ERP 데이터 업로드 결과
 #[derive(Debug, Serialize, Deserialize)]
@@ -45,13 +50,14 @@ pub async fn upload_mes_data(
     session_id: String,
     file_name: String,
     file_content: Vec<u8>,
+    database: State<'_, Database>,
 ) -> Result<MesUploadResult, String> {
     println!("📤 [IPC] upload_mes_data called!");
     println!("   session_id: {}", session_id);
     println!("   file_name: {}", file_name);
     println!("   file_size: {} bytes", file_content.len());
 
-    let service = MesDataService::new()
+    let service = MesDataService::from_database(database.inner().clone())
         .map_err(|e| format!("Service 초기화 실패: {}", e))?;
 
     let row_count = service.upload_mes_data(
@@ -91,13 +97,14 @@ pub async fn query_mes_data(
     session_id: String,
     question: String,
     top_k: Option<usize>,
+    database: State<'_, Database>,
 ) -> Result<MesQueryResult, String> {
     println!("🔍 [IPC] query_mes_data called!");
     println!("   session_id: {}", session_id);
     println!("   question: {}", question);
     println!("   top_k: {:?}", top_k);
 
-    let service = MesDataService::new()
+    let service = MesDataService::from_database(database.inner().clone())
         .map_err(|e| format!("Service 초기화 실패: {}", e))?;
 
     let answer = service.query_mes_data(
@@ -130,11 +137,12 @@ pub async fn query_mes_data(
 #[tauri::command]
 pub async fn delete_mes_session(
     session_id: String,
+    database: State<'_, Database>,
 ) -> Result<usize, String> {
     println!("🗑️  [IPC] delete_mes_session called!");
     println!("   session_id: {}", session_id);
 
-    let service = MesDataService::new()
+    let service = MesDataService::from_database(database.inner().clone())
         .map_err(|e| format!("Service 초기화 실패: {}", e))?;
 
     let deleted = service.delete_session_data(&session_id)
@@ -157,11 +165,12 @@ pub async fn delete_mes_session(
 #[tauri::command]
 pub async fn get_mes_session_stats(
     session_id: String,
+    database: State<'_, Database>,
 ) -> Result<Option<MesSessionStats>, String> {
     println!("📊 [IPC] get_mes_session_stats called!");
     println!("   session_id: {}", session_id);
 
-    let service = MesDataService::new()
+    let service = MesDataService::from_database(database.inner().clone())
         .map_err(|e| format!("Service 초기화 실패: {}", e))?;
 
     let stats = service.get_session_stats(&session_id)
@@ -179,3 +188,119 @@ pub async fn get_mes_session_stats(
 
     Ok(result)
 }
+
+/// Tauri command: 대용량 MES 파일 경로 적재 (CSV/xlsx/Parquet, 스트리밍, 진행률 이벤트)
+///
+/// 배치마다 `mes-ingest-progress` 이벤트(IngestProgress)를 발생시킨다.
+/// xlsx는 sheet로 시트를 지정하며 생략 시 데이터가 있는 첫 시트를 적재한다.
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// const unlisten = await listen<IngestProgress>('mes-ingest-progress', (e) => {
+///   const { rows_processed, total_rows, bytes_read, total_bytes } = e.payload;
+///   setProgress(total_rows ? rows_processed / total_rows : bytes_read / (total_bytes ?? 1));
+/// });
+/// const filePath = await open({ filters: [{ name: 'MES', extensions: ['csv', 'xlsx', 'xls', 'parquet'] }] });
+/// const sheets = await invoke<SheetSummary[]>('list_mes_sheets', { filePath }); // xlsx만
+/// const upload = await invoke<MesUpload>('ingest_mes_file', { sessionId, filePath, sheet: sheets[0]?.name });
+/// console.log(`${upload.row_count}건 적재 (${upload.source_format})`, upload.columns);
+/// unlisten();
+/// ```
+#[tauri::command]
+pub async fn ingest_mes_file(
+    app_handle: tauri::AppHandle,
+    session_id: String,
+    file_path: String,
+    sheet: Option<String>,
+    database: State<'_, Database>,
+) -> Result<MesUpload, String> {
+    println!("📤 [IPC] ingest_mes_file called!");
+    println!("   session_id: {}", session_id);
+    println!("   file_path: {}", file_path);
+    println!("   sheet: {:?}", sheet);
+
+    // 수십만 행 적재는 blocking 작업이므로 별도 스레드에서 실행
+    let db = database.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let service = MesIngestService::from_database(db);
+
+        service
+            .ingest_file(&session_id, std::path::Path::new(&file_path), sheet.as_deref(), &mut |progress| {
+                if let Err(e) = app_handle.emit_all("mes-ingest-progress", progress.clone()) {
+                    eprintln!("⚠️  진행률 이벤트 전송 실패: {}", e);
+                }
+            })
+            .map_err(|e| format!("적재 실패: {}", e))
+    })
+    .await
+    .map_err(|e| format!("적재 작업 실패: {}", e))?
+}
+
+/// Tauri command: xlsx 통합문서 시트 목록 (시트별 감지된 헤더 포함)
+#[tauri::command]
+pub async fn list_mes_sheets(file_path: String) -> Result<Vec<SheetSummary>, String> {
+    tokio::task::spawn_blocking(move || {
+        mes_import::list_sheets(std::path::Path::new(&file_path))
+            .map_err(|e| format!("시트 목록 조회 실패: {}", e))
+    })
+    .await
+    .map_err(|e| format!("시트 목록 조회 작업 실패: {}", e))?
+}
+
+/// Tauri command: MES 업로드 기록 목록 (컬럼 스키마 포함, session_id 없으면 전체)
+#[tauri::command]
+pub async fn list_mes_uploads(
+    session_id: Option<String>,
+    database: State<'_, Database>,
+) -> Result<Vec<MesUpload>, String> {
+    let service = MesIngestService::from_database(database.inner().clone());
+
+    service.list_uploads(session_id.as_deref())
+        .map_err(|e| format!("업로드 기록 조회 실패: {}", e))
+}
+
+/// Tauri command: MES 업로드 기록 상세
+#[tauri::command]
+pub async fn get_mes_upload(
+    upload_id: String,
+    database: State<'_, Database>,
+) -> Result<Option<MesUpload>, String> {
+    let service = MesIngestService::from_database(database.inner().clone());
+
+    service.get_upload(&upload_id)
+        .map_err(|e| format!("업로드 기록 조회 실패: {}", e))
+}
+
+/// Tauri command: 질문 → 구조화 질의 계획 (세션 컬럼 스키마로 검증, 구조화할 수 없으면 null)
+///
+/// Frontend 사용 예시:
+/// ```typescript
+/// const plan = await invoke<QueryPlan | null>('plan_mes_query', {
+///   sessionId, question: '온도가 90도 이상인 데이터는?'
+/// });
+/// // { filters: [{ column: '온도', op: 'gte', value: 90 }], ... }
+/// ```
+#[tauri::command]
+pub async fn plan_mes_query(
+    session_id: String,
+    question: String,
+    database: State<'_, Database>,
+) -> Result<Option<QueryPlan>, String> {
+    let service = MesQueryService::from_database(database.inner().clone());
+
+    service.plan(&session_id, &question)
+        .map_err(|e| format!("질의 계획 실패: {}", e))
+}
+
+/// Tauri command: 구조화 질의 실행 (계획은 실행 전에 다시 검증)
+#[tauri::command]
+pub async fn run_mes_query(
+    session_id: String,
+    plan: QueryPlan,
+    database: State<'_, Database>,
+) -> Result<QueryOutcome, String> {
+    let service = MesQueryService::from_database(database.inner().clone());
+
+    service.execute(&session_id, plan)
+        .map_err(|e| format!("질의 실행 실패: {}", e))
+}
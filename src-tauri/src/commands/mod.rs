@@ -1,5 +1,6 @@
 pub mod judgment;
 pub mod learning;
+pub mod prompt;
 pub mod bi;
 pub mod chat;
 pub mod workflow;
@@ -8,7 +9,11 @@ pub mod workflow_types;
 pub mod system;
 pub mod update;
 pub mod backup;
+pub mod retention;
+pub mod audit;
+pub mod auth;
 pub mod ccp;
 pub mod mes;
 pub mod database;
 pub mod chart;
+pub mod lot;
@@ -0,0 +1,40 @@
+use crate::database::{PromptTemplate, PromptVariantStats};
+use crate::services::prompt_engine::PromptEngine;
+
+/// 템플릿을 A/B 실험에 추가하거나 트래픽 가중치 변경 (0이면 트래픽 제외)
+#[tauri::command]
+pub async fn set_prompt_variant_weight(template_id: String, weight: f64) -> Result<(), String> {
+    println!("🧪 [IPC] set_prompt_variant_weight called! template_id: {}, weight: {}", template_id, weight);
+    let engine = PromptEngine::new().map_err(|e| e.to_string())?;
+    engine
+        .set_variant_weight(&template_id, weight)
+        .map_err(|e| e.to_string())
+}
+
+/// A/B 실험에서 템플릿 제외
+#[tauri::command]
+pub async fn remove_prompt_variant(template_id: String) -> Result<(), String> {
+    println!("🧪 [IPC] remove_prompt_variant called! template_id: {}", template_id);
+    let engine = PromptEngine::new().map_err(|e| e.to_string())?;
+    engine.remove_variant(&template_id).map_err(|e| e.to_string())
+}
+
+/// template_type별 변형 성과 비교 (정확도, 신뢰도, 지연시간, 토큰 비용)
+#[tauri::command]
+pub async fn get_prompt_ab_report(template_type: String) -> Result<Vec<PromptVariantStats>, String> {
+    println!("🧪 [IPC] get_prompt_ab_report called! template_type: {}", template_type);
+    let engine = PromptEngine::new().map_err(|e| e.to_string())?;
+    engine
+        .get_variant_report(&template_type)
+        .map_err(|e| e.to_string())
+}
+
+/// 승자 템플릿을 is_active로 승격하고 실험 종료
+#[tauri::command]
+pub async fn promote_prompt_template(template_id: String) -> Result<PromptTemplate, String> {
+    println!("🧪 [IPC] promote_prompt_template called! template_id: {}", template_id);
+    let engine = PromptEngine::new().map_err(|e| e.to_string())?;
+    engine
+        .promote_variant(&template_id)
+        .map_err(|e| e.to_string())
+}
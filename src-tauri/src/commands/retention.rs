@@ -0,0 +1,67 @@
+use crate::database::retention::{self, ArchiveFile, ArchiveQueryResult, DataRetentionPolicy, RetentionStatus};
+use crate::database::{Database, RetentionReport};
+use crate::services::auth::{self, Permission};
+use crate::utils::app_paths::AppPaths;
+use tauri::State;
+
+fn app_paths() -> Result<AppPaths, String> {
+    AppPaths::resolve().map_err(|e| format!("Failed to resolve data directory: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_retention_policy() -> Result<DataRetentionPolicy, String> {
+    DataRetentionPolicy::load(&app_paths()?.data_dir())
+        .map_err(|e| format!("Failed to load retention policy: {}", e))
+}
+
+/// 보존 정책 저장 (HACCP 보존 기간은 법정 최소 기간 미만 불가)
+#[tauri::command]
+pub async fn update_retention_policy(policy: DataRetentionPolicy) -> Result<DataRetentionPolicy, String> {
+    auth::require(Permission::ManageDataRetention).map_err(|e| e.to_string())?;
+    let data_dir = app_paths()?
+        .ensure_data_dir()
+        .map_err(|e| format!("Failed to prepare data directory: {}", e))?;
+
+    policy
+        .save(&data_dir)
+        .map_err(|e| format!("Failed to save retention policy: {}", e))?;
+
+    Ok(policy)
+}
+
+#[tauri::command]
+pub async fn get_retention_status() -> Result<RetentionStatus, String> {
+    RetentionStatus::load(&app_paths()?.data_dir())
+        .map_err(|e| format!("Failed to load retention status: {}", e))
+}
+
+/// 보존 정책 즉시 적용 (정책 enabled와 무관)
+#[tauri::command]
+pub async fn run_retention_now(database: State<'_, Database>) -> Result<RetentionReport, String> {
+    auth::require(Permission::ManageDataRetention).map_err(|e| e.to_string())?;
+    let database = database.inner().clone();
+    let paths = app_paths()?;
+
+    tokio::task::spawn_blocking(move || retention::run_for_app(&database, &paths))
+        .await
+        .map_err(|e| format!("Failed to run retention task: {}", e))?
+        .map_err(|e| format!("Failed to apply retention policy: {:#}", e))
+}
+
+#[tauri::command]
+pub async fn list_archives() -> Result<Vec<ArchiveFile>, String> {
+    retention::list_archives(&app_paths()?.archive_dir())
+        .map_err(|e| format!("Failed to list archives: {}", e))
+}
+
+/// 월별 아카이브 읽기 전용 조회 (SELECT만 허용)
+#[tauri::command]
+pub async fn query_archive(table: String, month: String, sql: String) -> Result<ArchiveQueryResult, String> {
+    auth::require(Permission::RunCustomQueries).map_err(|e| e.to_string())?;
+    let archive_dir = app_paths()?.archive_dir();
+
+    tokio::task::spawn_blocking(move || retention::query_archive(&archive_dir, &table, &month, &sql))
+        .await
+        .map_err(|e| format!("Failed to run archive query task: {}", e))?
+        .map_err(|e| format!("Failed to query archive: {:#}", e))
+}
@@ -1,4 +1,8 @@
+use crate::database::{Database, DemoDataReport};
+use crate::services::auth::{self, Permission, UserStore};
+use crate::utils::app_paths::AppPaths;
 use serde::{Deserialize, Serialize};
+use tauri::State;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SystemStatus {
@@ -20,20 +24,12 @@ pub struct SystemStats {
 #[tauri::command]
 pub async fn get_system_status() -> Result<SystemStatus, String> {
     println!("ℹ️ [IPC] get_system_status called!");
-    use crate::database::Database;
-
     let db_connected = Database::new().is_ok();
     let claude_configured = std::env::var("ANTHROPIC_API_KEY").is_ok();
 
-    let db_path = if let Some(data_dir) = dirs::data_local_dir() {
-        data_dir
-            .join("Judgify")
-            .join("judgify.db")
-            .to_string_lossy()
-            .to_string()
-    } else {
-        "Unknown".to_string()
-    };
+    let db_path = AppPaths::resolve()
+        .map(|paths| paths.db_path().to_string_lossy().to_string())
+        .unwrap_or_else(|_| "Unknown".to_string());
 
     Ok(SystemStatus {
         database_connected: db_connected,
@@ -45,11 +41,9 @@ pub async fn get_system_status() -> Result<SystemStatus, String> {
 }
 
 #[tauri::command]
-pub async fn get_system_stats() -> Result<SystemStats, String> {
+pub async fn get_system_stats(database: State<'_, Database>) -> Result<SystemStats, String> {
     println!("📊 [IPC] get_system_stats called!");
-    use crate::database::Database;
-
-    let db = Database::new().map_err(|e| e.to_string())?;
+    let db = database.inner().clone();
 
     // Simplified stats calculation
     let total_judgments = db
@@ -77,32 +71,24 @@ pub async fn get_system_stats() -> Result<SystemStats, String> {
 #[tauri::command]
 pub async fn get_data_directory() -> Result<String, String> {
     println!("📁 [IPC] get_data_directory called!");
-    if let Some(data_dir) = dirs::data_local_dir() {
-        Ok(data_dir.join("Judgify").to_string_lossy().to_string())
-    } else {
-        Err("Could not determine data directory".to_string())
-    }
+    AppPaths::resolve()
+        .map(|paths| paths.data_dir().to_string_lossy().to_string())
+        .map_err(|e| format!("Could not determine data directory: {}", e))
 }
 
+/// DB 파일 내보내기 (passphrase가 있으면 암호화, restore_backup으로 복구 가능)
+///
+/// WAL에만 있는 커밋도 포함되도록 DB 파일을 직접 복사하지 않고 읽기 연결에서 VACUUM INTO 스냅샷을 만든다.
 #[tauri::command]
-pub async fn export_database(export_path: String) -> Result<(), String> {
+pub async fn export_database(
+    export_path: String,
+    passphrase: Option<String>,
+    database: State<'_, Database>,
+) -> Result<(), String> {
     println!("💾 [IPC] export_database called! export_path: {:?}", export_path);
+    auth::require(Permission::ExportDatabase).map_err(|e| e.to_string())?;
     use std::fs;
-    use std::path::Path;
-
-    let db_path = if let Some(data_dir) = dirs::data_local_dir() {
-        data_dir.join("Judgify").join("judgify.db")
-    } else {
-        return Err("Could not determine database path".to_string());
-    };
-
-    // 원본 데이터베이스 파일 존재 여부 확인
-    if !db_path.exists() {
-        return Err(format!(
-            "데이터베이스 파일이 아직 생성되지 않았습니다. 앱을 먼저 사용하여 데이터를 생성한 후 백업하세요.\n경로: {:?}",
-            db_path
-        ));
-    }
+    use std::path::{Path, PathBuf};
 
     // 대상 디렉토리가 존재하지 않으면 생성
     if let Some(parent_dir) = Path::new(&export_path).parent() {
@@ -112,24 +98,74 @@ pub async fn export_database(export_path: String) -> Result<(), String> {
         }
     }
 
-    fs::copy(&db_path, &export_path).map_err(|e| e.to_string())?;
+    // VACUUM INTO는 기존 파일에 쓸 수 없으므로 대상 옆 임시 파일에 스냅샷 생성
+    let snapshot_path = PathBuf::from(format!("{}.snapshot", export_path));
+    let _ = fs::remove_file(&snapshot_path);
+
+    let db = database.inner().clone();
+    let snapshot = snapshot_path.clone();
+    tokio::task::spawn_blocking(move || {
+        let reader = db.get_reader();
+        let conn = reader.lock().map_err(|e| format!("DB lock 실패: {}", e))?;
+        conn.execute("VACUUM INTO ?1", [snapshot.to_string_lossy()])
+            .map(|_| ())
+            .map_err(|e| format!("데이터베이스 스냅샷 생성 실패: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let result = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => encrypt_export(&snapshot_path, Path::new(&export_path), passphrase),
+        None => fs::rename(&snapshot_path, &export_path).map_err(|e| e.to_string()),
+    };
+    let _ = fs::remove_file(&snapshot_path);
+    result?;
     println!("✅ [IPC] Database exported successfully to: {:?}", export_path);
 
     Ok(())
 }
 
+/// 스냅샷을 passphrase로 암호화해 내보내기 파일로 기록
+fn encrypt_export(snapshot_path: &std::path::Path, export_path: &std::path::Path, passphrase: String) -> Result<(), String> {
+    use crate::database::backup_crypto::{BackupKey, EncryptWriter};
+    use std::fs;
+
+    let mut source = fs::File::open(snapshot_path).map_err(|e| e.to_string())?;
+    let target = fs::File::create(export_path).map_err(|e| e.to_string())?;
+    let mut writer = EncryptWriter::new(target, &BackupKey::Passphrase(passphrase))
+        .map_err(|e| format!("암호화 준비 실패: {}", e))?;
+    std::io::copy(&mut source, &mut writer).map_err(|e| format!("암호화 실패: {}", e))?;
+    writer.finish().map_err(|e| format!("암호화 실패: {}", e))?;
+    Ok(())
+}
+
+/// 시연용 데모 데이터 적재 (opt-in, 이미 적재한 스크립트는 건너뜀)
 #[tauri::command]
-pub async fn get_token_metrics() -> Result<crate::database::sqlite::TokenMetrics, String> {
-    println!("📊 [IPC] get_token_metrics called!");
-    use crate::database::Database;
+pub async fn load_demo_data(database: State<'_, Database>) -> Result<DemoDataReport, String> {
+    println!("🌱 [IPC] load_demo_data called!");
+    auth::require(Permission::LoadDemoData).map_err(|e| e.to_string())?;
+    let db = database.inner().clone();
+    tokio::task::spawn_blocking(move || db.load_demo_data())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("데모 데이터 적재 실패: {}", e))
+}
 
-    let db = Database::new().map_err(|e| e.to_string())?;
+#[tauri::command]
+pub async fn get_token_metrics(
+    database: State<'_, Database>,
+) -> Result<crate::database::sqlite::TokenMetrics, String> {
+    println!("📊 [IPC] get_token_metrics called!");
+    let db = database.inner().clone();
     db.get_token_metrics().map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn save_api_key(api_key: String) -> Result<(), String> {
+pub async fn save_api_key(api_key: String, database: State<'_, Database>) -> Result<(), String> {
     println!("🔑 [IPC] save_api_key called!");
+    // 첫 관리자 생성 전에는 초기 설정을 위해 허용
+    auth::require_after_setup(&UserStore::from_database(database.inner().clone()), Permission::ManageApiKeys)
+        .map_err(|e| e.to_string())?;
 
     // API 키 형식 검증
     if !api_key.starts_with("sk-ant-") {
@@ -162,6 +198,7 @@ pub async fn save_api_key(api_key: String) -> Result<(), String> {
     Ok(())
 }
 
+/// 저장된 API 키를 현재 세션 환경 변수에 적용하고 마스킹된 값만 반환 (원문은 화면에 노출하지 않음)
 #[tauri::command]
 pub async fn load_api_key() -> Result<String, String> {
     println!("🔑 [IPC] load_api_key called!");
@@ -175,7 +212,7 @@ pub async fn load_api_key() -> Result<String, String> {
                     // 환경 변수에도 설정
                     std::env::set_var("ANTHROPIC_API_KEY", &api_key);
 
-                    Ok(api_key)
+                    Ok(mask_api_key(&api_key))
                 }
                 Err(e) => {
                     eprintln!("⚠️  [IPC] API 키 로드 실패: {}", e);
@@ -190,9 +227,22 @@ pub async fn load_api_key() -> Result<String, String> {
     }
 }
 
+/// API 키 마스킹 (접두어 + 마지막 4자리, 예: "sk-ant-...x1y2")
+fn mask_api_key(api_key: &str) -> String {
+    let chars: Vec<char> = api_key.chars().collect();
+    if chars.len() <= 11 {
+        return "...".to_string();
+    }
+    let prefix: String = chars[..7].iter().collect();
+    let suffix: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}...{}", prefix, suffix)
+}
+
 #[tauri::command]
-pub async fn delete_api_key() -> Result<(), String> {
+pub async fn delete_api_key(database: State<'_, Database>) -> Result<(), String> {
     println!("🔑 [IPC] delete_api_key called!");
+    auth::require_after_setup(&UserStore::from_database(database.inner().clone()), Permission::ManageApiKeys)
+        .map_err(|e| e.to_string())?;
 
     match keyring::Entry::new("Judgify", "claude_api_key") {
         Ok(entry) => {
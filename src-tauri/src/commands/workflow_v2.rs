@@ -2,14 +2,22 @@ use serde::{Deserialize, Serialize};
 use crate::services::workflow_service::WorkflowService;
 use crate::engines::rule_engine::RuleEngine;
 use crate::services::judgment_engine::{JudgmentEngine, JudgmentInput};
+use crate::services::spc_service::{SpcRequest, SpcService};
+use crate::services::ccp_corrective_action::{CorrectiveActionStore, CORRECTIVE_ACTION_WORKFLOW_ID};
+use crate::services::lot_disposition::{LotDispositionStore, LOT_RELEASE_WORKFLOW_ID};
+use crate::services::traceability::{TraceDirection, TraceabilityService};
+use crate::services::audit_log::{self, NewAuditEntry};
+use crate::services::auth::{self, Permission};
 use serde_json::json;
+use crate::database::Database;
 use rusqlite::{params, Connection};
+use std::sync::{Arc, Mutex};
 
 /// Phase 9 WorkflowBuilderV2용 데이터 구조
 ///
 /// Ver2.0 6개 NodeType:
 /// - TRIGGER: 트리거 (임계값, 스케줄, 이벤트, 수동)
-/// - QUERY: 데이터 조회 (DB, API, 센서, 파일)
+/// - QUERY: 데이터 조회 (DB, API, 센서, 파일, LOT 추적)
 /// - CALC: 계산 (수식, 집계, 변환)
 /// - JUDGMENT: AI 판단 (Rule/LLM/Hybrid)
 /// - APPROVAL: 승인 (수동, 자동, 조건부)
@@ -192,6 +200,8 @@ pub async fn list_workflows_v2() -> Result<Vec<WorkflowListItem>, String> {
 pub async fn delete_workflow_v2(workflow_id: String) -> Result<String, String> {
     println!("🗑️ [WorkflowV2] 워크플로우 삭제: {}", workflow_id);
 
+    auth::require(Permission::DeleteWorkflows).map_err(|e| e.to_string())?;
+
     let service = WorkflowService::new()
         .map_err(|e| format!("WorkflowService 초기화 실패: {}", e))?;
 
@@ -323,15 +333,18 @@ pub async fn simulate_workflow_v2(
 
     // DB에 실행 이력 저장
     let execution_id = match get_db_connection() {
-        Ok(conn) => {
-            match save_workflow_execution(
-                &conn,
-                &request.workflow_id,
-                &overall_status,
-                &steps_executed,
-                &global_data,
-                total_time,
-            ) {
+        Ok(db_conn) => {
+            let saved = db_conn.lock()
+                .map_err(|e| format!("DB lock 실패: {}", e))
+                .and_then(|conn| save_workflow_execution(
+                    &conn,
+                    &request.workflow_id,
+                    &overall_status,
+                    &steps_executed,
+                    &global_data,
+                    total_time,
+                ));
+            match saved {
                 Ok(id) => Some(id),
                 Err(e) => {
                     eprintln!("⚠️ [WorkflowV2] DB 저장 실패 (무시): {}", e);
@@ -483,15 +496,10 @@ async fn execute_query_step(
             // 실제 SQLite 데이터베이스 조회
             let query_type = config["queryType"].as_str().unwrap_or("sql");
 
-            // DB 경로 가져오기
-            let app_data = std::env::var("APPDATA")
-                .or_else(|_| std::env::var("HOME"))
-                .map_err(|e| format!("환경변수 오류: {}", e))?;
-            let db_path = std::path::PathBuf::from(app_data).join("Judgify").join("judgify.db");
-
-            // DB 연결
-            let conn = Connection::open(&db_path)
-                .map_err(|e| format!("DB 연결 실패: {}", e))?;
+            // DB 연결 (조회 전용이므로 읽기 연결 사용)
+            let db_conn = get_db_reader()?;
+            let conn = db_conn.lock()
+                .map_err(|e| format!("DB lock 실패: {}", e))?;
 
             // 쿼리 실행
             let query_result = if query.is_empty() {
@@ -597,6 +605,47 @@ async fn execute_query_step(
                 output_data,
             ))
         }
+        "traceability" => {
+            // LOT 추적 (config.lotNo 또는 입력 데이터의 config.lotField 값, 기본 lot_no)
+            let direction_value = config["direction"].as_str().unwrap_or("forward");
+            let direction = TraceDirection::parse(direction_value)
+                .ok_or_else(|| format!("지원하지 않는 추적 방향: {}", direction_value))?;
+            let lot_field = config["lotField"].as_str().unwrap_or("lot_no");
+            let lot_no = config["lotNo"]
+                .as_str()
+                .or_else(|| input_data[lot_field].as_str())
+                .filter(|lot| !lot.trim().is_empty())
+                .ok_or_else(|| format!("추적할 LOT 번호가 없습니다 (lotNo 또는 입력 필드 {})", lot_field))?;
+
+            let report = TraceabilityService::new()
+                .and_then(|service| service.trace(lot_no, direction))
+                .map_err(|e| format!("LOT 추적 실패: {}", e))?;
+            let trace_result = serde_json::to_value(&report)
+                .map_err(|e| format!("추적 결과 직렬화 실패: {}", e))?;
+
+            let mut output_data = input_data.clone();
+            if let Some(obj) = output_data.as_object_mut() {
+                obj.insert("trace_result".to_string(), trace_result.clone());
+                if let serde_json::Value::Object(trace_fields) = report.judgment_input() {
+                    obj.extend(trace_fields);
+                }
+            }
+
+            Ok((
+                json!({
+                    "step_type": "QUERY",
+                    "data_source": "traceability",
+                    "direction": direction,
+                    "lot_no": report.lot_no,
+                    "data": trace_result,
+                    "message": format!(
+                        "LOT 추적 완료 (완제품 LOT {}개, 출고 {}건)",
+                        report.summary.fg_lot_count, report.summary.shipment_count
+                    )
+                }),
+                output_data,
+            ))
+        }
         _ => Err(format!("지원하지 않는 데이터 소스: {}", data_source)),
     }
 }
@@ -728,6 +777,9 @@ async fn execute_judgment_step(
     let config = &step.config;
     let judgment_method = config["judgmentMethod"].as_str().unwrap_or("rule");
 
+    // SPC 설정이 있으면 관리도 분석 결과(spc_* 변수)를 판단 입력에 병합
+    let input_data = &with_spc_input(config, input_data)?;
+
     match judgment_method {
         "rule" => {
             // Rule Engine만 사용
@@ -783,6 +835,36 @@ async fn execute_judgment_step(
     }
 }
 
+/// JUDGMENT 노드의 `spc` 설정(SpcRequest)으로 SPC 분석 후 입력 데이터에 spc_* 변수 추가
+fn with_spc_input(
+    config: &serde_json::Value,
+    input_data: &serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let Some(spc_config) = config.get("spc").filter(|v| v.is_object()) else {
+        return Ok(input_data.clone());
+    };
+
+    let request: SpcRequest = serde_json::from_value(spc_config.clone())
+        .map_err(|e| format!("SPC 설정 오류: {}", e))?;
+    let analysis = SpcService::new()
+        .and_then(|service| service.analyze(&request))
+        .map_err(|e| format!("SPC 분석 실패: {}", e))?;
+
+    let mut merged = match input_data {
+        serde_json::Value::Object(map) => map.clone(),
+        serde_json::Value::Null => serde_json::Map::new(),
+        other => {
+            let mut map = serde_json::Map::new();
+            map.insert("input".to_string(), other.clone());
+            map
+        }
+    };
+    if let serde_json::Value::Object(spc_fields) = analysis.judgment_input() {
+        merged.extend(spc_fields);
+    }
+    Ok(serde_json::Value::Object(merged))
+}
+
 /// APPROVAL 스텝 실행
 ///
 /// 시뮬레이션 모드 vs 실제 모드:
@@ -1102,15 +1184,17 @@ async fn execute_alert_step(
 
 // ================== DB 저장 헬퍼 함수 ==================
 
-/// DB 연결 가져오기
-fn get_db_connection() -> Result<Connection, String> {
-    let app_data_dir = dirs::data_dir()
-        .ok_or("AppData 디렉토리를 찾을 수 없습니다")?
-        .join("Judgify");
-
-    let db_path = app_data_dir.join("judgify.db");
+/// 앱 DB 쓰기 연결 (연결 풀 공유, 백업 복구 시 함께 닫혔다가 다시 열림)
+fn get_db_connection() -> Result<Arc<Mutex<Connection>>, String> {
+    Database::new()
+        .map(|db| db.get_connection())
+        .map_err(|e| format!("DB 연결 실패: {}", e))
+}
 
-    Connection::open(&db_path)
+/// 앱 DB 읽기 전용 연결 (조회 전용)
+fn get_db_reader() -> Result<Arc<Mutex<Connection>>, String> {
+    Database::new()
+        .map(|db| db.get_reader())
         .map_err(|e| format!("DB 연결 실패: {}", e))
 }
 
@@ -1156,7 +1240,9 @@ pub async fn get_workflow_executions(
     workflow_id: String,
     limit: Option<i64>,
 ) -> Result<Vec<WorkflowExecutionListItem>, String> {
-    let conn = get_db_connection()?;
+    let db_conn = get_db_reader()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     let limit_value = limit.unwrap_or(50);
 
@@ -1197,7 +1283,9 @@ pub async fn get_workflow_executions(
 pub async fn get_workflow_execution_detail(
     execution_id: String,
 ) -> Result<WorkflowExecutionDetail, String> {
-    let conn = get_db_connection()?;
+    let db_conn = get_db_reader()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     let mut stmt = conn
         .prepare(
@@ -1481,6 +1569,8 @@ pub struct ApprovalRequest {
 pub struct ApprovalDecision {
     pub request_id: String,
     pub decision: String, // "approved" or "rejected"
+    /// 로그인 사용자로 기록됨 (요청 값은 무시)
+    #[serde(default)]
     pub decided_by: String,
     pub comment: Option<String>,
 }
@@ -1503,13 +1593,9 @@ fn create_approval_request(
     let expires_at = now + chrono::Duration::minutes(timeout_minutes);
 
     // DB에 승인 요청 저장
-    let app_data = std::env::var("APPDATA")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|e| format!("환경변수 오류: {}", e))?;
-    let db_path = std::path::PathBuf::from(app_data).join("Judgify").join("judgify.db");
-
-    let conn = Connection::open(&db_path)
-        .map_err(|e| format!("DB 연결 실패: {}", e))?;
+    let db_conn = get_db_connection()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     conn.execute(
         "INSERT INTO approval_requests (id, workflow_id, workflow_name, step_id, step_name, approval_type, status, approvers, input_data, condition, timeout_minutes, created_at, expires_at)
@@ -1557,13 +1643,9 @@ fn create_approval_request(
 pub async fn get_pending_approvals() -> Result<Vec<ApprovalRequest>, String> {
     println!("📋 [APPROVAL] 대기 중인 승인 요청 조회");
 
-    let app_data = std::env::var("APPDATA")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|e| format!("환경변수 오류: {}", e))?;
-    let db_path = std::path::PathBuf::from(app_data).join("Judgify").join("judgify.db");
-
-    let conn = Connection::open(&db_path)
-        .map_err(|e| format!("DB 연결 실패: {}", e))?;
+    let db_conn = get_db_connection()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     // 만료된 요청 자동 처리
     let now = chrono::Utc::now().to_rfc3339();
@@ -1610,24 +1692,28 @@ pub async fn get_pending_approvals() -> Result<Vec<ApprovalRequest>, String> {
 
 /// 승인/거부 처리
 #[tauri::command]
-pub async fn process_approval(decision: ApprovalDecision) -> Result<serde_json::Value, String> {
+pub async fn process_approval(mut decision: ApprovalDecision) -> Result<serde_json::Value, String> {
     println!("📋 [APPROVAL] 승인 처리: {} → {}", decision.request_id, decision.decision);
 
+    // QA 이상 로그인 사용자만 승인/거부 가능, 처리자는 세션 사용자
+    let user = auth::require(Permission::DecideApprovals).map_err(|e| e.to_string())?;
+    decision.decided_by = user.username;
+
     if decision.decision != "approved" && decision.decision != "rejected" {
         return Err("decision은 'approved' 또는 'rejected'만 가능합니다".to_string());
     }
 
-    let app_data = std::env::var("APPDATA")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|e| format!("환경변수 오류: {}", e))?;
-    let db_path = std::path::PathBuf::from(app_data).join("Judgify").join("judgify.db");
-
-    let conn = Connection::open(&db_path)
-        .map_err(|e| format!("DB 연결 실패: {}", e))?;
+    let db_conn = get_db_connection()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     let now = chrono::Utc::now().to_rfc3339();
 
-    let affected = conn.execute(
+    // 승인 처리와 감사 기록을 함께 커밋
+    let tx = conn.unchecked_transaction()
+        .map_err(|e| format!("트랜잭션 시작 실패: {}", e))?;
+
+    let affected = tx.execute(
         "UPDATE approval_requests SET status = ?1, decided_by = ?2, decided_at = ?3, comment = ?4 WHERE id = ?5 AND status = 'pending'",
         params![&decision.decision, &decision.decided_by, &now, &decision.comment, &decision.request_id],
     ).map_err(|e| format!("승인 처리 실패: {}", e))?;
@@ -1636,8 +1722,46 @@ pub async fn process_approval(decision: ApprovalDecision) -> Result<serde_json::
         return Err(format!("승인 요청을 찾을 수 없거나 이미 처리되었습니다: {}", decision.request_id));
     }
 
+    audit_log::append(&tx, &NewAuditEntry {
+        actor: &decision.decided_by,
+        action: audit_log::ACTION_APPROVAL_DECIDE,
+        entity_type: "approval_request",
+        entity_id: &decision.request_id,
+        before: Some(json!({ "status": "pending" })),
+        after: Some(json!({
+            "status": decision.decision,
+            "decided_by": decision.decided_by,
+            "decided_at": now,
+            "comment": decision.comment,
+        })),
+    }).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| format!("승인 처리 저장 실패: {}", e))?;
+
     println!("✅ [APPROVAL] 승인 처리 완료: {} by {}", decision.decision, decision.decided_by);
 
+    let (workflow_id, step_id): (String, String) = conn.query_row(
+        "SELECT workflow_id, step_id FROM approval_requests WHERE id = ?1",
+        params![&decision.request_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).map_err(|e| format!("승인 요청 조회 실패: {}", e))?;
+    // 아래 저장소들이 같은 쓰기 연결을 잠그므로 먼저 해제
+    drop(conn);
+
+    // 서비스 승인 요청(시정조치 검증, LOT 보류 해제)이면 결과를 해당 레코드에 반영
+    match workflow_id.as_str() {
+        CORRECTIVE_ACTION_WORKFLOW_ID => {
+            CorrectiveActionStore::new()
+                .and_then(|store| store.sync_verification(&step_id))
+                .map_err(|e| format!("시정조치 검증 반영 실패: {}", e))?;
+        }
+        LOT_RELEASE_WORKFLOW_ID => {
+            LotDispositionStore::new()
+                .and_then(|store| store.sync_release(&step_id))
+                .map_err(|e| format!("LOT 보류 해제 반영 실패: {}", e))?;
+        }
+        _ => {}
+    }
+
     Ok(json!({
         "request_id": decision.request_id,
         "decision": decision.decision,
@@ -1652,13 +1776,9 @@ pub async fn process_approval(decision: ApprovalDecision) -> Result<serde_json::
 pub async fn get_approval_request(request_id: String) -> Result<ApprovalRequest, String> {
     println!("📋 [APPROVAL] 승인 요청 상세 조회: {}", request_id);
 
-    let app_data = std::env::var("APPDATA")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|e| format!("환경변수 오류: {}", e))?;
-    let db_path = std::path::PathBuf::from(app_data).join("Judgify").join("judgify.db");
-
-    let conn = Connection::open(&db_path)
-        .map_err(|e| format!("DB 연결 실패: {}", e))?;
+    let db_conn = get_db_reader()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     let mut stmt = conn.prepare(
         "SELECT id, workflow_id, workflow_name, step_id, step_name, approval_type, status, approvers, input_data, condition, timeout_minutes, decided_by, decided_at, comment, created_at, expires_at
@@ -1753,7 +1873,9 @@ pub async fn get_workflow_schedules(
 ) -> Result<Vec<WorkflowSchedule>, String> {
     println!("📅 [SCHEDULER] 스케줄 목록 조회");
 
-    let conn = get_db_connection()?;
+    let db_conn = get_db_reader()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
     let active_filter = active_only.unwrap_or(false);
 
     let mut result: Vec<WorkflowSchedule> = Vec::new();
@@ -1807,7 +1929,9 @@ pub async fn create_workflow_schedule(
     let next_run = _schedule.upcoming(chrono::Utc).next()
         .map(|dt| dt.to_rfc3339());
 
-    let conn = get_db_connection()?;
+    let db_conn = get_db_connection()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     conn.execute(
         "INSERT INTO workflow_schedules (id, workflow_id, workflow_name, cron_expression, timezone, is_active, input_data, next_run_at, created_at, updated_at)
@@ -1852,7 +1976,9 @@ pub async fn toggle_workflow_schedule(
 ) -> Result<serde_json::Value, String> {
     println!("📅 [SCHEDULER] 스케줄 토글: {} → {}", schedule_id, if is_active { "활성화" } else { "비활성화" });
 
-    let conn = get_db_connection()?;
+    let db_conn = get_db_connection()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
     let now = chrono::Utc::now().to_rfc3339();
 
     let affected = conn.execute(
@@ -1876,7 +2002,9 @@ pub async fn toggle_workflow_schedule(
 pub async fn delete_workflow_schedule(schedule_id: String) -> Result<serde_json::Value, String> {
     println!("📅 [SCHEDULER] 스케줄 삭제: {}", schedule_id);
 
-    let conn = get_db_connection()?;
+    let db_conn = get_db_connection()?;
+    let conn = db_conn.lock()
+        .map_err(|e| format!("DB lock 실패: {}", e))?;
 
     let affected = conn.execute(
         "DELETE FROM workflow_schedules WHERE id = ?1",
@@ -2331,6 +2459,32 @@ mod tests {
         println!("✅ QUERY (file) 유닛 테스트 성공!");
     }
 
+    #[tokio::test]
+    async fn test_query_step_traceability_requires_lot() {
+        let step = WorkflowStep {
+            id: "query-6".to_string(),
+            step_type: "QUERY".to_string(),
+            label: "원료 LOT 정방향 추적".to_string(),
+            config: json!({
+                "dataSource": "traceability",
+                "direction": "forward",
+                "lotField": "material_lot_no"
+            }),
+        };
+
+        let result = execute_query_step(&step, &json!({"test": "data"})).await;
+        assert!(result.unwrap_err().contains("추적할 LOT 번호가 없습니다"));
+
+        let step = WorkflowStep {
+            config: json!({"dataSource": "traceability", "direction": "sideways", "lotNo": "RM001"}),
+            ..step
+        };
+        let result = execute_query_step(&step, &json!({})).await;
+        assert!(result.unwrap_err().contains("지원하지 않는 추적 방향"));
+
+        println!("✅ QUERY (traceability) 입력 검증 테스트 성공!");
+    }
+
     #[tokio::test]
     async fn test_query_step_invalid_source() {
         let step = WorkflowStep {
@@ -2735,7 +2889,7 @@ fn create_workflow_dsl_prompt() -> String {
 
 # Available Node Types (6개):
 1. **TRIGGER**: Event-based activation (시간, 센서, Webhook 등)
-2. **QUERY**: Data retrieval (DB, API, File 등)
+2. **QUERY**: Data retrieval (DB, API, File 등; LOT 추적은 dataSource "traceability", direction "forward"|"backward", lotNo)
 3. **CALC**: Mathematical calculations (통계, 집계 등)
 4. **JUDGMENT**: Rule-based or AI-powered decision (하이브리드 판단)
 5. **APPROVAL**: Human approval gates (생산팀장, 품질팀장 등)
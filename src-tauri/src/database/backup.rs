@@ -1,15 +1,108 @@
+use crate::database::backup_crypto::{self, BackupKey, DecryptReader, EncryptWriter, KeySource};
 use anyhow::{Result, Context};
 use std::path::{Path, PathBuf};
 use std::fs;
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use flate2::Compression;
 use flate2::write::GzEncoder;
-use std::io::Write;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+
+/// 백업 아카이브 확장자 (gzip, 암호화 시 `.enc` 추가)
+const ARCHIVE_SUFFIX: &str = ".db.gz";
+const ENCRYPTED_ARCHIVE_SUFFIX: &str = ".db.gz.enc";
+
+/// 백업 파일명 timestamp 형식 (UTC)
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S_%3f";
+/// 밀리초 도입 이전 백업 파일명 timestamp 형식
+const LEGACY_BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// 백업 실행 계기
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupTrigger {
+    /// 사용자 요청
+    #[default]
+    Manual,
+    /// 일/주 단위 자동 백업
+    Scheduled,
+    AppStart,
+    PreMigration,
+    PreRestore,
+}
+
+impl BackupTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackupTrigger::Manual => "manual",
+            BackupTrigger::Scheduled => "scheduled",
+            BackupTrigger::AppStart => "app_start",
+            BackupTrigger::PreMigration => "pre_migration",
+            BackupTrigger::PreRestore => "pre_restore",
+        }
+    }
+}
+
+/// 복구 미리보기 (검증 통과한 백업 내용 요약)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorePreview {
+    pub backup_path: PathBuf,
+    /// 매니페스트 정보 (매니페스트 도입 이전 백업은 None)
+    pub created_at: Option<String>,
+    pub app_version: Option<String>,
+    pub integrity_check: String,
+    pub migrations: Vec<String>,
+    /// 복구 후 적용될 마이그레이션 (구버전 백업)
+    pub pending_migrations: Vec<String>,
+    pub row_counts: BTreeMap<String, i64>,
+    pub newest_judgment_at: Option<String>,
+    /// 아카이브 암호화 키 출처 (평문은 None)
+    pub encryption: Option<KeySource>,
+}
+
+/// 복구 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoreReport {
+    pub preview: RestorePreview,
+    /// 복구 전 DB (`.before_restore`, 기존 DB가 없었으면 None)
+    pub safety_backup: Option<PathBuf>,
+}
+
+/// 백업 매니페스트 (아카이브 옆 `<아카이브>.manifest.json`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub archive_file: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub trigger: BackupTrigger,
+    pub app_version: String,
+    pub sqlite_version: String,
+    /// 적용된 마이그레이션 (_migrations, 적용 순)
+    pub migrations: Vec<String>,
+    /// 테이블별 행 수
+    pub row_counts: BTreeMap<String, i64>,
+    /// 스냅샷 PRAGMA integrity_check 결과 ("ok"만 기록됨)
+    pub integrity_check: String,
+    /// 압축 전 DB 스냅샷 SHA-256
+    pub db_sha256: String,
+    pub db_size_bytes: u64,
+    /// 아카이브(.db.gz / .db.gz.enc) SHA-256
+    pub archive_sha256: String,
+    pub archive_size_bytes: u64,
+    /// 아카이브 암호화 키 출처 (평문 백업은 None)
+    #[serde(default)]
+    pub encryption: Option<KeySource>,
+}
 
 /// 데이터베이스 백업 관리자
 pub struct BackupManager {
     db_path: PathBuf,
     backup_dir: PathBuf,
+    /// 새 백업 암호화 키 (None이면 평문 gzip)
+    encryption: Option<BackupKey>,
 }
 
 impl BackupManager {
@@ -24,68 +117,470 @@ impl BackupManager {
         fs::create_dir_all(&backup_dir)
             .context("백업 디렉토리 생성 실패")?;
 
-        Ok(Self { db_path, backup_dir })
+        Ok(Self { db_path, backup_dir, encryption: None })
+    }
+
+    /// 백업 디렉토리를 지정해 생성 (보조 백업 위치: 네트워크 공유 폴더 등)
+    pub fn with_backup_dir(db_path: PathBuf, backup_dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&backup_dir)
+            .context(format!("백업 디렉토리 생성 실패: {}", backup_dir.display()))?;
+
+        Ok(Self { db_path, backup_dir, encryption: None })
+    }
+
+    /// 새 백업을 암호화 (복구 시에는 아카이브 헤더의 키 출처를 따름)
+    pub fn with_encryption(mut self, key: Option<BackupKey>) -> Self {
+        self.encryption = key;
+        self
+    }
+
+    pub fn backup_dir(&self) -> &Path {
+        &self.backup_dir
     }
 
-    /// 데이터베이스 백업 수행 (gzip 압축)
-    /// 
+    /// 데이터베이스 온라인 백업 수행 (VACUUM INTO 스냅샷 → 무결성 검사 → gzip 압축 + 매니페스트)
+    ///
+    /// 앱/스케줄러가 쓰는 중에도 일관된 스냅샷을 얻으며 WAL에만 있는 변경도 포함된다.
+    ///
     /// 반환값: 백업 파일 경로
     pub fn create_backup(&self) -> Result<PathBuf> {
-        // 백업 파일명 생성 (timestamp 포함)
-        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-        let backup_filename = format!("judgify_backup_{}.db.gz", timestamp);
+        Ok(self.create_backup_with_manifest(BackupTrigger::Manual)?.0)
+    }
+
+    /// 백업 수행 후 (백업 파일 경로, 매니페스트) 반환
+    pub fn create_backup_with_manifest(&self, trigger: BackupTrigger) -> Result<(PathBuf, BackupManifest)> {
+        // 백업 파일명 생성 (timestamp 포함, 같은 초 연속 백업 구분용 밀리초)
+        let timestamp = Utc::now().format(BACKUP_TIMESTAMP_FORMAT);
+        let suffix = if self.encryption.is_some() { ENCRYPTED_ARCHIVE_SUFFIX } else { ARCHIVE_SUFFIX };
+        let backup_filename = format!("judgify_backup_{}{}", timestamp, suffix);
         let backup_path = self.backup_dir.join(&backup_filename);
+        let snapshot_path = self.backup_dir.join(format!("judgify_backup_{}.db.snapshot", timestamp));
+
+        let result = self.write_backup(&snapshot_path, &backup_path, &backup_filename, trigger);
+        // 스냅샷은 성공/실패와 무관하게 삭제, 실패 시 불완전한 아카이브도 삭제
+        let _ = fs::remove_file(&snapshot_path);
+        let manifest = match result {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let _ = fs::remove_file(&backup_path);
+                let _ = fs::remove_file(Self::manifest_path(&backup_path));
+                return Err(e);
+            }
+        };
+
+        println!(
+            "✅ 백업 완료 [{}]: {} ({}개 테이블, 마이그레이션 {}개)",
+            trigger.as_str(),
+            backup_path.display(),
+            manifest.row_counts.len(),
+            manifest.migrations.len()
+        );
+        Ok((backup_path, manifest))
+    }
 
-        // DB 파일 읽기
-        let db_data = fs::read(&self.db_path)
-            .context("데이터베이스 파일 읽기 실패")?;
+    fn write_backup(
+        &self,
+        snapshot_path: &Path,
+        backup_path: &Path,
+        backup_filename: &str,
+        trigger: BackupTrigger,
+    ) -> Result<BackupManifest> {
+        // 1. 읽기 전용 연결에서 VACUUM INTO로 일관된 스냅샷 생성
+        {
+            let source = Connection::open_with_flags(&self.db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .context("데이터베이스 열기 실패")?;
+            source
+                .execute("VACUUM INTO ?1", [snapshot_path.to_string_lossy()])
+                .context("데이터베이스 스냅샷 생성 실패")?;
+        }
 
-        // gzip 압축
-        let backup_file = fs::File::create(&backup_path)
+        // 2. 스냅샷 무결성 검사 + 매니페스트 정보 수집
+        //    (FTS5 인덱스 검사는 쓰기 가능한 연결이 필요 → 백업 전용 스냅샷이므로 읽기/쓰기로 연다)
+        let (integrity_check, sqlite_version, migrations, row_counts) = {
+            let snapshot = Connection::open(snapshot_path)
+                .context("스냅샷 열기 실패")?;
+            let integrity_check: String = snapshot
+                .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+                .context("무결성 검사 실패")?;
+            if integrity_check != "ok" {
+                return Err(anyhow::anyhow!("스냅샷 무결성 검사 실패: {}", integrity_check));
+            }
+            let sqlite_version: String = snapshot.query_row("SELECT sqlite_version()", [], |row| row.get(0))?;
+            (integrity_check, sqlite_version, Self::applied_migrations(&snapshot)?, Self::table_row_counts(&snapshot)?)
+        };
+
+        // 3. gzip 압축 (+ 암호화), 압축 전 스냅샷 체크섬 동시 계산
+        let mut snapshot_file = fs::File::open(snapshot_path)
+            .context("스냅샷 읽기 실패")?;
+        let backup_file = fs::File::create(backup_path)
             .context("백업 파일 생성 실패")?;
-        let mut encoder = GzEncoder::new(backup_file, Compression::default());
-        encoder.write_all(&db_data)
-            .context("백업 파일 압축 중 오류")?;
-        encoder.finish()
+        let (db_sha256, db_size_bytes) = match &self.encryption {
+            Some(key) => {
+                let (writer, db_sha256, db_size_bytes) =
+                    Self::compress_into(&mut snapshot_file, EncryptWriter::new(backup_file, key)?)?;
+                writer.finish()
+                    .context("백업 파일 암호화 완료 실패")?;
+                (db_sha256, db_size_bytes)
+            }
+            None => {
+                let (_, db_sha256, db_size_bytes) = Self::compress_into(&mut snapshot_file, backup_file)?;
+                (db_sha256, db_size_bytes)
+            }
+        };
+
+        // 4. 매니페스트 기록
+        let (archive_sha256, archive_size_bytes) = Self::file_sha256(backup_path)?;
+        let manifest = BackupManifest {
+            archive_file: backup_filename.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            trigger,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            sqlite_version,
+            migrations,
+            row_counts,
+            integrity_check,
+            db_sha256,
+            db_size_bytes,
+            archive_sha256,
+            archive_size_bytes,
+            encryption: self.encryption.as_ref().map(BackupKey::source),
+        };
+        fs::write(Self::manifest_path(backup_path), serde_json::to_string_pretty(&manifest)?)
+            .context("매니페스트 저장 실패")?;
+
+        Ok(manifest)
+    }
+
+    /// gzip 압축해 writer에 기록 (writer, 원본 SHA-256, 원본 크기)
+    fn compress_into<W: Write>(source: &mut impl Read, writer: W) -> Result<(W, String, u64)> {
+        let mut encoder = GzEncoder::new(writer, Compression::default());
+        let mut hasher = Sha256::new();
+        let mut size = 0u64;
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let n = source.read(&mut buffer)
+                .context("스냅샷 읽기 실패")?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+            size += n as u64;
+            encoder.write_all(&buffer[..n])
+                .context("백업 파일 압축 중 오류")?;
+        }
+        let writer = encoder.finish()
             .context("백업 파일 압축 완료 실패")?;
+        Ok((writer, format!("{:x}", hasher.finalize()), size))
+    }
+
+    /// 백업 파일의 매니페스트 경로 (`judgify_backup_*.db.gz.manifest.json`)
+    pub fn manifest_path(backup_path: &Path) -> PathBuf {
+        let mut file_name = backup_path.as_os_str().to_os_string();
+        file_name.push(".manifest.json");
+        PathBuf::from(file_name)
+    }
+
+    /// 백업 파일명의 생성 시각 (UTC)
+    pub fn backup_timestamp(backup_path: &Path) -> Option<NaiveDateTime> {
+        let file_name = backup_path.file_name()?.to_str()?;
+        let timestamp = file_name.strip_prefix("judgify_backup_")?;
+        let timestamp = timestamp
+            .strip_suffix(ENCRYPTED_ARCHIVE_SUFFIX)
+            .or_else(|| timestamp.strip_suffix(ARCHIVE_SUFFIX))?;
+        NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT)
+            .or_else(|_| NaiveDateTime::parse_from_str(timestamp, LEGACY_BACKUP_TIMESTAMP_FORMAT))
+            .ok()
+    }
+
+    /// 다른 백업 디렉토리로 아카이브 + 매니페스트 복사 (체크섬 검증)
+    pub fn copy_backup_to(&self, backup_path: &Path, destination: &BackupManager) -> Result<PathBuf> {
+        let file_name = backup_path
+            .file_name()
+            .context("백업 파일명을 찾을 수 없습니다")?;
+        let target_path = destination.backup_dir.join(file_name);
+
+        fs::copy(backup_path, &target_path)
+            .context(format!("백업 복사 실패: {}", target_path.display()))?;
+        let manifest_path = Self::manifest_path(backup_path);
+        if manifest_path.exists() {
+            fs::copy(&manifest_path, Self::manifest_path(&target_path))
+                .context("매니페스트 복사 실패")?;
+        }
+
+        let (source_sha256, _) = Self::file_sha256(backup_path)?;
+        let (target_sha256, _) = Self::file_sha256(&target_path)?;
+        if source_sha256 != target_sha256 {
+            let _ = destination.delete_backup(&target_path);
+            return Err(anyhow::anyhow!("복사된 백업 체크섬 불일치: {}", target_path.display()));
+        }
+
+        Ok(target_path)
+    }
+
+    /// 백업 매니페스트 조회 (매니페스트 도입 이전 백업은 None)
+    pub fn read_manifest(&self, backup_path: &Path) -> Result<Option<BackupManifest>> {
+        let manifest_path = Self::manifest_path(backup_path);
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&manifest_path)
+            .context("매니페스트 읽기 실패")?;
+        Ok(Some(serde_json::from_str(&content).context("매니페스트 파싱 실패")?))
+    }
+
+    fn applied_migrations(conn: &Connection) -> Result<Vec<String>> {
+        let exists = conn
+            .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_migrations'")?
+            .exists([])?;
+        if !exists {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn.prepare("SELECT name FROM _migrations ORDER BY id")?;
+        let migrations = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(migrations)
+    }
+
+    fn table_row_counts(conn: &Connection) -> Result<BTreeMap<String, i64>> {
+        let mut stmt = conn.prepare(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )?;
+        let tables = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+
+        let mut row_counts = BTreeMap::new();
+        for table in tables {
+            let count: i64 = conn.query_row(
+                &format!("SELECT COUNT(*) FROM \"{}\"", table.replace('"', "\"\"")),
+                [],
+                |row| row.get(0),
+            )?;
+            row_counts.insert(table, count);
+        }
+        Ok(row_counts)
+    }
+
+    /// 파일 SHA-256 (hex, 크기)
+    fn file_sha256(path: &Path) -> Result<(String, u64)> {
+        let mut file = fs::File::open(path)
+            .context(format!("파일 열기 실패: {}", path.display()))?;
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut file, &mut hasher)
+            .context(format!("파일 읽기 실패: {}", path.display()))?;
+        Ok((format!("{:x}", hasher.finalize()), size))
+    }
 
-        println!("✅ 백업 완료: {}", backup_path.display());
-        Ok(backup_path)
+    /// 복구 미리보기: 임시 파일로 압축 해제 후 검증 (현재 DB는 변경하지 않음)
+    ///
+    /// passphrase: 암호로 보호된 아카이브의 암호 (평문/keyring 아카이브는 무시)
+    pub fn preview_restore(&self, backup_path: &Path, passphrase: Option<&str>) -> Result<RestorePreview> {
+        let temp_path = self.restore_temp_path();
+        let result = self.extract_and_validate(backup_path, &temp_path, passphrase);
+        let _ = fs::remove_file(&temp_path);
+        result
     }
 
     /// 백업 파일에서 데이터베이스 복구
-    /// 
-    /// 주의: 기존 데이터베이스를 덮어씁니다!
-    pub fn restore_from_backup(&self, backup_path: &Path) -> Result<()> {
+    ///
+    /// 1. 임시 파일로 복호화/압축 해제 → 체크섬/무결성/스키마 버전 검증
+    /// 2. 모든 DB 연결을 닫고 현재 DB를 `.before_restore`로, 임시 파일을 DB로 rename (원자적 교체)
+    /// 3. 새 DB 스키마 초기화(미적용 마이그레이션) + 무결성 재검사, 실패 시 `.before_restore`로 롤백
+    /// 4. 기존 연결 객체를 새 DB로 다시 연다 (핫 스왑)
+    pub fn restore_from_backup(&self, backup_path: &Path, passphrase: Option<&str>) -> Result<RestoreReport> {
+        let temp_path = self.restore_temp_path();
+        let result = self.extract_and_validate(backup_path, &temp_path, passphrase).and_then(|preview| {
+            let safety_backup = crate::database::Database::with_connections_closed(&self.db_path, || {
+                self.swap_in(&temp_path)
+            })?;
+            Ok(RestoreReport { preview, safety_backup })
+        });
+        let _ = fs::remove_file(&temp_path);
+        let report = result?;
+
+        println!("✅ 복구 완료: {} ← {}", self.db_path.display(), backup_path.display());
+        Ok(report)
+    }
+
+    /// DB와 같은 디렉토리의 임시 파일 (rename이 원자적이도록 같은 파일시스템)
+    fn restore_temp_path(&self) -> PathBuf {
+        self.db_path.with_extension("db.restore_tmp")
+    }
+
+    fn safety_backup_path(&self) -> PathBuf {
+        self.db_path.with_extension("db.before_restore")
+    }
+
+    fn extract_and_validate(&self, backup_path: &Path, temp_path: &Path, passphrase: Option<&str>) -> Result<RestorePreview> {
         // 백업 파일 존재 확인
         if !backup_path.exists() {
             return Err(anyhow::anyhow!("백업 파일을 찾을 수 없습니다: {}", backup_path.display()));
         }
 
-        // 기존 DB 백업 (safety)
-        let safety_backup = self.db_path.with_extension("db.before_restore");
-        if self.db_path.exists() {
-            fs::copy(&self.db_path, &safety_backup)
+        // 매니페스트가 있으면 아카이브 체크섬 확인
+        let manifest = self.read_manifest(backup_path)?;
+        if let Some(manifest) = &manifest {
+            let (archive_sha256, _) = Self::file_sha256(backup_path)?;
+            if archive_sha256 != manifest.archive_sha256 {
+                return Err(anyhow::anyhow!("백업 파일 체크섬 불일치 (손상되었거나 변조됨): {}", backup_path.display()));
+            }
+        }
+
+        // 복호화 + gzip 압축 해제 (임시 파일)
+        let _ = fs::remove_file(temp_path);
+        let encryption = backup_crypto::encrypted_key_source(backup_path)?;
+        {
+            let backup_file = fs::File::open(backup_path)
+                .context("백업 파일 열기 실패")?;
+            let archive: Box<dyn Read> = if encryption.is_some() {
+                Box::new(DecryptReader::new(backup_file, passphrase)?)
+            } else {
+                Box::new(backup_file)
+            };
+            let mut temp_file = fs::File::create(temp_path)
+                .context("복구 임시 파일 생성 실패")?;
+            Self::decompress_into(archive, &mut temp_file)?;
+            temp_file.sync_all()
+                .context("복구 임시 파일 저장 실패")?;
+        }
+
+        // 임시 사본이므로 읽기/쓰기로 연다 (FTS5 무결성 검사에 필요)
+        let conn = Connection::open(temp_path)
+            .context("백업 DB 열기 실패")?;
+        let integrity_check: String = conn
+            .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+            .context("백업 DB가 SQLite 데이터베이스가 아닙니다")?;
+        if integrity_check != "ok" {
+            return Err(anyhow::anyhow!("백업 DB 무결성 검사 실패: {}", integrity_check));
+        }
+
+        let row_counts = Self::table_row_counts(&conn)?;
+        if !row_counts.contains_key("judgments") {
+            return Err(anyhow::anyhow!("Judgify 데이터베이스 백업이 아닙니다 (judgments 테이블 없음)"));
+        }
+
+        // 스키마 버전: 이 앱이 모르는 마이그레이션이 있으면 더 새 버전 앱의 백업
+        let migrations = Self::applied_migrations(&conn)?;
+        let unknown: Vec<&String> = migrations
+            .iter()
+            .filter(|name| !crate::database::migrations::is_known_migration(name))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(anyhow::anyhow!(
+                "더 최신 버전 앱에서 만든 백업입니다 (알 수 없는 마이그레이션: {:?}{})",
+                unknown,
+                manifest.as_ref().map(|m| format!(", 앱 버전 {}", m.app_version)).unwrap_or_default()
+            ));
+        }
+        let pending_migrations = crate::database::migrations::migration_names()
+            .iter()
+            .filter(|name| !migrations.iter().any(|applied| applied == *name))
+            .map(|name| name.to_string())
+            .collect();
+
+        let newest_judgment_at: Option<String> = conn
+            .query_row("SELECT MAX(created_at) FROM judgments", [], |row| row.get(0))
+            .context("판단 기록 조회 실패")?;
+
+        Ok(RestorePreview {
+            backup_path: backup_path.to_path_buf(),
+            created_at: manifest.as_ref().map(|m| m.created_at.clone()),
+            app_version: manifest.as_ref().map(|m| m.app_version.clone()),
+            integrity_check,
+            migrations,
+            pending_migrations,
+            row_counts,
+            newest_judgment_at,
+            encryption,
+        })
+    }
+
+    /// gzip 아카이브면 압축 해제, 아니면 그대로 복사 (`export_database`로 내보낸 SQLite 파일)
+    fn decompress_into(archive: impl Read, output: &mut impl Write) -> Result<()> {
+        let mut reader = BufReader::new(archive);
+        let is_gzip = reader.fill_buf().context("백업 파일 읽기 실패")?.starts_with(&[0x1f, 0x8b]);
+        if is_gzip {
+            std::io::copy(&mut flate2::read::GzDecoder::new(reader), output)
+                .context("백업 파일 압축 해제 실패")?;
+        } else {
+            std::io::copy(&mut reader, output)
+                .context("백업 파일 읽기 실패")?;
+        }
+        Ok(())
+    }
+
+    /// 연결이 모두 닫힌 상태에서 DB 파일 교체, 실패 시 롤백 (반환: 안전 백업 경로)
+    fn swap_in(&self, temp_path: &Path) -> Result<Option<PathBuf>> {
+        let safety_backup = self.safety_backup_path();
+        for path in [&safety_backup, &Self::sidecar(&safety_backup, "-wal")] {
+            if path.exists() {
+                fs::remove_file(path).context("이전 안전 백업 삭제 실패")?;
+            }
+        }
+
+        // 현재 DB(+ 체크포인트 안 된 WAL)를 안전 백업으로 이동
+        let had_database = self.db_path.exists();
+        if had_database {
+            fs::rename(&self.db_path, &safety_backup)
                 .context("안전 백업 생성 실패")?;
+            let wal = Self::sidecar(&self.db_path, "-wal");
+            if wal.exists() {
+                fs::rename(&wal, Self::sidecar(&safety_backup, "-wal"))
+                    .context("WAL 파일 이동 실패")?;
+            }
+            let _ = fs::remove_file(Self::sidecar(&self.db_path, "-shm"));
             println!("🔒 기존 DB 안전 백업: {}", safety_backup.display());
         }
 
-        // gzip 압축 해제
-        let backup_file = fs::File::open(backup_path)
-            .context("백업 파일 열기 실패")?;
-        let mut decoder = flate2::read::GzDecoder::new(backup_file);
-        let mut restored_data = Vec::new();
-        std::io::Read::read_to_end(&mut decoder, &mut restored_data)
-            .context("백업 파일 압축 해제 실패")?;
+        let swapped = fs::rename(temp_path, &self.db_path)
+            .context("데이터베이스 파일 교체 실패")
+            .and_then(|_| Self::initialize_restored(&self.db_path));
+        if let Err(e) = swapped {
+            eprintln!("❌ 복구 실패, 이전 DB로 롤백: {}", e);
+            if had_database {
+                self.rollback(&safety_backup)
+                    .context(format!("롤백 실패 (안전 백업: {})", safety_backup.display()))?;
+            }
+            return Err(e);
+        }
 
-        // 복구된 데이터를 DB 파일에 쓰기
-        fs::write(&self.db_path, restored_data)
-            .context("데이터베이스 파일 복구 실패")?;
+        Ok(had_database.then_some(safety_backup))
+    }
 
-        println!("✅ 복구 완료: {} ← {}", self.db_path.display(), backup_path.display());
+    /// 복구된 DB 스키마 초기화 (구버전 백업은 미적용 마이그레이션 적용) + 무결성 재검사
+    fn initialize_restored(db_path: &Path) -> Result<()> {
+        let conn = Connection::open(db_path).context("복구된 DB 열기 실패")?;
+        crate::database::Database::init_schema(&conn).context("복구된 DB 스키마 초기화 실패")?;
+        let integrity_check: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+        if integrity_check != "ok" {
+            return Err(anyhow::anyhow!("복구된 DB 무결성 검사 실패: {}", integrity_check));
+        }
         Ok(())
     }
 
+    fn rollback(&self, safety_backup: &Path) -> Result<()> {
+        for suffix in ["", "-wal", "-shm"] {
+            let path = Self::sidecar(&self.db_path, suffix);
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+        }
+        fs::rename(safety_backup, &self.db_path)?;
+        let safety_wal = Self::sidecar(safety_backup, "-wal");
+        if safety_wal.exists() {
+            fs::rename(&safety_wal, Self::sidecar(&self.db_path, "-wal"))?;
+        }
+        Ok(())
+    }
+
+    /// `judgify.db` → `judgify.db-wal` 등 SQLite 부속 파일 경로
+    fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
     /// 모든 백업 파일 목록 조회 (최신순)
     pub fn list_backups(&self) -> Result<Vec<PathBuf>> {
         let mut backups: Vec<PathBuf> = fs::read_dir(&self.backup_dir)
@@ -93,7 +588,7 @@ impl BackupManager {
             .filter_map(|entry| {
                 let entry = entry.ok()?;
                 let path = entry.path();
-                if path.extension()?.to_str()? == "gz" {
+                if matches!(path.extension()?.to_str()?, "gz" | "enc") {
                     Some(path)
                 } else {
                     None
@@ -113,9 +608,7 @@ impl BackupManager {
 
         let mut deleted_count = 0;
         for backup_path in to_delete {
-            fs::remove_file(backup_path)
-                .context(format!("백업 파일 삭제 실패: {}", backup_path.display()))?;
-            println!("🗑️  삭제: {}", backup_path.display());
+            self.delete_backup(backup_path)?;
             deleted_count += 1;
         }
 
@@ -126,6 +619,19 @@ impl BackupManager {
         Ok(deleted_count)
     }
 
+    /// 백업 파일과 매니페스트 삭제
+    pub fn delete_backup(&self, backup_path: &Path) -> Result<()> {
+        fs::remove_file(backup_path)
+            .context(format!("백업 파일 삭제 실패: {}", backup_path.display()))?;
+        let manifest_path = Self::manifest_path(backup_path);
+        if manifest_path.exists() {
+            fs::remove_file(&manifest_path)
+                .context(format!("매니페스트 삭제 실패: {}", manifest_path.display()))?;
+        }
+        println!("🗑️  삭제: {}", backup_path.display());
+        Ok(())
+    }
+
     /// 백업 파일 크기 합계 (압축된 크기)
     pub fn get_total_backup_size(&self) -> Result<u64> {
         let backups = self.list_backups()?;
@@ -144,14 +650,42 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    /// 테스트용 SQLite DB 생성 (WAL 모드, 체크포인트 비활성화)
+    fn create_test_db(db_path: &Path, rows: usize) -> Result<Connection> {
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             PRAGMA wal_autocheckpoint = 0;
+             CREATE TABLE _migrations (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE);
+             INSERT INTO _migrations (name) VALUES ('001_knowledge_base.sql'), ('002_erp_schema.sql');
+             CREATE TABLE judgments (
+                 id TEXT PRIMARY KEY, workflow_id TEXT NOT NULL, input_data TEXT NOT NULL, result INTEGER NOT NULL,
+                 confidence REAL NOT NULL, method_used TEXT NOT NULL, explanation TEXT, created_at TEXT NOT NULL
+             );",
+        )?;
+        for i in 0..rows {
+            conn.execute(
+                "INSERT INTO judgments (id, workflow_id, input_data, result, confidence, method_used, created_at)
+                 VALUES (?1, 'wf-1', '{}', 1, 0.9, 'rule', ?2)",
+                [format!("j-{}", i), format!("2025-03-{:02}T09:00:00Z", i + 1)],
+            )?;
+        }
+        Ok(conn)
+    }
+
+    fn judgment_count(db_path: &Path) -> Result<i64> {
+        let conn = Connection::open(db_path)?;
+        Ok(conn.query_row("SELECT COUNT(*) FROM judgments", [], |row| row.get(0))?)
+    }
+
     #[test]
     fn test_create_and_restore_backup() -> Result<()> {
         // 임시 디렉토리 생성
         let temp_dir = TempDir::new()?;
         let db_path = temp_dir.path().join("test.db");
 
-        // 테스트 DB 파일 생성
-        fs::write(&db_path, b"test database content")?;
+        // 테스트 DB 생성
+        drop(create_test_db(&db_path, 3)?);
 
         // BackupManager 초기화
         let manager = BackupManager::new(db_path.clone())?;
@@ -160,15 +694,174 @@ mod tests {
         let backup_path = manager.create_backup()?;
         assert!(backup_path.exists());
 
-        // DB 파일 수정
-        fs::write(&db_path, b"modified content")?;
+        // DB 수정
+        Connection::open(&db_path)?.execute("DELETE FROM judgments", [])?;
+        assert_eq!(judgment_count(&db_path)?, 0);
+
+        // 미리보기 (현재 DB는 변경 없음)
+        let preview = manager.preview_restore(&backup_path, None)?;
+        assert_eq!(preview.row_counts.get("judgments"), Some(&3));
+        assert_eq!(preview.newest_judgment_at.as_deref(), Some("2025-03-03T09:00:00Z"));
+        assert_eq!(preview.migrations, vec!["001_knowledge_base.sql", "002_erp_schema.sql"]);
+        assert!(preview.pending_migrations.contains(&"0004_mes_schema.sql".to_string()));
+        assert_eq!(judgment_count(&db_path)?, 0);
 
         // 백업에서 복구
-        manager.restore_from_backup(&backup_path)?;
+        let report = manager.restore_from_backup(&backup_path, None)?;
+
+        // 복구된 내용 검증 (안전 백업에는 복구 직전 상태)
+        assert_eq!(judgment_count(&db_path)?, 3);
+        let safety_backup = report.safety_backup.expect("안전 백업");
+        assert_eq!(judgment_count(&safety_backup)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_hot_swaps_open_connections() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test.db");
+        drop(create_test_db(&db_path, 2)?);
+
+        // 앱처럼 Database 핸들을 연 채로 복구
+        let database = crate::database::Database::open(db_path.clone())?;
+        let manager = BackupManager::new(db_path.clone())?;
+        let backup_path = manager.create_backup()?;
+        database.get_connection().lock().unwrap().execute("DELETE FROM judgments", [])?;
+
+        manager.restore_from_backup(&backup_path, None)?;
+
+        let count: i64 = database
+            .get_connection()
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM judgments", [], |row| row.get(0))?;
+        assert_eq!(count, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_rolls_back_on_failure() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        // 스키마 초기화에 실패하는 백업 (judgments.workflow_id 없음 → 인덱스 생성 실패)
+        let broken_path = temp_dir.path().join("broken.db");
+        Connection::open(&broken_path)?.execute_batch(
+            "CREATE TABLE judgments (id TEXT PRIMARY KEY, created_at TEXT NOT NULL);",
+        )?;
+        let broken_backup = BackupManager::new(broken_path)?.create_backup()?;
+
+        // 더 최신 앱의 백업 (알 수 없는 마이그레이션)
+        let newer_path = temp_dir.path().join("newer.db");
+        let newer_conn = create_test_db(&newer_path, 1)?;
+        newer_conn.execute("INSERT INTO _migrations (name) VALUES ('999_future.sql')", [])?;
+        drop(newer_conn);
+        let newer_backup = BackupManager::new(newer_path)?.create_backup()?;
+
+        let db_path = temp_dir.path().join("test.db");
+        drop(create_test_db(&db_path, 3)?);
+        let manager = BackupManager::new(db_path.clone())?;
+
+        let error = manager.preview_restore(&newer_backup, None).unwrap_err();
+        assert!(error.to_string().contains("999_future.sql"));
+
+        assert!(manager.restore_from_backup(&broken_backup, None).is_err());
+        assert_eq!(judgment_count(&db_path)?, 3);
+        assert!(!db_path.with_extension("db.before_restore").exists());
+        assert!(!db_path.with_extension("db.restore_tmp").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_backup_and_export_restore() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test.db");
+        drop(create_test_db(&db_path, 2)?);
+
+        let manager = BackupManager::new(db_path.clone())?
+            .with_encryption(Some(BackupKey::Passphrase("공장 백업 암호".to_string())));
+        let (backup_path, manifest) = manager.create_backup_with_manifest(BackupTrigger::Manual)?;
+        assert!(backup_path.to_string_lossy().ends_with(".db.gz.enc"));
+        assert_eq!(manifest.encryption, Some(KeySource::Passphrase));
+        assert!(BackupManager::backup_timestamp(&backup_path).is_some());
+        assert_eq!(manager.list_backups()?, vec![backup_path.clone()]);
+
+        // 평문 DB 내용이 아카이브에 노출되지 않음
+        assert!(!fs::read(&backup_path)?.windows(4).any(|window| window == b"wf-1"));
+
+        // 암호 없음/틀린 암호는 현재 DB를 건드리지 않고 실패
+        Connection::open(&db_path)?.execute("DELETE FROM judgments", [])?;
+        assert!(manager.restore_from_backup(&backup_path, None).is_err());
+        assert!(manager.restore_from_backup(&backup_path, Some("틀린 암호")).is_err());
+        assert_eq!(judgment_count(&db_path)?, 0);
+
+        let report = manager.restore_from_backup(&backup_path, Some("공장 백업 암호"))?;
+        assert_eq!(report.preview.encryption, Some(KeySource::Passphrase));
+        assert_eq!(judgment_count(&db_path)?, 2);
+
+        // 암호화 내보내기 (gzip 없는 SQLite 파일)도 복구 가능
+        let export_path = temp_dir.path().join("export.db.enc");
+        let mut writer = EncryptWriter::new(fs::File::create(&export_path)?, &BackupKey::Passphrase("내보내기".to_string()))?;
+        std::io::copy(&mut fs::File::open(&db_path)?, &mut writer)?;
+        writer.finish()?;
+        Connection::open(&db_path)?.execute("DELETE FROM judgments", [])?;
+        let preview = manager.preview_restore(&export_path, Some("내보내기"))?;
+        assert_eq!(preview.row_counts.get("judgments"), Some(&2));
 
-        // 복구된 내용 검증
-        let restored_content = fs::read(&db_path)?;
-        assert_eq!(restored_content, b"test database content");
+        Ok(())
+    }
+
+    #[test]
+    fn test_backup_manifest_includes_wal_changes() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test.db");
+
+        // 연결을 연 채로 두어 변경이 WAL 파일에만 있는 상태에서 백업
+        let live_conn = create_test_db(&db_path, 5)?;
+        assert!(db_path.with_extension("db-wal").exists());
+
+        let manager = BackupManager::new(db_path.clone())?;
+        let (backup_path, manifest) = manager.create_backup_with_manifest(BackupTrigger::AppStart)?;
+        drop(live_conn);
+
+        assert_eq!(manifest.integrity_check, "ok");
+        assert_eq!(manifest.trigger, BackupTrigger::AppStart);
+        assert_eq!(manifest.app_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest.migrations, vec!["001_knowledge_base.sql", "002_erp_schema.sql"]);
+        assert_eq!(manifest.row_counts.get("judgments"), Some(&5));
+        assert_eq!(manager.read_manifest(&backup_path)?, Some(manifest.clone()));
+
+        // 체크섬: 아카이브 파일 / 압축 해제한 스냅샷
+        let (archive_sha256, archive_size_bytes) = BackupManager::file_sha256(&backup_path)?;
+        assert_eq!(manifest.archive_sha256, archive_sha256);
+        assert_eq!(manifest.archive_size_bytes, archive_size_bytes);
+        let mut decoder = flate2::read::GzDecoder::new(fs::File::open(&backup_path)?);
+        let mut snapshot = Vec::new();
+        decoder.read_to_end(&mut snapshot)?;
+        assert_eq!(manifest.db_sha256, format!("{:x}", Sha256::digest(&snapshot)));
+        assert_eq!(manifest.db_size_bytes, snapshot.len() as u64);
+
+        // 스냅샷 임시 파일은 남지 않음
+        let leftovers = fs::read_dir(temp_dir.path().join("backups"))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".snapshot"))
+            .count();
+        assert_eq!(leftovers, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_backup_rejects_non_database() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test.db");
+        fs::write(&db_path, b"test database content")?;
+
+        let manager = BackupManager::new(db_path)?;
+        assert!(manager.create_backup().is_err());
+        assert!(manager.list_backups()?.is_empty());
 
         Ok(())
     }
@@ -177,7 +870,7 @@ mod tests {
     fn test_list_backups() -> Result<()> {
         let temp_dir = TempDir::new()?;
         let db_path = temp_dir.path().join("test.db");
-        fs::write(&db_path, b"test")?;
+        drop(create_test_db(&db_path, 1)?);
 
         let manager = BackupManager::new(db_path)?;
 
@@ -199,7 +892,7 @@ mod tests {
     fn test_cleanup_old_backups() -> Result<()> {
         let temp_dir = TempDir::new()?;
         let db_path = temp_dir.path().join("test.db");
-        fs::write(&db_path, b"test")?;
+        drop(create_test_db(&db_path, 1)?);
 
         let manager = BackupManager::new(db_path)?;
 
@@ -213,9 +906,14 @@ mod tests {
         let deleted = manager.cleanup_old_backups(2)?;
         assert_eq!(deleted, 3);
 
-        // 백업 개수 검증
+        // 백업 개수 검증 (매니페스트도 함께 정리)
         let backups = manager.list_backups()?;
         assert_eq!(backups.len(), 2);
+        let manifests = fs::read_dir(temp_dir.path().join("backups"))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".manifest.json"))
+            .count();
+        assert_eq!(manifests, 2);
 
         Ok(())
     }
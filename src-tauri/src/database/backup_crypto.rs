@@ -0,0 +1,408 @@
+// 백업/내보내기 아카이브 암호화 (XChaCha20-Poly1305 STREAM, 64KB 청크 단위 인증)
+//
+// 파일 형식: 헤더 56바이트 + 암호문 청크들 (청크마다 16바이트 태그, 헤더는 모든 청크의 AAD)
+// - magic "JGFYENC1" (8)
+// - 키 출처 (1): 1 = 암호(Argon2id), 2 = 시스템 keyring
+// - Argon2 m/t/p 비용 (u32 LE × 3, keyring은 0)
+// - salt (16, keyring은 0)
+// - STREAM nonce (19)
+//
+// 청크 순서 변경/삭제/잘림은 STREAM 카운터와 마지막 청크 플래그로 검출된다.
+
+use anyhow::{Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::stream::{DecryptorBE32, EncryptorBE32};
+use chacha20poly1305::aead::{KeyInit, Payload};
+use chacha20poly1305::XChaCha20Poly1305;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 8] = b"JGFYENC1";
+const HEADER_LEN: usize = 8 + 1 + 12 + 16 + 19;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 19;
+const CHUNK_SIZE: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+
+/// keyring 저장 위치 (API 키와 같은 서비스명)
+const KEYRING_SERVICE: &str = "Judgify";
+const KEYRING_USER: &str = "backup_encryption_key";
+
+/// 암호화 키 출처
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// 사용자 암호 (다른 PC에서도 복구 가능)
+    Passphrase,
+    /// 시스템 keyring에 저장된 무작위 키 (자동 백업용, 이 PC 계정에서만 복구 가능)
+    Keyring,
+}
+
+impl KeySource {
+    fn to_byte(self) -> u8 {
+        match self {
+            KeySource::Passphrase => 1,
+            KeySource::Keyring => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self> {
+        match byte {
+            1 => Ok(KeySource::Passphrase),
+            2 => Ok(KeySource::Keyring),
+            other => Err(anyhow::anyhow!("알 수 없는 암호화 키 출처: {}", other)),
+        }
+    }
+}
+
+/// 암호화에 사용할 키
+#[derive(Clone)]
+pub enum BackupKey {
+    Passphrase(String),
+    Keyring,
+}
+
+impl BackupKey {
+    pub fn source(&self) -> KeySource {
+        match self {
+            BackupKey::Passphrase(_) => KeySource::Passphrase,
+            BackupKey::Keyring => KeySource::Keyring,
+        }
+    }
+}
+
+// 암호가 로그에 남지 않도록 Debug 출력에서 제외
+impl fmt::Debug for BackupKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupKey::Passphrase(_) => f.write_str("Passphrase(***)"),
+            BackupKey::Keyring => f.write_str("Keyring"),
+        }
+    }
+}
+
+/// 파일 앞부분이 암호화 헤더인지 확인
+pub fn is_encrypted(prefix: &[u8]) -> bool {
+    prefix.starts_with(MAGIC)
+}
+
+/// 파일이 암호화되어 있으면 키 출처 반환
+pub fn encrypted_key_source(path: &std::path::Path) -> Result<Option<KeySource>> {
+    let mut header = [0u8; MAGIC.len() + 1];
+    let mut file = std::fs::File::open(path)
+        .context(format!("파일 열기 실패: {}", path.display()))?;
+    if read_full(&mut file, &mut header)? < header.len() || !is_encrypted(&header) {
+        return Ok(None);
+    }
+    KeySource::from_byte(header[MAGIC.len()]).map(Some)
+}
+
+/// keyring 백업 키 준비 (정책에서 암호화 활성화 시 저장 전 keychain 사용 가능 여부 확인)
+pub fn ensure_keyring_key() -> Result<()> {
+    keyring_key(true).map(|_| ())
+}
+
+/// keyring 백업 키 조회 (없으면 create가 true일 때 새로 생성해 저장)
+fn keyring_key(create: bool) -> Result<[u8; 32]> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .context("시스템 저장소 초기화 실패")?;
+
+    match entry.get_password() {
+        Ok(hex) => decode_hex_key(&hex).context("keyring 백업 키 형식 오류"),
+        Err(keyring::Error::NoEntry) if create => {
+            let mut key = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut key);
+            entry
+                .set_password(&encode_hex(&key))
+                .context("시스템 저장소에 백업 키 저장 실패")?;
+            println!("🔑 백업 암호화 키를 시스템 keychain에 생성했습니다.");
+            Ok(key)
+        }
+        Err(keyring::Error::NoEntry) => Err(anyhow::anyhow!(
+            "이 PC의 시스템 keychain에 백업 암호화 키가 없습니다 (다른 PC/계정에서 만든 백업)"
+        )),
+        Err(e) => Err(anyhow::anyhow!("시스템 저장소에서 백업 키 조회 실패: {}", e)),
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex_key(hex: &str) -> Result<[u8; 32]> {
+    if hex.len() != 64 {
+        return Err(anyhow::anyhow!("키 길이 오류: {}", hex.len()));
+    }
+    let mut key = [0u8; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)?;
+    }
+    Ok(key)
+}
+
+fn derive_passphrase_key(passphrase: &str, salt: &[u8], params: Params) -> Result<[u8; 32]> {
+    if passphrase.is_empty() {
+        return Err(anyhow::anyhow!("암호가 비어 있습니다"));
+    }
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("암호 키 유도 실패: {}", e))?;
+    Ok(key)
+}
+
+/// 가능한 만큼 채워 읽기 (EOF면 채운 길이 반환)
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn crypto_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// 암호화 Writer (finish 호출 필수: 마지막 청크 기록)
+pub struct EncryptWriter<W: Write> {
+    inner: W,
+    encryptor: Option<EncryptorBE32<XChaCha20Poly1305>>,
+    header: [u8; HEADER_LEN],
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> EncryptWriter<W> {
+    pub fn new(mut inner: W, key: &BackupKey) -> Result<Self> {
+        let mut header = [0u8; HEADER_LEN];
+        header[..8].copy_from_slice(MAGIC);
+        header[8] = key.source().to_byte();
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        header[HEADER_LEN - NONCE_LEN..].copy_from_slice(&nonce);
+
+        let key_bytes = match key {
+            BackupKey::Passphrase(passphrase) => {
+                let params = Params::default();
+                let mut salt = [0u8; SALT_LEN];
+                rand::thread_rng().fill_bytes(&mut salt);
+                header[9..13].copy_from_slice(&params.m_cost().to_le_bytes());
+                header[13..17].copy_from_slice(&params.t_cost().to_le_bytes());
+                header[17..21].copy_from_slice(&params.p_cost().to_le_bytes());
+                header[21..21 + SALT_LEN].copy_from_slice(&salt);
+                derive_passphrase_key(passphrase, &salt, params)?
+            }
+            BackupKey::Keyring => keyring_key(true)?,
+        };
+
+        inner.write_all(&header).context("암호화 헤더 기록 실패")?;
+        let cipher = XChaCha20Poly1305::new(&key_bytes.into());
+        let encryptor = EncryptorBE32::from_aead(cipher, &nonce.into());
+
+        Ok(Self {
+            inner,
+            encryptor: Some(encryptor),
+            header,
+            buffer: Vec::with_capacity(CHUNK_SIZE * 2),
+        })
+    }
+
+    /// 마지막 청크 암호화 후 내부 Writer 반환
+    pub fn finish(mut self) -> io::Result<W> {
+        let encryptor = self.encryptor.take().ok_or_else(|| crypto_error("이미 종료된 암호화 스트림"))?;
+        let ciphertext = encryptor
+            .encrypt_last(Payload { msg: &self.buffer, aad: &self.header })
+            .map_err(|_| crypto_error("암호화 실패"))?;
+        self.inner.write_all(&ciphertext)?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncryptWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let encryptor = self.encryptor.as_mut().ok_or_else(|| crypto_error("이미 종료된 암호화 스트림"))?;
+        self.buffer.extend_from_slice(data);
+        // 마지막 청크는 finish에서 기록해야 하므로 CHUNK_SIZE를 초과할 때만 내보냄
+        while self.buffer.len() > CHUNK_SIZE {
+            let ciphertext = encryptor
+                .encrypt_next(Payload { msg: &self.buffer[..CHUNK_SIZE], aad: &self.header })
+                .map_err(|_| crypto_error("암호화 실패"))?;
+            self.inner.write_all(&ciphertext)?;
+            self.buffer.drain(..CHUNK_SIZE);
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// 복호화 Reader (청크 단위 인증, 위조/잘림은 InvalidData 오류)
+pub struct DecryptReader<R: Read> {
+    inner: R,
+    decryptor: Option<DecryptorBE32<XChaCha20Poly1305>>,
+    header: [u8; HEADER_LEN],
+    /// 다음 암호문 청크 (마지막 청크 판별용 미리 읽기)
+    next_chunk: Vec<u8>,
+    plaintext: Vec<u8>,
+    position: usize,
+}
+
+impl<R: Read> DecryptReader<R> {
+    /// 헤더를 읽고 키 준비 (암호 기반이면 passphrase 필수, keyring이면 이 PC의 키 사용)
+    pub fn new(mut inner: R, passphrase: Option<&str>) -> Result<Self> {
+        let mut header = [0u8; HEADER_LEN];
+        if read_full(&mut inner, &mut header)? < HEADER_LEN || !is_encrypted(&header) {
+            return Err(anyhow::anyhow!("암호화된 아카이브가 아닙니다"));
+        }
+
+        let key_bytes = match KeySource::from_byte(header[8])? {
+            KeySource::Passphrase => {
+                let passphrase = passphrase
+                    .ok_or_else(|| anyhow::anyhow!("암호로 보호된 백업입니다. 암호를 입력하세요"))?;
+                let cost = |range: std::ops::Range<usize>| {
+                    u32::from_le_bytes(header[range].try_into().expect("4바이트"))
+                };
+                let (m_cost, t_cost, p_cost) = (cost(9..13), cost(13..17), cost(17..21));
+                // 헤더는 아직 인증 전이므로 EncryptWriter가 쓰는 값보다 큰 비용은 거부
+                // (조작된 파일로 수 GB 메모리 할당 / 장시간 키 유도를 유발하지 못하도록)
+                let max = Params::default();
+                if m_cost > max.m_cost() || t_cost > max.t_cost() || p_cost > max.p_cost() {
+                    return Err(anyhow::anyhow!(
+                        "암호화 헤더 오류: 지원하지 않는 키 유도 비용 (m={}, t={}, p={})",
+                        m_cost, t_cost, p_cost
+                    ));
+                }
+                let params = Params::new(m_cost, t_cost, p_cost, Some(32))
+                    .map_err(|e| anyhow::anyhow!("암호화 헤더 오류: {}", e))?;
+                derive_passphrase_key(passphrase, &header[21..21 + SALT_LEN], params)?
+            }
+            KeySource::Keyring => keyring_key(false)?,
+        };
+
+        let cipher = XChaCha20Poly1305::new(&key_bytes.into());
+        let nonce: [u8; NONCE_LEN] = header[HEADER_LEN - NONCE_LEN..].try_into().expect("nonce 길이");
+        let decryptor = DecryptorBE32::from_aead(cipher, &nonce.into());
+
+        let mut reader = Self {
+            inner,
+            decryptor: Some(decryptor),
+            header,
+            next_chunk: Vec::new(),
+            plaintext: Vec::new(),
+            position: 0,
+        };
+        reader.next_chunk = reader.read_chunk()?;
+        // 첫 청크를 바로 복호화해 암호 오류를 생성 시점에 알림
+        reader.decrypt_next_chunk().map_err(|_| anyhow::anyhow!("복호화 실패: 암호가 틀렸거나 파일이 손상되었습니다"))?;
+        Ok(reader)
+    }
+
+    fn read_chunk(&mut self) -> io::Result<Vec<u8>> {
+        let mut chunk = vec![0u8; CHUNK_SIZE + TAG_LEN];
+        let n = read_full(&mut self.inner, &mut chunk)?;
+        chunk.truncate(n);
+        Ok(chunk)
+    }
+
+    fn decrypt_next_chunk(&mut self) -> io::Result<()> {
+        let current = std::mem::take(&mut self.next_chunk);
+        if current.is_empty() {
+            return Err(crypto_error("암호화 아카이브가 잘렸습니다"));
+        }
+        self.next_chunk = self.read_chunk()?;
+
+        let payload = Payload { msg: &current, aad: &self.header };
+        let plaintext = if self.next_chunk.is_empty() {
+            let decryptor = self.decryptor.take().expect("decryptor");
+            decryptor.decrypt_last(payload)
+        } else {
+            let decryptor = self.decryptor.as_mut().expect("decryptor");
+            decryptor.decrypt_next(payload)
+        };
+        self.plaintext = plaintext.map_err(|_| crypto_error("복호화 실패: 파일이 손상되었거나 변조되었습니다"))?;
+        self.position = 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.plaintext.len() {
+            if self.decryptor.is_none() {
+                return Ok(0);
+            }
+            self.decrypt_next_chunk()?;
+        }
+        let n = buffer.len().min(self.plaintext.len() - self.position);
+        buffer[..n].copy_from_slice(&self.plaintext[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encrypt(data: &[u8], key: &BackupKey) -> Result<Vec<u8>> {
+        let mut writer = EncryptWriter::new(Vec::new(), key)?;
+        writer.write_all(data)?;
+        Ok(writer.finish()?)
+    }
+
+    fn decrypt(data: &[u8], passphrase: Option<&str>) -> Result<Vec<u8>> {
+        let mut plaintext = Vec::new();
+        DecryptReader::new(data, passphrase)?.read_to_end(&mut plaintext)?;
+        Ok(plaintext)
+    }
+
+    #[test]
+    fn test_passphrase_round_trip_and_tampering() -> Result<()> {
+        let key = BackupKey::Passphrase("correct horse".to_string());
+
+        // 빈 데이터, 청크 경계 정확히 일치, 여러 청크
+        for size in [0, CHUNK_SIZE, CHUNK_SIZE * 2 + 123] {
+            let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+            let encrypted = encrypt(&data, &key)?;
+            assert!(is_encrypted(&encrypted));
+            assert_eq!(decrypt(&encrypted, Some("correct horse"))?, data);
+        }
+
+        let data = vec![7u8; CHUNK_SIZE + 10];
+        let encrypted = encrypt(&data, &key)?;
+
+        // 틀린 암호 / 암호 없음
+        assert!(decrypt(&encrypted, Some("wrong")).is_err());
+        assert!(decrypt(&encrypted, None).unwrap_err().to_string().contains("암호"));
+
+        // 변조된 바이트 (두 번째 청크)
+        let mut tampered = encrypted.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 0x01;
+        assert!(decrypt(&tampered, Some("correct horse")).is_err());
+
+        // 청크 경계에서 잘림 (마지막 청크 삭제)
+        let truncated = &encrypted[..HEADER_LEN + CHUNK_SIZE + TAG_LEN];
+        assert!(decrypt(truncated, Some("correct horse")).is_err());
+
+        // 헤더의 키 유도 비용 조작 (키 유도 전에 거부)
+        let mut inflated = encrypted.clone();
+        inflated[9..13].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decrypt(&inflated, Some("correct horse"))
+            .unwrap_err()
+            .to_string()
+            .contains("키 유도 비용"));
+
+        Ok(())
+    }
+}
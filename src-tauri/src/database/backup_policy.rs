@@ -0,0 +1,470 @@
+// 자동 백업 정책 + GFS(grandfather-father-son) 보존
+//
+// 정책/상태는 백업 디렉토리의 JSON 파일에 저장한다 (DB 복구로 정책이 되돌아가지 않도록 DB 밖에 둠).
+// - backup_policy.json: 주기, 계기별 사용 여부, 보존 개수, 보조 백업 위치, 암호화 여부
+// - backup_status.json: 마지막 성공/실패, 연속 실패 횟수 (실패 알림용)
+
+use crate::database::backup::{BackupManager, BackupManifest, BackupTrigger, RestoreReport};
+use crate::database::backup_crypto::BackupKey;
+use anyhow::{Context, Result};
+use chrono::{Datelike, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const POLICY_FILE: &str = "backup_policy.json";
+const STATUS_FILE: &str = "backup_status.json";
+
+/// 자동 백업 주기
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFrequency {
+    Daily,
+    Weekly,
+    Off,
+}
+
+/// 백업 보존 개수
+///
+/// GFS(일/주/월 단위 최신 백업 1개씩)는 자동 백업(스케줄/앱 시작)에만 적용하고,
+/// 수동/복구 전/마이그레이션 전 백업은 GFS에서 빼고 최신 `manual`개를 따로 보존한다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub daily: usize,
+    pub weekly: usize,
+    pub monthly: usize,
+    pub manual: usize,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self { daily: 7, weekly: 4, monthly: 12, manual: 20 }
+    }
+}
+
+/// 자동 백업 정책
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupPolicy {
+    pub frequency: BackupFrequency,
+    pub on_app_start: bool,
+    pub before_migrations: bool,
+    pub before_restore: bool,
+    pub retention: RetentionPolicy,
+    /// 보조 백업 위치 (네트워크 공유 폴더 등, 같은 보존 정책 적용)
+    pub secondary_dir: Option<PathBuf>,
+    /// 시스템 keyring 키로 아카이브 암호화 (자동 백업 포함, 이 PC 계정에서만 복구 가능)
+    pub encrypt_with_keyring: bool,
+}
+
+impl Default for BackupPolicy {
+    fn default() -> Self {
+        Self {
+            frequency: BackupFrequency::Daily,
+            on_app_start: true,
+            before_migrations: true,
+            before_restore: true,
+            retention: RetentionPolicy::default(),
+            secondary_dir: None,
+            encrypt_with_keyring: false,
+        }
+    }
+}
+
+impl BackupPolicy {
+    /// 정책 로드 (파일이 없으면 기본값)
+    pub fn load(backup_dir: &Path) -> Result<Self> {
+        let path = backup_dir.join(POLICY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .context("백업 정책 읽기 실패")?;
+        serde_json::from_str(&content).context("백업 정책 파싱 실패")
+    }
+
+    pub fn save(&self, backup_dir: &Path) -> Result<()> {
+        fs::write(backup_dir.join(POLICY_FILE), serde_json::to_string_pretty(self)?)
+            .context("백업 정책 저장 실패")
+    }
+
+    /// 정책상 새 백업 암호화 키
+    pub fn encryption_key(&self) -> Option<BackupKey> {
+        self.encrypt_with_keyring.then_some(BackupKey::Keyring)
+    }
+
+    /// 계기별 백업 사용 여부 (수동/스케줄은 항상 허용, 스케줄 주기는 is_due로 판단)
+    pub fn allows(&self, trigger: BackupTrigger) -> bool {
+        match trigger {
+            BackupTrigger::Manual | BackupTrigger::Scheduled => true,
+            BackupTrigger::AppStart => self.on_app_start,
+            BackupTrigger::PreMigration => self.before_migrations,
+            BackupTrigger::PreRestore => self.before_restore,
+        }
+    }
+}
+
+/// 자동 백업 상태 (실패 알림용)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupStatus {
+    pub last_success_at: Option<String>,
+    pub last_backup_path: Option<String>,
+    pub last_trigger: Option<BackupTrigger>,
+    pub last_failure_at: Option<String>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    /// 보조 위치 복사 실패 (주 백업은 성공)
+    pub secondary_error: Option<String>,
+}
+
+impl BackupStatus {
+    pub fn load(backup_dir: &Path) -> Result<Self> {
+        let path = backup_dir.join(STATUS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .context("백업 상태 읽기 실패")?;
+        serde_json::from_str(&content).context("백업 상태 파싱 실패")
+    }
+
+    pub fn save(&self, backup_dir: &Path) -> Result<()> {
+        fs::write(backup_dir.join(STATUS_FILE), serde_json::to_string_pretty(self)?)
+            .context("백업 상태 저장 실패")
+    }
+
+    /// 마지막 시도가 실패했거나 보조 위치 복사에 실패한 상태
+    pub fn needs_alert(&self) -> bool {
+        self.consecutive_failures > 0 || self.secondary_error.is_some()
+    }
+}
+
+/// 백업 1회 실행 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupRunReport {
+    pub backup_path: PathBuf,
+    pub manifest: BackupManifest,
+    /// 보존 정책으로 삭제된 백업 수 (주 위치)
+    pub pruned: usize,
+    pub secondary_path: Option<PathBuf>,
+    pub secondary_error: Option<String>,
+}
+
+/// 정책상 허용된 계기면 백업 실행 (비활성 계기는 None)
+pub fn run_backup_if_enabled(db_path: &Path, trigger: BackupTrigger) -> Result<Option<BackupRunReport>> {
+    let manager = BackupManager::new(db_path.to_path_buf())?;
+    if !BackupPolicy::load(manager.backup_dir())?.allows(trigger) {
+        return Ok(None);
+    }
+    run_backup(db_path, trigger).map(Some)
+}
+
+/// 백업 → 보존 정책 적용 → 보조 위치 복사, 결과를 backup_status.json에 기록
+pub fn run_backup(db_path: &Path, trigger: BackupTrigger) -> Result<BackupRunReport> {
+    run_backup_with_key(db_path, trigger, None)
+}
+
+/// 지정 키로 암호화해 백업 (수동 백업 암호, None이면 정책의 암호화 설정)
+pub fn run_backup_with_key(db_path: &Path, trigger: BackupTrigger, key: Option<BackupKey>) -> Result<BackupRunReport> {
+    run_backup_protecting(db_path, trigger, key, None)
+}
+
+/// 복구 전 백업(정책 before_restore) 후 복구, 사전 백업 실패 시 복구 중단
+///
+/// 사전 백업의 보존 정책 적용에서 복구할 백업은 삭제하지 않는다.
+pub fn restore_with_pre_backup(db_path: &Path, backup_path: &Path, passphrase: Option<&str>) -> Result<RestoreReport> {
+    let manager = BackupManager::new(db_path.to_path_buf())?;
+    if BackupPolicy::load(manager.backup_dir())?.allows(BackupTrigger::PreRestore) {
+        run_backup_protecting(db_path, BackupTrigger::PreRestore, None, Some(backup_path))
+            .context("복구 전 백업 실패")?;
+    }
+    manager.restore_from_backup(backup_path, passphrase)
+}
+
+fn run_backup_protecting(
+    db_path: &Path,
+    trigger: BackupTrigger,
+    key: Option<BackupKey>,
+    protected: Option<&Path>,
+) -> Result<BackupRunReport> {
+    let manager = BackupManager::new(db_path.to_path_buf())?;
+    let policy = BackupPolicy::load(manager.backup_dir())?;
+    let manager = manager.with_encryption(key.or_else(|| policy.encryption_key()));
+    let mut status = BackupStatus::load(manager.backup_dir()).unwrap_or_default();
+    let now = Utc::now().to_rfc3339();
+
+    let (backup_path, manifest) = match manager.create_backup_with_manifest(trigger) {
+        Ok(created) => created,
+        Err(e) => {
+            status.last_failure_at = Some(now);
+            status.last_error = Some(format!("[{}] {}", trigger.as_str(), e));
+            status.consecutive_failures += 1;
+            if let Err(save_error) = status.save(manager.backup_dir()) {
+                eprintln!("⚠️  백업 상태 저장 실패: {}", save_error);
+            }
+            return Err(e);
+        }
+    };
+
+    let pruned = apply_retention(&manager, &policy.retention, protected)?;
+
+    let (secondary_path, secondary_error) = match &policy.secondary_dir {
+        Some(dir) => match copy_to_secondary(&manager, &backup_path, db_path, dir, &policy.retention) {
+            Ok(path) => (Some(path), None),
+            Err(e) => {
+                eprintln!("⚠️  보조 백업 실패 ({}): {}", dir.display(), e);
+                (None, Some(format!("{}: {}", dir.display(), e)))
+            }
+        },
+        None => (None, None),
+    };
+
+    status.last_success_at = Some(now);
+    status.last_backup_path = Some(backup_path.to_string_lossy().to_string());
+    status.last_trigger = Some(trigger);
+    status.last_error = None;
+    status.consecutive_failures = 0;
+    status.secondary_error = secondary_error.clone();
+    status.save(manager.backup_dir())?;
+
+    Ok(BackupRunReport {
+        backup_path,
+        manifest,
+        pruned,
+        secondary_path,
+        secondary_error,
+    })
+}
+
+fn copy_to_secondary(
+    manager: &BackupManager,
+    backup_path: &Path,
+    db_path: &Path,
+    secondary_dir: &Path,
+    retention: &RetentionPolicy,
+) -> Result<PathBuf> {
+    let secondary = BackupManager::with_backup_dir(db_path.to_path_buf(), secondary_dir.to_path_buf())?;
+    let copied = manager.copy_backup_to(backup_path, &secondary)?;
+    apply_retention(&secondary, retention, None)?;
+    Ok(copied)
+}
+
+/// 스케줄 백업 시점 여부 (마지막 백업 이후 주기 경과)
+pub fn is_due(frequency: BackupFrequency, last_backup_at: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    let interval = match frequency {
+        BackupFrequency::Daily => Duration::days(1),
+        BackupFrequency::Weekly => Duration::weeks(1),
+        BackupFrequency::Off => return false,
+    };
+    last_backup_at.is_none_or(|last| now - last >= interval)
+}
+
+/// 가장 최근 백업 시각 (계기 무관)
+pub fn last_backup_at(manager: &BackupManager) -> Result<Option<NaiveDateTime>> {
+    Ok(manager
+        .list_backups()?
+        .iter()
+        .filter_map(|path| BackupManager::backup_timestamp(path))
+        .max())
+}
+
+/// 보존 정책 적용, 삭제된 백업 수 반환
+///
+/// 자동 백업은 GFS, 수동/복구 전/마이그레이션 전 백업은 최신 `retention.manual`개를 보존한다
+/// (매니페스트가 없는 이전 백업은 수동 백업으로 취급). `protected` 백업은 삭제하지 않는다.
+pub fn apply_retention(manager: &BackupManager, retention: &RetentionPolicy, protected: Option<&Path>) -> Result<usize> {
+    let mut automatic = Vec::new();
+    let mut on_demand = Vec::new();
+    for path in manager.list_backups()? {
+        let Some(timestamp) = BackupManager::backup_timestamp(&path) else {
+            continue;
+        };
+        let trigger = manager.read_manifest(&path).ok().flatten().map(|manifest| manifest.trigger);
+        match trigger {
+            Some(BackupTrigger::Scheduled | BackupTrigger::AppStart) => automatic.push((path, timestamp)),
+            _ => on_demand.push((path, timestamp)),
+        }
+    }
+
+    let mut keep = gfs_retain(&automatic, retention);
+    on_demand.sort_by_key(|(_, timestamp)| std::cmp::Reverse(*timestamp));
+    keep.extend(on_demand.iter().take(retention.manual.max(1)).map(|(path, _)| path.clone()));
+    if let Some(protected) = protected {
+        keep.insert(protected.to_path_buf());
+    }
+
+    let mut deleted = 0;
+    for (path, _) in automatic.iter().chain(on_demand.iter()) {
+        if !keep.contains(path) {
+            manager.delete_backup(path)?;
+            deleted += 1;
+        }
+    }
+    Ok(deleted)
+}
+
+/// GFS 보존 대상: 최근 N일/N주/N개월 각 구간의 가장 최신 백업 (가장 최신 백업은 항상 보존)
+pub fn gfs_retain(backups: &[(PathBuf, NaiveDateTime)], retention: &RetentionPolicy) -> BTreeSet<PathBuf> {
+    let mut sorted: Vec<&(PathBuf, NaiveDateTime)> = backups.iter().collect();
+    sorted.sort_by_key(|(_, timestamp)| std::cmp::Reverse(*timestamp));
+
+    let mut keep = BTreeSet::new();
+    if let Some((newest, _)) = sorted.first() {
+        keep.insert(newest.clone());
+    }
+
+    let mut keep_latest_per = |limit: usize, bucket: &dyn Fn(&NaiveDateTime) -> (i32, u32)| {
+        let mut seen = BTreeSet::new();
+        for (path, timestamp) in &sorted {
+            if seen.len() >= limit {
+                break;
+            }
+            if seen.insert(bucket(timestamp)) {
+                keep.insert(path.clone());
+            }
+        }
+    };
+    keep_latest_per(retention.daily, &|t| (t.year(), t.ordinal()));
+    keep_latest_per(retention.weekly, &|t| (t.iso_week().year(), t.iso_week().week()));
+    keep_latest_per(retention.monthly, &|t| (t.year(), t.month()));
+
+    keep
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use tempfile::TempDir;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_gfs_retain() {
+        // 2025-01-01 ~ 2025-03-31 매일 02:00, 3월 31일은 12:00 추가
+        let mut backups = Vec::new();
+        let mut day = at("2025-01-01 02:00:00");
+        while day <= at("2025-03-31 02:00:00") {
+            backups.push((PathBuf::from(day.format("%Y%m%d_%H%M").to_string()), day));
+            day += Duration::days(1);
+        }
+        backups.push((PathBuf::from("20250331_1200"), at("2025-03-31 12:00:00")));
+
+        let retention = RetentionPolicy { daily: 3, weekly: 2, monthly: 3, manual: 0 };
+        let keep = gfs_retain(&backups, &retention);
+        let expected: BTreeSet<PathBuf> = [
+            // daily: 3/31(최신), 3/30, 3/29
+            "20250331_1200",
+            "20250330_0200",
+            "20250329_0200",
+            // weekly: 이번 주(3/31 월요일) + 지난주 마지막(3/30 일요일) → daily와 겹침
+            // monthly: 3월(3/31), 2월 말, 1월 말
+            "20250228_0200",
+            "20250131_0200",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        assert_eq!(keep, expected);
+
+        // 보존 개수가 0이어도 최신 백업은 유지
+        let none = RetentionPolicy { daily: 0, weekly: 0, monthly: 0, manual: 0 };
+        assert_eq!(gfs_retain(&backups, &none).len(), 1);
+    }
+
+    #[test]
+    fn test_restore_same_day_backup_with_pre_restore_backup() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test.db");
+        {
+            let conn = Connection::open(&db_path)?;
+            crate::database::migrations::migrate_up(&conn, false)?;
+            conn.execute(
+                "INSERT INTO judgments (id, workflow_id, input_data, result, confidence, method_used, created_at)
+                 VALUES ('j-1', 'wf-1', '{}', 1, 0.9, 'rule', '2025-03-03T09:00:00Z')",
+                [],
+            )?;
+        }
+        let manager = BackupManager::new(db_path.clone())?;
+        BackupPolicy { before_restore: true, ..BackupPolicy::default() }.save(manager.backup_dir())?;
+
+        // 같은 날 수동 백업 → 자동 백업 여러 번 (GFS는 하루 최신 1개만 남김)
+        let manual = run_backup(&db_path, BackupTrigger::Manual)?.backup_path;
+        run_backup(&db_path, BackupTrigger::Scheduled)?;
+        run_backup(&db_path, BackupTrigger::AppStart)?;
+        assert!(manual.exists(), "자동 백업 보존 정책이 수동 백업을 지우면 안 됨");
+
+        Connection::open(&db_path)?.execute("DELETE FROM judgments", [])?;
+        let report = restore_with_pre_backup(&db_path, &manual, None)?;
+        assert!(report.safety_backup.is_some());
+        let count: i64 = Connection::open(&db_path)?.query_row("SELECT COUNT(*) FROM judgments", [], |row| row.get(0))?;
+        assert_eq!(count, 1);
+        assert!(manual.exists());
+
+        // 복구 전 백업도 GFS 대상이 아님 (자동 백업 1개 + 수동 + 복구 전)
+        let triggers: Vec<BackupTrigger> = manager
+            .list_backups()?
+            .iter()
+            .filter_map(|path| manager.read_manifest(path).ok().flatten().map(|m| m.trigger))
+            .collect();
+        assert!(triggers.contains(&BackupTrigger::PreRestore));
+        assert_eq!(triggers.iter().filter(|t| **t == BackupTrigger::Manual).count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_due() {
+        let now = at("2025-03-10 09:00:00");
+        assert!(is_due(BackupFrequency::Daily, None, now));
+        assert!(is_due(BackupFrequency::Daily, Some(at("2025-03-09 08:59:00")), now));
+        assert!(!is_due(BackupFrequency::Daily, Some(at("2025-03-09 10:00:00")), now));
+        assert!(!is_due(BackupFrequency::Weekly, Some(at("2025-03-05 09:00:00")), now));
+        assert!(is_due(BackupFrequency::Weekly, Some(at("2025-03-03 09:00:00")), now));
+        assert!(!is_due(BackupFrequency::Off, None, now));
+    }
+
+    #[test]
+    fn test_run_backup_secondary_and_failure_status() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db_path = temp_dir.path().join("test.db");
+        Connection::open(&db_path)?.execute_batch("CREATE TABLE judgments (id TEXT PRIMARY KEY);")?;
+
+        let secondary_dir = temp_dir.path().join("share");
+        let manager = BackupManager::new(db_path.clone())?;
+        let policy = BackupPolicy {
+            secondary_dir: Some(secondary_dir.clone()),
+            before_restore: false,
+            ..BackupPolicy::default()
+        };
+        policy.save(manager.backup_dir())?;
+        assert_eq!(BackupPolicy::load(manager.backup_dir())?, policy);
+
+        let report = run_backup(&db_path, BackupTrigger::Scheduled)?;
+        let secondary_path = report.secondary_path.expect("보조 위치 복사");
+        assert!(secondary_path.starts_with(&secondary_dir));
+        assert!(BackupManager::manifest_path(&secondary_path).exists());
+        assert_eq!(report.manifest.trigger, BackupTrigger::Scheduled);
+        assert!(run_backup_if_enabled(&db_path, BackupTrigger::PreRestore)?.is_none());
+
+        let status = BackupStatus::load(manager.backup_dir())?;
+        assert_eq!(status.last_trigger, Some(BackupTrigger::Scheduled));
+        assert!(!status.needs_alert());
+
+        // DB가 손상되면 실패 상태 기록 (연속 실패 횟수 누적)
+        fs::write(&db_path, b"not a database")?;
+        assert!(run_backup(&db_path, BackupTrigger::Scheduled).is_err());
+        assert!(run_backup(&db_path, BackupTrigger::AppStart).is_err());
+        let status = BackupStatus::load(manager.backup_dir())?;
+        assert!(status.needs_alert());
+        assert_eq!(status.consecutive_failures, 2);
+        assert!(status.last_error.unwrap().starts_with("[app_start]"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+// 데모 데이터 적재 (opt-in: `load_demo_data` 명령 / `init_db --demo`)
+// 퓨어웰 음료㈜ 시연용 ERP/MES/지식베이스 데이터 + 샘플 판단 기록
+//
+// 스키마는 migrations.rs가 관리하며, 여기서는 데이터만 넣는다.
+// 적재한 스크립트는 _migrations에 version 없이 이름으로 기록된다
+// (버전 관리 이전 앱이 마이그레이션으로 넣은 DB도 같은 이름이라 다시 적재하지 않음).
+
+use crate::database::migrations;
+use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
+
+/// 데모 데이터 SQL (컴파일 시점에 바이너리 포함)
+struct DemoScript {
+    name: &'static str,
+    sql: &'static str,
+}
+
+/// 데모 데이터 스크립트 (순서대로 실행)
+const DEMO_SCRIPTS: &[DemoScript] = &[
+    DemoScript {
+        name: "004_seed_knowledge.sql",
+        sql: include_str!("../../demo_data/004_seed_knowledge.sql"),
+    },
+    DemoScript {
+        name: "005_seed_erp_master.sql",
+        sql: include_str!("../../demo_data/005_seed_erp_master.sql"),
+    },
+    DemoScript {
+        name: "006_seed_erp_transaction.sql",
+        sql: include_str!("../../demo_data/006_seed_erp_transaction.sql"),
+    },
+    DemoScript {
+        name: "007_seed_mes.sql",
+        sql: include_str!("../../demo_data/007_seed_mes.sql"),
+    },
+    DemoScript {
+        name: "008_seed_sales_history.sql",
+        sql: include_str!("../../demo_data/008_seed_sales_history.sql"),
+    },
+    DemoScript {
+        name: "009_seed_2025_sales.sql",
+        sql: include_str!("../../demo_data/009_seed_2025_sales.sql"),
+    },
+    DemoScript {
+        name: "011_seed_additional.sql",
+        sql: include_str!("../../demo_data/011_seed_additional.sql"),
+    },
+    DemoScript {
+        name: "012_seed_erp_extended.sql",
+        sql: include_str!("../../demo_data/012_seed_erp_extended.sql"),
+    },
+    DemoScript {
+        name: "013_seed_mes_extended.sql",
+        sql: include_str!("../../demo_data/013_seed_mes_extended.sql"),
+    },
+    DemoScript {
+        name: "014_seed_mes_complete.sql",
+        sql: include_str!("../../demo_data/014_seed_mes_complete.sql"),
+    },
+    DemoScript {
+        name: "015_seed_erp_2025_full.sql",
+        sql: include_str!("../../demo_data/015_seed_erp_2025_full.sql"),
+    },
+    DemoScript {
+        name: "016_seed_recent_sensor_data.sql",
+        sql: include_str!("../../demo_data/016_seed_recent_sensor_data.sql"),
+    },
+    DemoScript {
+        name: "017_fix_warehouse_inventory_mapping.sql",
+        sql: include_str!("../../demo_data/017_fix_warehouse_inventory_mapping.sql"),
+    },
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DemoDataReport {
+    /// 이번에 적재한 스크립트
+    pub loaded: Vec<String>,
+    /// 이미 적재돼 있던 스크립트
+    pub already_loaded: Vec<String>,
+    /// 기존 데이터와 키가 겹쳐 건너뛴 스크립트 (해당 스크립트는 롤백됨)
+    pub conflicts: Vec<String>,
+}
+
+/// 앱에 포함된 데모 데이터 스크립트 이름
+pub fn demo_script_names() -> Vec<&'static str> {
+    DEMO_SCRIPTS.iter().map(|script| script.name).collect()
+}
+
+/// 데모 데이터 적재 (스키마가 최신이어야 함 - `Database::open` 이후 호출)
+///
+/// 스크립트마다 트랜잭션으로 실행하며, 이미 적재한 스크립트는 건너뛴다.
+pub fn load_demo_data(conn: &Connection) -> Result<DemoDataReport> {
+    let pending = migrations::migrate_up(conn, true)?;
+    if !pending.steps.is_empty() {
+        return Err(migrations::migration_error(format!(
+            "스키마 마이그레이션이 먼저 필요합니다 (미적용 {}개)",
+            pending.steps.len()
+        )));
+    }
+
+    crate::database::seed::seed_sample_data(conn)?;
+
+    // 일부 스크립트가 시드에 없는 마스터 코드를 참조하므로 적재 중에는 외래키 검사를 끈다
+    // (트랜잭션 안에서는 바꿀 수 없어 스크립트 실행 전후로 설정, 끝나면 원래 설정 복원)
+    let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+    let mut report = DemoDataReport::default();
+    let result = load_scripts(conn, &mut report);
+    if foreign_keys {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    }
+    result?;
+
+    if !report.loaded.is_empty() {
+        rebuild_knowledge_base_fts(conn)?;
+    }
+
+    eprintln!(
+        "📊 데모 데이터: {}개 적재, {}개 기존, {}개 중복",
+        report.loaded.len(),
+        report.already_loaded.len(),
+        report.conflicts.len()
+    );
+    Ok(report)
+}
+
+fn load_scripts(conn: &Connection, report: &mut DemoDataReport) -> Result<()> {
+    for script in DEMO_SCRIPTS {
+        let loaded: bool = conn
+            .prepare("SELECT 1 FROM _migrations WHERE name = ?1")?
+            .exists([script.name])?;
+        if loaded {
+            report.already_loaded.push(script.name.to_string());
+            continue;
+        }
+
+        eprintln!("🌱 데모 데이터 적재: {}", script.name);
+        let tx = conn.unchecked_transaction()?;
+        match tx.execute_batch(script.sql) {
+            Ok(()) => {
+                tx.execute(
+                    "INSERT INTO _migrations (name, checksum) VALUES (?1, ?2)",
+                    rusqlite::params![script.name, migrations::checksum(script.sql)],
+                )?;
+                tx.commit()?;
+                report.loaded.push(script.name.to_string());
+            }
+            // 사용자가 같은 코드의 마스터/거래를 이미 등록한 경우: 해당 스크립트만 건너뜀
+            Err(e) if e.to_string().contains("UNIQUE constraint failed") => {
+                eprintln!("   ⚠️  기존 데이터와 중복되어 건너뜀: {} - {}", script.name, e);
+                report.conflicts.push(script.name.to_string());
+            }
+            Err(e) => {
+                return Err(migrations::migration_error(format!("데모 데이터 적재 실패: {} - {}", script.name, e)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Knowledge Base FTS5 인덱스 재구축
+fn rebuild_knowledge_base_fts(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "DELETE FROM knowledge_base_fts;
+         INSERT INTO knowledge_base_fts (rowid, title, content, tags)
+         SELECT rowid, title, content, tags FROM knowledge_base;"
+    )?;
+
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM knowledge_base_fts", [], |row| row.get(0))?;
+    eprintln!("✅ Knowledge Base FTS5 재구축 완료: {}건", count);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_demo_data_once() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let conn = Connection::open(temp_dir.path().join("demo.db"))?;
+        migrations::migrate_up(&conn, false)?;
+
+        let report = load_demo_data(&conn)?;
+        assert_eq!(report.loaded.len(), DEMO_SCRIPTS.len(), "{:?}", report);
+
+        let items: i64 = conn.query_row("SELECT COUNT(*) FROM item_mst", [], |row| row.get(0))?;
+        assert!(items > 0);
+        let kb_hits: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM knowledge_base_fts WHERE knowledge_base_fts MATCH '퓨어웰'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert!(kb_hits > 0);
+        let monthly: i64 = conn.query_row("SELECT COUNT(*) FROM v_monthly_sales", [], |row| row.get(0))?;
+        assert!(monthly > 0);
+
+        // 두 번째 호출은 아무것도 하지 않음 (스키마 버전도 그대로)
+        let again = load_demo_data(&conn)?;
+        assert!(again.loaded.is_empty());
+        assert_eq!(again.already_loaded.len(), DEMO_SCRIPTS.len());
+        assert!(migrations::migrate_up(&conn, true)?.steps.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,186 +1,505 @@
-// 앱 시작시 자동 실행되는 마이그레이션 모듈
+// 버전별 스키마 마이그레이션 실행기 (앱 시작, init_db, 복구 후 재오픈에서 공통 사용)
 // SQL 파일들을 컴파일 시점에 바이너리에 포함시킴 (include_str!)
+//
+// - migrations/NNNN_이름.sql: 스키마 전용 (테이블/인덱스/뷰/트리거), 데이터는 demo_data.rs
+// - migrations/NNNN_이름.down.sql: 되돌리기
+// - _migrations: 적용 버전 + SQL 체크섬 (적용 후 파일이 수정되면 실행 거부)
 
+use crate::database::backup::BackupTrigger;
+use crate::database::backup_policy;
 use rusqlite::{Connection, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// 마이그레이션 SQL 정의 (컴파일 시점에 바이너리 포함)
 struct Migration {
+    version: u32,
     name: &'static str,
-    sql: &'static str,
+    up: &'static str,
+    down: &'static str,
 }
 
-/// 모든 마이그레이션 SQL 포함 (순서대로 실행)
+/// 스키마 마이그레이션 (버전 순)
 const MIGRATIONS: &[Migration] = &[
     Migration {
-        name: "001_knowledge_base.sql",
-        sql: include_str!("../../migrations/001_knowledge_base.sql"),
+        version: 1,
+        name: "0001_core_schema.sql",
+        up: include_str!("../../migrations/0001_core_schema.sql"),
+        down: include_str!("../../migrations/0001_core_schema.down.sql"),
     },
     Migration {
-        name: "002_erp_schema.sql",
-        sql: include_str!("../../migrations/002_erp_schema.sql"),
+        version: 2,
+        name: "0002_knowledge_base.sql",
+        up: include_str!("../../migrations/0002_knowledge_base.sql"),
+        down: include_str!("../../migrations/0002_knowledge_base.down.sql"),
     },
     Migration {
-        name: "003_mes_schema.sql",
-        sql: include_str!("../../migrations/003_mes_schema.sql"),
+        version: 3,
+        name: "0003_erp_schema.sql",
+        up: include_str!("../../migrations/0003_erp_schema.sql"),
+        down: include_str!("../../migrations/0003_erp_schema.down.sql"),
     },
     Migration {
-        name: "004_seed_knowledge.sql",
-        sql: include_str!("../../migrations/004_seed_knowledge.sql"),
+        version: 4,
+        name: "0004_mes_schema.sql",
+        up: include_str!("../../migrations/0004_mes_schema.sql"),
+        down: include_str!("../../migrations/0004_mes_schema.down.sql"),
     },
     Migration {
-        name: "005_seed_erp_master.sql",
-        sql: include_str!("../../migrations/005_seed_erp_master.sql"),
+        version: 5,
+        name: "0005_erp_mes_extensions.sql",
+        up: include_str!("../../migrations/0005_erp_mes_extensions.sql"),
+        down: include_str!("../../migrations/0005_erp_mes_extensions.down.sql"),
     },
     Migration {
-        name: "006_seed_erp_transaction.sql",
-        sql: include_str!("../../migrations/006_seed_erp_transaction.sql"),
+        version: 6,
+        name: "0006_reporting_views.sql",
+        up: include_str!("../../migrations/0006_reporting_views.sql"),
+        down: include_str!("../../migrations/0006_reporting_views.down.sql"),
     },
     Migration {
-        name: "007_seed_mes.sql",
-        sql: include_str!("../../migrations/007_seed_mes.sql"),
+        version: 7,
+        name: "0007_audit_log.sql",
+        up: include_str!("../../migrations/0007_audit_log.sql"),
+        down: include_str!("../../migrations/0007_audit_log.down.sql"),
     },
     Migration {
-        name: "008_seed_sales_history.sql",
-        sql: include_str!("../../migrations/008_seed_sales_history.sql"),
-    },
-    Migration {
-        name: "009_seed_2025_sales.sql",
-        sql: include_str!("../../migrations/009_seed_2025_sales.sql"),
-    },
-    Migration {
-        name: "010_additional_erp_mes.sql",
-        sql: include_str!("../../migrations/010_additional_erp_mes.sql"),
-    },
-    Migration {
-        name: "011_seed_additional.sql",
-        sql: include_str!("../../migrations/011_seed_additional.sql"),
-    },
-    Migration {
-        name: "012_seed_erp_extended.sql",
-        sql: include_str!("../../migrations/012_seed_erp_extended.sql"),
-    },
-    Migration {
-        name: "013_seed_mes_extended.sql",
-        sql: include_str!("../../migrations/013_seed_mes_extended.sql"),
-    },
-    Migration {
-        name: "014_seed_mes_complete.sql",
-        sql: include_str!("../../migrations/014_seed_mes_complete.sql"),
-    },
-    Migration {
-        name: "015_seed_erp_2025_full.sql",
-        sql: include_str!("../../migrations/015_seed_erp_2025_full.sql"),
-    },
-    Migration {
-        name: "016_seed_recent_sensor_data.sql",
-        sql: include_str!("../../migrations/016_seed_recent_sensor_data.sql"),
-    },
-    Migration {
-        name: "017_fix_warehouse_inventory_mapping.sql",
-        sql: include_str!("../../migrations/017_fix_warehouse_inventory_mapping.sql"),
+        version: 8,
+        name: "0008_users.sql",
+        up: include_str!("../../migrations/0008_users.sql"),
+        down: include_str!("../../migrations/0008_users.down.sql"),
     },
 ];
 
-/// 마이그레이션 실행 (앱 시작시 자동 호출)
-pub fn apply_migrations(conn: &Connection) -> Result<()> {
-    // 마이그레이션 추적 테이블 생성
+/// 버전 관리 도입 이전에 스키마 파일로 기록되던 이름 (_migrations에 version 없이 남아 있음)
+const LEGACY_SCHEMA_NAMES: &[&str] = &[
+    "001_knowledge_base.sql",
+    "002_erp_schema.sql",
+    "003_mes_schema.sql",
+    "010_additional_erp_mes.sql",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationDirection {
+    Up,
+    Down,
+}
+
+/// 실행(또는 dry-run에서 실행 예정)된 마이그레이션 1건
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStep {
+    pub version: u32,
+    pub name: String,
+    pub direction: MigrationDirection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    pub steps: Vec<MigrationStep>,
+    /// 실행 전 스키마 버전
+    pub from_version: u32,
+    /// 실행 후 (dry-run이면 실행했을 때) 스키마 버전
+    pub to_version: u32,
+}
+
+/// 마이그레이션별 적용 상태
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: String,
+    pub applied_at: Option<String>,
+    /// 적용 당시 체크섬과 현재 SQL 일치 여부 (미적용이면 None)
+    pub checksum_matches: Option<bool>,
+}
+
+/// _migrations에 기록된 버전
+struct AppliedMigration {
+    version: u32,
+    name: String,
+    checksum: Option<String>,
+    applied_at: Option<String>,
+}
+
+/// 앱에 포함된 스키마 마이그레이션 이름 (실행 순)
+pub fn migration_names() -> Vec<&'static str> {
+    MIGRATIONS.iter().map(|migration| migration.name).collect()
+}
+
+/// 이 앱이 아는 _migrations 기록인지 (스키마 + 구버전 스키마 이름 + 데모 데이터)
+pub fn is_known_migration(name: &str) -> bool {
+    MIGRATIONS.iter().any(|migration| migration.name == name)
+        || LEGACY_SCHEMA_NAMES.contains(&name)
+        || crate::database::demo_data::demo_script_names().contains(&name)
+}
+
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|migration| migration.version).unwrap_or(0)
+}
+
+/// SQL 체크섬 (SHA-256, 줄바꿈 차이는 무시 - Windows 체크아웃의 CRLF 대비)
+pub fn checksum(sql: &str) -> String {
+    format!("{:x}", Sha256::digest(sql.replace('\r', "").as_bytes()))
+}
+
+/// 마이그레이션 실행 (앱 시작시 자동 호출, 적용 전 백업 포함)
+pub fn apply_migrations(conn: &Connection) -> Result<MigrationReport> {
+    let pending = migrate_up(conn, true)?;
+    if pending.steps.is_empty() {
+        return Ok(pending);
+    }
+
+    backup_before_migrations(conn);
+
+    let report = migrate_up(conn, false)?;
+    eprintln!("📊 마이그레이션 완료: v{} → v{} ({}개 적용)", report.from_version, report.to_version, report.steps.len());
+    Ok(report)
+}
+
+/// 미적용 마이그레이션 실행 (dry_run이면 계획만 반환, DB 변경 없음)
+///
+/// 이미 적용된 마이그레이션의 SQL이 바뀌었거나 앱이 모르는 버전이 적용돼 있으면 실행하지 않는다.
+pub fn migrate_up(conn: &Connection, dry_run: bool) -> Result<MigrationReport> {
+    let applied = applied_migrations(conn)?;
+    verify_applied(&applied)?;
+
+    let from_version = current_version(&applied);
+    let steps: Vec<MigrationStep> = MIGRATIONS
+        .iter()
+        .filter(|migration| !applied.iter().any(|a| a.version == migration.version))
+        .map(|migration| MigrationStep {
+            version: migration.version,
+            name: migration.name.to_string(),
+            direction: MigrationDirection::Up,
+        })
+        .collect();
+    let to_version = steps.last().map(|step| step.version).unwrap_or(from_version).max(from_version);
+
+    if !dry_run && !steps.is_empty() {
+        ensure_tracking_table(conn)?;
+        if applied.is_empty() && table_exists(conn, "judgments")? {
+            upgrade_legacy_schema(conn)?;
+        }
+
+        for step in &steps {
+            let migration = find_migration(step.version)?;
+            eprintln!("📄 마이그레이션 실행: {}", migration.name);
+            let tx = conn.unchecked_transaction()?;
+            tx.execute_batch(migration.up)
+                .map_err(|e| migration_error(format!("마이그레이션 실패: {} - {}", migration.name, e)))?;
+            tx.execute(
+                "INSERT INTO _migrations (name, version, checksum) VALUES (?1, ?2, ?3)",
+                rusqlite::params![migration.name, migration.version, checksum(migration.up)],
+            )?;
+            tx.commit()?;
+        }
+    }
+
+    Ok(MigrationReport { dry_run, steps, from_version, to_version })
+}
+
+/// target_version 이후에 적용된 마이그레이션을 역순으로 되돌림 (dry_run이면 계획만 반환)
+pub fn migrate_down(conn: &Connection, target_version: u32, dry_run: bool) -> Result<MigrationReport> {
+    let applied = applied_migrations(conn)?;
+    verify_applied(&applied)?;
+
+    let from_version = current_version(&applied);
+    let mut to_revert: Vec<&AppliedMigration> = applied.iter().filter(|a| a.version > target_version).collect();
+    to_revert.sort_by_key(|a| std::cmp::Reverse(a.version));
+
+    let steps: Vec<MigrationStep> = to_revert
+        .iter()
+        .map(|a| MigrationStep {
+            version: a.version,
+            name: a.name.clone(),
+            direction: MigrationDirection::Down,
+        })
+        .collect();
+    let to_version = applied.iter().map(|a| a.version).filter(|version| *version <= target_version).max().unwrap_or(0);
+
+    if !dry_run {
+        for step in &steps {
+            let migration = find_migration(step.version)?;
+            eprintln!("↩️  마이그레이션 되돌리기: {}", migration.name);
+            let tx = conn.unchecked_transaction()?;
+            tx.execute_batch(migration.down)
+                .map_err(|e| migration_error(format!("되돌리기 실패: {} - {}", migration.name, e)))?;
+            tx.execute("DELETE FROM _migrations WHERE version = ?1", [migration.version])?;
+            tx.commit()?;
+        }
+    }
+
+    Ok(MigrationReport { dry_run, steps, from_version, to_version })
+}
+
+/// 마이그레이션별 적용 상태 (DB 변경 없음)
+pub fn status(conn: &Connection) -> Result<Vec<MigrationStatus>> {
+    let applied = applied_migrations(conn)?;
+    Ok(MIGRATIONS
+        .iter()
+        .map(|migration| {
+            let record = applied.iter().find(|a| a.version == migration.version);
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.to_string(),
+                applied_at: record.and_then(|a| a.applied_at.clone()),
+                checksum_matches: record.map(|a| a.checksum.as_deref() == Some(checksum(migration.up).as_str())),
+            }
+        })
+        .collect())
+}
+
+fn find_migration(version: u32) -> Result<&'static Migration> {
+    MIGRATIONS
+        .iter()
+        .find(|migration| migration.version == version)
+        .ok_or_else(|| migration_error(format!("알 수 없는 마이그레이션 버전: {}", version)))
+}
+
+fn current_version(applied: &[AppliedMigration]) -> u32 {
+    applied.iter().map(|a| a.version).max().unwrap_or(0)
+}
+
+/// 적용된 마이그레이션이 현재 SQL과 같은지 확인 (수정된 파일 / 더 새 버전 앱의 DB 거부)
+fn verify_applied(applied: &[AppliedMigration]) -> Result<()> {
+    let unknown: Vec<&str> = applied
+        .iter()
+        .filter(|a| !MIGRATIONS.iter().any(|m| m.version == a.version))
+        .map(|a| a.name.as_str())
+        .collect();
+    if !unknown.is_empty() {
+        return Err(migration_error(format!(
+            "더 최신 버전 앱에서 마이그레이션된 DB입니다 (알 수 없는 마이그레이션: {:?})",
+            unknown
+        )));
+    }
+
+    let modified: Vec<&str> = applied
+        .iter()
+        .filter(|a| {
+            let migration = MIGRATIONS.iter().find(|m| m.version == a.version);
+            migration.is_some_and(|m| a.checksum.as_deref() != Some(checksum(m.up).as_str()))
+        })
+        .map(|a| a.name.as_str())
+        .collect();
+    if !modified.is_empty() {
+        return Err(migration_error(format!(
+            "적용된 마이그레이션 파일이 수정되었습니다 (새 번호의 마이그레이션으로 추가하세요): {:?}",
+            modified
+        )));
+    }
+
+    Ok(())
+}
+
+/// _migrations의 버전 기록 (테이블/컬럼이 없으면 빈 목록 - dry-run에서 DB를 바꾸지 않도록)
+fn applied_migrations(conn: &Connection) -> Result<Vec<AppliedMigration>> {
+    if !table_exists(conn, "_migrations")? || !column_exists(conn, "_migrations", "version")? {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT version, name, checksum, applied_at FROM _migrations WHERE version IS NOT NULL ORDER BY version",
+    )?;
+    let applied = stmt
+        .query_map([], |row| {
+            Ok(AppliedMigration {
+                version: row.get(0)?,
+                name: row.get(1)?,
+                checksum: row.get(2)?,
+                applied_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+    Ok(applied)
+}
+
+/// 추적 테이블 생성 (구버전 테이블에는 version/checksum 컬럼 추가)
+///
+/// version이 NULL인 행은 데모 데이터 또는 버전 관리 도입 이전 기록이다.
+fn ensure_tracking_table(conn: &Connection) -> Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS _migrations (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             name TEXT NOT NULL UNIQUE,
-            applied_at TEXT NOT NULL DEFAULT (datetime('now'))
+            applied_at TEXT NOT NULL DEFAULT (datetime('now')),
+            version INTEGER,
+            checksum TEXT
         )",
         [],
     )?;
+    add_column_if_missing(conn, "_migrations", "applied_at", "TEXT")?;
+    add_column_if_missing(conn, "_migrations", "version", "INTEGER")?;
+    add_column_if_missing(conn, "_migrations", "checksum", "TEXT")?;
+    conn.execute_batch(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_migrations_version ON _migrations(version) WHERE version IS NOT NULL;",
+    )?;
+    Ok(())
+}
 
-    let mut applied_count = 0;
-    let mut skipped_count = 0;
-
-    for migration in MIGRATIONS {
-        // 이미 적용된 마이그레이션인지 확인
-        let already_applied: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM _migrations WHERE name = ?",
-            [migration.name],
-            |row| row.get(0),
-        ).unwrap_or(0);
-
-        if already_applied > 0 {
-            skipped_count += 1;
-            continue;
+/// 버전 관리 이전 DB를 0001 기준 스키마로 맞춤
+///
+/// 기존 init_schema는 ALTER TABLE로 컬럼을 추가해 왔으므로, 기준 마이그레이션(CREATE ... IF NOT EXISTS)이
+/// 기존 테이블을 건너뛰어도 인덱스 생성에 필요한 컬럼이 있도록 먼저 보강한다.
+fn upgrade_legacy_schema(conn: &Connection) -> Result<()> {
+    eprintln!("🔄 버전 관리 이전 DB 감지: 기준 스키마로 보강");
+    let legacy_columns = [
+        ("judgments", "template_id", "TEXT"),
+        ("judgments", "template_version", "INTEGER"),
+        ("judgments", "latency_ms", "INTEGER"),
+        ("ccp_docs", "doc_version_id", "TEXT"),
+        ("mes_data_logs", "upload_id", "TEXT"),
+        ("mes_uploads", "source_format", "TEXT NOT NULL DEFAULT 'csv'"),
+        ("mes_uploads", "sheet_name", "TEXT"),
+    ];
+    for (table, column, definition) in legacy_columns {
+        if table_exists(conn, table)? {
+            add_column_if_missing(conn, table, column, definition)?;
         }
+    }
 
-        // 마이그레이션 실행
-        eprintln!("📄 마이그레이션 실행: {}", migration.name);
-        match conn.execute_batch(migration.sql) {
-            Ok(()) => {
-                // 마이그레이션 적용 기록
-                conn.execute(
-                    "INSERT INTO _migrations (name) VALUES (?)",
-                    [migration.name],
-                )?;
-                eprintln!("   ✅ 완료: {}", migration.name);
-                applied_count += 1;
-            }
-            Err(e) => {
-                eprintln!("   ❌ 실패: {} - {}", migration.name, e);
-                // 마이그레이션 실패시 에러 반환 (치명적이지 않은 경우 계속 진행)
-                // 일부 중복 INSERT 등은 무시
-                if e.to_string().contains("UNIQUE constraint failed") {
-                    eprintln!("   ⚠️  중복 데이터 무시하고 계속 진행");
-                    // 마이그레이션 적용 기록 (중복 무시)
-                    let _ = conn.execute(
-                        "INSERT OR IGNORE INTO _migrations (name) VALUES (?)",
-                        [migration.name],
-                    );
-                    applied_count += 1;
-                }
-                // 다른 에러는 계속 진행 (데이터가 이미 있을 수 있음)
-            }
+    // 트리거 도입 이전 DB: 인덱스가 원본과 어긋나 있으면 1회 재구축
+    if table_exists(conn, "ccp_docs")? && table_exists(conn, "ccp_docs_fts")? {
+        let docs_count: i64 = conn.query_row("SELECT COUNT(*) FROM ccp_docs", [], |row| row.get(0))?;
+        let fts_count: i64 = conn.query_row("SELECT COUNT(*) FROM ccp_docs_fts", [], |row| row.get(0))?;
+        if docs_count != fts_count {
+            conn.execute_batch(
+                "DELETE FROM ccp_docs_fts;
+                 INSERT INTO ccp_docs_fts(rowid, title, content) SELECT id, title, content FROM ccp_docs;"
+            )?;
         }
     }
 
-    // FTS5 인덱스 재구축 (knowledge_base)
-    if applied_count > 0 {
-        eprintln!("📊 FTS5 인덱스 재구축 중...");
-        let _ = rebuild_fts5_indexes(conn);
+    Ok(())
+}
+
+/// 기존 DB에 미적용 마이그레이션이 있으면 적용 전 백업 (정책 before_migrations)
+///
+/// 백업 실패는 backup_status.json에 기록되어 스케줄러가 알리며, 앱 시작은 막지 않는다.
+fn backup_before_migrations(conn: &Connection) {
+    // 신규 DB는 백업할 데이터가 없음
+    if !table_exists(conn, "judgments").unwrap_or(false) {
+        return;
+    }
+    let Some(db_path) = conn.path().filter(|path| !path.is_empty()) else {
+        return;
+    };
+
+    match backup_policy::run_backup_if_enabled(std::path::Path::new(db_path), BackupTrigger::PreMigration) {
+        Ok(Some(report)) => eprintln!("💾 마이그레이션 전 백업: {}", report.backup_path.display()),
+        Ok(None) => {}
+        Err(e) => eprintln!("⚠️  마이그레이션 전 백업 실패: {}", e),
     }
+}
 
-    eprintln!("📊 마이그레이션 완료: {}개 적용, {}개 스킵", applied_count, skipped_count);
+pub(crate) fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    conn.prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1")?
+        .exists([table])
+}
 
+fn column_exists(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    conn.prepare(&format!("SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1", table))?
+        .exists([column])
+}
+
+/// 테이블에 컬럼이 없으면 ALTER TABLE로 추가
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    if !column_exists(conn, table, column)? {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))?;
+    }
     Ok(())
 }
 
-/// FTS5 인덱스 재구축
-fn rebuild_fts5_indexes(conn: &Connection) -> Result<()> {
-    // Knowledge Base FTS 재구축
-    let kb_exists: bool = conn
-        .prepare("SELECT 1 FROM sqlite_master WHERE type='table' AND name='knowledge_base'")
-        .and_then(|mut stmt| stmt.exists([]))
-        .unwrap_or(false);
-
-    if kb_exists {
-        let fts_exists: bool = conn
-            .prepare("SELECT 1 FROM sqlite_master WHERE type='table' AND name='knowledge_base_fts'")
-            .and_then(|mut stmt| stmt.exists([]))
-            .unwrap_or(false);
-
-        if fts_exists {
-            // 기존 FTS 데이터 삭제 후 재구축
-            let _ = conn.execute_batch("DELETE FROM knowledge_base_fts");
-            let _ = conn.execute_batch(r#"
-                INSERT INTO knowledge_base_fts (rowid, title, content, tags)
-                SELECT rowid, title, content, tags FROM knowledge_base
-            "#);
-
-            let count: i64 = conn.query_row(
-                "SELECT COUNT(*) FROM knowledge_base_fts",
-                [],
-                |row| row.get(0),
-            ).unwrap_or(0);
-
-            eprintln!("✅ Knowledge Base FTS5 재구축 완료: {}건", count);
-        }
+pub(crate) fn migration_error(message: String) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_ERROR), Some(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn user_objects(conn: &Connection) -> Result<i64> {
+        conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name NOT LIKE 'sqlite_%' AND name NOT LIKE '_migrations%' AND name != 'idx_migrations_version'",
+            [],
+            |row| row.get(0),
+        )
     }
 
-    Ok(())
+    #[test]
+    fn test_dry_run_up_down_and_checksum() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let conn = Connection::open(temp_dir.path().join("migrate.db"))?;
+
+        // dry-run은 DB를 바꾸지 않는다
+        let plan = migrate_up(&conn, true)?;
+        assert_eq!(plan.steps.len(), MIGRATIONS.len());
+        assert_eq!((plan.from_version, plan.to_version), (0, latest_version()));
+        assert!(!table_exists(&conn, "_migrations")?);
+
+        let report = migrate_up(&conn, false)?;
+        assert_eq!(report.to_version, latest_version());
+        assert!(migrate_up(&conn, false)?.steps.is_empty());
+        assert!(status(&conn)?.iter().all(|s| s.checksum_matches == Some(true)));
+
+        // 스키마 전용: 데모 데이터 없음
+        let items: i64 = conn.query_row("SELECT COUNT(*) FROM item_mst", [], |row| row.get(0))?;
+        assert_eq!(items, 0);
+
+        // 3 이후 되돌리기 → 다시 적용
+        let plan = migrate_down(&conn, 3, true)?;
+        assert_eq!(plan.steps.iter().map(|s| s.version).collect::<Vec<_>>(), vec![8, 7, 6, 5, 4]);
+        assert!(table_exists(&conn, "sensor_log")?);
+        migrate_down(&conn, 3, false)?;
+        assert!(!table_exists(&conn, "sensor_log")?);
+        assert!(table_exists(&conn, "item_mst")?);
+        assert_eq!(migrate_up(&conn, false)?.steps.len(), 5);
+
+        // 전부 되돌리면 스키마 객체가 남지 않음
+        migrate_down(&conn, 0, false)?;
+        assert_eq!(user_objects(&conn)?, 0);
+        migrate_up(&conn, false)?;
+
+        // 적용 후 SQL이 바뀌면 거부
+        conn.execute("UPDATE _migrations SET checksum = 'edited' WHERE version = 2", [])?;
+        let err = migrate_up(&conn, true).unwrap_err().to_string();
+        assert!(err.contains("0002_knowledge_base.sql"), "{}", err);
+        assert_eq!(status(&conn)?[1].checksum_matches, Some(false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_legacy_database_is_adopted() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let conn = Connection::open(temp_dir.path().join("legacy.db"))?;
+        // 버전 관리 이전: 이름만 기록, 컬럼 추가 전 judgments / ccp_docs
+        conn.execute_batch(
+            "CREATE TABLE _migrations (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE,
+                 applied_at TEXT NOT NULL DEFAULT (datetime('now')));
+             INSERT INTO _migrations (name) VALUES ('001_knowledge_base.sql'), ('005_seed_erp_master.sql');
+             CREATE TABLE judgments (id TEXT PRIMARY KEY, workflow_id TEXT NOT NULL, input_data TEXT NOT NULL,
+                 result INTEGER NOT NULL, confidence REAL NOT NULL, method_used TEXT NOT NULL, explanation TEXT,
+                 created_at TEXT NOT NULL);
+             INSERT INTO judgments VALUES ('j-1', 'wf-1', '{}', 1, 0.9, 'rule', NULL, '2025-01-01T00:00:00Z');
+             CREATE TABLE ccp_docs (id INTEGER PRIMARY KEY AUTOINCREMENT, company_id TEXT NOT NULL,
+                 ccp_id TEXT NOT NULL, title TEXT NOT NULL, section_type TEXT NOT NULL, content TEXT NOT NULL,
+                 created_at TEXT NOT NULL DEFAULT (datetime('now')));",
+        )?;
+
+        migrate_up(&conn, false)?;
+
+        assert!(column_exists(&conn, "judgments", "template_id")?);
+        assert!(column_exists(&conn, "ccp_docs", "doc_version_id")?);
+        let judgments: i64 = conn.query_row("SELECT COUNT(*) FROM judgments", [], |row| row.get(0))?;
+        assert_eq!(judgments, 1);
+        // 구버전 기록은 이력으로 남음
+        let names: i64 = conn.query_row("SELECT COUNT(*) FROM _migrations WHERE version IS NULL", [], |row| row.get(0))?;
+        assert_eq!(names, 2);
+        assert!(is_known_migration("001_knowledge_base.sql") && is_known_migration("005_seed_erp_master.sql"));
+
+        Ok(())
+    }
 }
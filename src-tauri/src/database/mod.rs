@@ -1,11 +1,19 @@
 pub mod sqlite;
+pub mod pool;  // 연결 풀 (쓰기 1 + 읽기 전용 N, WAL)
 pub mod models;
 pub mod seed;
 pub mod backup;
-pub mod migrations;  // 앱 시작시 자동 마이그레이션
+pub mod backup_policy;  // 자동 백업 정책 + GFS 보존
+pub mod backup_crypto;  // 백업/내보내기 아카이브 암호화
+pub mod migrations;  // 버전별 스키마 마이그레이션 (체크섬 / 되돌리기 / dry-run)
+pub mod demo_data;  // 시연용 데모 데이터 (opt-in)
+pub mod retention;  // 테이블별 데이터 보존 / 월별 압축 아카이브
 
 pub use sqlite::Database;
+pub use pool::{DbPool, PoolConfig};
 pub use models::*;
 pub use seed::seed_sample_data;
-pub use backup::BackupManager;
-pub use migrations::apply_migrations;
+pub use backup::{BackupManager, BackupManifest, BackupTrigger, RestorePreview, RestoreReport};
+pub use migrations::{apply_migrations, MigrationReport, MigrationStatus};
+pub use demo_data::DemoDataReport;
+pub use retention::{DataRetentionPolicy, RetentionReport};
@@ -11,6 +11,9 @@ pub struct Judgment {
     pub method_used: String,
     pub explanation: String,
     pub created_at: DateTime<Utc>,
+    pub template_id: Option<String>, // A/B 테스트로 선택된 프롬프트 템플릿
+    pub template_version: Option<i32>,
+    pub latency_ms: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,6 +38,22 @@ pub struct TrainingSample {
     pub created_at: DateTime<Utc>,
 }
 
+/// 추출 Rule 홀드아웃 평가 리포트 (Rule 버전별 보관)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuleEvaluationRecord {
+    pub id: String,
+    pub workflow_id: String,
+    /// 승격시 새 Rule 버전, 거부시 평가 당시 버전
+    pub workflow_version: i32,
+    pub rule_expression: Option<String>,
+    pub promoted: bool,
+    pub f1_score: Option<f64>,
+    pub min_f1: f64,
+    /// 전체 리포트 (RuleExtractionReport JSON)
+    pub report_json: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Feedback {
     pub id: String,
@@ -59,6 +78,25 @@ pub struct PromptTemplate {
     pub updated_at: DateTime<Utc>,
 }
 
+/// 프롬프트 A/B 테스트 변형별 성과 (판단 정확도/신뢰도/지연시간/토큰 비용)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PromptVariantStats {
+    pub template_id: String,
+    pub template_name: String,
+    pub template_version: i32,
+    pub is_active: bool,
+    pub weight: Option<f64>, // None = 현재 실험에 포함되지 않음
+    pub judgment_count: i64,
+    pub feedback_count: i64,
+    pub positive_feedback_count: i64,
+    pub accuracy: Option<f64>, // 긍정 피드백 / 전체 피드백 (피드백 없으면 None)
+    pub avg_confidence: Option<f64>,
+    pub avg_latency_ms: Option<f64>,
+    pub total_tokens: i64,
+    pub total_cost_usd: f64,
+    pub avg_cost_per_judgment_usd: Option<f64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TokenUsage {
     pub id: String,
@@ -96,6 +134,49 @@ pub struct CcpDocWithScore {
     pub section_type: String,
     pub content: String,
     pub score: f64, // BM25 점수 (낮을수록 관련도 높음)
+    #[serde(default)]
+    pub doc_version_id: Option<String>, // 시드 문서는 None
+    #[serde(default)]
+    pub revision: Option<i32>, // 판단 시점에 유효했던 개정 번호
+}
+
+/// CCP 문서 가져오기 요청 (content 또는 file_path 중 하나)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CcpDocImportRequest {
+    pub company_id: String,
+    pub ccp_id: String,
+    pub title: String,
+    pub doc_key: Option<String>,       // 개정 단위 식별자 (기본: title)
+    pub format: Option<String>,        // "markdown" | "text" | "csv" (기본: 파일 확장자)
+    pub content: Option<String>,
+    pub file_path: Option<String>,
+    pub effective_from: Option<String>, // 시행일 (기본: 현재 시각)
+}
+
+/// CCP 문서 개정본
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CcpDocVersion {
+    pub id: String,
+    pub company_id: String,
+    pub ccp_id: String,
+    pub doc_key: String,
+    pub title: String,
+    pub revision: i32,
+    pub source_format: String,
+    pub source_name: Option<String>,
+    pub checksum: String, // SHA-256 (동일 내용 재가져오기 감지)
+    pub section_count: i32,
+    pub effective_from: String,
+    pub effective_to: Option<String>, // None = 현행
+    pub created_at: String,
+}
+
+/// CCP 문서 가져오기 결과
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CcpDocImportResult {
+    pub version: CcpDocVersion,
+    pub sections: Vec<CcpDoc>,
+    pub unchanged: bool, // 현행 개정본과 내용이 같아 새 개정을 만들지 않음
 }
 
 /// CCP 센서 로그 모델
@@ -160,4 +241,43 @@ pub struct CcpJudgmentResponse {
     pub llm_summary: String,
     pub evidence_docs: Vec<CcpDocWithScore>,
     pub judgment_id: String,
+    #[serde(default)]
+    pub data_source: String, // "ccp_sensors" (데모) | "ccp_check_log" (MES)
+    #[serde(default)]
+    pub lot_results: Vec<CcpLotJudgment>,
+    #[serde(default)]
+    pub check_results: Vec<CcpCheckEvaluation>, // PASS가 아닌 점검만
+    #[serde(default)]
+    pub corrective_action_id: Option<String>, // 이번 판단으로 발행된 시정조치
+}
+
+/// MES CCP 점검 1건 판정 결과 (ccp_check_log)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CcpCheckEvaluation {
+    pub check_id: i64,
+    pub batch_lot_no: String,
+    pub ccp_type: String, // "PASTEURIZATION" | "METAL_DETECTION" | "COOLING"
+    pub check_time: String,
+    pub equip_cd: String,
+    pub recorded_result: String, // MES에 기록된 결과
+    pub judged_result: String,   // 한계기준 재판정 결과 ("PASS" | "FAIL" | "DEVIATION")
+    pub temp_deviation: Option<f64>,     // 실측 - 한계 온도 (°C)
+    pub time_deviation_sec: Option<i64>, // 실측 - 한계 시간 (초)
+    pub reasons: Vec<String>,
+}
+
+/// 배치 LOT 단위 CCP 판정 결과
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CcpLotJudgment {
+    pub batch_lot_no: String,
+    pub ccp_type: String,
+    pub check_count: i32,
+    pub pass_count: i32,
+    pub deviation_count: i32,
+    pub fail_count: i32,
+    pub worst_temp_deviation: Option<f64>,
+    pub worst_time_deviation_sec: Option<i64>,
+    pub result: String, // 가장 심각한 점검 결과
+    pub first_check_time: String,
+    pub last_check_time: String,
 }
@@ -0,0 +1,179 @@
+// SQLite 연결 풀 (쓰기 연결 1개 + 읽기 전용 연결 N개, WAL 모드)
+//
+// WAL 모드에서는 읽기가 쓰기를 막지 않으므로 조회는 읽기 연결로 분산하고,
+// 쓰기는 하나의 연결로 직렬화해 SQLITE_BUSY를 피한다.
+
+use rusqlite::{Connection, OpenFlags, Result};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// 연결 풀 설정
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// 읽기 전용 연결 수
+    pub readers: usize,
+    /// 잠금 대기 시간 (다른 프로세스/연결이 쓰는 중일 때)
+    pub busy_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            readers: 4,
+            busy_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+pub struct DbPool {
+    path: PathBuf,
+    config: PoolConfig,
+    writer: Arc<Mutex<Connection>>,
+    readers: Vec<Arc<Mutex<Connection>>>,
+    next_reader: AtomicUsize,
+}
+
+impl DbPool {
+    /// 쓰기 연결을 열고 init으로 스키마 준비 후 읽기 연결 생성
+    ///
+    /// 읽기 전용 연결은 파일/WAL을 만들 수 없으므로 스키마 초기화가 끝난 뒤에 연다.
+    pub fn open(path: PathBuf, config: PoolConfig, init: impl FnOnce(&Connection) -> Result<()>) -> Result<Self> {
+        let writer = Self::open_connection(&path, false, config.busy_timeout)?;
+        init(&writer)?;
+
+        let readers = (0..config.readers)
+            .map(|_| Self::open_connection(&path, true, config.busy_timeout).map(|conn| Arc::new(Mutex::new(conn))))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            path,
+            config,
+            writer: Arc::new(Mutex::new(writer)),
+            readers,
+            next_reader: AtomicUsize::new(0),
+        })
+    }
+
+    /// 풀 연결 공통 설정 (쓰기: WAL + synchronous NORMAL, 읽기: 읽기 전용)
+    pub(crate) fn open_connection(path: &Path, read_only: bool, busy_timeout: Duration) -> Result<Connection> {
+        let conn = if read_only {
+            Connection::open_with_flags(
+                path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?
+        } else {
+            Connection::open(path)?
+        };
+        conn.busy_timeout(busy_timeout)?;
+        if !read_only {
+            conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get::<_, String>(0))?;
+            conn.execute_batch("PRAGMA synchronous = NORMAL;")?;
+        }
+        Ok(conn)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 쓰기 연결 (기존 `Database::get_connection` 호환)
+    pub fn writer(&self) -> Arc<Mutex<Connection>> {
+        Arc::clone(&self.writer)
+    }
+
+    /// 읽기 전용 연결 (비어 있는 연결 우선, 모두 사용 중이면 순환 배정)
+    pub fn reader(&self) -> Arc<Mutex<Connection>> {
+        Arc::clone(self.pick_reader())
+    }
+
+    pub fn write(&self) -> MutexGuard<'_, Connection> {
+        self.writer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn read(&self) -> MutexGuard<'_, Connection> {
+        self.pick_reader().lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn pick_reader(&self) -> &Arc<Mutex<Connection>> {
+        if self.readers.is_empty() {
+            return &self.writer;
+        }
+        let start = self.next_reader.fetch_add(1, Ordering::Relaxed);
+        let count = self.readers.len();
+        (0..count)
+            .map(|offset| &self.readers[(start + offset) % count])
+            .find(|reader| reader.try_lock().is_ok())
+            .unwrap_or(&self.readers[start % count])
+    }
+
+    /// 모든 연결을 닫은 상태에서 작업 실행 후 같은 연결 객체로 다시 연다 (복구 핫 스왑)
+    ///
+    /// 진행 중인 쿼리가 끝날 때까지 각 연결의 lock을 기다린다 (쓰기 → 읽기 순).
+    /// 재연결 실패 시 해당 연결은 메모리 DB로 남는다.
+    pub(crate) fn with_closed<T>(&self, operation: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+        let mut guards: Vec<(MutexGuard<'_, Connection>, bool)> = Vec::with_capacity(self.readers.len() + 1);
+        let connections = std::iter::once((&self.writer, false)).chain(self.readers.iter().map(|reader| (reader, true)));
+        for (conn, read_only) in connections {
+            let mut guard = conn.lock().unwrap_or_else(|e| e.into_inner());
+            let closed = std::mem::replace(&mut *guard, Connection::open_in_memory()?);
+            if let Err((_, e)) = closed.close() {
+                eprintln!("⚠️  연결 종료 실패: {}", e);
+            }
+            guards.push((guard, read_only));
+        }
+
+        let result = operation();
+
+        // 쓰기 연결을 먼저 열어야 읽기 전용 연결이 WAL 파일을 공유할 수 있다
+        for (guard, read_only) in guards.iter_mut() {
+            match Self::open_connection(&self.path, *read_only, self.config.busy_timeout) {
+                Ok(conn) => **guard = conn,
+                Err(e) => eprintln!("❌ DB 재연결 실패: {}", e),
+            }
+        }
+
+        result
+    }
+
+    pub(crate) fn connection_count(&self) -> usize {
+        self.readers.len() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_readers_see_committed_writes_and_reject_writes() -> Result<()> {
+        let temp_dir = TempDir::new().unwrap();
+        let pool = DbPool::open(temp_dir.path().join("pool.db"), PoolConfig::default(), |conn| {
+            conn.execute_batch("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL);")
+        })?;
+
+        let journal_mode: String = pool.write().query_row("PRAGMA journal_mode", [], |row| row.get(0))?;
+        assert_eq!(journal_mode, "wal");
+
+        pool.write().execute("INSERT INTO items (name) VALUES ('a'), ('b')", [])?;
+
+        // 모든 읽기 연결이 커밋된 쓰기를 본다
+        for _ in 0..pool.connection_count() {
+            let count: i64 = pool.read().query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))?;
+            assert_eq!(count, 2);
+        }
+
+        // 읽기 연결로는 쓸 수 없다
+        assert!(pool.read().execute("INSERT INTO items (name) VALUES ('c')", []).is_err());
+
+        // 읽기 중(잠금 보유)에도 다른 읽기 연결 배정
+        let held = pool.read();
+        let count: i64 = pool.read().query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))?;
+        assert_eq!(count, 2);
+        drop(held);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,835 @@
+// 데이터 보존 정책 (테이블별 N일 보관 / 월별 압축 아카이브)
+//
+// 판단 기록, 워크플로우 실행 기록, 채팅 메시지, 센서 로그, MES 업로드 로그는 계속 쌓이므로
+// 테이블마다 다음 중 하나를 지정한다.
+// - keep_all: 그대로 보관
+// - delete:   keep_days보다 오래된 행 삭제
+// - archive:  keep_days보다 오래된 행을 `archives/<테이블>/<YYYY-MM>.db.gz`로 옮긴 뒤 삭제
+//             (gzip된 SQLite 파일, `query_archive`로 읽기 전용 조회)
+//
+// HACCP 기록(CCP 파라미터/설비 센서 로그, LOT 보류/개선조치 근거 판단)은
+// 정책과 무관하게 haccp_retention_days(최소 2년)가 지나기 전에는 옮기거나 지우지 않는다.
+//
+// 백그라운드 작업은 opt-in이다: 기본 정책은 꺼져 있고(enabled: false), 테이블별 기간은
+// update_retention_policy로 켤 때 쓰는 제안값이다 (업그레이드 직후 이력이 옮겨지지 않도록).
+//
+// 정책/상태는 데이터 디렉토리의 JSON 파일에 저장한다 (백업 정책과 같은 방식).
+// - retention_policy.json: 사용 여부, 테이블별 규칙, HACCP 보존 기간, 실행 후 VACUUM 여부
+// - retention_status.json: 마지막 실행 시각/결과/오류 (백그라운드 작업 주기 판단용)
+
+use crate::database::Database;
+use crate::utils::app_paths::AppPaths;
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rusqlite::{params_from_iter, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const POLICY_FILE: &str = "retention_policy.json";
+const STATUS_FILE: &str = "retention_status.json";
+
+/// HACCP 기록 법정 최소 보존 기간 (식품위생법 시행규칙: 기록 2년 보관)
+pub const HACCP_MIN_RETENTION_DAYS: u32 = 730;
+
+/// 보존 작업 최소 주기
+const RUN_INTERVAL_HOURS: i64 = 24;
+
+/// 아카이브 조회 최대 행 수
+const MAX_QUERY_ROWS: usize = 1000;
+
+/// 테이블별 처리 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    KeepAll,
+    Delete,
+    Archive,
+}
+
+/// 테이블 보존 규칙
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableRetentionRule {
+    pub action: RetentionAction,
+    /// 원본 DB에 남길 기간 (일)
+    pub keep_days: u32,
+}
+
+impl TableRetentionRule {
+    const fn new(action: RetentionAction, keep_days: u32) -> Self {
+        Self { action, keep_days }
+    }
+}
+
+/// 데이터 보존 정책
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataRetentionPolicy {
+    pub enabled: bool,
+    pub judgments: TableRetentionRule,
+    pub workflow_executions: TableRetentionRule,
+    pub chat_messages: TableRetentionRule,
+    pub sensor_log: TableRetentionRule,
+    pub mes_data_logs: TableRetentionRule,
+    /// HACCP 기록 보존 기간 (HACCP_MIN_RETENTION_DAYS 이상)
+    pub haccp_retention_days: u32,
+    /// 행을 옮긴 뒤 VACUUM으로 DB 파일 크기 축소
+    pub vacuum_after_run: bool,
+}
+
+impl Default for DataRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            judgments: TableRetentionRule::new(RetentionAction::Archive, 365),
+            workflow_executions: TableRetentionRule::new(RetentionAction::Archive, 180),
+            chat_messages: TableRetentionRule::new(RetentionAction::Archive, 180),
+            sensor_log: TableRetentionRule::new(RetentionAction::Archive, 90),
+            mes_data_logs: TableRetentionRule::new(RetentionAction::Archive, 180),
+            haccp_retention_days: HACCP_MIN_RETENTION_DAYS,
+            vacuum_after_run: true,
+        }
+    }
+}
+
+impl DataRetentionPolicy {
+    /// 정책 로드 (파일이 없으면 기본값)
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(POLICY_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .context("보존 정책 읽기 실패")?;
+        serde_json::from_str(&content).context("보존 정책 파싱 실패")
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        self.validate()?;
+        fs::write(data_dir.join(POLICY_FILE), serde_json::to_string_pretty(self)?)
+            .context("보존 정책 저장 실패")
+    }
+
+    /// HACCP 보존 기간은 법정 기간 미만으로 줄일 수 없음
+    pub fn validate(&self) -> Result<()> {
+        if self.haccp_retention_days < HACCP_MIN_RETENTION_DAYS {
+            return Err(anyhow::anyhow!(
+                "HACCP 기록 보존 기간은 {}일 이상이어야 합니다 (현재 {}일)",
+                HACCP_MIN_RETENTION_DAYS,
+                self.haccp_retention_days
+            ));
+        }
+        for target in TARGETS {
+            let rule = self.rule(target.table);
+            if rule.action != RetentionAction::KeepAll && rule.keep_days == 0 {
+                return Err(anyhow::anyhow!("{} 보관 기간은 1일 이상이어야 합니다", target.table));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rule(&self, table: &str) -> TableRetentionRule {
+        match table {
+            "judgments" => self.judgments,
+            "workflow_executions" => self.workflow_executions,
+            "chat_messages" => self.chat_messages,
+            "sensor_log" => self.sensor_log,
+            "mes_data_logs" => self.mes_data_logs,
+            _ => TableRetentionRule::new(RetentionAction::KeepAll, 0),
+        }
+    }
+}
+
+/// 보존 대상 테이블이 있는 DB
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TargetDb {
+    Main,
+    Chat,
+}
+
+/// 보존 대상 테이블 정의
+struct RetentionTarget {
+    table: &'static str,
+    key_column: &'static str,
+    time_column: &'static str,
+    db: TargetDb,
+    /// 함께 옮길 하위 테이블 (테이블, 참조 컬럼)
+    dependents: &'static [(&'static str, &'static str)],
+    /// HACCP 기록 조건 (해당 행은 haccp_retention_days 동안 보존)
+    haccp_filter: Option<&'static str>,
+}
+
+const TARGETS: &[RetentionTarget] = &[
+    RetentionTarget {
+        table: "judgments",
+        key_column: "id",
+        time_column: "created_at",
+        db: TargetDb::Main,
+        dependents: &[("feedbacks", "judgment_id"), ("token_usage", "judgment_id")],
+        // LOT 보류 / CCP 개선조치의 근거가 된 판단
+        haccp_filter: Some(
+            "id IN (SELECT source_ref FROM lot_holds WHERE source_type = 'judgment' AND source_ref IS NOT NULL)
+             OR id IN (SELECT trigger_ref FROM ccp_corrective_actions WHERE trigger_source = 'judgment')",
+        ),
+    },
+    RetentionTarget {
+        table: "workflow_executions",
+        key_column: "id",
+        time_column: "created_at",
+        db: TargetDb::Main,
+        dependents: &[],
+        haccp_filter: None,
+    },
+    RetentionTarget {
+        table: "chat_messages",
+        key_column: "id",
+        time_column: "created_at",
+        db: TargetDb::Chat,
+        dependents: &[],
+        haccp_filter: None,
+    },
+    RetentionTarget {
+        table: "sensor_log",
+        key_column: "id",
+        time_column: "recorded_at",
+        db: TargetDb::Main,
+        dependents: &[],
+        // CCP 파라미터 또는 CCP 설비의 모니터링 기록
+        haccp_filter: Some(
+            "param_cd IN (SELECT param_cd FROM param_mst WHERE is_ccp = 1)
+             OR equip_cd IN (SELECT equip_cd FROM equipment_mst WHERE is_ccp = 1)",
+        ),
+    },
+    RetentionTarget {
+        table: "mes_data_logs",
+        key_column: "id",
+        time_column: "created_at",
+        db: TargetDb::Main,
+        dependents: &[],
+        haccp_filter: None,
+    },
+];
+
+/// 보존 대상 테이블 이름
+pub fn target_tables() -> Vec<&'static str> {
+    TARGETS.iter().map(|target| target.table).collect()
+}
+
+/// 테이블별 실행 결과
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableRetentionResult {
+    pub table: String,
+    pub action: Option<RetentionAction>,
+    /// 이 날짜(YYYY-MM-DD)보다 오래된 행이 대상
+    pub cutoff: Option<String>,
+    pub archived: usize,
+    pub deleted: usize,
+    /// 기간이 지났지만 HACCP 보존 기간 때문에 남긴 행
+    pub haccp_preserved: usize,
+    /// 이번에 쓴 아카이브 파일
+    pub archive_files: Vec<String>,
+}
+
+/// 보존 작업 1회 실행 결과
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetentionReport {
+    pub ran_at: String,
+    pub tables: Vec<TableRetentionResult>,
+    pub vacuumed: bool,
+}
+
+impl RetentionReport {
+    pub fn removed_rows(&self) -> usize {
+        self.tables.iter().map(|table| table.archived + table.deleted).sum()
+    }
+}
+
+/// 보존 작업 상태 (마지막 실행)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionStatus {
+    pub last_run_at: Option<String>,
+    pub last_report: Option<RetentionReport>,
+    pub last_error: Option<String>,
+}
+
+impl RetentionStatus {
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let path = data_dir.join(STATUS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .context("보존 작업 상태 읽기 실패")?;
+        serde_json::from_str(&content).context("보존 작업 상태 파싱 실패")
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        fs::write(data_dir.join(STATUS_FILE), serde_json::to_string_pretty(self)?)
+            .context("보존 작업 상태 저장 실패")
+    }
+
+    /// 마지막 실행 이후 RUN_INTERVAL_HOURS가 지났는지
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        match self.last_run_at.as_deref().and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok()) {
+            Some(last) => now - last.naive_utc() >= Duration::hours(RUN_INTERVAL_HOURS),
+            None => true,
+        }
+    }
+}
+
+/// 보존 작업 실행 대상 DB
+pub struct RetentionDatabases<'a> {
+    pub main: &'a Connection,
+    /// 채팅 DB (없으면 chat_messages 건너뜀)
+    pub chat: Option<&'a Connection>,
+}
+
+/// 정책대로 보존 작업 실행 (today 기준으로 기간 계산)
+pub fn run_retention(
+    dbs: &RetentionDatabases<'_>,
+    policy: &DataRetentionPolicy,
+    archive_dir: &Path,
+    today: NaiveDate,
+) -> Result<RetentionReport> {
+    policy.validate()?;
+
+    let mut report = RetentionReport {
+        ran_at: Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+
+    for target in TARGETS {
+        let conn = match target.db {
+            TargetDb::Main => dbs.main,
+            TargetDb::Chat => match dbs.chat {
+                Some(conn) => conn,
+                None => continue,
+            },
+        };
+        let rule = policy.rule(target.table);
+        if rule.action == RetentionAction::KeepAll || !table_exists(conn, target.table)? {
+            report.tables.push(TableRetentionResult {
+                table: target.table.to_string(),
+                action: Some(rule.action),
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let result = apply_rule(conn, target, rule, policy.haccp_retention_days, archive_dir, today)
+            .with_context(|| format!("{} 보존 작업 실패", target.table))?;
+        if result.archived + result.deleted > 0 {
+            println!(
+                "🗄️  [RETENTION] {}: 아카이브 {}건, 삭제 {}건, HACCP 보존 {}건",
+                target.table, result.archived, result.deleted, result.haccp_preserved
+            );
+        }
+        report.tables.push(result);
+    }
+
+    if policy.vacuum_after_run && report.removed_rows() > 0 {
+        let mut vacuumed = true;
+        for conn in std::iter::once(dbs.main).chain(dbs.chat) {
+            // 다른 연결이 읽는 중이면 실패할 수 있음 (다음 실행에서 다시 시도)
+            if let Err(e) = conn.execute_batch("VACUUM;") {
+                eprintln!("⚠️  [RETENTION] VACUUM 실패: {}", e);
+                vacuumed = false;
+            }
+        }
+        report.vacuumed = vacuumed;
+    }
+
+    Ok(report)
+}
+
+/// 앱 DB/채팅 DB에 저장된 정책 적용 후 상태 기록 (백그라운드 작업/수동 실행 공용)
+///
+/// 정책의 enabled는 백그라운드 작업만 확인하며, 수동 실행은 항상 규칙을 적용한다.
+pub fn run_for_app(database: &Database, paths: &AppPaths) -> Result<RetentionReport> {
+    let data_dir = paths.ensure_data_dir()?;
+    let policy = DataRetentionPolicy::load(&data_dir)?;
+
+    let result = (|| {
+        let chat_path = paths.chat_db_path();
+        let chat = if chat_path.exists() { Some(Connection::open(&chat_path)?) } else { None };
+        let main = database.get_connection();
+        let main = main.lock().unwrap_or_else(|e| e.into_inner());
+        let dbs = RetentionDatabases { main: &main, chat: chat.as_ref() };
+        run_retention(&dbs, &policy, &paths.archive_dir(), Utc::now().date_naive())
+    })();
+
+    let mut status = RetentionStatus::load(&data_dir).unwrap_or_default();
+    status.last_run_at = Some(Utc::now().to_rfc3339());
+    match &result {
+        Ok(report) => {
+            status.last_report = Some(report.clone());
+            status.last_error = None;
+        }
+        Err(e) => status.last_error = Some(format!("{:#}", e)),
+    }
+    status.save(&data_dir)?;
+
+    result
+}
+
+/// 대상 행 조건 (SQL + 바인딩 값)
+struct Scope {
+    clause: String,
+    params: Vec<String>,
+}
+
+impl Scope {
+    /// cutoff보다 오래된 행 (HACCP 행은 haccp_cutoff보다 오래된 것만)
+    fn expired(target: &RetentionTarget, cutoff: &str, haccp_cutoff: &str) -> Self {
+        let mut scope = Self {
+            clause: format!("{} < ?", target.time_column),
+            params: vec![cutoff.to_string()],
+        };
+        if let Some(filter) = target.haccp_filter {
+            scope.clause.push_str(&format!(" AND (NOT ({}) OR {} < ?)", filter, target.time_column));
+            scope.params.push(haccp_cutoff.to_string());
+        }
+        scope
+    }
+
+    fn in_month(mut self, target: &RetentionTarget, month: &str) -> Self {
+        self.clause.push_str(&format!(" AND substr({}, 1, 7) = ?", target.time_column));
+        self.params.push(month.to_string());
+        self
+    }
+}
+
+fn apply_rule(
+    conn: &Connection,
+    target: &RetentionTarget,
+    rule: TableRetentionRule,
+    haccp_retention_days: u32,
+    archive_dir: &Path,
+    today: NaiveDate,
+) -> Result<TableRetentionResult> {
+    let cutoff = date_before(today, rule.keep_days);
+    let haccp_cutoff = date_before(today, rule.keep_days.max(haccp_retention_days));
+    let mut result = TableRetentionResult {
+        table: target.table.to_string(),
+        action: Some(rule.action),
+        cutoff: Some(cutoff.clone()),
+        ..Default::default()
+    };
+
+    if let Some(filter) = target.haccp_filter {
+        let preserved: i64 = conn.query_row(
+            &format!(
+                "SELECT COUNT(*) FROM {} WHERE {} < ?1 AND {} >= ?2 AND ({})",
+                target.table, target.time_column, target.time_column, filter
+            ),
+            [&cutoff, &haccp_cutoff],
+            |row| row.get(0),
+        )?;
+        result.haccp_preserved = preserved as usize;
+    }
+
+    let expired = Scope::expired(target, &cutoff, &haccp_cutoff);
+    match rule.action {
+        RetentionAction::KeepAll => {}
+        RetentionAction::Delete => {
+            let tx = conn.unchecked_transaction()?;
+            result.deleted = delete_scope(&tx, target, &expired)?;
+            tx.commit()?;
+        }
+        RetentionAction::Archive => {
+            let months: Vec<String> = {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT DISTINCT substr({}, 1, 7) FROM {} WHERE {} ORDER BY 1",
+                    target.time_column, target.table, expired.clause
+                ))?;
+                let rows = stmt.query_map(params_from_iter(expired.params.iter()), |row| row.get(0))?;
+                rows.collect::<rusqlite::Result<_>>()?
+            };
+
+            for month in months {
+                let scope = Scope::expired(target, &cutoff, &haccp_cutoff).in_month(target, &month);
+                let path = archive_path(archive_dir, target.table, &month)?;
+                result.archived += archive_scope(conn, target, &scope, &path)?;
+
+                // 아카이브 파일을 쓴 뒤에만 원본에서 삭제 (중간에 실패해도 다음 실행에서 다시 옮김)
+                let tx = conn.unchecked_transaction()?;
+                delete_scope(&tx, target, &scope)?;
+                tx.commit()?;
+                result.archive_files.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// 하위 테이블 → 대상 테이블 순서로 삭제, 대상 테이블 삭제 건수 반환
+fn delete_scope(conn: &Connection, target: &RetentionTarget, scope: &Scope) -> Result<usize> {
+    for (table, column) in target.dependents {
+        conn.execute(
+            &format!(
+                "DELETE FROM {} WHERE {} IN (SELECT {} FROM {} WHERE {})",
+                table, column, target.key_column, target.table, scope.clause
+            ),
+            params_from_iter(scope.params.iter()),
+        )?;
+    }
+    let deleted = conn.execute(
+        &format!("DELETE FROM {} WHERE {}", target.table, scope.clause),
+        params_from_iter(scope.params.iter()),
+    )?;
+    Ok(deleted)
+}
+
+/// 월별 아카이브 파일에 행 추가 (기존 파일이 있으면 풀어서 이어 쓰고 다시 압축)
+fn archive_scope(conn: &Connection, target: &RetentionTarget, scope: &Scope, path: &Path) -> Result<usize> {
+    let work_path = path.with_extension("work");
+    if work_path.exists() {
+        fs::remove_file(&work_path)?;
+    }
+    if path.exists() {
+        decompress(path, &work_path)?;
+    }
+
+    conn.execute("ATTACH DATABASE ?1 AS archive", [work_path.to_string_lossy()])
+        .context("아카이브 파일 연결 실패")?;
+    let copied = copy_to_archive(conn, target, scope);
+    let detached = conn.execute_batch("DETACH DATABASE archive");
+    let copied = copied?;
+    detached?;
+
+    // 압축본을 임시 파일로 쓴 뒤 교체 (쓰는 중 실패해도 기존 아카이브 유지)
+    let tmp_path = path.with_extension("tmp");
+    {
+        let mut input = fs::File::open(&work_path)?;
+        let mut encoder = GzEncoder::new(fs::File::create(&tmp_path)?, Compression::default());
+        std::io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+    }
+    fs::rename(&tmp_path, path).context("아카이브 파일 교체 실패")?;
+    fs::remove_file(&work_path)?;
+
+    Ok(copied)
+}
+
+fn copy_to_archive(conn: &Connection, target: &RetentionTarget, scope: &Scope) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    for (table, column) in target.dependents {
+        let columns = prepare_archive_table(&tx, table, "id")?;
+        tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO archive.{table} ({columns}) SELECT {columns} FROM main.{table}
+                 WHERE {column} IN (SELECT {key} FROM main.{parent} WHERE {clause})",
+                table = table,
+                columns = columns,
+                column = column,
+                key = target.key_column,
+                parent = target.table,
+                clause = scope.clause
+            ),
+            params_from_iter(scope.params.iter()),
+        )?;
+    }
+
+    let columns = prepare_archive_table(&tx, target.table, target.key_column)?;
+    let copied = tx.execute(
+        &format!(
+            "INSERT OR IGNORE INTO archive.{table} ({columns}) SELECT {columns} FROM main.{table} WHERE {clause}",
+            table = target.table,
+            columns = columns,
+            clause = scope.clause
+        ),
+        params_from_iter(scope.params.iter()),
+    )?;
+    tx.commit()?;
+    Ok(copied)
+}
+
+/// 아카이브 테이블 생성/컬럼 보강 (원본에 컬럼이 추가된 경우), 복사할 컬럼 목록 반환
+fn prepare_archive_table(conn: &Connection, table: &str, key_column: &str) -> Result<String> {
+    conn.execute_batch(&format!(
+        "CREATE TABLE IF NOT EXISTS archive.{table} AS SELECT * FROM main.{table} WHERE 0;
+         CREATE UNIQUE INDEX IF NOT EXISTS archive.ux_{table}_{key} ON {table}({key});",
+        table = table,
+        key = key_column
+    ))?;
+
+    let source_columns = table_columns(conn, "main", table)?;
+    let archive_columns = table_columns(conn, "archive", table)?;
+    for column in source_columns.iter().filter(|column| !archive_columns.contains(column)) {
+        conn.execute_batch(&format!("ALTER TABLE archive.{} ADD COLUMN {}", table, column))?;
+    }
+    Ok(source_columns.join(", "))
+}
+
+fn table_columns(conn: &Connection, schema: &str, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA {}.table_info({})", schema, table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(columns)
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    Ok(conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1")?
+        .exists([table])?)
+}
+
+fn date_before(today: NaiveDate, days: u32) -> String {
+    (today - Duration::days(days as i64)).format("%Y-%m-%d").to_string()
+}
+
+fn decompress(path: &Path, output_path: &Path) -> Result<()> {
+    let mut decoder = flate2::read::GzDecoder::new(fs::File::open(path)?);
+    let mut output = fs::File::create(output_path)?;
+    std::io::copy(&mut decoder, &mut output)
+        .context(format!("아카이브 압축 해제 실패: {}", path.display()))?;
+    Ok(())
+}
+
+/// 아카이브 파일 경로 (테이블/월 검증 포함)
+pub fn archive_path(archive_dir: &Path, table: &str, month: &str) -> Result<PathBuf> {
+    if !TARGETS.iter().any(|target| target.table == table) {
+        return Err(anyhow::anyhow!("보존 대상 테이블이 아닙니다: {}", table));
+    }
+    if NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").is_err() || month.len() != 7 {
+        return Err(anyhow::anyhow!("아카이브 월 형식이 올바르지 않습니다 (YYYY-MM): {}", month));
+    }
+    let dir = archive_dir.join(table);
+    fs::create_dir_all(&dir)
+        .context(format!("아카이브 디렉토리 생성 실패: {}", dir.display()))?;
+    Ok(dir.join(format!("{}.db.gz", month)))
+}
+
+/// 아카이브 파일 정보
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveFile {
+    pub table: String,
+    pub month: String,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// 아카이브 목록 (테이블, 월 순)
+pub fn list_archives(archive_dir: &Path) -> Result<Vec<ArchiveFile>> {
+    let mut archives = Vec::new();
+    for table in target_tables() {
+        let dir = archive_dir.join(table);
+        if !dir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&dir).context("아카이브 디렉토리 읽기 실패")? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(month) = name.strip_suffix(".db.gz") {
+                archives.push(ArchiveFile {
+                    table: table.to_string(),
+                    month: month.to_string(),
+                    path: entry.path().to_string_lossy().to_string(),
+                    size_bytes: entry.metadata()?.len(),
+                });
+            }
+        }
+    }
+    archives.sort_by(|a, b| (&a.table, &a.month).cmp(&(&b.table, &b.month)));
+    Ok(archives)
+}
+
+/// 아카이브 조회 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveQueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// MAX_QUERY_ROWS에서 잘림
+    pub truncated: bool,
+}
+
+/// 아카이브 읽기 전용 조회 (SELECT만 허용, 압축 해제본은 archives/.cache에 보관)
+pub fn query_archive(archive_dir: &Path, table: &str, month: &str, sql: &str) -> Result<ArchiveQueryResult> {
+    let path = archive_path(archive_dir, table, month)?;
+    if !path.exists() {
+        return Err(anyhow::anyhow!("아카이브가 없습니다: {} {}", table, month));
+    }
+
+    let cache_dir = archive_dir.join(".cache").join(table);
+    fs::create_dir_all(&cache_dir)?;
+    let cache_path = cache_dir.join(format!("{}.db", month));
+    let stale = match (fs::metadata(&cache_path), fs::metadata(&path)) {
+        (Ok(cache), Ok(archive)) => cache.modified()? < archive.modified()?,
+        _ => true,
+    };
+    if stale {
+        let tmp_path = cache_path.with_extension("tmp");
+        decompress(&path, &tmp_path)?;
+        fs::rename(&tmp_path, &cache_path)?;
+    }
+
+    let conn = Connection::open_with_flags(&cache_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .context("아카이브 열기 실패")?;
+    let mut stmt = conn.prepare(sql).context("아카이브 조회 SQL 오류")?;
+    if !stmt.readonly() {
+        return Err(anyhow::anyhow!("아카이브는 조회(SELECT)만 가능합니다"));
+    }
+
+    let columns: Vec<String> = stmt.column_names().iter().map(|name| name.to_string()).collect();
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    let mut query = stmt.query([])?;
+    while let Some(row) = query.next()? {
+        if rows.len() == MAX_QUERY_ROWS {
+            truncated = true;
+            break;
+        }
+        let values = (0..columns.len())
+            .map(|i| {
+                Ok(match row.get_ref(i)? {
+                    rusqlite::types::ValueRef::Null => serde_json::Value::Null,
+                    rusqlite::types::ValueRef::Integer(v) => serde_json::json!(v),
+                    rusqlite::types::ValueRef::Real(v) => serde_json::json!(v),
+                    rusqlite::types::ValueRef::Text(v) => serde_json::json!(String::from_utf8_lossy(v)),
+                    rusqlite::types::ValueRef::Blob(v) => serde_json::json!(format!("<blob {} bytes>", v.len())),
+                })
+            })
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.push(values);
+    }
+
+    Ok(ArchiveQueryResult { columns, rows, truncated })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn open_db(dir: &Path) -> Result<Connection> {
+        let conn = Connection::open(dir.join("retention.db"))?;
+        crate::database::migrations::migrate_up(&conn, false)?;
+        conn.execute_batch(
+            "INSERT INTO equipment_mst (equip_cd, equip_nm, equip_type, is_ccp) VALUES ('PAST-01', '살균기', 'PASTEURIZER', 1);
+             INSERT INTO equipment_mst (equip_cd, equip_nm, equip_type, is_ccp) VALUES ('MIX-01', '배합기', 'MIXER', 0);
+             INSERT INTO param_mst (param_cd, param_nm, unit, is_ccp) VALUES ('PAST_TEMP', '살균 온도', '℃', 1);
+             INSERT INTO param_mst (param_cd, param_nm, unit, is_ccp) VALUES ('MIX_RPM', '교반 속도', 'rpm', 0);",
+        )?;
+        Ok(conn)
+    }
+
+    #[test]
+    fn test_archive_preserves_haccp_records() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let conn = open_db(temp_dir.path())?;
+        let archive_dir = temp_dir.path().join("archives");
+        let today = NaiveDate::from_ymd_opt(2026, 6, 15).unwrap();
+
+        for (equip, param, at) in [
+            ("MIX-01", "MIX_RPM", "2026-01-10 08:00:00"),
+            ("MIX-01", "MIX_RPM", "2026-02-20 08:00:00"),
+            ("PAST-01", "PAST_TEMP", "2026-01-10 08:00:00"),
+            ("PAST-01", "PAST_TEMP", "2023-03-01 08:00:00"),
+            ("MIX-01", "MIX_RPM", "2026-06-10 08:00:00"),
+        ] {
+            conn.execute(
+                "INSERT INTO sensor_log (equip_cd, param_cd, recorded_at, value) VALUES (?1, ?2, ?3, 1.0)",
+                [equip, param, at],
+            )?;
+        }
+        conn.execute_batch(
+            "INSERT INTO judgments (id, workflow_id, input_data, result, confidence, method_used, created_at)
+             VALUES ('j-old', 'wf', '{}', 1, 0.9, 'rule', '2025-01-05T00:00:00Z'),
+                    ('j-hold', 'wf', '{}', 0, 0.9, 'rule', '2025-01-06T00:00:00Z');
+             INSERT INTO feedbacks (id, judgment_id, feedback_type, value, created_at)
+             VALUES ('f-1', 'j-old', 'thumbs', 1, '2025-01-05T01:00:00Z');
+             INSERT INTO lot_holds (id, lot_no, lot_type, reason, source_type, source_ref, held_by)
+             VALUES ('h-1', 'B-001', 'BATCH', '살균 온도 이탈', 'judgment', 'j-hold', 'system');",
+        )?;
+
+        // 기본 정책은 꺼져 있음 (백그라운드 작업은 opt-in)
+        assert!(!DataRetentionPolicy::default().enabled);
+        let policy = DataRetentionPolicy { enabled: true, vacuum_after_run: false, ..Default::default() };
+        let dbs = RetentionDatabases { main: &conn, chat: None };
+        let report = run_retention(&dbs, &policy, &archive_dir, today)?;
+
+        let sensor = report.tables.iter().find(|t| t.table == "sensor_log").unwrap();
+        // 90일 지난 비 CCP 2건 + 2년 지난 CCP 1건 이동, 2년 안의 CCP 1건 보존
+        assert_eq!(sensor.archived, 3);
+        assert_eq!(sensor.haccp_preserved, 1);
+        let remaining: Vec<String> = conn
+            .prepare("SELECT recorded_at FROM sensor_log ORDER BY recorded_at")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        assert_eq!(remaining, vec!["2026-01-10 08:00:00", "2026-06-10 08:00:00"]);
+
+        // LOT 보류 근거 판단은 남고, 나머지는 피드백과 함께 아카이브로 이동
+        let judgments: Vec<String> = conn
+            .prepare("SELECT id FROM judgments")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        assert_eq!(judgments, vec!["j-hold"]);
+        let feedbacks: i64 = conn.query_row("SELECT COUNT(*) FROM feedbacks", [], |row| row.get(0))?;
+        assert_eq!(feedbacks, 0);
+
+        let archives = list_archives(&archive_dir)?;
+        let months: Vec<(&str, &str)> = archives.iter().map(|a| (a.table.as_str(), a.month.as_str())).collect();
+        assert_eq!(
+            months,
+            vec![("judgments", "2025-01"), ("sensor_log", "2023-03"), ("sensor_log", "2026-01"), ("sensor_log", "2026-02")]
+        );
+
+        let result = query_archive(&archive_dir, "judgments", "2025-01", "SELECT f.id, j.id FROM feedbacks f JOIN judgments j ON j.id = f.judgment_id")?;
+        assert_eq!(result.rows, vec![vec![serde_json::json!("f-1"), serde_json::json!("j-old")]]);
+        assert!(query_archive(&archive_dir, "judgments", "2025-01", "DELETE FROM judgments").is_err());
+        assert!(query_archive(&archive_dir, "../judgments", "2025-01", "SELECT 1").is_err());
+
+        // 같은 달에 다시 옮기면 기존 아카이브에 이어 씀
+        conn.execute(
+            "INSERT INTO sensor_log (equip_cd, param_cd, recorded_at, value) VALUES ('MIX-01', 'MIX_RPM', '2026-01-11 08:00:00', 2.0)",
+            [],
+        )?;
+        run_retention(&dbs, &policy, &archive_dir, today)?;
+        let january = query_archive(&archive_dir, "sensor_log", "2026-01", "SELECT COUNT(*) FROM sensor_log")?;
+        assert_eq!(january.rows[0][0], serde_json::json!(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_policy_and_haccp_floor() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let conn = open_db(temp_dir.path())?;
+        let today = NaiveDate::from_ymd_opt(2026, 6, 15).unwrap();
+
+        conn.execute_batch(
+            "INSERT INTO workflow_executions (workflow_id, status, steps_executed, final_result, created_at)
+             VALUES ('wf', 'success', '[]', '{}', '2026-01-01 00:00:00'),
+                    ('wf', 'success', '[]', '{}', '2026-06-01 00:00:00');",
+        )?;
+
+        let mut policy = DataRetentionPolicy {
+            workflow_executions: TableRetentionRule::new(RetentionAction::Delete, 30),
+            vacuum_after_run: false,
+            ..Default::default()
+        };
+        let report = run_retention(
+            &RetentionDatabases { main: &conn, chat: None },
+            &policy,
+            &temp_dir.path().join("archives"),
+            today,
+        )?;
+        let executions = report.tables.iter().find(|t| t.table == "workflow_executions").unwrap();
+        assert_eq!(executions.deleted, 1);
+        assert!(executions.archive_files.is_empty());
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM workflow_executions", [], |row| row.get(0))?;
+        assert_eq!(count, 1);
+
+        // HACCP 보존 기간은 법정 기간 미만으로 저장할 수 없음
+        policy.haccp_retention_days = 365;
+        assert!(policy.save(temp_dir.path()).is_err());
+        assert!(!temp_dir.path().join(POLICY_FILE).exists());
+
+        Ok(())
+    }
+}
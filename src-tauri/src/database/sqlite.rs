@@ -1,338 +1,125 @@
 use rusqlite::{Connection, Result, params};
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 use crate::database::models::*;
+use crate::database::pool::{DbPool, PoolConfig};
 use chrono::Utc;
+use once_cell::sync::{Lazy, OnceCell};
 use serde::{Deserialize, Serialize};
 
+/// 앱 전역 DB (첫 `Database::new()`에서 한 번만 스키마 초기화, 이후 같은 풀 공유)
+static APP_DATABASE: OnceCell<Database> = OnceCell::new();
+
+/// (DB 파일 경로, 연결 풀) 등록 항목
+type PoolEntry = (PathBuf, Weak<DbPool>);
+
+/// 열린 연결 풀 목록 (DB 파일 경로별, 복구 시 핫 스왑 대상)
+static OPEN_POOLS: Lazy<Mutex<Vec<PoolEntry>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+/// 복구 중에는 새 연결을 열지 않도록 막는 게이트 (복구: write, 연결 열기: read)
+static CONNECTION_GATE: RwLock<()> = RwLock::new(());
+
+#[derive(Clone)]
 pub struct Database {
-    conn: Arc<Mutex<Connection>>,
+    pool: Arc<DbPool>,
 }
 
 impl Database {
     /// Get a cloned Arc to the database connection for use in services
+    ///
+    /// 쓰기 가능한 연결 (조회 전용이면 `get_reader` 사용)
     pub fn get_connection(&self) -> Arc<Mutex<Connection>> {
-        Arc::clone(&self.conn)
+        self.pool.writer()
     }
 
+    /// 읽기 전용 연결 (WAL 모드라 쓰기 중에도 동시 조회 가능)
+    pub fn get_reader(&self) -> Arc<Mutex<Connection>> {
+        self.pool.reader()
+    }
+
+    /// 앱 전역 DB 핸들 (연결 풀 공유, 스키마 초기화/시드/마이그레이션은 최초 1회)
     pub fn new() -> Result<Self> {
-        let db_path = Self::get_db_path()?;
-        let conn = Connection::open(db_path)?;
+        APP_DATABASE
+            .get_or_try_init(|| Self::open(Self::get_db_path()?))
+            .cloned()
+    }
 
-        // Initialize schema
-        Self::init_schema(&conn)?;
+    /// 지정 경로의 DB 열기 (새 연결 풀 + 스키마 초기화, 복구 핫 스왑 대상으로 등록)
+    pub fn open(db_path: PathBuf) -> Result<Self> {
+        Self::open_with_config(db_path, PoolConfig::default())
+    }
 
-        Ok(Self {
-            conn: Arc::new(Mutex::new(conn)),
-        })
+    pub fn open_with_config(db_path: PathBuf, config: PoolConfig) -> Result<Self> {
+        let _gate = CONNECTION_GATE.read().unwrap_or_else(|e| e.into_inner());
+        let pool = Arc::new(DbPool::open(db_path.clone(), config, Self::init_schema)?);
+
+        let mut registry = OPEN_POOLS.lock().unwrap_or_else(|e| e.into_inner());
+        registry.retain(|(_, weak)| weak.strong_count() > 0);
+        registry.push((Self::registry_key(&db_path), Arc::downgrade(&pool)));
+
+        Ok(Self { pool })
     }
 
-    fn get_db_path() -> Result<PathBuf> {
-        let app_data = std::env::var("APPDATA")
-            .or_else(|_| std::env::var("HOME"))
-            .map_err(|e| rusqlite::Error::InvalidPath(PathBuf::from(e.to_string())))?;
-
-        let db_dir = PathBuf::from(app_data).join("Judgify");
-        std::fs::create_dir_all(&db_dir)
-            .map_err(|e| rusqlite::Error::InvalidPath(PathBuf::from(e.to_string())))?;
-
-        Ok(db_dir.join("judgify.db"))
-    }
-
-    fn init_schema(conn: &Connection) -> Result<()> {
-        conn.execute_batch(
-            "CREATE TABLE IF NOT EXISTS judgments (
-                id TEXT PRIMARY KEY,
-                workflow_id TEXT NOT NULL,
-                input_data TEXT NOT NULL,
-                result INTEGER NOT NULL,
-                confidence REAL NOT NULL,
-                method_used TEXT NOT NULL,
-                explanation TEXT,
-                created_at TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS workflows (
-                id TEXT PRIMARY KEY,
-                name TEXT NOT NULL,
-                definition TEXT NOT NULL,
-                rule_expression TEXT,
-                version INTEGER DEFAULT 1,
-                is_active INTEGER DEFAULT 1,
-                created_at TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS training_samples (
-                id TEXT PRIMARY KEY,
-                workflow_id TEXT NOT NULL,
-                input_data TEXT NOT NULL,
-                expected_result INTEGER NOT NULL,
-                actual_result INTEGER,
-                accuracy REAL,
-                created_at TEXT NOT NULL,
-                FOREIGN KEY (workflow_id) REFERENCES workflows(id)
-            );
-
-            CREATE TABLE IF NOT EXISTS feedbacks (
-                id TEXT PRIMARY KEY,
-                judgment_id TEXT NOT NULL,
-                feedback_type TEXT NOT NULL,
-                value INTEGER NOT NULL,
-                comment TEXT,
-                created_at TEXT NOT NULL,
-                FOREIGN KEY (judgment_id) REFERENCES judgments(id)
-            );
-
-            CREATE TABLE IF NOT EXISTS prompt_templates (
-                id TEXT PRIMARY KEY,
-                name TEXT NOT NULL,
-                template_type TEXT NOT NULL,
-                content TEXT NOT NULL,
-                variables TEXT NOT NULL,
-                version INTEGER DEFAULT 1,
-                is_active INTEGER DEFAULT 1,
-                token_limit INTEGER,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS token_usage (
-                id TEXT PRIMARY KEY,
-                judgment_id TEXT NOT NULL,
-                service TEXT NOT NULL,
-                tokens_used INTEGER NOT NULL,
-                cost_usd REAL NOT NULL,
-                complexity TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                FOREIGN KEY (judgment_id) REFERENCES judgments(id)
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_judgments_workflow ON judgments(workflow_id);
-            CREATE INDEX IF NOT EXISTS idx_judgments_created ON judgments(created_at);
-            CREATE INDEX IF NOT EXISTS idx_training_workflow ON training_samples(workflow_id);
-
-            -- Composite indexes for performance optimization (Task 2.2)
-            -- 1. Judgment workflow + time index (Dashboard getJudgmentHistory optimization)
-            CREATE INDEX IF NOT EXISTS idx_judgments_workflow_created
-              ON judgments(workflow_id, created_at DESC);
-
-            -- 2. TrainingSample search index (Learning Service optimization)
-            CREATE INDEX IF NOT EXISTS idx_training_workflow_accuracy
-              ON training_samples(workflow_id, accuracy DESC, created_at DESC);
-
-            -- 3. Feedback + Judgment JOIN index (complex query optimization)
-            CREATE INDEX IF NOT EXISTS idx_feedbacks_judgment_type
-              ON feedbacks(judgment_id, feedback_type, value);
-
-            -- 4. Feedback covering index (optimized retrieval with all columns)
-            CREATE INDEX IF NOT EXISTS idx_feedbacks_covering
-              ON feedbacks(judgment_id, feedback_type, value, created_at);
-
-            -- 5. PromptTemplate type + active index (template selection optimization)
-            CREATE INDEX IF NOT EXISTS idx_templates_type_active
-              ON prompt_templates(template_type, is_active, version DESC);
-
-            -- 6. Token Usage indexes (MCP cost tracking optimization)
-            CREATE INDEX IF NOT EXISTS idx_token_usage_created
-              ON token_usage(created_at DESC);
-
-            CREATE INDEX IF NOT EXISTS idx_token_usage_service_created
-              ON token_usage(service, created_at DESC);
-
-            CREATE INDEX IF NOT EXISTS idx_token_usage_judgment
-              ON token_usage(judgment_id);
-
-            -- ============================================================
-            -- CCP 데모용 테이블 (RAG + 룰베이스 판단)
-            -- ============================================================
-
-            -- CCP 정책 문서 테이블
-            CREATE TABLE IF NOT EXISTS ccp_docs (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                company_id TEXT NOT NULL,
-                ccp_id TEXT NOT NULL,
-                title TEXT NOT NULL,
-                section_type TEXT NOT NULL,
-                content TEXT NOT NULL,
-                created_at TEXT NOT NULL DEFAULT (datetime('now'))
-            );
-
-            -- FTS5 전문검색 인덱스 (BM25 알고리즘)
-            CREATE VIRTUAL TABLE IF NOT EXISTS ccp_docs_fts
-            USING fts5(title, content, tokenize='porter unicode61');
-
-            CREATE INDEX IF NOT EXISTS idx_ccp_docs_company
-            ON ccp_docs(company_id, ccp_id);
-
-            -- CCP 센서 로그 테이블
-            CREATE TABLE IF NOT EXISTS ccp_sensors (
-                log_id INTEGER PRIMARY KEY AUTOINCREMENT,
-                company_id TEXT NOT NULL,
-                ccp_id TEXT NOT NULL,
-                log_date TEXT NOT NULL,
-                measured_value REAL NOT NULL,
-                result TEXT NOT NULL CHECK(result IN ('OK', 'NG')),
-                operator_name TEXT,
-                action_taken TEXT,
-                created_at TEXT NOT NULL DEFAULT (datetime('now'))
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_ccp_sensors_date
-            ON ccp_sensors(company_id, ccp_id, log_date);
-
-            -- CCP 판단 결과 테이블
-            CREATE TABLE IF NOT EXISTS ccp_judgments (
-                id TEXT PRIMARY KEY,
-                company_id TEXT NOT NULL,
-                ccp_id TEXT NOT NULL,
-                period_from TEXT NOT NULL,
-                period_to TEXT NOT NULL,
-                total_logs INTEGER NOT NULL,
-                ng_count INTEGER NOT NULL,
-                ng_rate REAL NOT NULL,
-                avg_value REAL NOT NULL,
-                risk_level TEXT NOT NULL CHECK(risk_level IN ('LOW', 'MEDIUM', 'HIGH')),
-                rule_reason TEXT,
-                llm_summary TEXT,
-                evidence_docs TEXT,
-                created_at TEXT NOT NULL DEFAULT (datetime('now'))
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_ccp_judgments_company
-            ON ccp_judgments(company_id, created_at DESC);
-
-            CREATE INDEX IF NOT EXISTS idx_ccp_judgments_ccp
-            ON ccp_judgments(company_id, ccp_id, created_at DESC);
-
-            -- ============================================================
-            -- MES/ERP RAG 테이블 (Phase 8: Generic CSV Upload & Query)
-            -- ============================================================
-
-            -- MES 데이터 로그 테이블
-            CREATE TABLE IF NOT EXISTS mes_data_logs (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                session_id TEXT NOT NULL,
-                file_name TEXT NOT NULL,
-                row_index INTEGER NOT NULL,
-                raw_json TEXT NOT NULL,
-                content TEXT NOT NULL,
-                created_at TEXT NOT NULL DEFAULT (datetime('now'))
-            );
-
-            -- FTS5 전문검색 인덱스 (BM25 알고리즘)
-            CREATE VIRTUAL TABLE IF NOT EXISTS mes_data_logs_fts
-            USING fts5(content, tokenize='porter unicode61');
-
-            -- FTS5 자동 동기화 트리거
-            CREATE TRIGGER IF NOT EXISTS mes_data_logs_ai AFTER INSERT ON mes_data_logs
-            BEGIN
-                INSERT INTO mes_data_logs_fts(rowid, content) VALUES (new.id, new.content);
-            END;
-
-            CREATE TRIGGER IF NOT EXISTS mes_data_logs_ad AFTER DELETE ON mes_data_logs
-            BEGIN
-                DELETE FROM mes_data_logs_fts WHERE rowid = old.id;
-            END;
-
-            CREATE TRIGGER IF NOT EXISTS mes_data_logs_au AFTER UPDATE ON mes_data_logs
-            BEGIN
-                UPDATE mes_data_logs_fts SET content = new.content WHERE rowid = new.id;
-            END;
-
-            CREATE INDEX IF NOT EXISTS idx_mes_data_logs_session
-            ON mes_data_logs(session_id, created_at DESC);
-
-            -- ============================================================
-            -- Workflow 승인 요청 테이블 (Phase 9: APPROVAL Node)
-            -- ============================================================
-
-            CREATE TABLE IF NOT EXISTS approval_requests (
-                id TEXT PRIMARY KEY,
-                workflow_id TEXT NOT NULL,
-                workflow_name TEXT NOT NULL,
-                step_id TEXT NOT NULL,
-                step_name TEXT NOT NULL,
-                approval_type TEXT NOT NULL CHECK(approval_type IN ('manual', 'conditional')),
-                status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'approved', 'rejected', 'expired')),
-                approvers TEXT NOT NULL,
-                input_data TEXT NOT NULL,
-                condition TEXT,
-                timeout_minutes INTEGER DEFAULT 60,
-                decided_by TEXT,
-                decided_at TEXT,
-                comment TEXT,
-                created_at TEXT NOT NULL DEFAULT (datetime('now')),
-                expires_at TEXT
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_approval_requests_status
-            ON approval_requests(status, created_at DESC);
-
-            CREATE INDEX IF NOT EXISTS idx_approval_requests_workflow
-            ON approval_requests(workflow_id, status);
-
-            -- ============================================================
-            -- Workflow 스케줄러 테이블 (Phase 9: Cron-based Scheduler)
-            -- ============================================================
-
-            CREATE TABLE IF NOT EXISTS workflow_schedules (
-                id TEXT PRIMARY KEY,
-                workflow_id TEXT NOT NULL,
-                workflow_name TEXT NOT NULL,
-                cron_expression TEXT NOT NULL,
-                timezone TEXT NOT NULL DEFAULT 'Asia/Seoul',
-                is_active INTEGER NOT NULL DEFAULT 1,
-                input_data TEXT NOT NULL DEFAULT '{}',
-                last_run_at TEXT,
-                next_run_at TEXT,
-                run_count INTEGER NOT NULL DEFAULT 0,
-                last_status TEXT CHECK(last_status IN ('success', 'failed', 'running')),
-                last_error TEXT,
-                created_at TEXT NOT NULL DEFAULT (datetime('now')),
-                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_workflow_schedules_active
-            ON workflow_schedules(is_active, next_run_at);
-
-            CREATE INDEX IF NOT EXISTS idx_workflow_schedules_workflow
-            ON workflow_schedules(workflow_id);
-
-            -- Workflow 실행 이력 테이블 (Phase 9: Execution History)
-            CREATE TABLE IF NOT EXISTS workflow_executions (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                workflow_id TEXT NOT NULL,
-                status TEXT NOT NULL,
-                steps_executed TEXT NOT NULL,
-                final_result TEXT NOT NULL,
-                execution_time_ms INTEGER NOT NULL DEFAULT 0,
-                created_at TEXT NOT NULL DEFAULT (datetime('now'))
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_workflow_executions_workflow
-            ON workflow_executions(workflow_id, created_at DESC);"
-        )?;
+    fn registry_key(db_path: &Path) -> PathBuf {
+        std::fs::canonicalize(db_path).unwrap_or_else(|_| db_path.to_path_buf())
+    }
 
-        // Seed sample data for demo (only if database is empty)
-        crate::database::seed::seed_sample_data(conn)?;
+    /// 해당 DB 파일의 모든 연결을 닫은 상태에서 작업 실행 후 같은 연결 객체로 다시 연다 (복구 핫 스왑)
+    ///
+    /// 진행 중인 쿼리가 끝날 때까지 각 연결의 lock을 기다리며, 작업 중에는 새 연결 열기도 대기한다.
+    /// 작업 결과와 무관하게 연결은 다시 열리며, 재연결 실패 시 해당 연결은 메모리 DB로 남는다.
+    pub fn with_connections_closed<T>(
+        db_path: &Path,
+        operation: impl FnOnce() -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let _gate = CONNECTION_GATE.write().unwrap_or_else(|e| e.into_inner());
+        let key = Self::registry_key(db_path);
+        let pools: Vec<Arc<DbPool>> = {
+            let mut registry = OPEN_POOLS.lock().unwrap_or_else(|e| e.into_inner());
+            registry.retain(|(_, weak)| weak.strong_count() > 0);
+            registry
+                .iter()
+                .filter(|(path, _)| *path == key)
+                .filter_map(|(_, weak)| weak.upgrade())
+                .collect()
+        };
 
-        // 🔥 앱 시작시 자동 마이그레이션 실행 (ERP/MES 테이블 + 시드 데이터)
-        // 마이그레이션 SQL은 컴파일 시점에 바이너리에 포함됨 (include_str!)
-        eprintln!("🔄 앱 시작: ERP/MES 마이그레이션 확인 중...");
-        if let Err(e) = crate::database::migrations::apply_migrations(conn) {
-            eprintln!("⚠️  마이그레이션 경고 (무시 가능): {}", e);
-            // 마이그레이션 실패는 치명적이지 않음 - 기존 데이터가 있을 수 있음
-        }
+        let connection_count: usize = pools.iter().map(|pool| pool.connection_count()).sum();
+        println!("🔌 DB 연결 {}개 종료: {}", connection_count, db_path.display());
+
+        // 풀마다 중첩해 모든 풀의 연결이 닫힌 상태에서 작업 실행
+        let operation: Box<dyn FnOnce() -> anyhow::Result<T> + '_> = Box::new(operation);
+        let result = pools.iter().fold(operation, |operation, pool| {
+            Box::new(move || pool.with_closed(operation))
+        })();
 
+        println!("🔌 DB 연결 {}개 재연결", connection_count);
+        result
+    }
+
+    pub fn get_db_path() -> Result<PathBuf> {
+        crate::utils::app_paths::db_path()
+            .map_err(|e| rusqlite::Error::InvalidPath(PathBuf::from(e.to_string())))
+    }
+
+    /// 스키마 준비 (migrations/ 버전별 마이그레이션, 데모 데이터는 `demo_data::load_demo_data`로 별도 적재)
+    pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
+        crate::database::migrations::apply_migrations(conn)?;
         Ok(())
     }
 
+    /// 시연용 데모 데이터 적재 (opt-in, 이미 적재한 스크립트는 건너뜀)
+    pub fn load_demo_data(&self) -> Result<crate::database::demo_data::DemoDataReport> {
+        let conn = self.pool.write();
+        crate::database::demo_data::load_demo_data(&conn)
+    }
+
     // Judgment operations
     pub fn save_judgment(&self, judgment: &Judgment) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.write();
         conn.execute(
-            "INSERT INTO judgments (id, workflow_id, input_data, result, confidence, method_used, explanation, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO judgments (id, workflow_id, input_data, result, confidence, method_used, explanation, created_at, template_id, template_version, latency_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 &judgment.id,
                 &judgment.workflow_id,
@@ -342,15 +129,18 @@ impl Database {
                 &judgment.method_used,
                 &judgment.explanation,
                 judgment.created_at.to_rfc3339(),
+                &judgment.template_id,
+                judgment.template_version,
+                judgment.latency_ms,
             ],
         )?;
         Ok(())
     }
 
     pub fn get_judgment(&self, id: &str) -> Result<Option<Judgment>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
-            "SELECT id, workflow_id, input_data, result, confidence, method_used, explanation, created_at
+            "SELECT id, workflow_id, input_data, result, confidence, method_used, explanation, created_at, template_id, template_version, latency_ms
              FROM judgments WHERE id = ?1"
         )?;
 
@@ -364,6 +154,9 @@ impl Database {
                 method_used: row.get(5)?,
                 explanation: row.get(6)?,
                 created_at: row.get::<_, String>(7)?.parse().unwrap_or(Utc::now()),
+                template_id: row.get(8)?,
+                template_version: row.get(9)?,
+                latency_ms: row.get(10)?,
             })
         });
 
@@ -375,17 +168,17 @@ impl Database {
     }
 
     pub fn get_judgment_history(&self, workflow_id: Option<String>, limit: u32) -> Result<Vec<Judgment>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
 
         let (query, params_vec): (String, Vec<Box<dyn rusqlite::ToSql>>) = if let Some(wid) = workflow_id {
             (
-                "SELECT id, workflow_id, input_data, result, confidence, method_used, explanation, created_at
+                "SELECT id, workflow_id, input_data, result, confidence, method_used, explanation, created_at, template_id, template_version, latency_ms
                  FROM judgments WHERE workflow_id = ?1 ORDER BY created_at DESC LIMIT ?2".to_string(),
                 vec![Box::new(wid), Box::new(limit as i32)]
             )
         } else {
             (
-                "SELECT id, workflow_id, input_data, result, confidence, method_used, explanation, created_at
+                "SELECT id, workflow_id, input_data, result, confidence, method_used, explanation, created_at, template_id, template_version, latency_ms
                  FROM judgments ORDER BY created_at DESC LIMIT ?1".to_string(),
                 vec![Box::new(limit as i32)]
             )
@@ -404,6 +197,9 @@ impl Database {
                 method_used: row.get(5)?,
                 explanation: row.get(6)?,
                 created_at: row.get::<_, String>(7)?.parse().unwrap_or(Utc::now()),
+                template_id: row.get(8)?,
+                template_version: row.get(9)?,
+                latency_ms: row.get(10)?,
             })
         })?;
 
@@ -416,7 +212,12 @@ impl Database {
 
     // Workflow operations
     pub fn save_workflow(&self, workflow: &Workflow) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.write();
+        Self::upsert_workflow(&conn, workflow)
+    }
+
+    /// 주어진 연결(트랜잭션)에서 워크플로우 저장 (감사 기록과 같은 트랜잭션으로 묶을 때 사용)
+    pub(crate) fn upsert_workflow(conn: &Connection, workflow: &Workflow) -> Result<()> {
         conn.execute(
             "INSERT INTO workflows (id, name, definition, rule_expression, version, is_active, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
@@ -440,7 +241,7 @@ impl Database {
     }
 
     pub fn get_workflow(&self, id: &str) -> Result<Option<Workflow>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, name, definition, rule_expression, version, is_active, created_at
              FROM workflows WHERE id = ?1"
@@ -464,7 +265,7 @@ impl Database {
     }
 
     pub fn get_all_workflows(&self) -> Result<Vec<Workflow>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, name, definition, rule_expression, version, is_active, created_at
              FROM workflows WHERE is_active = 1 ORDER BY created_at DESC"
@@ -491,7 +292,7 @@ impl Database {
 
     // Training sample operations
     pub fn save_training_sample(&self, sample: &TrainingSample) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.write();
         conn.execute(
             "INSERT INTO training_samples (id, workflow_id, input_data, expected_result, actual_result, accuracy, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
@@ -509,7 +310,7 @@ impl Database {
     }
 
     pub fn get_training_samples(&self, workflow_id: &str, limit: u32) -> Result<Vec<TrainingSample>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, workflow_id, input_data, expected_result, actual_result, accuracy, created_at
              FROM training_samples WHERE workflow_id = ?1 ORDER BY created_at DESC LIMIT ?2"
@@ -534,9 +335,115 @@ impl Database {
         Ok(samples)
     }
 
+    /// 훈련 샘플 임베딩 저장 (little-endian f32 BLOB)
+    pub fn save_training_sample_embedding(
+        &self,
+        sample_id: &str,
+        workflow_id: &str,
+        model: &str,
+        embedding: &[f32],
+    ) -> Result<()> {
+        let bytes: Vec<u8> = embedding.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let conn = self.pool.write();
+        conn.execute(
+            "INSERT INTO training_sample_embeddings (sample_id, model, workflow_id, dimensions, embedding, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+             ON CONFLICT(sample_id, model) DO UPDATE SET
+                dimensions = excluded.dimensions,
+                embedding = excluded.embedding,
+                created_at = excluded.created_at",
+            params![
+                sample_id,
+                model,
+                workflow_id,
+                embedding.len() as i64,
+                bytes,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 워크플로우의 저장된 임베딩 (sample_id, embedding)
+    pub fn get_training_sample_embeddings(&self, workflow_id: &str, model: &str) -> Result<Vec<(String, Vec<f32>)>> {
+        let conn = self.pool.read();
+        let mut stmt = conn.prepare(
+            "SELECT sample_id, embedding FROM training_sample_embeddings
+             WHERE workflow_id = ?1 AND model = ?2"
+        )?;
+
+        let rows = stmt.query_map(params![workflow_id, model], |row| {
+            let bytes: Vec<u8> = row.get(1)?;
+            let embedding = bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect();
+            Ok((row.get::<_, String>(0)?, embedding))
+        })?;
+
+        let mut embeddings = Vec::new();
+        for embedding in rows {
+            embeddings.push(embedding?);
+        }
+        Ok(embeddings)
+    }
+
+    /// Rule 평가 리포트 저장
+    pub fn save_rule_evaluation_report(&self, record: &RuleEvaluationRecord) -> Result<()> {
+        let conn = self.pool.write();
+        conn.execute(
+            "INSERT INTO rule_evaluation_reports
+               (id, workflow_id, workflow_version, rule_expression, promoted, f1_score, min_f1, report_json, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                &record.id,
+                &record.workflow_id,
+                record.workflow_version,
+                &record.rule_expression,
+                record.promoted as i32,
+                record.f1_score,
+                record.min_f1,
+                &record.report_json,
+                record.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// 워크플로우의 Rule 평가 리포트 (최신순)
+    pub fn get_rule_evaluation_reports(&self, workflow_id: &str, limit: u32) -> Result<Vec<RuleEvaluationRecord>> {
+        let conn = self.pool.read();
+        let mut stmt = conn.prepare(
+            "SELECT id, workflow_id, workflow_version, rule_expression, promoted, f1_score, min_f1, report_json, created_at
+             FROM rule_evaluation_reports WHERE workflow_id = ?1
+             ORDER BY created_at DESC LIMIT ?2"
+        )?;
+
+        let rows = stmt.query_map(params![workflow_id, limit as i32], |row| {
+            Ok(RuleEvaluationRecord {
+                id: row.get(0)?,
+                workflow_id: row.get(1)?,
+                workflow_version: row.get(2)?,
+                rule_expression: row.get(3)?,
+                promoted: row.get::<_, i32>(4)? != 0,
+                f1_score: row.get(5)?,
+                min_f1: row.get(6)?,
+                report_json: row.get(7)?,
+                created_at: row.get::<_, String>(8)?.parse().unwrap_or(Utc::now()),
+            })
+        })?;
+
+        let mut records = Vec::new();
+        for record in rows {
+            records.push(record?);
+        }
+        Ok(records)
+    }
+
     // Feedback operations
     pub fn save_feedback(&self, feedback: &Feedback) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.write();
         conn.execute(
             "INSERT INTO feedbacks (id, judgment_id, feedback_type, value, comment, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
@@ -554,7 +461,7 @@ impl Database {
 
     // PromptTemplate operations
     pub fn save_prompt_template(&self, template: &PromptTemplate) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.write();
         conn.execute(
             "INSERT INTO prompt_templates (id, name, template_type, content, variables, version, is_active, token_limit, created_at, updated_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
@@ -584,7 +491,7 @@ impl Database {
     }
 
     pub fn get_prompt_template(&self, id: &str) -> Result<Option<PromptTemplate>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, name, template_type, content, variables, version, is_active, token_limit, created_at, updated_at
              FROM prompt_templates WHERE id = ?1"
@@ -611,7 +518,7 @@ impl Database {
     }
 
     pub fn get_active_template_by_type(&self, template_type: &str) -> Result<Option<PromptTemplate>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, name, template_type, content, variables, version, is_active, token_limit, created_at, updated_at
              FROM prompt_templates
@@ -640,7 +547,7 @@ impl Database {
     }
 
     pub fn get_all_prompt_templates(&self) -> Result<Vec<PromptTemplate>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, name, template_type, content, variables, version, is_active, token_limit, created_at, updated_at
              FROM prompt_templates ORDER BY template_type, version DESC"
@@ -669,14 +576,162 @@ impl Database {
     }
 
     pub fn delete_prompt_template(&self, id: &str) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.write();
         conn.execute("DELETE FROM prompt_templates WHERE id = ?1", params![id])?;
         Ok(())
     }
 
+    // Prompt A/B test operations
+    /// 템플릿을 A/B 실험에 등록하거나 트래픽 가중치 변경
+    pub fn set_prompt_variant_weight(&self, template_id: &str, weight: f64) -> Result<()> {
+        let conn = self.pool.write();
+        let template_type: String = conn.query_row(
+            "SELECT template_type FROM prompt_templates WHERE id = ?1",
+            params![template_id],
+            |row| row.get(0),
+        )?;
+
+        conn.execute(
+            "INSERT INTO prompt_ab_variants (template_id, template_type, weight, created_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(template_id) DO UPDATE SET weight = excluded.weight",
+            params![template_id, template_type, weight, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_prompt_variant(&self, template_id: &str) -> Result<()> {
+        let conn = self.pool.write();
+        conn.execute("DELETE FROM prompt_ab_variants WHERE template_id = ?1", params![template_id])?;
+        Ok(())
+    }
+
+    /// 실험 중인 변형 템플릿과 가중치 (가중치 0인 변형 제외)
+    pub fn get_prompt_variants(&self, template_type: &str) -> Result<Vec<(PromptTemplate, f64)>> {
+        let conn = self.pool.read();
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name, t.template_type, t.content, t.variables, t.version, t.is_active, t.token_limit, t.created_at, t.updated_at, v.weight
+             FROM prompt_ab_variants v
+             JOIN prompt_templates t ON t.id = v.template_id
+             WHERE v.template_type = ?1 AND v.weight > 0
+             ORDER BY t.version DESC"
+        )?;
+
+        let rows = stmt.query_map(params![template_type], |row| {
+            Ok((
+                PromptTemplate {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    template_type: row.get(2)?,
+                    content: row.get(3)?,
+                    variables: row.get(4)?,
+                    version: row.get(5)?,
+                    is_active: row.get::<_, i32>(6)? != 0,
+                    token_limit: row.get(7)?,
+                    created_at: row.get::<_, String>(8)?.parse().unwrap_or(Utc::now()),
+                    updated_at: row.get::<_, String>(9)?.parse().unwrap_or(Utc::now()),
+                },
+                row.get::<_, f64>(10)?,
+            ))
+        })?;
+
+        let mut variants = Vec::new();
+        for variant in rows {
+            variants.push(variant?);
+        }
+        Ok(variants)
+    }
+
+    /// template_type의 모든 템플릿 버전별 판단 성과 집계
+    pub fn get_prompt_variant_stats(&self, template_type: &str) -> Result<Vec<PromptVariantStats>> {
+        let conn = self.pool.read();
+        let mut stmt = conn.prepare(
+            "SELECT
+                t.id, t.name, t.version, t.is_active, v.weight,
+                (SELECT COUNT(*) FROM judgments j WHERE j.template_id = t.id),
+                (SELECT COUNT(*) FROM feedbacks f JOIN judgments j ON j.id = f.judgment_id
+                  WHERE j.template_id = t.id AND f.value <> 0),
+                (SELECT COUNT(*) FROM feedbacks f JOIN judgments j ON j.id = f.judgment_id
+                  WHERE j.template_id = t.id AND f.value > 0),
+                (SELECT AVG(j.confidence) FROM judgments j WHERE j.template_id = t.id),
+                (SELECT AVG(j.latency_ms) FROM judgments j WHERE j.template_id = t.id AND j.latency_ms IS NOT NULL),
+                (SELECT COALESCE(SUM(tu.tokens_used), 0) FROM token_usage tu JOIN judgments j ON j.id = tu.judgment_id
+                  WHERE j.template_id = t.id),
+                (SELECT COALESCE(SUM(tu.cost_usd), 0.0) FROM token_usage tu JOIN judgments j ON j.id = tu.judgment_id
+                  WHERE j.template_id = t.id)
+             FROM prompt_templates t
+             LEFT JOIN prompt_ab_variants v ON v.template_id = t.id
+             WHERE t.template_type = ?1
+             ORDER BY t.version DESC"
+        )?;
+
+        let rows = stmt.query_map(params![template_type], |row| {
+            let judgment_count: i64 = row.get(5)?;
+            let feedback_count: i64 = row.get(6)?;
+            let positive_feedback_count: i64 = row.get(7)?;
+            let total_cost_usd: f64 = row.get(11)?;
+
+            Ok(PromptVariantStats {
+                template_id: row.get(0)?,
+                template_name: row.get(1)?,
+                template_version: row.get(2)?,
+                is_active: row.get::<_, i32>(3)? != 0,
+                weight: row.get(4)?,
+                judgment_count,
+                feedback_count,
+                positive_feedback_count,
+                accuracy: if feedback_count > 0 {
+                    Some(positive_feedback_count as f64 / feedback_count as f64)
+                } else {
+                    None
+                },
+                avg_confidence: row.get(8)?,
+                avg_latency_ms: row.get(9)?,
+                total_tokens: row.get(10)?,
+                total_cost_usd,
+                avg_cost_per_judgment_usd: if judgment_count > 0 {
+                    Some(total_cost_usd / judgment_count as f64)
+                } else {
+                    None
+                },
+            })
+        })?;
+
+        let mut stats = Vec::new();
+        for stat in rows {
+            stats.push(stat?);
+        }
+        Ok(stats)
+    }
+
+    /// 승자 템플릿만 활성화하고 해당 template_type의 A/B 실험 종료
+    pub fn promote_prompt_template(&self, template_id: &str) -> Result<()> {
+        let mut conn = self.pool.write();
+        let tx = conn.transaction()?;
+
+        let template_type: String = tx.query_row(
+            "SELECT template_type FROM prompt_templates WHERE id = ?1",
+            params![template_id],
+            |row| row.get(0),
+        )?;
+
+        tx.execute(
+            "UPDATE prompt_templates
+             SET is_active = CASE WHEN id = ?1 THEN 1 ELSE 0 END, updated_at = ?3
+             WHERE template_type = ?2",
+            params![template_id, template_type, Utc::now().to_rfc3339()],
+        )?;
+        tx.execute(
+            "DELETE FROM prompt_ab_variants WHERE template_type = ?1",
+            params![template_type],
+        )?;
+
+        tx.commit()
+    }
+
     // Token Usage operations (MCP cost tracking)
     pub fn save_token_usage(&self, token_usage: &TokenUsage) -> Result<()> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.write();
         conn.execute(
             "INSERT INTO token_usage (id, judgment_id, service, tokens_used, cost_usd, complexity, created_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
@@ -694,7 +749,7 @@ impl Database {
     }
 
     pub fn get_token_usage_by_judgment(&self, judgment_id: &str) -> Result<Vec<TokenUsage>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, judgment_id, service, tokens_used, cost_usd, complexity, created_at
              FROM token_usage
@@ -726,7 +781,7 @@ impl Database {
         start_date: &str,
         end_date: &str,
     ) -> Result<Vec<TokenUsage>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT id, judgment_id, service, tokens_used, cost_usd, complexity, created_at
              FROM token_usage
@@ -758,7 +813,7 @@ impl Database {
         service: &str,
         limit: Option<u32>,
     ) -> Result<Vec<TokenUsage>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let query = if let Some(lim) = limit {
             format!(
                 "SELECT id, judgment_id, service, tokens_used, cost_usd, complexity, created_at
@@ -802,7 +857,7 @@ impl Database {
         start_date: &str,
         end_date: &str,
     ) -> Result<TokenUsageSummary> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
         let mut stmt = conn.prepare(
             "SELECT
                 SUM(tokens_used) as total_tokens,
@@ -850,7 +905,7 @@ impl Database {
     ///
     /// Returns overall token usage metrics including cache savings
     pub fn get_token_metrics(&self) -> Result<TokenMetrics> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.pool.read();
 
         // Get total tokens and cost
         let mut stmt = conn.prepare(
@@ -931,11 +986,3 @@ pub struct TokenMetrics {
     pub cache_hit_rate: f64,
     pub avg_tokens_per_request: f64,
 }
-
-impl Clone for Database {
-    fn clone(&self) -> Self {
-        Self {
-            conn: Arc::clone(&self.conn),
-        }
-    }
-}
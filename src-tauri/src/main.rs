@@ -100,7 +100,7 @@ fn mask_api_key(api_key: &str) -> String {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    // Database 인스턴스 생성
+    // 앱 전역 연결 풀 생성 (스키마 초기화/시드/마이그레이션은 여기서 1회, 이후 Database::new()는 같은 풀 공유)
     let database = match database::Database::new() {
         Ok(db) => db,
         Err(e) => {
@@ -110,7 +110,14 @@ pub fn run() {
     };
 
     tauri::Builder::default()
-        .manage(database) // Database state 등록
+        .manage(database) // Database state 등록 (커맨드에 State<'_, Database>로 주입)
+        .setup(|app| {
+            // 자동 백업 스케줄러 (앱 시작 백업 + 일/주 단위 백업 + 실패 알림)
+            services::backup_scheduler::start(app.handle());
+            // 데이터 보존 작업 (테이블별 삭제/월별 아카이브, HACCP 기록은 법정 기간 보존)
+            services::retention_scheduler::start(app.handle());
+            Ok(())
+        })
         .system_tray(tray::create_tray())
         .on_system_tray_event(tray::handle_tray_event)
         .on_window_event(|event| {
@@ -126,6 +133,13 @@ pub fn run() {
             learning::save_feedback,
             learning::get_few_shot_samples,
             learning::extract_rules,
+            learning::get_rule_evaluation_reports,
+
+            // Prompt A/B Test Commands
+            prompt::set_prompt_variant_weight,
+            prompt::remove_prompt_variant,
+            prompt::get_prompt_ab_report,
+            prompt::promote_prompt_template,
 
             // BI Service Commands
             bi::generate_bi_insight,
@@ -172,6 +186,7 @@ pub fn run() {
             system::get_system_stats,
             system::get_data_directory,
             system::export_database,
+            system::load_demo_data,
             system::get_token_metrics,
             system::save_api_key,
             system::load_api_key,
@@ -184,21 +199,68 @@ pub fn run() {
 
             // Backup Commands (Phase 8 Task 8.3)
             backup::create_backup,
+            backup::get_backup_manifest,
+            backup::preview_restore,
             backup::restore_backup,
             backup::list_backups,
             backup::get_backup_info,
+            backup::get_backup_policy,
+            backup::update_backup_policy,
+            backup::get_backup_status,
+
+            // Data Retention Commands
+            retention::get_retention_policy,
+            retention::update_retention_policy,
+            retention::get_retention_status,
+            retention::run_retention_now,
+            retention::list_archives,
+            retention::query_archive,
+
+            // Audit Log Commands (HACCP 감사 대응)
+            audit::list_audit_log,
+            audit::verify_audit_log,
+            audit::export_audit_log,
+
+            // Auth Commands (로컬 사용자 / 역할 기반 권한)
+            auth::get_auth_status,
+            auth::create_initial_admin,
+            auth::login,
+            auth::logout,
+            auth::change_password,
+            auth::list_users,
+            auth::create_user,
+            auth::update_user,
+            auth::reset_user_password,
 
             // CCP Demo Commands (RAG + Rule-based Judgment)
             ccp::search_ccp_docs,
             ccp::judge_ccp_status,
             ccp::debug_ccp_database,
             ccp::rebuild_fts5_index,
+            ccp::import_ccp_document,
+            ccp::list_ccp_doc_versions,
+            ccp::get_ccp_risk_policy,
+            ccp::list_ccp_risk_policies,
+            ccp::save_ccp_risk_policy,
+            ccp::delete_ccp_risk_policy,
+            ccp::list_corrective_actions,
+            ccp::get_corrective_action,
+            ccp::open_corrective_actions_for_checks,
+            ccp::update_corrective_action,
+            ccp::request_corrective_action_verification,
+            ccp::close_corrective_action,
 
             // MES/ERP RAG Commands (Phase 8: Generic CSV Upload & Query)
             mes::upload_mes_data,
             mes::query_mes_data,
             mes::delete_mes_session,
             mes::get_mes_session_stats,
+            mes::ingest_mes_file,
+            mes::list_mes_sheets,
+            mes::list_mes_uploads,
+            mes::get_mes_upload,
+            mes::plan_mes_query,
+            mes::run_mes_query,
 
             // Database Viewer Commands
             commands::database::get_database_tables,
@@ -208,6 +270,15 @@ pub fn run() {
             // Chart Service Commands (MES 스키마 기반 차트 생성)
             commands::chart::generate_chart,
             commands::chart::get_chart_examples,
+            commands::chart::analyze_spc,
+            commands::chart::generate_spc_chart,
+            commands::lot::list_lot_holds,
+            commands::lot::get_lot_hold,
+            commands::lot::get_lot_hold_history,
+            commands::lot::place_lot_hold,
+            commands::lot::check_lot_outbound,
+            commands::lot::request_lot_release,
+            commands::lot::trace_lot,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,99 @@
+// services/approval_request.rs - approval_requests 공용 헬퍼
+//
+// 워크플로우 APPROVAL 노드와 같은 approval_requests 테이블을 시정조치 검증, LOT 출하 승인 등
+// 서비스 단위 승인에서도 쓰기 위한 생성/결과 조회 함수.
+// 승인/거부 자체는 기존 process_approval 커맨드가 처리한다.
+
+use rusqlite::{Connection, OptionalExtension};
+
+/// 서비스 승인 요청 생성 정보 (approval_type = manual)
+pub struct NewApprovalRequest<'a> {
+    pub workflow_id: &'a str,
+    pub workflow_name: &'a str,
+    /// 승인 대상 ID (시정조치 ID, 보류 ID 등)
+    pub step_id: &'a str,
+    pub step_name: &'a str,
+    pub approvers: &'a str,
+    pub input_data: &'a serde_json::Value,
+    pub timeout_minutes: i64,
+}
+
+/// 승인 요청 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalOutcome {
+    /// pending | approved | rejected | expired (만료 시각이 지난 pending은 expired로 갱신됨)
+    pub status: String,
+    pub decided_by: Option<String>,
+    pub decided_at: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// 승인 요청 생성, 요청 ID 반환
+pub fn create_request(conn: &Connection, request: &NewApprovalRequest) -> anyhow::Result<String> {
+    let request_id = format!("apr-{}", uuid::Uuid::new_v4().to_string().split('-').next().unwrap_or("000"));
+    let now = chrono::Utc::now();
+    let expires_at = now + chrono::Duration::minutes(request.timeout_minutes);
+
+    conn.execute(
+        "INSERT INTO approval_requests (id, workflow_id, workflow_name, step_id, step_name, approval_type, status, approvers, input_data, condition, timeout_minutes, created_at, expires_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, 'manual', 'pending', ?6, ?7, NULL, ?8, ?9, ?10)",
+        rusqlite::params![
+            request_id,
+            request.workflow_id,
+            request.workflow_name,
+            request.step_id,
+            request.step_name,
+            request.approvers,
+            serde_json::to_string(request.input_data)?,
+            request.timeout_minutes,
+            now.to_rfc3339(),
+            expires_at.to_rfc3339(),
+        ],
+    )?;
+
+    println!("📋 [APPROVAL] 승인 요청 생성: {} ({} / {})", request_id, request.workflow_name, request.step_id);
+    Ok(request_id)
+}
+
+/// 승인 요청 결과 조회 (만료된 pending 요청은 expired로 갱신)
+pub fn load_outcome(conn: &Connection, request_id: &str) -> anyhow::Result<ApprovalOutcome> {
+    let (status, decided_by, decided_at, comment, expires_at) = conn
+        .query_row(
+            "SELECT status, decided_by, decided_at, comment, expires_at FROM approval_requests WHERE id = ?1",
+            [request_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            },
+        )
+        .optional()?
+        .ok_or_else(|| anyhow::anyhow!("승인 요청을 찾을 수 없습니다: {}", request_id))?;
+
+    let expired = status == "pending"
+        && expires_at.is_some_and(|expires| expires < chrono::Utc::now().to_rfc3339());
+    if expired {
+        conn.execute(
+            "UPDATE approval_requests SET status = 'expired' WHERE id = ?1 AND status = 'pending'",
+            [request_id],
+        )?;
+    }
+
+    Ok(ApprovalOutcome {
+        status: if expired { "expired".to_string() } else { status },
+        decided_by,
+        decided_at: decided_at.as_deref().map(to_local_timestamp),
+        comment,
+    })
+}
+
+/// approval_requests의 RFC3339 시각을 "YYYY-MM-DD HH:MM:SS"(UTC) 형식으로 변환
+pub fn to_local_timestamp(value: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&chrono::Utc).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|_| value.to_string())
+}
@@ -0,0 +1,486 @@
+// services/audit_log.rs - 규제 기록 감사 로그 (추가 전용, 해시 체인)
+//
+// CCP 판단, 승인/거부, 룰 변경, 워크플로우 생성/수정/삭제를 누가(actor) 무엇을(action)
+// 어떤 값에서 어떤 값으로(before/after) 언제 했는지 audit_log에 남긴다.
+//
+// - 각 항목의 hash = SHA-256(prev_hash + 항목 내용), 첫 항목의 prev_hash는 GENESIS_HASH
+// - DB 트리거가 UPDATE/DELETE를 막고, prev_hash UNIQUE가 체인 분기를 막는다
+// - `verify`는 체인을 처음부터 다시 계산해 변조/삭제(중간/끝)를 찾는다
+// - `export_csv`는 감사인 제출용 CSV (hash 포함, 엑셀에서 열 수 있도록 UTF-8 BOM)
+
+use crate::database::Database;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// 첫 항목의 prev_hash
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// 검증 결과에 담는 최대 문제 수
+const MAX_REPORTED_PROBLEMS: usize = 100;
+
+/// 감사 항목 종류 (action)
+pub const ACTION_CCP_JUDGMENT: &str = "ccp_judgment.create";
+pub const ACTION_APPROVAL_DECIDE: &str = "approval.decide";
+pub const ACTION_RULE_UPDATE: &str = "rule.update";
+pub const ACTION_WORKFLOW_CREATE: &str = "workflow.create";
+pub const ACTION_WORKFLOW_UPDATE: &str = "workflow.update";
+pub const ACTION_WORKFLOW_DELETE: &str = "workflow.delete";
+
+/// 새 감사 항목
+pub struct NewAuditEntry<'a> {
+    pub actor: &'a str,
+    pub action: &'a str,
+    /// 대상 테이블/객체 종류 (workflow, approval_request, ccp_judgment 등)
+    pub entity_type: &'a str,
+    pub entity_id: &'a str,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// 기록된 감사 항목
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub seq: i64,
+    pub occurred_at: String,
+    pub actor: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub before_json: Option<String>,
+    pub after_json: Option<String>,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// 항목 내용으로 계산한 해시 (seq 포함 → 항목 삭제/순서 변경도 감지)
+    fn compute_hash(&self) -> String {
+        let content = serde_json::json!([
+            self.seq,
+            self.occurred_at,
+            self.actor,
+            self.action,
+            self.entity_type,
+            self.entity_id,
+            self.before_json,
+            self.after_json,
+        ]);
+        let mut hasher = Sha256::new();
+        hasher.update(self.prev_hash.as_bytes());
+        hasher.update(content.to_string().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// 체인 검증 문제
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditProblem {
+    pub seq: i64,
+    pub message: String,
+}
+
+/// 체인 검증 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditVerification {
+    pub valid: bool,
+    pub checked: usize,
+    pub last_seq: Option<i64>,
+    pub last_hash: Option<String>,
+    pub problems: Vec<AuditProblem>,
+}
+
+/// 감사 로그 조회 조건
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditQuery {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub actor: Option<String>,
+    /// RFC3339 또는 YYYY-MM-DD (이상)
+    pub from: Option<String>,
+    /// RFC3339 또는 YYYY-MM-DD (미만)
+    pub to: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// 감사인 제출용 내보내기 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditExportReport {
+    pub path: String,
+    pub entries: usize,
+    /// 내보낸 시점의 체인 검증 결과
+    pub verification: AuditVerification,
+}
+
+/// 현재 작업자 (앱 로그인 사용자, 로그인 전이면 OS 계정, 확인 불가 시 "unknown")
+pub fn current_actor() -> String {
+    if let Some(user) = crate::services::auth::session().current_user() {
+        return user.username;
+    }
+    std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// 감사 항목 추가 (변경과 같은 트랜잭션에서 호출하면 변경/기록이 함께 커밋됨)
+pub fn append(conn: &Connection, entry: &NewAuditEntry) -> anyhow::Result<AuditEntry> {
+    let prev_hash: String = conn
+        .query_row("SELECT hash FROM audit_log ORDER BY seq DESC LIMIT 1", [], |row| row.get(0))
+        .optional()?
+        .unwrap_or_else(|| GENESIS_HASH.to_string());
+    // AUTOINCREMENT는 삭제된 seq도 재사용하지 않으므로 sqlite_sequence 기준으로 다음 seq 결정
+    let last_seq: i64 = conn
+        .query_row("SELECT seq FROM sqlite_sequence WHERE name = 'audit_log'", [], |row| row.get(0))
+        .optional()?
+        .unwrap_or(0);
+
+    let mut record = AuditEntry {
+        seq: last_seq + 1,
+        occurred_at: chrono::Utc::now().to_rfc3339(),
+        actor: entry.actor.to_string(),
+        action: entry.action.to_string(),
+        entity_type: entry.entity_type.to_string(),
+        entity_id: entry.entity_id.to_string(),
+        before_json: entry.before.as_ref().map(|v| v.to_string()),
+        after_json: entry.after.as_ref().map(|v| v.to_string()),
+        prev_hash,
+        hash: String::new(),
+    };
+    record.hash = record.compute_hash();
+
+    conn.execute(
+        "INSERT INTO audit_log (seq, occurred_at, actor, action, entity_type, entity_id, before_json, after_json, prev_hash, hash)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        rusqlite::params![
+            record.seq,
+            record.occurred_at,
+            record.actor,
+            record.action,
+            record.entity_type,
+            record.entity_id,
+            record.before_json,
+            record.after_json,
+            record.prev_hash,
+            record.hash,
+        ],
+    )
+    .map_err(|e| anyhow::anyhow!("감사 로그 기록 실패: {}", e))?;
+
+    Ok(record)
+}
+
+/// 해시 체인 전체 검증
+pub fn verify(conn: &Connection) -> anyhow::Result<AuditVerification> {
+    let mut stmt = conn.prepare(&format!("{} ORDER BY seq", SELECT_ENTRIES))?;
+    let entries = stmt.query_map([], entry_from_row)?;
+
+    let mut problems = Vec::new();
+    let mut report = |seq: i64, message: String| {
+        if problems.len() < MAX_REPORTED_PROBLEMS {
+            problems.push(AuditProblem { seq, message });
+        }
+    };
+
+    let mut checked = 0;
+    let mut expected_prev = GENESIS_HASH.to_string();
+    let mut expected_seq = 1;
+    let mut last: Option<(i64, String)> = None;
+    for entry in entries {
+        let entry = entry?;
+        checked += 1;
+        if entry.seq != expected_seq {
+            report(entry.seq, format!("seq {}~{} 항목이 없습니다 (삭제 의심)", expected_seq, entry.seq - 1));
+        }
+        if entry.prev_hash != expected_prev {
+            report(entry.seq, "이전 항목 해시와 연결되지 않습니다".to_string());
+        }
+        if entry.compute_hash() != entry.hash {
+            report(entry.seq, "항목 내용이 기록 당시 해시와 다릅니다 (변조 의심)".to_string());
+        }
+        expected_prev = entry.hash.clone();
+        expected_seq = entry.seq + 1;
+        last = Some((entry.seq, entry.hash));
+    }
+
+    // 마지막 항목들이 지워졌는지 (AUTOINCREMENT 최대값과 비교)
+    let max_seq: Option<i64> = conn
+        .query_row("SELECT seq FROM sqlite_sequence WHERE name = 'audit_log'", [], |row| row.get(0))
+        .optional()?;
+    if let Some(max_seq) = max_seq {
+        let last_seq = last.as_ref().map(|(seq, _)| *seq).unwrap_or(0);
+        if max_seq > last_seq {
+            report(max_seq, format!("seq {}~{} 항목이 없습니다 (마지막 항목 삭제 의심)", last_seq + 1, max_seq));
+        }
+    }
+
+    Ok(AuditVerification {
+        valid: problems.is_empty(),
+        checked,
+        last_seq: last.as_ref().map(|(seq, _)| *seq),
+        last_hash: last.map(|(_, hash)| hash),
+        problems,
+    })
+}
+
+const SELECT_ENTRIES: &str =
+    "SELECT seq, occurred_at, actor, action, entity_type, entity_id, before_json, after_json, prev_hash, hash FROM audit_log";
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<AuditEntry> {
+    Ok(AuditEntry {
+        seq: row.get(0)?,
+        occurred_at: row.get(1)?,
+        actor: row.get(2)?,
+        action: row.get(3)?,
+        entity_type: row.get(4)?,
+        entity_id: row.get(5)?,
+        before_json: row.get(6)?,
+        after_json: row.get(7)?,
+        prev_hash: row.get(8)?,
+        hash: row.get(9)?,
+    })
+}
+
+pub struct AuditLogStore {
+    db: Database,
+}
+
+impl AuditLogStore {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub fn record(&self, entry: &NewAuditEntry) -> anyhow::Result<AuditEntry> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let tx = conn.unchecked_transaction()?;
+        let record = append(&tx, entry)?;
+        tx.commit()?;
+        Ok(record)
+    }
+
+    /// 변경과 감사 기록을 한 트랜잭션으로 커밋 (감사 기록 실패시 변경도 롤백)
+    pub fn record_change<T>(
+        &self,
+        entry: &NewAuditEntry,
+        change: impl FnOnce(&Connection) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let tx = conn.unchecked_transaction()?;
+        let value = change(&tx)?;
+        append(&tx, entry)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    /// 감사 로그 조회 (최신순, 기본 200건)
+    pub fn list(&self, query: &AuditQuery) -> anyhow::Result<Vec<AuditEntry>> {
+        let db_conn = self.db.get_reader();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut sql = format!("{} WHERE 1 = 1", SELECT_ENTRIES);
+        let mut params: Vec<String> = Vec::new();
+        for (column, op, value) in [
+            ("entity_type", "=", &query.entity_type),
+            ("entity_id", "=", &query.entity_id),
+            ("actor", "=", &query.actor),
+            ("occurred_at", ">=", &query.from),
+            ("occurred_at", "<", &query.to),
+        ] {
+            if let Some(value) = value {
+                params.push(value.clone());
+                sql.push_str(&format!(" AND {} {} ?{}", column, op, params.len()));
+            }
+        }
+        sql.push_str(&format!(" ORDER BY seq DESC LIMIT {}", query.limit.unwrap_or(200)));
+
+        let mut stmt = conn.prepare(&sql)?;
+        let entries = stmt
+            .query_map(rusqlite::params_from_iter(params.iter()), entry_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    pub fn verify(&self) -> anyhow::Result<AuditVerification> {
+        let db_conn = self.db.get_reader();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        verify(&conn)
+    }
+
+    /// 전체 감사 로그를 CSV로 내보내기 (seq 순, 검증 결과 포함 반환)
+    pub fn export_csv(&self, path: &Path) -> anyhow::Result<AuditExportReport> {
+        let db_conn = self.db.get_reader();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        // 검증과 내보내기가 같은 스냅샷을 보도록 읽기 트랜잭션 안에서 실행
+        let tx = conn.unchecked_transaction()?;
+        let verification = verify(&tx)?;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::File::create(path)
+            .map_err(|e| anyhow::anyhow!("내보내기 파일 생성 실패: {}", e))?;
+        std::io::Write::write_all(&mut file, "\u{feff}".as_bytes())?;
+        let mut writer = csv::Writer::from_writer(file);
+        writer.write_record([
+            "seq", "occurred_at", "actor", "action", "entity_type", "entity_id",
+            "before_json", "after_json", "prev_hash", "hash",
+        ])?;
+
+        let mut stmt = tx.prepare(&format!("{} ORDER BY seq", SELECT_ENTRIES))?;
+        let mut entries = 0;
+        for entry in stmt.query_map([], entry_from_row)? {
+            let entry = entry?;
+            writer.write_record([
+                entry.seq.to_string(),
+                entry.occurred_at,
+                entry.actor,
+                entry.action,
+                entry.entity_type,
+                entry.entity_id,
+                entry.before_json.unwrap_or_default(),
+                entry.after_json.unwrap_or_default(),
+                entry.prev_hash,
+                entry.hash,
+            ])?;
+            entries += 1;
+        }
+        writer.flush()?;
+
+        Ok(AuditExportReport {
+            path: path.to_string_lossy().to_string(),
+            entries,
+            verification,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn event<'a>(entity_id: &'a str, after: serde_json::Value) -> NewAuditEntry<'a> {
+        NewAuditEntry {
+            actor: "qa-kim",
+            action: ACTION_WORKFLOW_UPDATE,
+            entity_type: "workflow",
+            entity_id,
+            before: None,
+            after: Some(after),
+        }
+    }
+
+    #[test]
+    fn test_chain_detects_tampering() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let conn = Connection::open(temp_dir.path().join("audit.db"))?;
+        crate::database::migrations::migrate_up(&conn, false)?;
+
+        let first = append(&conn, &event("wf-1", serde_json::json!({ "version": 2 })))?;
+        let second = append(&conn, &event("wf-1", serde_json::json!({ "version": 3 })))?;
+        append(&conn, &event("wf-2", serde_json::json!({ "version": 2 })))?;
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+
+        let verification = verify(&conn)?;
+        assert!(verification.valid, "{:?}", verification.problems);
+        assert_eq!((verification.checked, verification.last_seq), (3, Some(3)));
+
+        // 트리거가 수정/삭제를 막음
+        assert!(conn.execute("UPDATE audit_log SET actor = 'someone' WHERE seq = 2", []).is_err());
+        assert!(conn.execute("DELETE FROM audit_log WHERE seq = 3", []).is_err());
+
+        // 트리거를 지우고 내용을 바꾸면 검증에서 드러남
+        conn.execute_batch(
+            "DROP TRIGGER trg_audit_log_no_update;
+             DROP TRIGGER trg_audit_log_no_delete;
+             UPDATE audit_log SET after_json = '{\"version\":9}' WHERE seq = 2;",
+        )?;
+        let verification = verify(&conn)?;
+        assert!(!verification.valid);
+        assert_eq!(verification.problems[0].seq, 2);
+
+        // 마지막 항목 삭제도 감지
+        conn.execute("UPDATE audit_log SET after_json = ?1 WHERE seq = 2", [&second.after_json])?;
+        assert!(verify(&conn)?.valid);
+        conn.execute("DELETE FROM audit_log WHERE seq = 3", [])?;
+        let verification = verify(&conn)?;
+        assert!(!verification.valid);
+        assert_eq!(verification.problems[0].seq, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_change_rolls_back_when_audit_fails() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db = Database::open(temp_dir.path().join("audit.db"))?;
+        let store = AuditLogStore::from_database(db.clone());
+        let workflow = crate::database::Workflow {
+            id: "wf-rollback".to_string(),
+            name: "Rollback".to_string(),
+            definition: "{}".to_string(),
+            rule_expression: None,
+            version: 1,
+            is_active: true,
+            created_at: chrono::Utc::now(),
+        };
+
+        // 감사 기록 INSERT를 실패시키면 워크플로우 저장도 롤백되어야 함
+        db.get_connection().lock().unwrap().execute_batch(
+            "CREATE TEMP TRIGGER fail_audit BEFORE INSERT ON audit_log
+             BEGIN SELECT RAISE(ABORT, 'audit failure'); END;",
+        )?;
+        let entry = event(&workflow.id, serde_json::json!({ "version": 1 }));
+        assert!(store
+            .record_change(&entry, |conn| Ok(Database::upsert_workflow(conn, &workflow)?))
+            .is_err());
+        assert!(db.get_workflow(&workflow.id)?.is_none());
+
+        db.get_connection().lock().unwrap().execute_batch("DROP TRIGGER temp.fail_audit;")?;
+        store.record_change(&entry, |conn| Ok(Database::upsert_workflow(conn, &workflow)?))?;
+        assert!(db.get_workflow(&workflow.id)?.is_some());
+        assert_eq!(store.verify()?.checked, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_csv() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let db = Database::open(temp_dir.path().join("audit_export.db"))?;
+        let store = AuditLogStore::from_database(db);
+
+        store.record(&event("wf-1", serde_json::json!({ "name": "살균 점검" })))?;
+        store.record(&event("wf-2", serde_json::json!({ "name": "금속 검출" })))?;
+        let entries = store.list(&AuditQuery { entity_id: Some("wf-2".to_string()), ..Default::default() })?;
+        assert_eq!(entries.len(), 1);
+
+        let path = temp_dir.path().join("export").join("audit.csv");
+        let report = store.export_csv(&path)?;
+        assert_eq!(report.entries, 2);
+        assert!(report.verification.valid);
+
+        let content = std::fs::read_to_string(&path)?;
+        assert!(content.starts_with('\u{feff}'));
+        assert_eq!(content.lines().count(), 3);
+        assert!(content.contains("살균 점검"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,612 @@
+// services/auth.rs - 로컬 사용자 계정 / 로그인 세션 / 역할 기반 권한
+//
+// 역할은 operator < qa < supervisor < admin 순서이며, 민감한 작업(Permission)마다
+// 최소 역할을 둔다. 앱은 한 PC에서 한 명이 쓰는 구조라 세션도 앱 전역 1개이고,
+// SESSION_IDLE_TIMEOUT 동안 권한 확인이 없으면 만료된다.
+//
+// - 첫 실행: 사용자가 없으면 `create_initial_admin`으로 관리자 1명만 만들 수 있음
+// - 비밀번호: Argon2id PHC 문자열로 저장, MAX_FAILED_ATTEMPTS 연속 실패 시 LOCKOUT 동안 잠금
+// - 계정 생성/역할 변경/비활성화/비밀번호 초기화는 감사 로그에 남긴다
+
+use crate::database::Database;
+use crate::services::audit_log::{self, NewAuditEntry};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// 마지막 권한 확인 이후 세션 유지 시간
+const SESSION_IDLE_TIMEOUT_MINUTES: i64 = 30;
+/// 연속 로그인 실패 허용 횟수 / 잠금 시간
+const MAX_FAILED_ATTEMPTS: i64 = 5;
+const LOCKOUT_MINUTES: i64 = 5;
+const MIN_PASSWORD_LEN: usize = 8;
+
+/// 사용자 역할 (선언 순서가 권한 서열)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Operator,
+    Qa,
+    Supervisor,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Operator => "operator",
+            Role::Qa => "qa",
+            Role::Supervisor => "supervisor",
+            Role::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "operator" => Some(Role::Operator),
+            "qa" => Some(Role::Qa),
+            "supervisor" => Some(Role::Supervisor),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Role::Operator => "작업자",
+            Role::Qa => "QA",
+            Role::Supervisor => "감독자",
+            Role::Admin => "관리자",
+        }
+    }
+
+    pub fn allows(&self, permission: Permission) -> bool {
+        *self >= permission.min_role()
+    }
+}
+
+/// 권한 확인이 필요한 작업
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// 승인 요청 승인/거부 (시정조치 검증, LOT 출하 보류 해제 포함)
+    DecideApprovals,
+    /// 검증된 HACCP 시정조치 종결
+    CloseCorrectiveActions,
+    DeleteWorkflows,
+    /// 사용자 정의 SQL 조회 (보관 아카이브 조회 포함)
+    RunCustomQueries,
+    RestoreBackups,
+    /// 자동 백업 정책 (주기, 보관, 보조 경로, 암호화) 변경
+    ManageBackupPolicy,
+    /// DB 전체 사본 내보내기
+    ExportDatabase,
+    /// 시연용 데모 데이터 적재
+    LoadDemoData,
+    ManageApiKeys,
+    /// 보존 정책 변경 / 즉시 실행 (데이터 삭제·이동)
+    ManageDataRetention,
+    ManageUsers,
+}
+
+impl Permission {
+    pub fn min_role(&self) -> Role {
+        match self {
+            Permission::DecideApprovals | Permission::CloseCorrectiveActions => Role::Qa,
+            Permission::DeleteWorkflows | Permission::RunCustomQueries => Role::Supervisor,
+            Permission::RestoreBackups
+            | Permission::ManageBackupPolicy
+            | Permission::ExportDatabase
+            | Permission::LoadDemoData
+            | Permission::ManageApiKeys
+            | Permission::ManageDataRetention
+            | Permission::ManageUsers => Role::Admin,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Permission::DecideApprovals => "승인/거부",
+            Permission::CloseCorrectiveActions => "시정조치 종결",
+            Permission::DeleteWorkflows => "워크플로우 삭제",
+            Permission::RunCustomQueries => "사용자 정의 쿼리 실행",
+            Permission::RestoreBackups => "백업 복구",
+            Permission::ManageBackupPolicy => "백업 정책 관리",
+            Permission::ExportDatabase => "DB 내보내기",
+            Permission::LoadDemoData => "데모 데이터 적재",
+            Permission::ManageApiKeys => "API 키 관리",
+            Permission::ManageDataRetention => "데이터 보존 정책 관리",
+            Permission::ManageUsers => "사용자 관리",
+        }
+    }
+}
+
+/// 사용자 계정 (비밀번호 해시 제외)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserAccount {
+    pub id: String,
+    pub username: String,
+    pub display_name: String,
+    pub role: Role,
+    pub is_active: bool,
+    pub last_login_at: Option<String>,
+    pub created_at: String,
+}
+
+/// 사용자 생성 정보
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewUser {
+    pub username: String,
+    pub display_name: String,
+    pub role: Role,
+    pub password: String,
+}
+
+/// 사용자 수정 정보 (None은 변경 없음)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserUpdate {
+    pub display_name: Option<String>,
+    pub role: Option<Role>,
+    pub is_active: Option<bool>,
+}
+
+pub struct UserStore {
+    db: Database,
+}
+
+impl UserStore {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    pub fn has_users(&self) -> anyhow::Result<bool> {
+        let db_conn = self.db.get_reader();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let exists = conn.prepare("SELECT 1 FROM users LIMIT 1")?.exists([])?;
+        Ok(exists)
+    }
+
+    /// 첫 관리자 생성 (사용자가 한 명도 없을 때만 가능)
+    pub fn create_initial_admin(&self, username: &str, display_name: &str, password: &str) -> anyhow::Result<UserAccount> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let tx = conn.unchecked_transaction()?;
+        if tx.prepare("SELECT 1 FROM users LIMIT 1")?.exists([])? {
+            return Err(anyhow::anyhow!("이미 사용자가 있습니다. 관리자 계정으로 로그인해 사용자를 추가하세요"));
+        }
+        let user = insert_user(&tx, username, &NewUser {
+            username: username.to_string(),
+            display_name: display_name.to_string(),
+            role: Role::Admin,
+            password: password.to_string(),
+        })?;
+        tx.commit()?;
+        Ok(user)
+    }
+
+    pub fn create_user(&self, actor: &str, user: &NewUser) -> anyhow::Result<UserAccount> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let tx = conn.unchecked_transaction()?;
+        let account = insert_user(&tx, actor, user)?;
+        tx.commit()?;
+        Ok(account)
+    }
+
+    /// 사용자 정보/역할/활성 여부 변경 (마지막 활성 관리자는 강등/비활성화 불가)
+    pub fn update_user(&self, actor: &str, id: &str, update: &UserUpdate) -> anyhow::Result<UserAccount> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let tx = conn.unchecked_transaction()?;
+        let before = load_user(&tx, id)?.ok_or_else(|| anyhow::anyhow!("사용자를 찾을 수 없습니다: {}", id))?;
+
+        let mut after = before.clone();
+        if let Some(display_name) = update.display_name.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+            after.display_name = display_name.to_string();
+        }
+        if let Some(role) = update.role {
+            after.role = role;
+        }
+        if let Some(is_active) = update.is_active {
+            after.is_active = is_active;
+        }
+
+        let demotes_admin = before.role == Role::Admin && before.is_active && (after.role != Role::Admin || !after.is_active);
+        if demotes_admin && active_admin_count(&tx)? <= 1 {
+            return Err(anyhow::anyhow!("마지막 관리자 계정은 역할을 바꾸거나 비활성화할 수 없습니다"));
+        }
+
+        tx.execute(
+            "UPDATE users SET display_name = ?1, role = ?2, is_active = ?3, updated_at = ?4 WHERE id = ?5",
+            rusqlite::params![after.display_name, after.role.as_str(), after.is_active, Utc::now().to_rfc3339(), id],
+        )?;
+        audit_log::append(&tx, &NewAuditEntry {
+            actor,
+            action: "user.update",
+            entity_type: "user",
+            entity_id: id,
+            before: Some(serde_json::to_value(&before)?),
+            after: Some(serde_json::to_value(&after)?),
+        })?;
+        tx.commit()?;
+        Ok(after)
+    }
+
+    /// 비밀번호 변경 (관리자 초기화 포함, 잠금도 해제)
+    pub fn set_password(&self, actor: &str, id: &str, password: &str) -> anyhow::Result<()> {
+        let password_hash = hash_password(password)?;
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let tx = conn.unchecked_transaction()?;
+        let updated = tx.execute(
+            "UPDATE users SET password_hash = ?1, failed_attempts = 0, locked_until = NULL, updated_at = ?2 WHERE id = ?3",
+            rusqlite::params![password_hash, Utc::now().to_rfc3339(), id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("사용자를 찾을 수 없습니다: {}", id));
+        }
+        audit_log::append(&tx, &NewAuditEntry {
+            actor,
+            action: "user.password_reset",
+            entity_type: "user",
+            entity_id: id,
+            before: None,
+            after: None,
+        })?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn list_users(&self) -> anyhow::Result<Vec<UserAccount>> {
+        let db_conn = self.db.get_reader();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let mut stmt = conn.prepare(&format!("{} ORDER BY username", SELECT_USERS))?;
+        let users = stmt
+            .query_map([], user_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(users)
+    }
+
+    /// 아이디/비밀번호 확인 (실패 횟수 기록, 잠금/비활성 계정 거부)
+    pub fn authenticate(&self, username: &str, password: &str) -> anyhow::Result<UserAccount> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let row: Option<(String, String, i64, Option<String>)> = conn
+            .query_row(
+                "SELECT id, password_hash, failed_attempts, locked_until FROM users WHERE username = ?1 AND is_active = 1",
+                [username.trim()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()?;
+        let invalid = || anyhow::anyhow!("아이디 또는 비밀번호가 올바르지 않습니다");
+        let (id, password_hash, failed_attempts, locked_until) = row.ok_or_else(invalid)?;
+
+        let now = Utc::now();
+        let locked = locked_until
+            .as_deref()
+            .and_then(|until| DateTime::parse_from_rfc3339(until).ok())
+            .filter(|until| *until > now);
+        if let Some(until) = locked {
+            return Err(anyhow::anyhow!(
+                "로그인 실패가 반복되어 계정이 잠겼습니다 ({} 이후 다시 시도)",
+                until.with_timezone(&Utc).format("%H:%M")
+            ));
+        }
+
+        if !verify_password(password, &password_hash) {
+            let attempts = failed_attempts + 1;
+            let locked_until = (attempts >= MAX_FAILED_ATTEMPTS)
+                .then(|| (now + Duration::minutes(LOCKOUT_MINUTES)).to_rfc3339());
+            conn.execute(
+                "UPDATE users SET failed_attempts = ?1, locked_until = ?2 WHERE id = ?3",
+                rusqlite::params![if locked_until.is_some() { 0 } else { attempts }, locked_until, id],
+            )?;
+            return Err(invalid());
+        }
+
+        conn.execute(
+            "UPDATE users SET failed_attempts = 0, locked_until = NULL, last_login_at = ?1 WHERE id = ?2",
+            rusqlite::params![now.to_rfc3339(), id],
+        )?;
+        load_user(&conn, &id)?.ok_or_else(invalid)
+    }
+}
+
+const SELECT_USERS: &str =
+    "SELECT id, username, display_name, role, is_active, last_login_at, created_at FROM users";
+
+fn user_from_row(row: &rusqlite::Row) -> rusqlite::Result<UserAccount> {
+    let role: String = row.get(3)?;
+    Ok(UserAccount {
+        id: row.get(0)?,
+        username: row.get(1)?,
+        display_name: row.get(2)?,
+        role: Role::parse(&role).unwrap_or(Role::Operator),
+        is_active: row.get(4)?,
+        last_login_at: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+fn load_user(conn: &Connection, id: &str) -> anyhow::Result<Option<UserAccount>> {
+    Ok(conn
+        .query_row(&format!("{} WHERE id = ?1", SELECT_USERS), [id], user_from_row)
+        .optional()?)
+}
+
+fn active_admin_count(conn: &Connection) -> anyhow::Result<i64> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) FROM users WHERE role = 'admin' AND is_active = 1",
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+fn insert_user(conn: &Connection, actor: &str, user: &NewUser) -> anyhow::Result<UserAccount> {
+    let username = user.username.trim();
+    let valid = (3..=32).contains(&username.len())
+        && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_');
+    if !valid {
+        return Err(anyhow::anyhow!("아이디는 영문/숫자/./-/_ 3~32자여야 합니다: {:?}", username));
+    }
+    let exists = conn.prepare("SELECT 1 FROM users WHERE username = ?1")?.exists([username])?;
+    if exists {
+        return Err(anyhow::anyhow!("이미 사용 중인 아이디입니다: {}", username));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let display_name = match user.display_name.trim() {
+        "" => username,
+        name => name,
+    };
+    let account = UserAccount {
+        id: uuid::Uuid::new_v4().to_string(),
+        username: username.to_string(),
+        display_name: display_name.to_string(),
+        role: user.role,
+        is_active: true,
+        last_login_at: None,
+        created_at: now.clone(),
+    };
+    conn.execute(
+        "INSERT INTO users (id, username, display_name, role, password_hash, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+        rusqlite::params![
+            account.id,
+            account.username,
+            account.display_name,
+            account.role.as_str(),
+            hash_password(&user.password)?,
+            now,
+        ],
+    )?;
+    audit_log::append(conn, &NewAuditEntry {
+        actor,
+        action: "user.create",
+        entity_type: "user",
+        entity_id: &account.id,
+        before: None,
+        after: Some(serde_json::to_value(&account)?),
+    })?;
+
+    println!("👤 [AUTH] 사용자 생성: {} ({})", account.username, account.role.as_str());
+    Ok(account)
+}
+
+fn hash_password(password: &str) -> anyhow::Result<String> {
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(anyhow::anyhow!("비밀번호는 {}자 이상이어야 합니다", MIN_PASSWORD_LEN));
+    }
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())
+        .map_err(|e| anyhow::anyhow!("salt 생성 실패: {}", e))?;
+    Ok(Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|e| anyhow::anyhow!("비밀번호 해시 실패: {}", e))?
+        .to_string())
+}
+
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+        .unwrap_or(false)
+}
+
+/// 로그인 세션
+#[derive(Debug, Clone)]
+struct Session {
+    user: UserAccount,
+    last_active_at: DateTime<Utc>,
+}
+
+/// 로그인 세션 관리 (앱 전역 1개는 `session()`)
+#[derive(Debug, Default)]
+pub struct SessionManager {
+    current: Mutex<Option<Session>>,
+}
+
+impl SessionManager {
+    pub fn login(&self, user: UserAccount) {
+        println!("🔐 [AUTH] 로그인: {} ({})", user.username, user.role.as_str());
+        *self.lock() = Some(Session { user, last_active_at: Utc::now() });
+    }
+
+    pub fn logout(&self) {
+        if let Some(session) = self.lock().take() {
+            println!("🔐 [AUTH] 로그아웃: {}", session.user.username);
+        }
+    }
+
+    /// 현재 로그인 사용자 (유휴 시간이 지났으면 세션 종료)
+    pub fn current_user(&self) -> Option<UserAccount> {
+        self.current_user_at(Utc::now())
+    }
+
+    fn current_user_at(&self, now: DateTime<Utc>) -> Option<UserAccount> {
+        let mut current = self.lock();
+        match current.as_mut() {
+            Some(session) if now - session.last_active_at <= Duration::minutes(SESSION_IDLE_TIMEOUT_MINUTES) => {
+                session.last_active_at = now;
+                Some(session.user.clone())
+            }
+            Some(_) => {
+                *current = None;
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 권한 확인 (로그인 + 역할), 통과하면 사용자 반환
+    pub fn require(&self, permission: Permission) -> anyhow::Result<UserAccount> {
+        let user = self
+            .current_user()
+            .ok_or_else(|| anyhow::anyhow!("로그인이 필요합니다 (세션이 없거나 만료됨)"))?;
+        if !user.role.allows(permission) {
+            return Err(anyhow::anyhow!(
+                "권한이 없습니다: {} ({} 이상 필요, 현재 {})",
+                permission.label(),
+                permission.min_role().label(),
+                user.role.label()
+            ));
+        }
+        Ok(user)
+    }
+
+    /// 세션 사용자 정보 갱신 (본인 계정 수정 시)
+    pub fn refresh(&self, user: &UserAccount) {
+        let mut current = self.lock();
+        if let Some(session) = current.as_mut().filter(|s| s.user.id == user.id) {
+            if user.is_active {
+                session.user = user.clone();
+            } else {
+                *current = None;
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Session>> {
+        self.current.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+static SESSION: Lazy<SessionManager> = Lazy::new(SessionManager::default);
+
+/// 앱 전역 로그인 세션
+pub fn session() -> &'static SessionManager {
+    &SESSION
+}
+
+/// 현재 세션에서 권한 확인
+pub fn require(permission: Permission) -> anyhow::Result<UserAccount> {
+    session().require(permission)
+}
+
+/// 첫 관리자 생성 전(사용자 0명)이면 통과, 이후에는 현재 세션에서 권한 확인
+///
+/// 새로 설치한 앱에서 계정을 만들기 전에도 Claude API 키를 저장할 수 있어야 하므로
+/// API 키 관리에만 사용한다.
+pub fn require_after_setup(users: &UserStore, permission: Permission) -> anyhow::Result<Option<UserAccount>> {
+    if !users.has_users()? {
+        return Ok(None);
+    }
+    require(permission).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn store(temp_dir: &TempDir) -> UserStore {
+        UserStore::from_database(Database::open(temp_dir.path().join("auth.db")).unwrap())
+    }
+
+    #[test]
+    fn test_accounts_and_lockout() -> anyhow::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let store = store(&temp_dir);
+        assert!(!store.has_users()?);
+        // 첫 관리자 생성 전에는 로그인 없이 API 키 설정 가능
+        assert!(require_after_setup(&store, Permission::ManageApiKeys)?.is_none());
+
+        let admin = store.create_initial_admin("admin", "관리자", "admin-pass-1")?;
+        assert_eq!(admin.role, Role::Admin);
+        assert!(store.create_initial_admin("admin2", "", "admin-pass-2").is_err());
+
+        let qa = store.create_user("admin", &NewUser {
+            username: "qa.kim".to_string(),
+            display_name: "김품질".to_string(),
+            role: Role::Qa,
+            password: "qa-pass-123".to_string(),
+        })?;
+        assert!(store.create_user("admin", &NewUser { password: "short".to_string(), username: "op1".to_string(), display_name: String::new(), role: Role::Operator }).is_err());
+
+        assert_eq!(store.authenticate("QA.KIM", "qa-pass-123")?.id, qa.id);
+        for _ in 0..MAX_FAILED_ATTEMPTS {
+            assert!(store.authenticate("qa.kim", "wrong-pass").is_err());
+        }
+        let err = store.authenticate("qa.kim", "qa-pass-123").unwrap_err().to_string();
+        assert!(err.contains("잠겼습니다"), "{}", err);
+        store.set_password("admin", &qa.id, "qa-pass-456")?;
+        assert!(store.authenticate("qa.kim", "qa-pass-456").is_ok());
+
+        // 마지막 관리자는 강등 불가, 비활성 계정은 로그인 불가
+        assert!(store.update_user("admin", &admin.id, &UserUpdate { role: Some(Role::Qa), ..Default::default() }).is_err());
+        store.update_user("admin", &qa.id, &UserUpdate { is_active: Some(false), ..Default::default() })?;
+        assert!(store.authenticate("qa.kim", "qa-pass-456").is_err());
+
+        // 계정 변경은 감사 로그에 남음
+        let db_conn = store.db.get_reader();
+        let conn = db_conn.lock().unwrap();
+        let actions: Vec<String> = conn
+            .prepare("SELECT action FROM audit_log ORDER BY seq")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        assert_eq!(actions, vec!["user.create", "user.create", "user.password_reset", "user.update"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_session_permissions_and_timeout() {
+        let sessions = SessionManager::default();
+        assert!(sessions.require(Permission::DecideApprovals).is_err());
+
+        let user = UserAccount {
+            id: "u-1".to_string(),
+            username: "qa.kim".to_string(),
+            display_name: "김품질".to_string(),
+            role: Role::Qa,
+            is_active: true,
+            last_login_at: None,
+            created_at: Utc::now().to_rfc3339(),
+        };
+        sessions.login(user.clone());
+        assert_eq!(sessions.require(Permission::DecideApprovals).unwrap().id, "u-1");
+        let err = sessions.require(Permission::DeleteWorkflows).unwrap_err().to_string();
+        assert!(err.contains("감독자"), "{}", err);
+        assert!(sessions.require(Permission::CloseCorrectiveActions).is_ok());
+        assert!(sessions.require(Permission::ExportDatabase).is_err());
+
+        let later = Utc::now() + Duration::minutes(SESSION_IDLE_TIMEOUT_MINUTES + 1);
+        assert!(sessions.current_user_at(later).is_none());
+        assert!(sessions.current_user().is_none());
+    }
+}
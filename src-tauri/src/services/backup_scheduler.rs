@@ -0,0 +1,108 @@
+// services/backup_scheduler.rs - 자동 백업 스케줄러
+//
+// 앱 시작 시 백그라운드 작업으로 실행된다.
+// 1. 이전 실행에서 남은 백업 실패(마이그레이션 전 백업 등) 알림
+// 2. 앱 시작 백업 (정책 on_app_start)
+// 3. CHECK_INTERVAL마다 마지막 백업 이후 주기(일/주)가 지났으면 스케줄 백업
+// 백업 실패 또는 보조 위치 복사 실패 시 `backup-alert` 이벤트(BackupAlert)를 발생시킨다.
+
+use crate::database::backup_policy::{self, BackupPolicy, BackupStatus};
+use crate::database::{BackupManager, BackupTrigger, Database};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 스케줄 점검 주기
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// 백업 실패 알림 (backup-alert 이벤트 payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupAlert {
+    pub trigger: Option<BackupTrigger>,
+    pub message: String,
+    pub occurred_at: String,
+}
+
+/// 스케줄러 시작 (Tauri setup에서 1회 호출)
+pub fn start(app_handle: AppHandle) {
+    let db_path = match Database::get_db_path() {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("⚠️  [BACKUP] DB 경로 확인 실패, 자동 백업 비활성화: {}", e);
+            return;
+        }
+    };
+
+    tauri::async_runtime::spawn(async move {
+        alert_previous_failure(&app_handle, &db_path);
+        run(&app_handle, &db_path, BackupTrigger::AppStart).await;
+
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            match scheduled_backup_due(&db_path) {
+                Ok(true) => run(&app_handle, &db_path, BackupTrigger::Scheduled).await,
+                Ok(false) => {}
+                Err(e) => eprintln!("⚠️  [BACKUP] 스케줄 점검 실패: {}", e),
+            }
+        }
+    });
+    println!("⏰ [BACKUP] 자동 백업 스케줄러 시작");
+}
+
+/// 정책 주기 기준 스케줄 백업 시점 여부
+fn scheduled_backup_due(db_path: &Path) -> anyhow::Result<bool> {
+    let manager = BackupManager::new(db_path.to_path_buf())?;
+    let policy = BackupPolicy::load(manager.backup_dir())?;
+    let last_backup_at = backup_policy::last_backup_at(&manager)?;
+    Ok(backup_policy::is_due(policy.frequency, last_backup_at, Utc::now().naive_utc()))
+}
+
+async fn run(app_handle: &AppHandle, db_path: &Path, trigger: BackupTrigger) {
+    let path: PathBuf = db_path.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || backup_policy::run_backup_if_enabled(&path, trigger)).await;
+
+    match result {
+        Ok(Ok(Some(report))) => {
+            println!(
+                "💾 [BACKUP] {} 백업 완료: {} (보존 정책 삭제 {}개)",
+                trigger.as_str(),
+                report.backup_path.display(),
+                report.pruned
+            );
+            if let Some(error) = report.secondary_error {
+                emit_alert(app_handle, Some(trigger), format!("보조 백업 위치 복사 실패: {}", error));
+            }
+        }
+        Ok(Ok(None)) => {}
+        Ok(Err(e)) => emit_alert(app_handle, Some(trigger), format!("자동 백업 실패: {}", e)),
+        Err(e) => emit_alert(app_handle, Some(trigger), format!("백업 작업 실패: {}", e)),
+    }
+}
+
+fn alert_previous_failure(app_handle: &AppHandle, db_path: &Path) {
+    let status = BackupManager::new(db_path.to_path_buf())
+        .and_then(|manager| BackupStatus::load(manager.backup_dir()));
+    if let Ok(status) = status {
+        if status.needs_alert() {
+            let message = status
+                .last_error
+                .or(status.secondary_error)
+                .unwrap_or_else(|| "이전 백업 실패".to_string());
+            emit_alert(app_handle, None, message);
+        }
+    }
+}
+
+fn emit_alert(app_handle: &AppHandle, trigger: Option<BackupTrigger>, message: String) {
+    eprintln!("🚨 [BACKUP] {}", message);
+    let alert = BackupAlert {
+        trigger,
+        message,
+        occurred_at: Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = app_handle.emit_all("backup-alert", alert) {
+        eprintln!("⚠️  백업 알림 이벤트 전송 실패: {}", e);
+    }
+}
@@ -0,0 +1,519 @@
+// services/ccp_check_evaluator.rs - MES ccp_check_log 기반 CCP 한계기준 판정
+//
+// 살균(PASTEURIZATION), 금속검출(METAL_DETECTION), 냉각(COOLING) 점검 기록을
+// CCP 유형별 한계기준으로 다시 판정하고 배치 LOT 단위로 집계한다.
+
+use crate::database::{CcpCheckEvaluation, CcpLotJudgment, CcpStats};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const RESULT_PASS: &str = "PASS";
+pub const RESULT_FAIL: &str = "FAIL";
+pub const RESULT_DEVIATION: &str = "DEVIATION";
+
+/// MES CCP 유형 (ccp_check_log.ccp_type)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CcpType {
+    Pasteurization,
+    MetalDetection,
+    Cooling,
+}
+
+impl CcpType {
+    /// ccp_type 문자열 파싱 (MES CCP가 아닌 데모 CCP 코드는 None)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "PASTEURIZATION" => Some(CcpType::Pasteurization),
+            "METAL_DETECTION" => Some(CcpType::MetalDetection),
+            "COOLING" => Some(CcpType::Cooling),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CcpType::Pasteurization => "PASTEURIZATION",
+            CcpType::MetalDetection => "METAL_DETECTION",
+            CcpType::Cooling => "COOLING",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CcpType::Pasteurization => "살균",
+            CcpType::MetalDetection => "금속검출",
+            CcpType::Cooling => "냉각",
+        }
+    }
+}
+
+/// CCP 유형별 한계기준
+///
+/// 적용 우선순위: 정책에 설정된 한계 → 점검 기록의 목표값(target_*) → 유형별 기본값
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CriticalLimits {
+    /// 살균 최저 온도 (°C)
+    pub min_temp: Option<f64>,
+    /// 냉각 최고 온도 (°C)
+    pub max_temp: Option<f64>,
+    /// 살균 최소 유지 시간 (초)
+    pub min_time_sec: Option<i64>,
+    /// 냉각 최대 소요 시간 (초)
+    pub max_time_sec: Option<i64>,
+    /// 금속검출 Fe 테스트피스 최대 크기 (mm)
+    pub max_sensitivity_fe: Option<f64>,
+    /// 금속검출 SUS 테스트피스 최대 크기 (mm)
+    pub max_sensitivity_sus: Option<f64>,
+}
+
+impl CriticalLimits {
+    /// HACCP 일반 기준 기본값
+    pub fn defaults_for(ccp_type: CcpType) -> Self {
+        match ccp_type {
+            CcpType::Pasteurization => CriticalLimits {
+                min_temp: Some(85.0),
+                min_time_sec: Some(15),
+                ..Default::default()
+            },
+            CcpType::MetalDetection => CriticalLimits {
+                max_sensitivity_fe: Some(1.5),
+                max_sensitivity_sus: Some(2.0),
+                ..Default::default()
+            },
+            CcpType::Cooling => CriticalLimits {
+                max_temp: Some(10.0),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 설정된 한계기준이 하나라도 있는지
+    pub fn is_empty(&self) -> bool {
+        *self == CriticalLimits::default()
+    }
+}
+
+/// ccp_check_log 한 행
+#[derive(Debug, Clone, Default)]
+pub struct CcpCheckRecord {
+    pub id: i64,
+    pub batch_lot_no: String,
+    pub check_time: String,
+    pub equip_cd: String,
+    pub target_temp: Option<f64>,
+    pub actual_temp: Option<f64>,
+    pub target_time_sec: Option<i64>,
+    pub actual_time_sec: Option<i64>,
+    pub sensitivity_fe: Option<f64>,
+    pub sensitivity_sus: Option<f64>,
+    pub test_piece_detected: Option<i64>,
+    pub reject_confirmed: Option<i64>,
+    pub cool_time_sec: Option<i64>,
+    pub result: String,
+}
+
+impl CcpCheckRecord {
+    /// 대표 측정값 (통계용): 살균/냉각은 실측 온도, 금속검출은 Fe 감도
+    fn primary_value(&self, ccp_type: CcpType) -> Option<f64> {
+        match ccp_type {
+            CcpType::MetalDetection => self.sensitivity_fe,
+            _ => self.actual_temp,
+        }
+    }
+}
+
+/// 기간 내 점검 기록 조회 (check_time 오름차순)
+///
+/// 냉각 CCP는 target/actual_cool_temp가 비어 있고 target/actual_temp에 기록된 경우가 있어 둘 다 읽는다.
+pub fn load_checks(
+    conn: &Connection,
+    ccp_type: CcpType,
+    from: &str,
+    to: &str,
+    batch_lot_no: Option<&str>,
+) -> anyhow::Result<Vec<CcpCheckRecord>> {
+    let mut stmt = conn.prepare(
+        r#"
+            SELECT
+                id, batch_lot_no, check_time, equip_cd,
+                COALESCE(target_cool_temp, target_temp), COALESCE(actual_cool_temp, actual_temp),
+                target_time_sec, actual_time_sec,
+                sensitivity_fe, sensitivity_sus, test_piece_detected, reject_confirmed,
+                cool_time_sec, result
+            FROM ccp_check_log
+            WHERE ccp_type = ?1
+              AND check_time BETWEEN ?2 AND ?3
+              AND (?4 IS NULL OR batch_lot_no = ?4)
+            ORDER BY check_time, id
+        "#,
+    )?;
+
+    let checks = stmt
+        .query_map(rusqlite::params![ccp_type.as_str(), from, to, batch_lot_no], |row| {
+            Ok(CcpCheckRecord {
+                id: row.get(0)?,
+                batch_lot_no: row.get(1)?,
+                check_time: row.get(2)?,
+                equip_cd: row.get(3)?,
+                target_temp: row.get(4)?,
+                actual_temp: row.get(5)?,
+                target_time_sec: row.get(6)?,
+                actual_time_sec: row.get(7)?,
+                sensitivity_fe: row.get(8)?,
+                sensitivity_sus: row.get(9)?,
+                test_piece_detected: row.get(10)?,
+                reject_confirmed: row.get(11)?,
+                cool_time_sec: row.get(12)?,
+                result: row.get(13)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(checks)
+}
+
+/// 점검 1건 판정
+///
+/// - DEVIATION: 측정값이 한계기준을 벗어남 (살균 온도/시간 미달, 냉각 온도/시간 초과)
+/// - FAIL: 점검 자체가 불합격 (측정값 누락, 금속검출기 감도/테스트피스 검증 실패, 금속 이물 검출)
+pub fn evaluate_check(ccp_type: CcpType, check: &CcpCheckRecord, limits: &CriticalLimits) -> CcpCheckEvaluation {
+    let defaults = CriticalLimits::defaults_for(ccp_type);
+    let mut deviations: Vec<String> = Vec::new();
+    let mut failures: Vec<String> = Vec::new();
+    let mut temp_deviation = None;
+    let mut time_deviation_sec = None;
+
+    match ccp_type {
+        CcpType::Pasteurization => {
+            match (check.actual_temp, limits.min_temp.or(check.target_temp).or(defaults.min_temp)) {
+                (Some(actual), Some(min)) => {
+                    temp_deviation = Some(actual - min);
+                    if actual < min {
+                        deviations.push(format!("살균 온도 {:.1}°C < 기준 {:.1}°C", actual, min));
+                    }
+                }
+                (None, _) => failures.push("살균 온도 기록 누락".to_string()),
+                _ => {}
+            }
+            if let (Some(actual), Some(min)) = (check.actual_time_sec, limits.min_time_sec.or(check.target_time_sec).or(defaults.min_time_sec)) {
+                time_deviation_sec = Some(actual - min);
+                if actual < min {
+                    deviations.push(format!("살균 시간 {}초 < 기준 {}초", actual, min));
+                }
+            }
+        }
+        CcpType::Cooling => {
+            match (check.actual_temp, limits.max_temp.or(check.target_temp).or(defaults.max_temp)) {
+                (Some(actual), Some(max)) => {
+                    temp_deviation = Some(actual - max);
+                    if actual > max {
+                        deviations.push(format!("냉각 온도 {:.1}°C > 기준 {:.1}°C", actual, max));
+                    }
+                }
+                (None, _) => failures.push("냉각 온도 기록 누락".to_string()),
+                _ => {}
+            }
+            if let (Some(actual), Some(max)) = (check.cool_time_sec, limits.max_time_sec.or(defaults.max_time_sec)) {
+                time_deviation_sec = Some(actual - max);
+                if actual > max {
+                    deviations.push(format!("냉각 시간 {}초 > 기준 {}초", actual, max));
+                }
+            }
+        }
+        CcpType::MetalDetection => {
+            if check.test_piece_detected != Some(1) {
+                failures.push("테스트피스 미검출 (검출기 검증 실패)".to_string());
+            }
+            for (name, actual, max) in [
+                ("Fe", check.sensitivity_fe, limits.max_sensitivity_fe.or(defaults.max_sensitivity_fe)),
+                ("SUS", check.sensitivity_sus, limits.max_sensitivity_sus.or(defaults.max_sensitivity_sus)),
+            ] {
+                if let (Some(actual), Some(max)) = (actual, max) {
+                    if actual > max {
+                        failures.push(format!("{} 감도 {:.1}mm > 기준 {:.1}mm", name, actual, max));
+                    }
+                }
+            }
+            if let Some(rejects) = check.reject_confirmed.filter(|r| *r > 0) {
+                failures.push(format!("금속 이물 검출 {}건 (배출 확인)", rejects));
+            }
+        }
+    }
+
+    let judged_result = if !failures.is_empty() {
+        RESULT_FAIL
+    } else if !deviations.is_empty() {
+        RESULT_DEVIATION
+    } else {
+        RESULT_PASS
+    };
+
+    let mut reasons: Vec<String> = failures.into_iter().chain(deviations).collect();
+    if check.result != judged_result {
+        reasons.push(format!("MES 기록 결과 {}와 판정 {} 불일치", check.result, judged_result));
+    }
+
+    CcpCheckEvaluation {
+        check_id: check.id,
+        batch_lot_no: check.batch_lot_no.clone(),
+        ccp_type: ccp_type.as_str().to_string(),
+        check_time: check.check_time.clone(),
+        equip_cd: check.equip_cd.clone(),
+        recorded_result: check.result.clone(),
+        judged_result: judged_result.to_string(),
+        temp_deviation,
+        time_deviation_sec,
+        reasons,
+    }
+}
+
+/// 배치 LOT 단위 집계 (LOT 결과 = 가장 심각한 점검 결과, FAIL > DEVIATION > PASS)
+pub fn judge_lots(evaluations: &[CcpCheckEvaluation]) -> Vec<CcpLotJudgment> {
+    let mut lots: BTreeMap<&str, CcpLotJudgment> = BTreeMap::new();
+
+    for evaluation in evaluations {
+        let lot = lots
+            .entry(evaluation.batch_lot_no.as_str())
+            .or_insert_with(|| CcpLotJudgment {
+                batch_lot_no: evaluation.batch_lot_no.clone(),
+                ccp_type: evaluation.ccp_type.clone(),
+                check_count: 0,
+                pass_count: 0,
+                deviation_count: 0,
+                fail_count: 0,
+                worst_temp_deviation: None,
+                worst_time_deviation_sec: None,
+                result: RESULT_PASS.to_string(),
+                first_check_time: evaluation.check_time.clone(),
+                last_check_time: evaluation.check_time.clone(),
+            });
+
+        lot.check_count += 1;
+        match evaluation.judged_result.as_str() {
+            RESULT_FAIL => lot.fail_count += 1,
+            RESULT_DEVIATION => lot.deviation_count += 1,
+            _ => lot.pass_count += 1,
+        }
+        if severity(&evaluation.judged_result) > severity(&lot.result) {
+            lot.result = evaluation.judged_result.clone();
+        }
+
+        // 한계기준 쪽으로 가장 불리한 편차 (살균은 최소값, 냉각은 최대값)
+        let lower_is_worse = evaluation.ccp_type == CcpType::Pasteurization.as_str();
+        lot.worst_temp_deviation = worst(lot.worst_temp_deviation, evaluation.temp_deviation, lower_is_worse);
+        lot.worst_time_deviation_sec = worst(lot.worst_time_deviation_sec, evaluation.time_deviation_sec, lower_is_worse);
+
+        if evaluation.check_time < lot.first_check_time {
+            lot.first_check_time = evaluation.check_time.clone();
+        }
+        if evaluation.check_time > lot.last_check_time {
+            lot.last_check_time = evaluation.check_time.clone();
+        }
+    }
+
+    lots.into_values().collect()
+}
+
+/// 판정 결과로 CCP 통계 계산 (ng_count = PASS가 아닌 점검 수)
+pub fn summarize(ccp_type: CcpType, checks: &[CcpCheckRecord], evaluations: &[CcpCheckEvaluation]) -> CcpStats {
+    let total_logs = evaluations.len() as i32;
+    let ng_count = evaluations.iter().filter(|e| e.judged_result != RESULT_PASS).count() as i32;
+    let values: Vec<f64> = checks.iter().filter_map(|c| c.primary_value(ccp_type)).collect();
+
+    let (avg_value, min_value, max_value) = if values.is_empty() {
+        (0.0, 0.0, 0.0)
+    } else {
+        (
+            values.iter().sum::<f64>() / values.len() as f64,
+            values.iter().cloned().fold(f64::INFINITY, f64::min),
+            values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+        )
+    };
+
+    CcpStats {
+        total_logs,
+        ng_count,
+        ng_rate: if total_logs > 0 { ng_count as f64 / total_logs as f64 } else { 0.0 },
+        avg_value,
+        min_value,
+        max_value,
+    }
+}
+
+fn severity(result: &str) -> u8 {
+    match result {
+        RESULT_FAIL => 2,
+        RESULT_DEVIATION => 1,
+        _ => 0,
+    }
+}
+
+fn worst<T: PartialOrd + Copy>(current: Option<T>, value: Option<T>, lower_is_worse: bool) -> Option<T> {
+    match (current, value) {
+        (Some(c), Some(v)) => Some(if (v < c) == lower_is_worse { v } else { c }),
+        (c, v) => c.or(v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pasteurization(id: i64, lot: &str, temp: f64, time: i64, result: &str) -> CcpCheckRecord {
+        CcpCheckRecord {
+            id,
+            batch_lot_no: lot.to_string(),
+            check_time: format!("2024-11-20 09:{:02}:00", id),
+            equip_cd: "EQ-PAST-01".to_string(),
+            target_temp: Some(85.0),
+            actual_temp: Some(temp),
+            target_time_sec: Some(20),
+            actual_time_sec: Some(time),
+            result: result.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pasteurization_deviation() {
+        let limits = CriticalLimits::default();
+
+        let ok = evaluate_check(CcpType::Pasteurization, &pasteurization(1, "L1", 86.2, 22, "PASS"), &limits);
+        assert_eq!(ok.judged_result, RESULT_PASS);
+        assert!((ok.temp_deviation.unwrap() - 1.2).abs() < 1e-9);
+        assert!(ok.reasons.is_empty());
+
+        // 시드 데이터: 82.5°C / 25초 → 온도 미달 이탈
+        let low = evaluate_check(CcpType::Pasteurization, &pasteurization(2, "L2", 82.5, 25, "DEVIATION"), &limits);
+        assert_eq!(low.judged_result, RESULT_DEVIATION);
+        assert_eq!(low.time_deviation_sec, Some(5));
+        assert_eq!(low.reasons.len(), 1);
+
+        // MES에는 PASS로 기록됐지만 시간 미달
+        let short = evaluate_check(CcpType::Pasteurization, &pasteurization(3, "L3", 86.0, 18, "PASS"), &limits);
+        assert_eq!(short.judged_result, RESULT_DEVIATION);
+        assert!(short.reasons.iter().any(|r| r.contains("불일치")));
+    }
+
+    #[test]
+    fn test_cooling_limit_precedence() {
+        let limits = CriticalLimits::default();
+        let mut check = CcpCheckRecord {
+            target_temp: Some(25.0),
+            actual_temp: Some(24.8),
+            result: "PASS".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(evaluate_check(CcpType::Cooling, &check, &limits).judged_result, RESULT_PASS);
+
+        // 정책 한계가 설정되면 기록 목표값보다 우선
+        let configured = CriticalLimits {
+            max_temp: Some(20.0),
+            ..Default::default()
+        };
+        let evaluation = evaluate_check(CcpType::Cooling, &check, &configured);
+        assert_eq!(evaluation.judged_result, RESULT_DEVIATION);
+        assert!((evaluation.temp_deviation.unwrap() - 4.8).abs() < 1e-9);
+
+        // 목표값 없으면 기본 한계 10°C 적용
+        check.target_temp = None;
+        assert_eq!(evaluate_check(CcpType::Cooling, &check, &limits).judged_result, RESULT_DEVIATION);
+
+        check.actual_temp = None;
+        assert_eq!(evaluate_check(CcpType::Cooling, &check, &limits).judged_result, RESULT_FAIL);
+    }
+
+    #[test]
+    fn test_metal_detection_failures() {
+        let limits = CriticalLimits::default();
+        let mut check = CcpCheckRecord {
+            sensitivity_fe: Some(1.5),
+            sensitivity_sus: Some(2.0),
+            test_piece_detected: Some(1),
+            reject_confirmed: Some(0),
+            result: "PASS".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(evaluate_check(CcpType::MetalDetection, &check, &limits).judged_result, RESULT_PASS);
+
+        check.reject_confirmed = Some(3);
+        assert_eq!(evaluate_check(CcpType::MetalDetection, &check, &limits).judged_result, RESULT_FAIL);
+
+        check.reject_confirmed = Some(0);
+        check.sensitivity_sus = Some(2.5);
+        check.test_piece_detected = Some(0);
+        let evaluation = evaluate_check(CcpType::MetalDetection, &check, &limits);
+        assert_eq!(evaluation.judged_result, RESULT_FAIL);
+        assert_eq!(evaluation.reasons.len(), 3); // 테스트피스, SUS 감도, 기록 불일치
+    }
+
+    #[test]
+    fn test_judge_lots_takes_worst_result() {
+        let limits = CriticalLimits::default();
+        let checks = vec![
+            pasteurization(1, "L1", 86.0, 22, "PASS"),
+            pasteurization(2, "L1", 84.0, 22, "DEVIATION"),
+            pasteurization(3, "L1", 83.5, 21, "DEVIATION"),
+            pasteurization(4, "L2", 85.5, 20, "PASS"),
+        ];
+        let evaluations: Vec<_> = checks
+            .iter()
+            .map(|c| evaluate_check(CcpType::Pasteurization, c, &limits))
+            .collect();
+
+        let lots = judge_lots(&evaluations);
+
+        assert_eq!(lots.len(), 2);
+        assert_eq!(lots[0].batch_lot_no, "L1");
+        assert_eq!(lots[0].result, RESULT_DEVIATION);
+        assert_eq!((lots[0].pass_count, lots[0].deviation_count), (1, 2));
+        assert!((lots[0].worst_temp_deviation.unwrap() + 1.5).abs() < 1e-9);
+        assert_eq!(lots[0].last_check_time, "2024-11-20 09:03:00");
+        assert_eq!(lots[1].result, RESULT_PASS);
+
+        let stats = summarize(CcpType::Pasteurization, &checks, &evaluations);
+        assert_eq!((stats.total_logs, stats.ng_count), (4, 2));
+        assert!((stats.min_value - 83.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_load_checks_seed_deviation() {
+        // 시드 데이터 사용 (마이그레이션 007: 2024-11-20 살균 이탈)
+        let db = crate::database::Database::new().unwrap();
+        let db_conn = db.get_connection();
+        let conn = db_conn.lock().unwrap();
+
+        let checks = match load_checks(
+            &conn,
+            CcpType::Pasteurization,
+            "2024-11-20 00:00:00",
+            "2024-11-20 23:59:59",
+            Some("BATCH-241120-001"),
+        ) {
+            Ok(checks) if !checks.is_empty() => checks,
+            _ => {
+                println!("⚠️  Seed 데이터 없음 - 테스트 스킵");
+                return;
+            }
+        };
+
+        let limits = CriticalLimits::default();
+        let evaluations: Vec<_> = checks
+            .iter()
+            .map(|c| evaluate_check(CcpType::Pasteurization, c, &limits))
+            .collect();
+        let lots = judge_lots(&evaluations);
+
+        assert_eq!(lots.len(), 1);
+        assert_eq!(lots[0].result, RESULT_DEVIATION);
+    }
+
+    #[test]
+    fn test_ccp_type_parse() {
+        assert_eq!(CcpType::parse("pasteurization"), Some(CcpType::Pasteurization));
+        assert_eq!(CcpType::parse("METAL_DETECTION"), Some(CcpType::MetalDetection));
+        assert_eq!(CcpType::parse("CCP-01"), None);
+    }
+}
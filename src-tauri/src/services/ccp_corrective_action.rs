@@ -0,0 +1,731 @@
+// services/ccp_corrective_action.rs - HACCP 시정조치 워크플로우
+//
+// CCP 점검이 FAIL/DEVIATION으로 판정되면 시정조치를 자동 발행하고
+// (대상 LOT, 출하 보류 결정, 원인, 조치 내용) 검증은 APPROVAL 노드와 같은
+// approval_requests 승인 요청으로 받는다. 검증이 기록되기 전에는 종결할 수 없다.
+//
+// 상태: open → verification_pending → verified → closed
+//        (승인 거부/만료 시 verification_pending → open)
+
+use crate::database::{CcpCheckEvaluation, Database};
+use crate::services::ccp_check_evaluator::{self, CcpType, RESULT_FAIL, RESULT_PASS};
+use crate::services::ccp_doc_ingestion::normalize_timestamp;
+use crate::services::approval_request::{self, NewApprovalRequest};
+use crate::services::ccp_policy::CcpPolicyStore;
+use crate::services::lot_disposition;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// 시정조치 검증 승인 요청의 workflow_id (approval_requests)
+pub const CORRECTIVE_ACTION_WORKFLOW_ID: &str = "haccp-corrective-action";
+const CORRECTIVE_ACTION_WORKFLOW_NAME: &str = "HACCP 시정조치";
+const VERIFICATION_STEP_NAME: &str = "CCP 시정조치 검증";
+
+/// 검증 승인 기본 만료 시간 (7일)
+pub const DEFAULT_VERIFICATION_TIMEOUT_MINUTES: i64 = 7 * 24 * 60;
+
+/// 이탈 요약에 포함할 최대 점검 수
+const MAX_SUMMARY_LINES: usize = 20;
+
+pub const STATUS_OPEN: &str = "open";
+pub const STATUS_VERIFICATION_PENDING: &str = "verification_pending";
+pub const STATUS_VERIFIED: &str = "verified";
+pub const STATUS_CLOSED: &str = "closed";
+
+/// 출하 보류 결정 (PENDING = 미결정)
+pub const HOLD_DECISIONS: &[&str] = &["PENDING", "HOLD", "RELEASE", "REWORK", "DISPOSE"];
+
+/// HACCP 시정조치
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrectiveAction {
+    pub id: String,
+    pub company_id: String,
+    pub ccp_id: String,
+    pub trigger_source: String, // "judgment" | "check"
+    pub trigger_ref: String,    // 판단 ID 또는 점검 조회 범위
+    pub trigger_result: String, // "FAIL" | "DEVIATION"
+    pub affected_lots: Vec<String>,
+    pub check_ids: Vec<i64>,
+    pub deviation_summary: String,
+    pub hold_decision: String,
+    pub root_cause: Option<String>,
+    pub action_taken: Option<String>,
+    pub status: String,
+    pub approval_request_id: Option<String>,
+    pub verified_by: Option<String>,
+    pub verified_at: Option<String>,
+    pub verification_comment: Option<String>,
+    pub closed_by: Option<String>,
+    pub closed_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 시정조치 내용 수정 (None 필드는 유지)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorrectiveActionUpdate {
+    pub hold_decision: Option<String>,
+    pub root_cause: Option<String>,
+    pub action_taken: Option<String>,
+}
+
+/// 이탈 점검으로 발행할 시정조치 초안 (저장 전)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviationDraft {
+    pub trigger_result: String,
+    pub affected_lots: Vec<String>,
+    pub checks: Vec<(i64, String, String)>, // (check_id, batch_lot_no, judged_result)
+    pub summary: String,
+}
+
+/// PASS가 아닌 점검으로 시정조치 초안 생성 (이미 시정조치에 연결된 점검 제외, 대상이 없으면 None)
+pub fn draft_from_evaluations(
+    evaluations: &[CcpCheckEvaluation],
+    already_linked: &BTreeSet<i64>,
+) -> Option<DeviationDraft> {
+    let deviations: Vec<&CcpCheckEvaluation> = evaluations
+        .iter()
+        .filter(|e| e.judged_result != RESULT_PASS && !already_linked.contains(&e.check_id))
+        .collect();
+    if deviations.is_empty() {
+        return None;
+    }
+
+    let trigger_result = if deviations.iter().any(|e| e.judged_result == RESULT_FAIL) {
+        RESULT_FAIL
+    } else {
+        ccp_check_evaluator::RESULT_DEVIATION
+    };
+    let affected_lots: Vec<String> = deviations
+        .iter()
+        .map(|e| e.batch_lot_no.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut lines: Vec<String> = deviations
+        .iter()
+        .take(MAX_SUMMARY_LINES)
+        .map(|e| {
+            format!(
+                "[{}] LOT {} 점검 #{} ({}): {}",
+                e.judged_result,
+                e.batch_lot_no,
+                e.check_id,
+                e.check_time,
+                e.reasons.join(", ")
+            )
+        })
+        .collect();
+    if deviations.len() > MAX_SUMMARY_LINES {
+        lines.push(format!("외 {}건", deviations.len() - MAX_SUMMARY_LINES));
+    }
+
+    Some(DeviationDraft {
+        trigger_result: trigger_result.to_string(),
+        affected_lots,
+        checks: deviations
+            .iter()
+            .map(|e| (e.check_id, e.batch_lot_no.clone(), e.judged_result.clone()))
+            .collect(),
+        summary: lines.join("\n"),
+    })
+}
+
+/// 시정조치 저장소
+pub struct CorrectiveActionStore {
+    db: Database,
+}
+
+impl CorrectiveActionStore {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// CCP 이탈 점검에 대한 시정조치 발행 (새로 이탈한 점검이 없으면 None)
+    pub fn open_for_deviations(
+        &self,
+        company_id: &str,
+        ccp_id: &str,
+        trigger_source: &str,
+        trigger_ref: &str,
+        evaluations: &[CcpCheckEvaluation],
+    ) -> anyhow::Result<Option<CorrectiveAction>> {
+        let db_conn = self.db.get_connection();
+        let mut conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let linked = linked_check_ids(&conn, evaluations.iter().map(|e| e.check_id))?;
+        let Some(draft) = draft_from_evaluations(evaluations, &linked) else {
+            return Ok(None);
+        };
+
+        let id = format!("ca-{}", uuid::Uuid::new_v4());
+        let now = now_string();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO ccp_corrective_actions (
+                id, company_id, ccp_id, trigger_source, trigger_ref, trigger_result,
+                affected_lots, deviation_summary, status, created_at, updated_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10)",
+            rusqlite::params![
+                id,
+                company_id,
+                ccp_id,
+                trigger_source,
+                trigger_ref,
+                draft.trigger_result,
+                serde_json::to_string(&draft.affected_lots)?,
+                draft.summary,
+                STATUS_OPEN,
+                now,
+            ],
+        )?;
+        for (check_id, batch_lot_no, judged_result) in &draft.checks {
+            tx.execute(
+                "INSERT INTO ccp_corrective_action_checks (check_id, action_id, batch_lot_no, judged_result)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![check_id, id, batch_lot_no, judged_result],
+            )?;
+        }
+        tx.commit()?;
+
+        println!("🛠️  시정조치 발행: {} ({}, LOT {}개)", id, draft.trigger_result, draft.affected_lots.len());
+
+        require_action(&conn, &id).map(Some)
+    }
+
+    /// 센서 로그(ccp_sensors) 판단이 HIGH 위험도일 때 시정조치 발행
+    ///
+    /// 센서 로그에는 LOT/점검 ID가 없으므로 점검 연결 없이 판단 ID로 추적한다.
+    /// 같은 CCP에 종결되지 않은 센서 판단 시정조치가 있으면 새로 발행하지 않는다.
+    pub fn open_for_high_risk(
+        &self,
+        company_id: &str,
+        ccp_id: &str,
+        judgment_id: &str,
+        summary: &str,
+    ) -> anyhow::Result<Option<CorrectiveAction>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let already_open = conn
+            .prepare(
+                "SELECT 1 FROM ccp_corrective_actions a
+                 WHERE a.company_id = ?1 AND a.ccp_id = ?2 AND a.trigger_source = 'judgment' AND a.status != ?3
+                   AND NOT EXISTS (SELECT 1 FROM ccp_corrective_action_checks c WHERE c.action_id = a.id)",
+            )?
+            .exists(rusqlite::params![company_id, ccp_id, STATUS_CLOSED])?;
+        if already_open {
+            return Ok(None);
+        }
+
+        let id = format!("ca-{}", uuid::Uuid::new_v4());
+        conn.execute(
+            "INSERT INTO ccp_corrective_actions (
+                id, company_id, ccp_id, trigger_source, trigger_ref, trigger_result,
+                affected_lots, deviation_summary, status, created_at, updated_at
+             ) VALUES (?1, ?2, ?3, 'judgment', ?4, ?5, '[]', ?6, ?7, ?8, ?8)",
+            rusqlite::params![
+                id,
+                company_id,
+                ccp_id,
+                judgment_id,
+                ccp_check_evaluator::RESULT_DEVIATION,
+                summary,
+                STATUS_OPEN,
+                now_string(),
+            ],
+        )?;
+
+        println!("🛠️  시정조치 발행: {} (센서 로그 HIGH 위험도)", id);
+
+        require_action(&conn, &id).map(Some)
+    }
+
+    /// 기간 내 MES CCP 점검을 한계기준으로 재판정하고 이탈 점검에 대한 시정조치 발행
+    pub fn scan_checks(
+        &self,
+        company_id: &str,
+        ccp_type: &str,
+        from: &str,
+        to: &str,
+        batch_lot_no: Option<&str>,
+    ) -> anyhow::Result<Option<CorrectiveAction>> {
+        let parsed = CcpType::parse(ccp_type)
+            .ok_or_else(|| anyhow::anyhow!("지원하지 않는 CCP 유형: {}", ccp_type))?;
+        let from = normalize_timestamp(from, false)?;
+        let to = normalize_timestamp(to, true)?;
+
+        let policy = CcpPolicyStore::from_database(self.db.clone()).effective(company_id, parsed.as_str())?;
+        let checks = {
+            let db_conn = self.db.get_connection();
+            let conn = db_conn.lock()
+                .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+            ccp_check_evaluator::load_checks(&conn, parsed, &from, &to, batch_lot_no)?
+        };
+        let evaluations: Vec<CcpCheckEvaluation> = checks
+            .iter()
+            .map(|check| ccp_check_evaluator::evaluate_check(parsed, check, &policy.limits))
+            .collect();
+
+        let trigger_ref = format!("{} {} ~ {}", parsed.as_str(), from, to);
+        self.open_for_deviations(company_id, parsed.as_str(), "check", &trigger_ref, &evaluations)
+    }
+
+    pub fn get(&self, id: &str) -> anyhow::Result<Option<CorrectiveAction>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        load_action(&conn, id)
+    }
+
+    /// 회사별 시정조치 목록 (status 지정 시 해당 상태만, 최신순)
+    pub fn list(&self, company_id: &str, status: Option<&str>) -> anyhow::Result<Vec<CorrectiveAction>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let ids: Vec<String> = {
+            let mut stmt = conn.prepare(
+                "SELECT id FROM ccp_corrective_actions
+                 WHERE company_id = ?1 AND (?2 IS NULL OR status = ?2)
+                 ORDER BY created_at DESC, id",
+            )?;
+            let rows = stmt.query_map(rusqlite::params![company_id, status], |row| row.get(0))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut actions = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(action) = load_action(&conn, &id)? {
+                actions.push(action);
+            }
+        }
+        Ok(actions)
+    }
+
+    /// 보류 결정/원인/조치 내용 수정 (open 상태에서만 가능)
+    pub fn update(&self, id: &str, update: &CorrectiveActionUpdate) -> anyhow::Result<CorrectiveAction> {
+        if let Some(decision) = &update.hold_decision {
+            if !HOLD_DECISIONS.contains(&decision.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "지원하지 않는 보류 결정: {} (가능: {})",
+                    decision,
+                    HOLD_DECISIONS.join(", ")
+                ));
+            }
+        }
+
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let action = require_action(&conn, id)?;
+        if action.status != STATUS_OPEN {
+            return Err(anyhow::anyhow!(
+                "검증 요청 이후에는 시정조치를 수정할 수 없습니다 (현재 상태: {})",
+                action.status
+            ));
+        }
+
+        conn.execute(
+            "UPDATE ccp_corrective_actions SET
+                hold_decision = COALESCE(?2, hold_decision),
+                root_cause = COALESCE(?3, root_cause),
+                action_taken = COALESCE(?4, action_taken),
+                updated_at = ?5
+             WHERE id = ?1",
+            rusqlite::params![id, update.hold_decision, update.root_cause, update.action_taken, now_string()],
+        )?;
+
+        // 보류 결정 시 대상 LOT 출하 보류 (배치 LOT이 ERP에 없으면 건너뜀)
+        if update.hold_decision.as_deref() == Some("HOLD") {
+            let reason = format!("CCP 시정조치 보류 결정 ({})", action.trigger_result);
+            for lot_no in &action.affected_lots {
+                if let Err(e) = lot_disposition::place_hold(&conn, lot_no, None, &reason, "corrective_action", Some(id), "system") {
+                    println!("⚠️  LOT 보류 건너뜀: {} - {}", lot_no, e);
+                }
+            }
+        }
+
+        require_action(&conn, id)
+    }
+
+    /// 검증 승인 요청 (approval_requests에 manual 승인 요청 생성)
+    ///
+    /// 보류 결정, 원인, 조치 내용이 모두 기록되어 있어야 한다.
+    /// 승인/거부는 APPROVAL 노드와 같은 process_approval 커맨드로 처리된다.
+    pub fn request_verification(
+        &self,
+        id: &str,
+        approvers: &str,
+        timeout_minutes: Option<i64>,
+    ) -> anyhow::Result<CorrectiveAction> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let action = require_action(&conn, id)?;
+        if action.status != STATUS_OPEN {
+            return Err(anyhow::anyhow!("검증을 요청할 수 없는 상태입니다: {}", action.status));
+        }
+        let missing = missing_fields(&action);
+        if !missing.is_empty() {
+            return Err(anyhow::anyhow!("검증 요청 전에 다음 항목을 기록해야 합니다: {}", missing.join(", ")));
+        }
+
+        let request_id = approval_request::create_request(
+            &conn,
+            &NewApprovalRequest {
+                workflow_id: CORRECTIVE_ACTION_WORKFLOW_ID,
+                workflow_name: CORRECTIVE_ACTION_WORKFLOW_NAME,
+                step_id: id,
+                step_name: VERIFICATION_STEP_NAME,
+                approvers,
+                input_data: &serde_json::to_value(&action)?,
+                timeout_minutes: timeout_minutes.unwrap_or(DEFAULT_VERIFICATION_TIMEOUT_MINUTES),
+            },
+        )?;
+        conn.execute(
+            "UPDATE ccp_corrective_actions SET status = ?2, approval_request_id = ?3, updated_at = ?4 WHERE id = ?1",
+            rusqlite::params![id, STATUS_VERIFICATION_PENDING, request_id, now_string()],
+        )?;
+
+        require_action(&conn, id)
+    }
+
+    /// 승인 요청 결과를 시정조치에 반영
+    ///
+    /// - approved: 검증자/검증 시각 기록, 대상 ccp_check_log 점검에 조치/검증 정보 기록
+    /// - rejected/expired: open 상태로 되돌림 (내용 보완 후 재요청)
+    /// - pending: 변화 없음
+    pub fn sync_verification(&self, id: &str) -> anyhow::Result<CorrectiveAction> {
+        let db_conn = self.db.get_connection();
+        let mut conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let action = require_action(&conn, id)?;
+        if action.status != STATUS_VERIFICATION_PENDING {
+            return Ok(action);
+        }
+        let request_id = action
+            .approval_request_id
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("검증 승인 요청이 없습니다: {}", id))?;
+
+        let outcome = approval_request::load_outcome(&conn, &request_id)?;
+        let now = now_string();
+
+        match outcome.status.as_str() {
+            "approved" => {
+                let verified_by = outcome.decided_by.unwrap_or_default();
+                let verified_at = outcome.decided_at.unwrap_or_else(|| now.clone());
+
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "UPDATE ccp_corrective_actions SET status = ?2, verified_by = ?3, verified_at = ?4,
+                        verification_comment = ?5, updated_at = ?6
+                     WHERE id = ?1",
+                    rusqlite::params![id, STATUS_VERIFIED, verified_by, verified_at, outcome.comment, now],
+                )?;
+                tx.execute(
+                    "UPDATE ccp_check_log SET
+                        corrective_action = COALESCE(corrective_action, ?2),
+                        verified_by = ?3,
+                        verified_at = ?4
+                     WHERE id IN (SELECT check_id FROM ccp_corrective_action_checks WHERE action_id = ?1)",
+                    rusqlite::params![id, action.action_taken, verified_by, verified_at],
+                )?;
+                tx.commit()?;
+                println!("✅ 시정조치 검증 완료: {} by {}", id, verified_by);
+            }
+            "rejected" | "expired" => {
+                conn.execute(
+                    "UPDATE ccp_corrective_actions SET status = ?2, verification_comment = ?3, updated_at = ?4 WHERE id = ?1",
+                    rusqlite::params![id, STATUS_OPEN, outcome.comment, now],
+                )?;
+            }
+            _ => {}
+        }
+
+        require_action(&conn, id)
+    }
+
+    /// 시정조치 종결 (검증이 기록된 경우에만 가능)
+    pub fn close(&self, id: &str, closed_by: &str) -> anyhow::Result<CorrectiveAction> {
+        // 승인 결과가 아직 반영되지 않았을 수 있으므로 먼저 동기화
+        let action = self.sync_verification(id)?;
+
+        if action.status == STATUS_CLOSED {
+            return Err(anyhow::anyhow!("이미 종결된 시정조치입니다: {}", id));
+        }
+        if action.status != STATUS_VERIFIED || action.verified_at.is_none() {
+            return Err(anyhow::anyhow!(
+                "검증이 기록되지 않아 시정조치를 종결할 수 없습니다 (현재 상태: {})",
+                action.status
+            ));
+        }
+
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let now = now_string();
+        conn.execute(
+            "UPDATE ccp_corrective_actions SET status = ?2, closed_by = ?3, closed_at = ?4, updated_at = ?4
+             WHERE id = ?1 AND status = ?5",
+            rusqlite::params![id, STATUS_CLOSED, closed_by, now, STATUS_VERIFIED],
+        )?;
+
+        require_action(&conn, id)
+    }
+}
+
+/// 검증 요청 전 필수 항목 중 비어 있는 항목
+fn missing_fields(action: &CorrectiveAction) -> Vec<&'static str> {
+    let blank = |value: &Option<String>| value.as_deref().map(str::trim).unwrap_or("").is_empty();
+
+    let mut missing = Vec::new();
+    if action.hold_decision == "PENDING" {
+        missing.push("보류 결정");
+    }
+    if blank(&action.root_cause) {
+        missing.push("원인");
+    }
+    if blank(&action.action_taken) {
+        missing.push("조치 내용");
+    }
+    missing
+}
+
+/// 이미 시정조치에 연결된 점검 ID
+fn linked_check_ids<I: IntoIterator<Item = i64>>(conn: &Connection, check_ids: I) -> anyhow::Result<BTreeSet<i64>> {
+    let mut stmt = conn.prepare("SELECT 1 FROM ccp_corrective_action_checks WHERE check_id = ?1")?;
+    let mut linked = BTreeSet::new();
+    for check_id in check_ids {
+        if stmt.exists([check_id])? {
+            linked.insert(check_id);
+        }
+    }
+    Ok(linked)
+}
+
+fn require_action(conn: &Connection, id: &str) -> anyhow::Result<CorrectiveAction> {
+    load_action(conn, id)?.ok_or_else(|| anyhow::anyhow!("시정조치를 찾을 수 없습니다: {}", id))
+}
+
+fn load_action(conn: &Connection, id: &str) -> anyhow::Result<Option<CorrectiveAction>> {
+    let action = conn
+        .query_row(
+            "SELECT id, company_id, ccp_id, trigger_source, trigger_ref, trigger_result, affected_lots,
+                    deviation_summary, hold_decision, root_cause, action_taken, status, approval_request_id,
+                    verified_by, verified_at, verification_comment, closed_by, closed_at, created_at, updated_at
+             FROM ccp_corrective_actions WHERE id = ?1",
+            [id],
+            |row| {
+                let affected_lots: String = row.get(6)?;
+                Ok(CorrectiveAction {
+                    id: row.get(0)?,
+                    company_id: row.get(1)?,
+                    ccp_id: row.get(2)?,
+                    trigger_source: row.get(3)?,
+                    trigger_ref: row.get(4)?,
+                    trigger_result: row.get(5)?,
+                    affected_lots: serde_json::from_str(&affected_lots).unwrap_or_default(),
+                    check_ids: Vec::new(),
+                    deviation_summary: row.get(7)?,
+                    hold_decision: row.get(8)?,
+                    root_cause: row.get(9)?,
+                    action_taken: row.get(10)?,
+                    status: row.get(11)?,
+                    approval_request_id: row.get(12)?,
+                    verified_by: row.get(13)?,
+                    verified_at: row.get(14)?,
+                    verification_comment: row.get(15)?,
+                    closed_by: row.get(16)?,
+                    closed_at: row.get(17)?,
+                    created_at: row.get(18)?,
+                    updated_at: row.get(19)?,
+                })
+            },
+        )
+        .optional()?;
+
+    let Some(mut action) = action else {
+        return Ok(None);
+    };
+    let mut stmt = conn.prepare("SELECT check_id FROM ccp_corrective_action_checks WHERE action_id = ?1 ORDER BY check_id")?;
+    action.check_ids = stmt
+        .query_map([id], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(action))
+}
+
+fn now_string() -> String {
+    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluation(check_id: i64, lot: &str, result: &str) -> CcpCheckEvaluation {
+        CcpCheckEvaluation {
+            check_id,
+            batch_lot_no: lot.to_string(),
+            ccp_type: "PASTEURIZATION".to_string(),
+            check_time: "2025-11-01 09:00:00".to_string(),
+            equip_cd: "EQ-PAST-01".to_string(),
+            recorded_result: "PASS".to_string(),
+            judged_result: result.to_string(),
+            temp_deviation: Some(-1.0),
+            time_deviation_sec: None,
+            reasons: vec!["살균 온도 한계 미달".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_draft_from_evaluations() {
+        let evaluations = vec![
+            evaluation(1, "LOT-B", "DEVIATION"),
+            evaluation(2, "LOT-A", "PASS"),
+            evaluation(3, "LOT-A", "FAIL"),
+            evaluation(4, "LOT-C", "DEVIATION"),
+        ];
+
+        let draft = draft_from_evaluations(&evaluations, &BTreeSet::from([4])).unwrap();
+        assert_eq!(draft.trigger_result, "FAIL");
+        assert_eq!(draft.affected_lots, vec!["LOT-A", "LOT-B"]);
+        assert_eq!(draft.checks.len(), 2);
+        assert!(draft.summary.contains("LOT LOT-B 점검 #1"));
+
+        // 모두 PASS 또는 이미 연결된 점검이면 발행하지 않음
+        assert!(draft_from_evaluations(&evaluations, &BTreeSet::from([1, 3, 4])).is_none());
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let mut action = CorrectiveAction {
+            id: "ca-test".to_string(),
+            company_id: "TEST".to_string(),
+            ccp_id: "PASTEURIZATION".to_string(),
+            trigger_source: "check".to_string(),
+            trigger_ref: "test".to_string(),
+            trigger_result: "DEVIATION".to_string(),
+            affected_lots: vec![],
+            check_ids: vec![],
+            deviation_summary: String::new(),
+            hold_decision: "PENDING".to_string(),
+            root_cause: Some("  ".to_string()),
+            action_taken: None,
+            status: STATUS_OPEN.to_string(),
+            approval_request_id: None,
+            verified_by: None,
+            verified_at: None,
+            verification_comment: None,
+            closed_by: None,
+            closed_at: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        assert_eq!(missing_fields(&action), vec!["보류 결정", "원인", "조치 내용"]);
+
+        action.hold_decision = "HOLD".to_string();
+        action.root_cause = Some("열교환기 스케일".to_string());
+        action.action_taken = Some("재살균".to_string());
+        assert!(missing_fields(&action).is_empty());
+    }
+
+    #[test]
+    fn test_open_for_high_risk_sensor_judgment() {
+        let store = CorrectiveActionStore::new().unwrap();
+        let company = format!("TEST-CA-{}", uuid::Uuid::new_v4());
+
+        let action = store
+            .open_for_high_risk(&company, "CCP-01", "ccp-judgment-sensor", "NG 비율 11.9%")
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.status, STATUS_OPEN);
+        assert_eq!(action.trigger_result, "DEVIATION");
+        assert!(action.check_ids.is_empty());
+
+        // 종결 전에는 같은 CCP에 중복 발행하지 않음
+        assert!(store
+            .open_for_high_risk(&company, "CCP-01", "ccp-judgment-sensor-2", "NG 비율 12.0%")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_corrective_action_lifecycle() {
+        let store = CorrectiveActionStore::new().unwrap();
+        let company = format!("TEST-CA-{}", uuid::Uuid::new_v4());
+        // 실제 점검과 겹치지 않는 음수 ID 사용
+        let base = -(chrono::Utc::now().timestamp_micros() % 1_000_000_000) - 10;
+        let evaluations = vec![evaluation(base, "LOT-T1", "DEVIATION"), evaluation(base - 1, "LOT-T2", "FAIL")];
+
+        let action = store
+            .open_for_deviations(&company, "PASTEURIZATION", "judgment", "ccp-judgment-test", &evaluations)
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.status, STATUS_OPEN);
+        assert_eq!(action.trigger_result, "FAIL");
+        assert_eq!(action.check_ids.len(), 2);
+
+        // 같은 점검으로 재판정해도 중복 발행하지 않음
+        assert!(store
+            .open_for_deviations(&company, "PASTEURIZATION", "judgment", "ccp-judgment-test-2", &evaluations)
+            .unwrap()
+            .is_none());
+
+        // 필수 항목 없이 검증 요청/종결 불가
+        assert!(store.request_verification(&action.id, "qa@example.com", None).is_err());
+        assert!(store.close(&action.id, "qa@example.com").is_err());
+        assert!(store
+            .update(&action.id, &CorrectiveActionUpdate { hold_decision: Some("SHIP".to_string()), ..Default::default() })
+            .is_err());
+
+        store
+            .update(
+                &action.id,
+                &CorrectiveActionUpdate {
+                    hold_decision: Some("HOLD".to_string()),
+                    root_cause: Some("증기 밸브 고착".to_string()),
+                    action_taken: Some("밸브 교체 후 재살균".to_string()),
+                },
+            )
+            .unwrap();
+        let pending = store.request_verification(&action.id, "qa@example.com", None).unwrap();
+        assert_eq!(pending.status, STATUS_VERIFICATION_PENDING);
+        let request_id = pending.approval_request_id.clone().unwrap();
+
+        // 승인 전: 수정/종결 불가
+        assert!(store.update(&action.id, &CorrectiveActionUpdate::default()).is_err());
+        assert!(store.close(&action.id, "qa@example.com").is_err());
+
+        // APPROVAL 승인 처리 (process_approval과 같은 갱신)
+        {
+            let db_conn = store.db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            conn.execute(
+                "UPDATE approval_requests SET status = 'approved', decided_by = 'qa@example.com', decided_at = ?2, comment = '재살균 기록 확인'
+                 WHERE id = ?1",
+                rusqlite::params![request_id, chrono::Utc::now().to_rfc3339()],
+            )
+            .unwrap();
+        }
+
+        let closed = store.close(&action.id, "haccp-lead").unwrap();
+        assert_eq!(closed.status, STATUS_CLOSED);
+        assert_eq!(closed.verified_by.as_deref(), Some("qa@example.com"));
+        assert!(closed.verified_at.is_some());
+        assert_eq!(store.list(&company, Some(STATUS_CLOSED)).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,669 @@
+// services/ccp_doc_ingestion.rs - HACCP 계획서/SOP 문서 가져오기
+//
+// Markdown(헤딩 단위), 일반 텍스트(문단 단위), 섹션 CSV를 ccp_docs 섹션으로 분할하고
+// 문서 개정본(ccp_doc_versions)으로 저장한다. FTS5 인덱스는 ccp_docs 트리거가 동기화.
+
+use crate::database::{CcpDoc, CcpDocImportRequest, CcpDocImportResult, CcpDocVersion, Database};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// 섹션 최대 길이 (문자 수) - 초과시 문단 단위로 분할 (BM25 점수 편향 방지)
+pub const MAX_SECTION_CHARS: usize = 1200;
+
+/// 분류되지 않은 섹션 타입
+const DEFAULT_SECTION_TYPE: &str = "일반";
+
+/// section_type 분류 키워드 (앞에서부터 먼저 매칭, 시드 문서와 같은 한글 라벨 사용)
+const SECTION_KEYWORDS: &[(&str, &[&str])] = &[
+    ("시정조치", &["시정조치", "개선조치", "이탈", "corrective", "deviation"]),
+    ("모니터링", &["모니터링", "측정 주기", "측정 빈도", "monitoring"]),
+    ("점검 절차", &["점검", "검증", "교정", "verification", "calibration", "inspection"]),
+    ("기록 양식", &["기록", "양식", "record"]),
+    ("관리 기준", &["기준", "한계", "허용", "critical limit", "limit", "standard"]),
+];
+
+/// 입력 문서 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocFormat {
+    Markdown,
+    Text,
+    Csv,
+}
+
+impl DocFormat {
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Ok(DocFormat::Markdown),
+            "text" | "txt" | "plain" => Ok(DocFormat::Text),
+            "csv" => Ok(DocFormat::Csv),
+            other => Err(anyhow::anyhow!("지원하지 않는 문서 형식: {} (markdown, text, csv)", other)),
+        }
+    }
+
+    /// 파일 확장자로 형식 추정
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = std::path::Path::new(path).extension()?.to_str()?;
+        DocFormat::parse(extension).ok()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "markdown",
+            DocFormat::Text => "text",
+            DocFormat::Csv => "csv",
+        }
+    }
+}
+
+/// ccp_docs 한 행이 될 문서 섹션
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocSection {
+    pub section_type: String,
+    pub title: String,
+    pub content: String,
+}
+
+/// DB 시각 형식 (ccp_docs.created_at, 시드 데이터와 동일)
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// CCP 문서 저장소 (가져오기 + 개정 이력)
+///
+/// 같은 (company_id, ccp_id, doc_key)로 다시 가져오면 새 개정본이 되고,
+/// 직전 개정본은 새 시행일에 종료된다. 이전 섹션은 삭제하지 않으므로
+/// 과거 기간 판단은 당시 유효했던 개정본을 근거로 인용할 수 있다.
+pub struct CcpDocStore {
+    db: Database,
+}
+
+impl CcpDocStore {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    /// 문서 가져오기 (파싱 → 섹션 분할 → 개정본 저장)
+    ///
+    /// 현행 개정본과 내용(SHA-256)이 같으면 새 개정을 만들지 않고 `unchanged: true` 반환
+    pub fn import_document(&self, request: &CcpDocImportRequest) -> anyhow::Result<CcpDocImportResult> {
+        let title = request.title.trim();
+        if request.company_id.trim().is_empty() || request.ccp_id.trim().is_empty() || title.is_empty() {
+            return Err(anyhow::anyhow!("company_id, ccp_id, title은 필수입니다"));
+        }
+
+        let format = match (&request.format, &request.file_path) {
+            (Some(format), _) => DocFormat::parse(format)?,
+            (None, Some(path)) => DocFormat::from_path(path)
+                .ok_or_else(|| anyhow::anyhow!("파일 확장자로 문서 형식을 알 수 없습니다: {}", path))?,
+            (None, None) => return Err(anyhow::anyhow!("format 또는 file_path가 필요합니다")),
+        };
+
+        let content = match (&request.content, &request.file_path) {
+            (Some(content), _) => content.clone(),
+            (None, Some(path)) => std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("문서 파일 읽기 실패 ({}): {}", path, e))?,
+            (None, None) => return Err(anyhow::anyhow!("content 또는 file_path가 필요합니다")),
+        };
+        let content = content.trim_start_matches('\u{feff}');
+
+        let sections = chunk_document(format, title, content)?;
+        let checksum = format!("{:x}", Sha256::digest(content.as_bytes()));
+        let doc_key = request
+            .doc_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .unwrap_or(title)
+            .to_string();
+        let effective_from = match &request.effective_from {
+            Some(value) => normalize_timestamp(value, false)?,
+            None => Utc::now().format(TIMESTAMP_FORMAT).to_string(),
+        };
+        let source_name = request.file_path.as_ref().map(|path| {
+            std::path::Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone())
+        });
+
+        let db_conn = self.db.get_connection();
+        let mut conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        let tx = conn.transaction()?;
+
+        let current = tx
+            .query_row(
+                &format!(
+                    "{} WHERE company_id = ?1 AND ccp_id = ?2 AND doc_key = ?3 AND effective_to IS NULL
+                     ORDER BY revision DESC LIMIT 1",
+                    VERSION_SELECT
+                ),
+                rusqlite::params![request.company_id, request.ccp_id, doc_key],
+                row_to_version,
+            )
+            .optional()?;
+
+        if let Some(current) = current {
+            if current.checksum == checksum {
+                let sections = load_sections(&tx, &current.id)?;
+                return Ok(CcpDocImportResult { version: current, sections, unchanged: true });
+            }
+            if effective_from <= current.effective_from {
+                return Err(anyhow::anyhow!(
+                    "시행일({})은 현행 개정본 {}의 시행일({}) 이후여야 합니다",
+                    effective_from, current.revision, current.effective_from
+                ));
+            }
+            tx.execute(
+                "UPDATE ccp_doc_versions SET effective_to = ?1 WHERE id = ?2",
+                rusqlite::params![effective_from, current.id],
+            )?;
+        }
+
+        let revision: i32 = tx.query_row(
+            "SELECT COALESCE(MAX(revision), 0) + 1 FROM ccp_doc_versions
+             WHERE company_id = ?1 AND ccp_id = ?2 AND doc_key = ?3",
+            rusqlite::params![request.company_id, request.ccp_id, doc_key],
+            |row| row.get(0),
+        )?;
+        let created_at = Utc::now().format(TIMESTAMP_FORMAT).to_string();
+        let version = CcpDocVersion {
+            id: Uuid::new_v4().to_string(),
+            company_id: request.company_id.clone(),
+            ccp_id: request.ccp_id.clone(),
+            doc_key,
+            title: title.to_string(),
+            revision,
+            source_format: format.as_str().to_string(),
+            source_name,
+            checksum,
+            section_count: sections.len() as i32,
+            effective_from,
+            effective_to: None,
+            created_at: created_at.clone(),
+        };
+
+        tx.execute(
+            "INSERT INTO ccp_doc_versions (
+                id, company_id, ccp_id, doc_key, title, revision, source_format, source_name,
+                checksum, section_count, effective_from, effective_to, created_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, NULL, ?12)",
+            rusqlite::params![
+                version.id,
+                version.company_id,
+                version.ccp_id,
+                version.doc_key,
+                version.title,
+                version.revision,
+                version.source_format,
+                version.source_name,
+                version.checksum,
+                version.section_count,
+                version.effective_from,
+                version.created_at,
+            ],
+        )?;
+
+        for section in &sections {
+            tx.execute(
+                "INSERT INTO ccp_docs (company_id, ccp_id, title, section_type, content, created_at, doc_version_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    version.company_id,
+                    version.ccp_id,
+                    section.title,
+                    section.section_type,
+                    section.content,
+                    created_at,
+                    version.id,
+                ],
+            )?;
+        }
+
+        let saved = load_sections(&tx, &version.id)?;
+        tx.commit()?;
+
+        println!(
+            "📄 CCP 문서 가져오기: {} / {} '{}' rev.{} ({}개 섹션)",
+            version.company_id, version.ccp_id, version.doc_key, version.revision, saved.len()
+        );
+
+        Ok(CcpDocImportResult { version, sections: saved, unchanged: false })
+    }
+
+    /// 문서 개정 이력 조회 (문서별 최신 개정 순)
+    pub fn list_versions(&self, company_id: &str, ccp_id: Option<&str>) -> anyhow::Result<Vec<CcpDocVersion>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE company_id = ?1 AND (?2 IS NULL OR ccp_id = ?2)
+             ORDER BY ccp_id, doc_key, revision DESC",
+            VERSION_SELECT
+        ))?;
+        let versions = stmt
+            .query_map(rusqlite::params![company_id, ccp_id], row_to_version)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(versions)
+    }
+}
+
+const VERSION_SELECT: &str = "SELECT id, company_id, ccp_id, doc_key, title, revision, source_format, source_name,
+        checksum, section_count, effective_from, effective_to, created_at
+     FROM ccp_doc_versions";
+
+fn row_to_version(row: &rusqlite::Row) -> rusqlite::Result<CcpDocVersion> {
+    Ok(CcpDocVersion {
+        id: row.get(0)?,
+        company_id: row.get(1)?,
+        ccp_id: row.get(2)?,
+        doc_key: row.get(3)?,
+        title: row.get(4)?,
+        revision: row.get(5)?,
+        source_format: row.get(6)?,
+        source_name: row.get(7)?,
+        checksum: row.get(8)?,
+        section_count: row.get(9)?,
+        effective_from: row.get(10)?,
+        effective_to: row.get(11)?,
+        created_at: row.get(12)?,
+    })
+}
+
+fn load_sections(conn: &Connection, version_id: &str) -> anyhow::Result<Vec<CcpDoc>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, company_id, ccp_id, title, section_type, content, created_at
+         FROM ccp_docs WHERE doc_version_id = ?1 ORDER BY id",
+    )?;
+    let sections = stmt
+        .query_map([version_id], |row| {
+            Ok(CcpDoc {
+                id: row.get(0)?,
+                company_id: row.get(1)?,
+                ccp_id: row.get(2)?,
+                title: row.get(3)?,
+                section_type: row.get(4)?,
+                content: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(sections)
+}
+
+/// 날짜/시각을 DB 형식("YYYY-MM-DD HH:MM:SS")으로 변환
+///
+/// 날짜만 주어지면 `end_of_day`에 따라 00:00:00 또는 23:59:59
+/// (판단 기간 종료일은 그날 하루 전체를 포함해야 하므로 23:59:59)
+pub fn normalize_timestamp(value: &str, end_of_day: bool) -> anyhow::Result<String> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let time = if end_of_day { (23, 59, 59) } else { (0, 0, 0) };
+        let datetime = date
+            .and_hms_opt(time.0, time.1, time.2)
+            .ok_or_else(|| anyhow::anyhow!("잘못된 날짜: {}", value))?;
+        return Ok(datetime.format(TIMESTAMP_FORMAT).to_string());
+    }
+
+    let datetime = NaiveDateTime::parse_from_str(value, TIMESTAMP_FORMAT)
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .or_else(|_| DateTime::parse_from_rfc3339(value).map(|dt| dt.naive_utc()))
+        .map_err(|_| anyhow::anyhow!("날짜 형식 오류 (YYYY-MM-DD 또는 YYYY-MM-DD HH:MM:SS): {}", value))?;
+    Ok(datetime.format(TIMESTAMP_FORMAT).to_string())
+}
+
+/// 문서를 형식에 맞게 섹션으로 분할
+pub fn chunk_document(format: DocFormat, doc_title: &str, content: &str) -> anyhow::Result<Vec<DocSection>> {
+    let sections = match format {
+        DocFormat::Markdown => chunk_markdown(doc_title, content),
+        DocFormat::Text => chunk_text(doc_title, content),
+        DocFormat::Csv => parse_csv_sections(doc_title, content)?,
+    };
+
+    if sections.is_empty() {
+        return Err(anyhow::anyhow!("문서에서 섹션을 찾을 수 없습니다: {}", doc_title));
+    }
+    Ok(sections)
+}
+
+/// 헤딩/본문 키워드로 section_type 분류 (헤딩 우선)
+pub fn classify_section(heading: &str, content: &str) -> &'static str {
+    let find = |text: &str| {
+        let lower = text.to_lowercase();
+        SECTION_KEYWORDS
+            .iter()
+            .find(|(_, keywords)| keywords.iter().any(|k| lower.contains(k)))
+            .map(|(section_type, _)| *section_type)
+    };
+
+    find(heading)
+        .or_else(|| find(content))
+        .unwrap_or(DEFAULT_SECTION_TYPE)
+}
+
+/// Markdown: 헤딩(#~######) 단위 섹션, 헤딩 이전 본문은 문서 제목으로
+fn chunk_markdown(doc_title: &str, content: &str) -> Vec<DocSection> {
+    let mut sections = Vec::new();
+    let mut heading = String::new();
+    let mut body: Vec<&str> = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            push_sections(&mut sections, doc_title, &heading, &body.join("\n"));
+            heading = trimmed[level..].trim().to_string();
+            body.clear();
+        } else {
+            body.push(line);
+        }
+    }
+    push_sections(&mut sections, doc_title, &heading, &body.join("\n"));
+
+    sections
+}
+
+/// 일반 텍스트: 빈 줄로 구분된 문단을 최대 길이까지 묶고 문단 내용으로 분류
+fn chunk_text(doc_title: &str, content: &str) -> Vec<DocSection> {
+    let mut sections = Vec::new();
+    for paragraph in split_paragraphs(content) {
+        let section_type = classify_section("", &paragraph);
+        sections.push(DocSection {
+            section_type: section_type.to_string(),
+            title: String::new(),
+            content: paragraph,
+        });
+    }
+
+    // 같은 타입의 인접 문단은 최대 길이까지 합침
+    let mut merged: Vec<DocSection> = Vec::new();
+    for section in sections {
+        match merged.last_mut() {
+            Some(last)
+                if last.section_type == section.section_type
+                    && last.content.chars().count() + section.content.chars().count() < MAX_SECTION_CHARS =>
+            {
+                last.content.push_str("\n\n");
+                last.content.push_str(&section.content);
+            }
+            _ => merged.push(section),
+        }
+    }
+
+    let total = merged.len();
+    for (i, section) in merged.iter_mut().enumerate() {
+        section.title = if total == 1 {
+            doc_title.to_string()
+        } else {
+            format!("{} ({}/{})", doc_title, i + 1, total)
+        };
+    }
+    merged
+}
+
+/// 섹션 CSV: section_type(섹션/구분), content(내용) 필수, title(제목) 선택
+fn parse_csv_sections(doc_title: &str, content: &str) -> anyhow::Result<Vec<DocSection>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|h| h.trim_start_matches('\u{feff}').to_lowercase())
+        .collect();
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+
+    let type_idx = column(&["section_type", "섹션", "구분"])
+        .ok_or_else(|| anyhow::anyhow!("CSV에 section_type 컬럼이 없습니다"))?;
+    let content_idx = column(&["content", "내용"])
+        .ok_or_else(|| anyhow::anyhow!("CSV에 content 컬럼이 없습니다"))?;
+    let title_idx = column(&["title", "제목"]);
+
+    let mut sections = Vec::new();
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        let body = record.get(content_idx).unwrap_or("").trim();
+        if body.is_empty() {
+            continue;
+        }
+
+        let heading = title_idx
+            .and_then(|i| record.get(i))
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| format!("{} ({})", doc_title, row + 1));
+        let section_type = match record.get(type_idx).map(str::trim) {
+            Some(t) if !t.is_empty() => t.to_string(),
+            _ => classify_section(&heading, body).to_string(),
+        };
+
+        for (i, chunk) in split_long(body).into_iter().enumerate() {
+            sections.push(DocSection {
+                section_type: section_type.clone(),
+                title: if i == 0 { heading.clone() } else { format!("{} ({})", heading, i + 1) },
+                content: chunk,
+            });
+        }
+    }
+
+    Ok(sections)
+}
+
+/// 헤딩 섹션 추가 (빈 본문 무시, 긴 본문은 분할)
+fn push_sections(sections: &mut Vec<DocSection>, doc_title: &str, heading: &str, body: &str) {
+    let body = body.trim();
+    if body.is_empty() {
+        return;
+    }
+
+    let section_type = classify_section(heading, body);
+    let title = if heading.is_empty() {
+        doc_title.to_string()
+    } else {
+        format!("{} - {}", doc_title, heading)
+    };
+
+    for (i, chunk) in split_long(body).into_iter().enumerate() {
+        sections.push(DocSection {
+            section_type: section_type.to_string(),
+            title: if i == 0 { title.clone() } else { format!("{} ({})", title, i + 1) },
+            content: chunk,
+        });
+    }
+}
+
+fn split_paragraphs(content: &str) -> Vec<String> {
+    content
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// 최대 길이 초과 본문을 문단 → 문자 단위로 분할
+fn split_long(body: &str) -> Vec<String> {
+    if body.chars().count() <= MAX_SECTION_CHARS {
+        return vec![body.to_string()];
+    }
+
+    let mut chunks: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for paragraph in split_paragraphs(body) {
+        let pieces: Vec<String> = if paragraph.chars().count() > MAX_SECTION_CHARS {
+            paragraph
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(MAX_SECTION_CHARS)
+                .map(|c| c.iter().collect())
+                .collect()
+        } else {
+            vec![paragraph]
+        };
+
+        for piece in pieces {
+            if !current.is_empty()
+                && current.chars().count() + piece.chars().count() + 2 > MAX_SECTION_CHARS
+            {
+                chunks.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(&piece);
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_markdown_by_heading() {
+        let doc = "HACCP 계획 개요\n\n# 관리 기준\n중심 온도 75°C 이상 15초 유지\n\n## 모니터링 방법\n2시간마다 온도 측정\n\n## 시정조치\n즉시 라인 중단 후 재가열\n\n## 빈 섹션\n";
+
+        let sections = chunk_document(DocFormat::Markdown, "열처리 HACCP 계획", doc).unwrap();
+
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[0].title, "열처리 HACCP 계획");
+        assert_eq!(sections[1].title, "열처리 HACCP 계획 - 관리 기준");
+        assert_eq!(sections[1].section_type, "관리 기준");
+        assert_eq!(sections[2].section_type, "모니터링");
+        assert_eq!(sections[3].section_type, "시정조치");
+        assert_eq!(sections[3].content, "즉시 라인 중단 후 재가열");
+    }
+
+    #[test]
+    fn test_chunk_text_classifies_paragraphs() {
+        let doc = "허용 온도 범위는 75~85°C이다.\n\n온도계 검교정은 월 1회 실시한다.\n\nNG 발생시 제품 격리 후 시정조치를 기록한다.";
+
+        let sections = chunk_document(DocFormat::Text, "열처리 SOP", doc).unwrap();
+
+        let types: Vec<_> = sections.iter().map(|s| s.section_type.as_str()).collect();
+        assert_eq!(types, vec!["관리 기준", "점검 절차", "시정조치"]);
+        assert_eq!(sections[0].title, "열처리 SOP (1/3)");
+    }
+
+    #[test]
+    fn test_parse_csv_sections() {
+        let csv = "\u{feff}section_type,title,content\n관리 기준,한계 기준,최소 75°C 최대 90°C\n,냉각 조치,NG 발생시 재냉각 실시 (시정조치)\n기록 양식,,\n";
+
+        let sections = chunk_document(DocFormat::Csv, "냉각 SOP", csv).unwrap();
+
+        assert_eq!(sections.len(), 2); // 빈 content 행 무시
+        assert_eq!(sections[0].section_type, "관리 기준");
+        assert_eq!(sections[0].title, "한계 기준");
+        assert_eq!(sections[1].section_type, "시정조치"); // 빈 타입은 자동 분류
+
+        assert!(chunk_document(DocFormat::Csv, "x", "title,content\na,b\n").is_err());
+    }
+
+    #[test]
+    fn test_split_long_sections() {
+        let paragraph = "가".repeat(MAX_SECTION_CHARS - 100);
+        let body = format!("{}\n\n{}", paragraph, paragraph);
+
+        let chunks = split_long(&body);
+
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|c| c.chars().count() <= MAX_SECTION_CHARS));
+        assert_eq!(split_long(&"나".repeat(MAX_SECTION_CHARS * 2 + 1)).len(), 3);
+    }
+
+    fn import_request(company_id: &str, content: &str, effective_from: &str) -> CcpDocImportRequest {
+        CcpDocImportRequest {
+            company_id: company_id.to_string(),
+            ccp_id: "CCP-01".to_string(),
+            title: "열처리 HACCP 계획".to_string(),
+            doc_key: None,
+            format: Some("markdown".to_string()),
+            content: Some(content.to_string()),
+            file_path: None,
+            effective_from: Some(effective_from.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_import_document_versions_and_fts_sync() {
+        let store = CcpDocStore::new().unwrap();
+        let company = format!("TEST_{}", Uuid::new_v4().simple());
+
+        let v1 = store
+            .import_document(&import_request(&company, "# 관리 기준\n가열온도 75°C 이상", "2025-01-01"))
+            .unwrap();
+        assert_eq!(v1.version.revision, 1);
+        assert_eq!(v1.sections.len(), 1);
+        assert!(!v1.unchanged);
+
+        // 같은 내용 재가져오기 → 새 개정 없음
+        let again = store
+            .import_document(&import_request(&company, "# 관리 기준\n가열온도 75°C 이상", "2025-03-01"))
+            .unwrap();
+        assert!(again.unchanged);
+        assert_eq!(again.version.id, v1.version.id);
+
+        // 이전 시행일로는 개정 불가
+        assert!(store
+            .import_document(&import_request(&company, "# 관리 기준\n가열온도 80°C 이상", "2024-12-31"))
+            .is_err());
+
+        let v2 = store
+            .import_document(&import_request(&company, "# 관리 기준\n가열온도 80°C 이상", "2025-06-01"))
+            .unwrap();
+        assert_eq!(v2.version.revision, 2);
+
+        let versions = store.list_versions(&company, Some("CCP-01")).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].revision, 2);
+        assert_eq!(versions[1].effective_to.as_deref(), Some("2025-06-01 00:00:00"));
+
+        // 트리거로 FTS 인덱스 동기화 확인 (가져오기 → 인덱싱, 삭제 → 인덱스 제거)
+        let doc_ids = format!("{},{}", v1.sections[0].id, v2.sections[0].id);
+        let count_indexed = |conn: &Connection| -> i64 {
+            conn.query_row(
+                &format!("SELECT COUNT(*) FROM ccp_docs_fts WHERE rowid IN ({})", doc_ids),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        let db_conn = store.db.get_connection();
+        let conn = db_conn.lock().unwrap();
+        assert_eq!(count_indexed(&conn), 2);
+
+        conn.execute(
+            "DELETE FROM ccp_docs WHERE doc_version_id IN (SELECT id FROM ccp_doc_versions WHERE company_id = ?1)",
+            [&company],
+        )
+        .unwrap();
+        conn.execute("DELETE FROM ccp_doc_versions WHERE company_id = ?1", [&company]).unwrap();
+        assert_eq!(count_indexed(&conn), 0);
+    }
+
+    #[test]
+    fn test_normalize_timestamp() {
+        assert_eq!(normalize_timestamp("2025-11-14", false).unwrap(), "2025-11-14 00:00:00");
+        assert_eq!(normalize_timestamp("2025-11-14", true).unwrap(), "2025-11-14 23:59:59");
+        assert_eq!(normalize_timestamp("2025-11-14T09:30:00", true).unwrap(), "2025-11-14 09:30:00");
+        assert_eq!(normalize_timestamp("2025-11-14T09:30:00+09:00", false).unwrap(), "2025-11-14 00:30:00");
+        assert!(normalize_timestamp("11/14/2025", false).is_err());
+    }
+
+    #[test]
+    fn test_doc_format_from_path() {
+        assert_eq!(DocFormat::from_path("plan/CCP-01.md"), Some(DocFormat::Markdown));
+        assert_eq!(DocFormat::from_path("sop.TXT"), Some(DocFormat::Text));
+        assert_eq!(DocFormat::from_path("sections.csv"), Some(DocFormat::Csv));
+        assert_eq!(DocFormat::from_path("plan.pdf"), None);
+    }
+}
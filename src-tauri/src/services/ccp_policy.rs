@@ -0,0 +1,393 @@
+// services/ccp_policy.rs - 회사/CCP별 한계기준 및 위험도 정책
+//
+// 정책이 없는 CCP는 기본 정책(NG 비율 10% HIGH / 3% MEDIUM, 단일 이탈시 HIGH)을 사용한다.
+
+use crate::database::Database;
+use crate::services::ccp_check_evaluator::CriticalLimits;
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+
+/// 기본 NG 비율 임계값 (기존 rule_based_risk 기준)
+pub const DEFAULT_HIGH_NG_RATE: f64 = 0.10;
+pub const DEFAULT_MEDIUM_NG_RATE: f64 = 0.03;
+
+/// CCP 위험도 정책
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CcpRiskPolicy {
+    pub company_id: String,
+    pub ccp_id: String,
+    /// NG 비율이 이 값 이상이면 HIGH
+    pub high_ng_rate: f64,
+    /// NG 비율이 이 값 이상이면 MEDIUM
+    pub medium_ng_rate: f64,
+    /// 연속 NG가 이 횟수 이상이면 HIGH (None = 미적용)
+    pub max_consecutive_failures: Option<i32>,
+    /// 한계기준 이탈 1건이라도 있으면 HIGH
+    pub breach_is_high: bool,
+    /// 절대 한계기준 (데모 센서는 measured_value에 min_temp/max_temp 적용)
+    pub limits: CriticalLimits,
+    /// None = 저장되지 않은 기본 정책
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// 위험도 판정 입력 (판정 대상 데이터에서 계산)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RiskSignals {
+    pub ng_rate: f64,
+    /// 한계기준 이탈 건수
+    pub breach_count: usize,
+    /// 최장 연속 NG 횟수 (시간순)
+    pub max_consecutive_failures: usize,
+}
+
+/// 위험도 판정 결과 (reasons는 발동된 정책 규칙)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskAssessment {
+    pub risk_level: &'static str,
+    pub reasons: Vec<String>,
+}
+
+impl CcpRiskPolicy {
+    /// 기본 정책 (한계기준 미설정 → MES 점검은 기록 목표값/유형별 기본값으로 판정)
+    pub fn default_for(company_id: &str, ccp_id: &str) -> Self {
+        CcpRiskPolicy {
+            company_id: company_id.to_string(),
+            ccp_id: ccp_id.to_string(),
+            high_ng_rate: DEFAULT_HIGH_NG_RATE,
+            medium_ng_rate: DEFAULT_MEDIUM_NG_RATE,
+            max_consecutive_failures: None,
+            breach_is_high: true,
+            limits: CriticalLimits::default(),
+            updated_at: None,
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.company_id.trim().is_empty() || self.ccp_id.trim().is_empty() {
+            return Err(anyhow::anyhow!("company_id, ccp_id는 필수입니다"));
+        }
+        if !(0.0..=1.0).contains(&self.medium_ng_rate)
+            || !(0.0..=1.0).contains(&self.high_ng_rate)
+            || self.medium_ng_rate > self.high_ng_rate
+        {
+            return Err(anyhow::anyhow!(
+                "NG 비율 임계값은 0 ≤ MEDIUM({}) ≤ HIGH({}) ≤ 1 이어야 합니다",
+                self.medium_ng_rate, self.high_ng_rate
+            ));
+        }
+        if matches!(self.max_consecutive_failures, Some(n) if n < 1) {
+            return Err(anyhow::anyhow!("연속 NG 기준은 1 이상이어야 합니다"));
+        }
+        let limits = &self.limits;
+        if let (Some(min), Some(max)) = (limits.min_temp, limits.max_temp) {
+            if min > max {
+                return Err(anyhow::anyhow!("최저 온도({})가 최고 온도({})보다 큽니다", min, max));
+            }
+        }
+        if let (Some(min), Some(max)) = (limits.min_time_sec, limits.max_time_sec) {
+            if min > max {
+                return Err(anyhow::anyhow!("최소 시간({}초)이 최대 시간({}초)보다 큽니다", min, max));
+            }
+        }
+        Ok(())
+    }
+
+    /// NG 비율만으로 판정한 위험도
+    pub fn rate_risk(&self, ng_rate: f64) -> &'static str {
+        if ng_rate >= self.high_ng_rate {
+            "HIGH"
+        } else if ng_rate >= self.medium_ng_rate {
+            "MEDIUM"
+        } else {
+            "LOW"
+        }
+    }
+
+    /// 정책 규칙을 모두 평가해 가장 높은 위험도 선택
+    pub fn assess(&self, signals: &RiskSignals) -> RiskAssessment {
+        let rate_level = self.rate_risk(signals.ng_rate);
+        let threshold = match rate_level {
+            "HIGH" => format!(" ≥ HIGH 기준 {:.1}%", self.high_ng_rate * 100.0),
+            "MEDIUM" => format!(" ≥ MEDIUM 기준 {:.1}%", self.medium_ng_rate * 100.0),
+            _ => format!(" < MEDIUM 기준 {:.1}%", self.medium_ng_rate * 100.0),
+        };
+        let mut risk_level = rate_level;
+        let mut reasons = vec![format!(
+            "NG 비율 {:.1}%{} → {}",
+            signals.ng_rate * 100.0,
+            threshold,
+            rate_level
+        )];
+
+        if self.breach_is_high && signals.breach_count > 0 {
+            risk_level = "HIGH";
+            reasons.push(format!("한계기준 이탈 {}건 (단일 이탈시 HIGH) → HIGH", signals.breach_count));
+        }
+        if let Some(max) = self.max_consecutive_failures {
+            if signals.max_consecutive_failures >= max as usize {
+                risk_level = "HIGH";
+                reasons.push(format!(
+                    "연속 NG {}회 ≥ 기준 {}회 → HIGH",
+                    signals.max_consecutive_failures, max
+                ));
+            }
+        }
+
+        RiskAssessment { risk_level, reasons }
+    }
+
+    /// rule_reason에 인용할 정책 출처
+    pub fn citation(&self) -> String {
+        match &self.updated_at {
+            Some(updated_at) => format!("정책 {}/{} ({} 개정)", self.company_id, self.ccp_id, updated_at),
+            None => format!("정책 {}/{} (기본값)", self.company_id, self.ccp_id),
+        }
+    }
+}
+
+/// 측정값이 절대 한계기준을 벗어났는지 (데모 센서 로그용)
+pub fn breaches_limits(limits: &CriticalLimits, value: f64) -> bool {
+    limits.min_temp.is_some_and(|min| value < min) || limits.max_temp.is_some_and(|max| value > max)
+}
+
+/// 최장 연속 NG 횟수
+pub fn longest_failure_run<I: IntoIterator<Item = bool>>(is_failure: I) -> usize {
+    let (mut longest, mut current) = (0, 0);
+    for failed in is_failure {
+        current = if failed { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// CCP 위험도 정책 저장소
+pub struct CcpPolicyStore {
+    db: Database,
+}
+
+impl CcpPolicyStore {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// 저장된 정책 조회 (없으면 None)
+    pub fn get(&self, company_id: &str, ccp_id: &str) -> anyhow::Result<Option<CcpRiskPolicy>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let policy = conn
+            .query_row(
+                &format!("{} WHERE company_id = ?1 AND ccp_id = ?2", POLICY_SELECT),
+                rusqlite::params![company_id, ccp_id],
+                row_to_policy,
+            )
+            .optional()?;
+        Ok(policy)
+    }
+
+    /// 판정에 적용할 정책 (저장된 정책 → 기본 정책)
+    pub fn effective(&self, company_id: &str, ccp_id: &str) -> anyhow::Result<CcpRiskPolicy> {
+        Ok(self
+            .get(company_id, ccp_id)?
+            .unwrap_or_else(|| CcpRiskPolicy::default_for(company_id, ccp_id)))
+    }
+
+    pub fn list(&self, company_id: &str) -> anyhow::Result<Vec<CcpRiskPolicy>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut stmt = conn.prepare(&format!("{} WHERE company_id = ?1 ORDER BY ccp_id", POLICY_SELECT))?;
+        let policies = stmt
+            .query_map([company_id], row_to_policy)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(policies)
+    }
+
+    /// 정책 저장 (회사/CCP당 1개, 기존 정책 덮어쓰기)
+    pub fn save(&self, policy: &CcpRiskPolicy) -> anyhow::Result<CcpRiskPolicy> {
+        policy.validate()?;
+
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let updated_at = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        let limits = &policy.limits;
+        conn.execute(
+            "INSERT INTO ccp_risk_policies (
+                company_id, ccp_id, high_ng_rate, medium_ng_rate, max_consecutive_failures, breach_is_high,
+                min_temp, max_temp, min_time_sec, max_time_sec, max_sensitivity_fe, max_sensitivity_sus, updated_at
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(company_id, ccp_id) DO UPDATE SET
+                high_ng_rate = excluded.high_ng_rate,
+                medium_ng_rate = excluded.medium_ng_rate,
+                max_consecutive_failures = excluded.max_consecutive_failures,
+                breach_is_high = excluded.breach_is_high,
+                min_temp = excluded.min_temp,
+                max_temp = excluded.max_temp,
+                min_time_sec = excluded.min_time_sec,
+                max_time_sec = excluded.max_time_sec,
+                max_sensitivity_fe = excluded.max_sensitivity_fe,
+                max_sensitivity_sus = excluded.max_sensitivity_sus,
+                updated_at = excluded.updated_at",
+            rusqlite::params![
+                policy.company_id,
+                policy.ccp_id,
+                policy.high_ng_rate,
+                policy.medium_ng_rate,
+                policy.max_consecutive_failures,
+                policy.breach_is_high,
+                limits.min_temp,
+                limits.max_temp,
+                limits.min_time_sec,
+                limits.max_time_sec,
+                limits.max_sensitivity_fe,
+                limits.max_sensitivity_sus,
+                updated_at,
+            ],
+        )?;
+
+        Ok(CcpRiskPolicy {
+            updated_at: Some(updated_at),
+            ..policy.clone()
+        })
+    }
+
+    /// 정책 삭제 (이후 기본 정책 적용). 삭제 여부 반환
+    pub fn delete(&self, company_id: &str, ccp_id: &str) -> anyhow::Result<bool> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let deleted = conn.execute(
+            "DELETE FROM ccp_risk_policies WHERE company_id = ?1 AND ccp_id = ?2",
+            rusqlite::params![company_id, ccp_id],
+        )?;
+        Ok(deleted > 0)
+    }
+}
+
+const POLICY_SELECT: &str = "SELECT company_id, ccp_id, high_ng_rate, medium_ng_rate, max_consecutive_failures,
+        breach_is_high, min_temp, max_temp, min_time_sec, max_time_sec, max_sensitivity_fe, max_sensitivity_sus,
+        updated_at
+     FROM ccp_risk_policies";
+
+fn row_to_policy(row: &rusqlite::Row) -> rusqlite::Result<CcpRiskPolicy> {
+    Ok(CcpRiskPolicy {
+        company_id: row.get(0)?,
+        ccp_id: row.get(1)?,
+        high_ng_rate: row.get(2)?,
+        medium_ng_rate: row.get(3)?,
+        max_consecutive_failures: row.get(4)?,
+        breach_is_high: row.get(5)?,
+        limits: CriticalLimits {
+            min_temp: row.get(6)?,
+            max_temp: row.get(7)?,
+            min_time_sec: row.get(8)?,
+            max_time_sec: row.get(9)?,
+            max_sensitivity_fe: row.get(10)?,
+            max_sensitivity_sus: row.get(11)?,
+        },
+        updated_at: row.get(12)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_matches_legacy_thresholds() {
+        let policy = CcpRiskPolicy::default_for("COMP_A", "CCP-01");
+
+        assert_eq!(policy.rate_risk(0.10), "HIGH");
+        assert_eq!(policy.rate_risk(0.071), "MEDIUM");
+        assert_eq!(policy.rate_risk(0.018), "LOW");
+        assert!(policy.limits.is_empty());
+        assert!(policy.breach_is_high);
+    }
+
+    #[test]
+    fn test_single_breach_is_high() {
+        let policy = CcpRiskPolicy::default_for("COMP_A", "PASTEURIZATION");
+        let signals = RiskSignals {
+            ng_rate: 0.01,
+            breach_count: 1,
+            max_consecutive_failures: 1,
+        };
+
+        let assessment = policy.assess(&signals);
+        assert_eq!(assessment.risk_level, "HIGH");
+        assert_eq!(assessment.reasons.len(), 2);
+        assert!(assessment.reasons[0].contains("LOW"));
+
+        let lenient = CcpRiskPolicy { breach_is_high: false, ..policy };
+        assert_eq!(lenient.assess(&signals).risk_level, "LOW");
+    }
+
+    #[test]
+    fn test_consecutive_failure_rule() {
+        let policy = CcpRiskPolicy {
+            max_consecutive_failures: Some(3),
+            ..CcpRiskPolicy::default_for("COMP_A", "CCP-01")
+        };
+        let run = longest_failure_run([true, false, true, true, true, false]);
+        assert_eq!(run, 3);
+
+        let signals = RiskSignals {
+            ng_rate: 0.05,
+            breach_count: 0,
+            max_consecutive_failures: run,
+        };
+        let assessment = policy.assess(&signals);
+        assert_eq!(assessment.risk_level, "HIGH");
+        assert!(assessment.reasons[1].contains("연속 NG 3회"));
+    }
+
+    #[test]
+    fn test_validate_and_breaches() {
+        let mut policy = CcpRiskPolicy::default_for("COMP_A", "CCP-01");
+        policy.limits.min_temp = Some(75.0);
+        policy.limits.max_temp = Some(90.0);
+        assert!(policy.validate().is_ok());
+        assert!(breaches_limits(&policy.limits, 72.1));
+        assert!(!breaches_limits(&policy.limits, 78.5));
+
+        policy.medium_ng_rate = 0.2;
+        assert!(policy.validate().is_err());
+        policy.medium_ng_rate = 0.03;
+        policy.max_consecutive_failures = Some(0);
+        assert!(policy.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_store_roundtrip() {
+        let store = CcpPolicyStore::new().unwrap();
+        let company = format!("TEST_{}", uuid::Uuid::new_v4().simple());
+
+        let default = store.effective(&company, "CCP-01").unwrap();
+        assert!(default.updated_at.is_none());
+
+        let mut policy = default.clone();
+        policy.high_ng_rate = 0.05;
+        policy.max_consecutive_failures = Some(2);
+        policy.limits.min_temp = Some(75.0);
+        let saved = store.save(&policy).unwrap();
+        assert!(saved.updated_at.is_some());
+
+        let loaded = store.effective(&company, "CCP-01").unwrap();
+        assert_eq!(loaded, saved);
+        assert!(loaded.citation().contains("개정"));
+        assert_eq!(store.list(&company).unwrap().len(), 1);
+
+        assert!(store.delete(&company, "CCP-01").unwrap());
+        assert!(store.get(&company, "CCP-01").unwrap().is_none());
+    }
+}
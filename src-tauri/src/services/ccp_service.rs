@@ -1,13 +1,21 @@
 use serde_json;
 use uuid::Uuid;
-use crate::database::{Database, CcpDocWithScore, CcpStats, CcpJudgmentRequest, CcpJudgmentResponse};
+use crate::database::{
+    Database, CcpCheckEvaluation, CcpDocWithScore, CcpLotJudgment, CcpStats, CcpJudgmentRequest,
+    CcpJudgmentResponse,
+};
+use crate::services::ccp_check_evaluator::{self, CcpType, CriticalLimits, RESULT_DEVIATION, RESULT_FAIL, RESULT_PASS};
+use crate::services::ccp_policy::{self, CcpPolicyStore, CcpRiskPolicy, RiskSignals};
 use crate::services::llm_engine::LLMEngine;
+use crate::services::ccp_doc_ingestion::normalize_timestamp;
+use crate::services::ccp_corrective_action::CorrectiveActionStore;
+use crate::services::audit_log::{self, NewAuditEntry};
 
 /// CCP 데모 서비스 (RAG + 룰베이스 판단)
 ///
 /// 기능:
 /// 1. FTS5 BM25 기반 문서 검색 (RAG)
-/// 2. 센서 로그 통계 계산
+/// 2. 센서 로그 통계 계산 (데모 ccp_sensors / MES ccp_check_log)
 /// 3. 룰베이스 위험도 판정
 /// 4. LLM 자연어 요약 생성
 /// 5. 하이브리드 판단 결과 저장
@@ -18,9 +26,13 @@ pub struct CcpService {
 
 impl CcpService {
     pub fn new() -> anyhow::Result<Self> {
+        Self::from_database(Database::new()?)
+    }
+
+    pub fn from_database(db: Database) -> anyhow::Result<Self> {
         Ok(Self {
-            db: Database::new()?,
-            llm_engine: LLMEngine::new()?,
+            llm_engine: LLMEngine::from_database(db.clone())?,
+            db,
         })
     }
 
@@ -39,79 +51,66 @@ impl CcpService {
         ccp_id: Option<&str>,
         query: &str,
         top_k: usize,
+    ) -> anyhow::Result<Vec<CcpDocWithScore>> {
+        let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.search_ccp_docs_as_of(company_id, ccp_id, query, top_k, &now)
+    }
+
+    /// 특정 시점에 유효했던 개정본 기준 CCP 문서 검색
+    ///
+    /// 가져온 문서는 as_of 시점의 개정본 섹션만, 시드 문서(개정 정보 없음)는 항상 포함
+    ///
+    /// Parameters:
+    /// - as_of: 기준 시각 ("YYYY-MM-DD HH:MM:SS")
+    pub fn search_ccp_docs_as_of(
+        &self,
+        company_id: &str,
+        ccp_id: Option<&str>,
+        query: &str,
+        top_k: usize,
+        as_of: &str,
     ) -> anyhow::Result<Vec<CcpDocWithScore>> {
         let db_conn = self.db.get_connection();
         let conn = db_conn.lock()
             .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
 
-        let sql = if ccp_id.is_some() {
-            // CCP 필터 있음
-            r#"
-                SELECT
-                    d.id, d.company_id, d.ccp_id, d.title,
-                    d.section_type, d.content,
-                    bm25(ccp_docs_fts) AS score
-                FROM ccp_docs d
-                JOIN ccp_docs_fts ON d.id = ccp_docs_fts.rowid
-                WHERE d.company_id = ?1
-                  AND d.ccp_id = ?2
-                  AND ccp_docs_fts MATCH ?3
-                ORDER BY score
-                LIMIT ?4
-            "#
-        } else {
-            // CCP 필터 없음
-            r#"
-                SELECT
-                    d.id, d.company_id, d.ccp_id, d.title,
-                    d.section_type, d.content,
-                    bm25(ccp_docs_fts) AS score
-                FROM ccp_docs d
-                JOIN ccp_docs_fts ON d.id = ccp_docs_fts.rowid
-                WHERE d.company_id = ?1
-                  AND ccp_docs_fts MATCH ?2
-                ORDER BY score
-                LIMIT ?3
-            "#
-        };
+        let sql = r#"
+            SELECT
+                d.id, d.company_id, d.ccp_id, d.title,
+                d.section_type, d.content,
+                bm25(ccp_docs_fts) AS score,
+                d.doc_version_id, v.revision
+            FROM ccp_docs d
+            JOIN ccp_docs_fts ON d.id = ccp_docs_fts.rowid
+            LEFT JOIN ccp_doc_versions v ON v.id = d.doc_version_id
+            WHERE d.company_id = ?1
+              AND (?2 IS NULL OR d.ccp_id = ?2)
+              AND ccp_docs_fts MATCH ?3
+              AND (d.doc_version_id IS NULL
+                   OR (v.effective_from <= ?5 AND (v.effective_to IS NULL OR v.effective_to > ?5)))
+            ORDER BY score
+            LIMIT ?4
+        "#;
 
         let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params![company_id, ccp_id, query, top_k as i64, as_of],
+            |row| {
+                Ok(CcpDocWithScore {
+                    id: row.get(0)?,
+                    company_id: row.get(1)?,
+                    ccp_id: row.get(2)?,
+                    title: row.get(3)?,
+                    section_type: row.get(4)?,
+                    content: row.get(5)?,
+                    score: row.get(6)?,
+                    doc_version_id: row.get(7)?,
+                    revision: row.get(8)?,
+                })
+            },
+        )?;
 
-        let docs = if let Some(ccp) = ccp_id {
-            let rows = stmt.query_map(
-                rusqlite::params![company_id, ccp, query, top_k as i64],
-                |row| {
-                    Ok(CcpDocWithScore {
-                        id: row.get(0)?,
-                        company_id: row.get(1)?,
-                        ccp_id: row.get(2)?,
-                        title: row.get(3)?,
-                        section_type: row.get(4)?,
-                        content: row.get(5)?,
-                        score: row.get(6)?,
-                    })
-                },
-            )?;
-            rows.collect::<Result<Vec<_>, _>>()?
-        } else {
-            let rows = stmt.query_map(
-                rusqlite::params![company_id, query, top_k as i64],
-                |row| {
-                    Ok(CcpDocWithScore {
-                        id: row.get(0)?,
-                        company_id: row.get(1)?,
-                        ccp_id: row.get(2)?,
-                        title: row.get(3)?,
-                        section_type: row.get(4)?,
-                        content: row.get(5)?,
-                        score: row.get(6)?,
-                    })
-                },
-            )?;
-            rows.collect::<Result<Vec<_>, _>>()?
-        };
-
-        Ok(docs)
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
     }
 
     /// 센서 로그 통계 계산
@@ -173,22 +172,98 @@ impl CcpService {
         Ok(stats)
     }
 
-    /// 룰베이스 위험도 판정
+    /// MES CCP 점검 판정 (ccp_check_log)
+    ///
+    /// 기간 내 점검 기록을 CCP 유형별 한계기준으로 재판정하고 배치 LOT 단위로 집계
+    ///
+    /// Parameters:
+    /// - ccp_type: MES CCP 유형
+    /// - limits: 적용할 한계기준 (회사/CCP 정책)
+    /// - from / to: 기간 (YYYY-MM-DD, 종료일 포함)
+    /// - batch_lot_no: 특정 LOT만 판정 (선택)
+    ///
+    /// Returns: (통계, 점검별 판정, LOT별 판정)
+    pub fn judge_ccp_checks(
+        &self,
+        ccp_type: CcpType,
+        limits: &CriticalLimits,
+        from: &str,
+        to: &str,
+        batch_lot_no: Option<&str>,
+    ) -> anyhow::Result<(CcpStats, Vec<CcpCheckEvaluation>, Vec<CcpLotJudgment>)> {
+        let from = normalize_timestamp(from, false)?;
+        let to = normalize_timestamp(to, true)?;
+
+        let checks = {
+            let db_conn = self.db.get_connection();
+            let conn = db_conn.lock()
+                .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+            ccp_check_evaluator::load_checks(&conn, ccp_type, &from, &to, batch_lot_no)?
+        };
+
+        let evaluations: Vec<CcpCheckEvaluation> = checks
+            .iter()
+            .map(|check| ccp_check_evaluator::evaluate_check(ccp_type, check, limits))
+            .collect();
+        let lots = ccp_check_evaluator::judge_lots(&evaluations);
+        let stats = ccp_check_evaluator::summarize(ccp_type, &checks, &evaluations);
+
+        Ok((stats, evaluations, lots))
+    }
+
+    /// 데모 센서 로그의 정책 판정 신호 (한계기준 이탈 건수, 최장 연속 NG)
+    ///
+    /// ng_rate는 calculate_stats 결과를 사용하므로 여기서는 채우지 않는다.
+    fn sensor_signals(
+        &self,
+        company_id: &str,
+        ccp_id: &str,
+        from: &str,
+        to: &str,
+        limits: &CriticalLimits,
+    ) -> anyhow::Result<RiskSignals> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            r#"
+                SELECT measured_value, result
+                FROM ccp_sensors
+                WHERE company_id = ?1
+                  AND ccp_id = ?2
+                  AND log_date BETWEEN ?3 AND ?4
+                ORDER BY log_date, created_at, log_id
+            "#,
+        )?;
+        let logs = stmt
+            .query_map(rusqlite::params![company_id, ccp_id, from, to], |row| {
+                Ok((row.get::<_, f64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(RiskSignals {
+            ng_rate: 0.0,
+            breach_count: logs
+                .iter()
+                .filter(|(value, _)| ccp_policy::breaches_limits(limits, *value))
+                .count(),
+            max_consecutive_failures: ccp_policy::longest_failure_run(logs.iter().map(|(_, result)| result == "NG")),
+        })
+    }
+
+    /// 룰베이스 위험도 판정 (기본 정책의 NG 비율 규칙)
     ///
     /// 규칙:
     /// - NG 비율 >= 10% → HIGH
     /// - NG 비율 >= 3%  → MEDIUM
     /// - NG 비율 < 3%   → LOW
     ///
+    /// 회사/CCP별 정책이 있으면 judge_ccp_status는 CcpRiskPolicy::assess를 사용
+    ///
     /// Returns: "LOW" | "MEDIUM" | "HIGH"
-    fn rule_based_risk(&self, ng_rate: f64) -> &'static str {
-        if ng_rate >= 0.1 {
-            "HIGH"
-        } else if ng_rate >= 0.03 {
-            "MEDIUM"
-        } else {
-            "LOW"
-        }
+    pub fn rule_based_risk(&self, ng_rate: f64) -> &'static str {
+        CcpRiskPolicy::default_for("", "").rate_risk(ng_rate)
     }
 
     /// LLM 자연어 요약 생성
@@ -209,7 +284,10 @@ impl CcpService {
         let doc_titles: Vec<String> = evidence_docs
             .iter()
             .take(3)
-            .map(|d| format!("- {}", d.title))
+            .map(|d| match d.revision {
+                Some(revision) => format!("- {} (개정 {})", d.title, revision),
+                None => format!("- {}", d.title),
+            })
             .collect();
 
         // LLM 프롬프트 구성
@@ -270,7 +348,9 @@ impl CcpService {
         let evidence_json = serde_json::to_string(evidence_docs)?;
         let created_at = chrono::Utc::now().to_rfc3339();
 
-        conn.execute(
+        // 판단 저장과 감사 기록을 함께 커밋
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
             r#"
                 INSERT INTO ccp_judgments (
                     id, company_id, ccp_id, period_from, period_to,
@@ -296,13 +376,31 @@ impl CcpService {
             ],
         )?;
 
+        audit_log::append(&tx, &NewAuditEntry {
+            actor: &audit_log::current_actor(),
+            action: audit_log::ACTION_CCP_JUDGMENT,
+            entity_type: "ccp_judgment",
+            entity_id: judgment_id,
+            before: None,
+            after: Some(serde_json::json!({
+                "company_id": company_id,
+                "ccp_id": ccp_id,
+                "period_from": period_from,
+                "period_to": period_to,
+                "stats": stats,
+                "risk_level": risk_level,
+                "rule_reason": rule_reason,
+            })),
+        })?;
+        tx.commit()?;
+
         Ok(())
     }
 
     /// 메인 API: CCP 상태 판단 (하이브리드)
     ///
     /// 흐름:
-    /// 1. 센서 로그 통계 계산
+    /// 1. 통계 계산 (MES CCP 유형이면 ccp_check_log LOT별 한계기준 판정)
     /// 2. 룰베이스 위험도 판정
     /// 3. RAG 검색으로 증거 문서 수집
     /// 4. LLM으로 자연어 요약 생성
@@ -315,24 +413,73 @@ impl CcpService {
     ) -> anyhow::Result<CcpJudgmentResponse> {
         println!("🔍 CCP 판단 시작: {} / {}", request.company_id, request.ccp_id);
 
-        // 1. 센서 로그 통계 계산
-        let stats = self.calculate_stats(
-            &request.company_id,
-            &request.ccp_id,
-            &request.period_from,
-            &request.period_to,
-        )?;
+        // 회사/CCP별 한계기준 및 위험도 정책 (없으면 기본 정책)
+        let policy = CcpPolicyStore::from_database(self.db.clone())
+            .effective(&request.company_id, &request.ccp_id)?;
+
+        // 1. 통계 계산 (ccp_id가 MES CCP 유형이면 ccp_check_log, 아니면 데모 센서 로그)
+        let (stats, mut signals, check_results, lot_results, data_source) = match CcpType::parse(&request.ccp_id) {
+            Some(ccp_type) => {
+                let (stats, evaluations, lots) = self.judge_ccp_checks(
+                    ccp_type,
+                    &policy.limits,
+                    &request.period_from,
+                    &request.period_to,
+                    None,
+                )?;
+                let signals = RiskSignals {
+                    ng_rate: 0.0,
+                    breach_count: stats.ng_count as usize,
+                    max_consecutive_failures: ccp_policy::longest_failure_run(
+                        evaluations.iter().map(|e| e.judged_result != RESULT_PASS),
+                    ),
+                };
+                let non_pass: Vec<_> = evaluations
+                    .into_iter()
+                    .filter(|e| e.judged_result != RESULT_PASS)
+                    .collect();
+                (stats, signals, non_pass, lots, "ccp_check_log")
+            }
+            None => {
+                let stats = self.calculate_stats(
+                    &request.company_id,
+                    &request.ccp_id,
+                    &request.period_from,
+                    &request.period_to,
+                )?;
+                let signals = self.sensor_signals(
+                    &request.company_id,
+                    &request.ccp_id,
+                    &request.period_from,
+                    &request.period_to,
+                    &policy.limits,
+                )?;
+                (stats, signals, Vec::new(), Vec::new(), "ccp_sensors")
+            }
+        };
+        signals.ng_rate = stats.ng_rate;
 
         println!("📊 통계: 총 {}회, NG {}회, 비율 {:.1}%",
             stats.total_logs, stats.ng_count, stats.ng_rate * 100.0);
 
-        // 2. 룰베이스 위험도 판정
-        let risk_level = self.rule_based_risk(stats.ng_rate);
-        let rule_reason = format!(
-            "NG 비율 {:.1}%에 따른 {} 등급 판정",
-            stats.ng_rate * 100.0,
+        // 2. 정책 기반 위험도 판정 (NG 비율, 한계기준 이탈, 연속 NG 중 가장 높은 등급)
+        let assessment = policy.assess(&signals);
+        let risk_level = assessment.risk_level;
+        let mut rule_reason = format!(
+            "[{}] {} (최종 {} 등급 판정)",
+            policy.citation(),
+            assessment.reasons.join("; "),
             risk_level
         );
+        if !lot_results.is_empty() {
+            let count = |result: &str| lot_results.iter().filter(|l| l.result == result).count();
+            rule_reason.push_str(&format!(
+                " (LOT {}개 중 DEVIATION {}개, FAIL {}개)",
+                lot_results.len(),
+                count(RESULT_DEVIATION),
+                count(RESULT_FAIL)
+            ));
+        }
 
         println!("⚠️  위험도: {}", risk_level);
 
@@ -347,11 +494,14 @@ impl CcpService {
 
         println!("🔍 검색 쿼리: '{}'", search_query);
 
-        let evidence_docs = self.search_ccp_docs(
+        // 판단 기간 종료 시점에 유효했던 문서 개정본을 근거로 인용
+        let as_of = normalize_timestamp(&request.period_to, true)?;
+        let evidence_docs = self.search_ccp_docs_as_of(
             &request.company_id,
             Some(&request.ccp_id),
             search_query,
             3,
+            &as_of,
         )?;
 
         println!("📚 증거 문서: {}건 검색", evidence_docs.len());
@@ -378,7 +528,21 @@ impl CcpService {
 
         println!("✅ 판단 결과 저장: {}", judgment_id);
 
-        // 6. 응답 반환
+        // 6. FAIL/DEVIATION 점검이 있으면 시정조치 자동 발행 (이미 발행된 점검은 제외)
+        //    센서 로그는 점검 단위 판정이 없으므로 HIGH 위험도일 때 판단 단위로 발행
+        let corrective_actions = CorrectiveActionStore::from_database(self.db.clone());
+        let corrective_action = if data_source == "ccp_sensors" {
+            if risk_level == "HIGH" {
+                corrective_actions.open_for_high_risk(&request.company_id, &request.ccp_id, &judgment_id, &rule_reason)?
+            } else {
+                None
+            }
+        } else {
+            corrective_actions.open_for_deviations(&request.company_id, &request.ccp_id, "judgment", &judgment_id, &check_results)?
+        };
+        let corrective_action_id = corrective_action.map(|action| action.id);
+
+        // 7. 응답 반환
         Ok(CcpJudgmentResponse {
             stats,
             risk_level: risk_level.to_string(),
@@ -386,6 +550,10 @@ impl CcpService {
             llm_summary,
             evidence_docs,
             judgment_id,
+            data_source: data_source.to_string(),
+            lot_results,
+            check_results,
+            corrective_action_id,
         })
     }
 }
@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::env;
 
+use crate::services::spc_service::{ControlChartType, SpcAnalysis};
+
 /// 차트 타입
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -133,6 +135,9 @@ impl ChartService {
 - **fg_lot** (완제품LOT): fg_lot_no(PK), filling_lot_no(FK), fg_item_cd, qty, mfg_date, exp_date, qc_status(PENDING|PASS|FAIL|HOLD), location
 - **process_result** (공정실적): id(PK), batch_lot_no(FK), process_type(BATCHING|PASTEURIZATION|COOLING|HOLDING|TRANSFER), equipment_cd, start_time, end_time, target_temp, actual_temp, target_time_sec, actual_time_sec, result(OK|NG|PENDING), operator_id
 - **material_issue** (자재 출고): batch_lot_no(FK), seq, item_cd, lot_no, plan_qty, actual_qty, issue_time, operator_id
+- **lot_holds** (LOT 출하 보류): id(PK), lot_no, lot_type(BATCH|FG), status(HOLD|RELEASE_PENDING|RELEASED), reason, source_type(judgment|corrective_action|manual), held_at, released_at
+- **v_lot_hold_status** (보류 중인 완제품 LOT 뷰): fg_lot_no, fg_item_cd, batch_lot_no, hold_id, held_lot_no, lot_type, hold_status, reason, held_at
+  - 출고 가능 재고/출하 대상 조회 시 반드시 제외: `fg_lot_no NOT IN (SELECT fg_lot_no FROM v_lot_hold_status)`
 
 ### 4. 품질 검사 테이블
 - **qc_test** (품질검사-기본): qc_no(PK), test_type(INCOMING|IN_PROCESS|FINAL|HOLD_RELEASE), ref_type(INBOUND|BATCH|FILLING|FG), ref_no, item_cd, lot_no, test_date, tester_id, result(PASS|FAIL|CONDITIONAL), test_items(JSON), remarks
@@ -509,6 +514,76 @@ impl ChartService {
         }
     }
 
+    /// SPC 관리도 차트 (LLM 호출 없음 - API 키 불필요)
+    ///
+    /// 측정값/UCL/CL/LCL 라인과 규격 한계(있으면)를 Line 차트로 만들고,
+    /// 규칙 위반 점은 `violation_rules` 필드로 표시한다.
+    pub fn spc_chart(analysis: &SpcAnalysis) -> ChartResponse {
+        let limits = &analysis.limits;
+        let spec = analysis.spec.as_ref();
+        let number = |v: f64| serde_json::Number::from_f64(v).map(serde_json::Value::Number).unwrap_or(serde_json::Value::Null);
+
+        let chart_data: Vec<serde_json::Value> = analysis.points.iter().map(|point| {
+            let mut obj = serde_json::Map::new();
+            obj.insert("name".to_string(), serde_json::Value::String(point.label.clone()));
+            obj.insert("value".to_string(), number(point.value));
+            obj.insert("ucl".to_string(), number(limits.ucl));
+            obj.insert("cl".to_string(), number(limits.center_line));
+            obj.insert("lcl".to_string(), number(limits.lcl));
+            if let Some(usl) = spec.and_then(|s| s.usl) {
+                obj.insert("usl".to_string(), number(usl));
+            }
+            if let Some(lsl) = spec.and_then(|s| s.lsl) {
+                obj.insert("lsl".to_string(), number(lsl));
+            }
+            if !point.rules.is_empty() {
+                obj.insert("violation_rules".to_string(), json!(point.rules));
+            }
+            serde_json::Value::Object(obj)
+        }).collect();
+
+        let mut data_keys = vec![
+            DataKeyConfig { key: "value".to_string(), color: Some("#3b82f6".to_string()), label: "측정값".to_string() },
+            DataKeyConfig { key: "ucl".to_string(), color: Some("#ef4444".to_string()), label: "UCL".to_string() },
+            DataKeyConfig { key: "cl".to_string(), color: Some("#22c55e".to_string()), label: "CL".to_string() },
+            DataKeyConfig { key: "lcl".to_string(), color: Some("#ef4444".to_string()), label: "LCL".to_string() },
+        ];
+        if spec.and_then(|s| s.usl).is_some() {
+            data_keys.push(DataKeyConfig { key: "usl".to_string(), color: Some("#f59e0b".to_string()), label: "USL".to_string() });
+        }
+        if spec.and_then(|s| s.lsl).is_some() {
+            data_keys.push(DataKeyConfig { key: "lsl".to_string(), color: Some("#f59e0b".to_string()), label: "LSL".to_string() });
+        }
+
+        let chart_name = match limits.chart_type {
+            ControlChartType::IndividualMovingRange => "I-MR".to_string(),
+            ControlChartType::XbarR => format!("X̄-R (n={})", limits.subgroup_size),
+        };
+        let capability = analysis.capability.as_ref()
+            .map(|c| format!(", Cpk {:.2}", c.cpk))
+            .unwrap_or_default();
+        let status = if analysis.in_control {
+            "관리 상태".to_string()
+        } else {
+            format!("규칙 위반 {}건", analysis.violations.len())
+        };
+
+        ChartResponse {
+            chart_type: ChartType::Line,
+            title: format!("{} 관리도 - {}", chart_name, analysis.measure),
+            description: format!(
+                "CL {:.2}, UCL {:.2}, LCL {:.2} (측정값 {}개{})",
+                limits.center_line, limits.ucl, limits.lcl, analysis.sample_count, capability
+            ),
+            bar_line_data: Some(chart_data),
+            pie_data: None,
+            gauge_data: None,
+            data_keys: Some(data_keys),
+            x_axis_key: Some("name".to_string()),
+            insight: Some(status),
+        }
+    }
+
     /// 차트 데이터 기반 AI 인사이트 생성
     pub async fn generate_insight(&self, chart_response: &ChartResponse, user_request: &str) -> Result<String> {
         // 차트 데이터를 요약 텍스트로 변환
@@ -623,4 +698,25 @@ mod tests {
         let serialized = serde_json::to_string(&bar).unwrap();
         assert_eq!(serialized, r#""bar""#);
     }
+
+    #[test]
+    fn test_spc_chart() {
+        use crate::services::spc_service::{analyze_measurements, Measurement, SpecLimits};
+
+        let measurements: Vec<Measurement> = [85.2, 85.0, 84.8, 85.1, 90.0]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| Measurement { label: format!("P{}", i), value: *v })
+            .collect();
+        let spec = SpecLimits { lsl: Some(84.0), usl: None, target: None, source: "request".to_string() };
+        let analysis = analyze_measurements("sterilization_temp".to_string(), &measurements, 1, Some(spec)).unwrap();
+
+        let chart = ChartService::spc_chart(&analysis);
+        let data = chart.bar_line_data.unwrap();
+        assert_eq!(data.len(), 5);
+        assert!(data[0].get("lsl").is_some());
+        assert!(data[0].get("usl").is_none());
+        let keys: Vec<String> = chart.data_keys.unwrap().into_iter().map(|k| k.key).collect();
+        assert_eq!(keys, vec!["value", "ucl", "cl", "lcl", "lsl"]);
+    }
 }
@@ -8,6 +8,7 @@ use std::env;
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager};
 use uuid::Uuid;
+use crate::database::Database;
 use crate::utils::security::{sanitize_for_xml, detect_injection_attempt};
 use crate::services::cache_service::{CacheService, ChatMessage as CachedMessage};
 use crate::services::prompt_router::PromptRouter;
@@ -395,7 +396,7 @@ impl ChatService {
         };
         eprintln!("✅ ChatService initialized with API key: {}", masked);
 
-        let db_path = "chat_service.db";
+        let db_path = crate::utils::app_paths::chat_db_path()?;
         let db = Connection::open(db_path)?;
 
         // 테이블 생성
@@ -430,7 +431,7 @@ impl ChatService {
         };
         eprintln!("✅ ChatService (with AppHandle) initialized with API key: {}", masked);
 
-        let db_path = "chat_service.db";
+        let db_path = crate::utils::app_paths::chat_db_path()?;
         let db = Connection::open(db_path)?;
 
         Self::init_db(&db)?;
@@ -1013,16 +1014,13 @@ Examples:
         let plan = chart_service.generate_chart_plan(&user_request).await?;
         println!("📋 Chart plan generated: {} (SQL: {})", plan.title, plan.sql);
 
-        // 2. DB 연결
-        let db_path = std::env::var("APPDATA")
-            .map(|p| std::path::PathBuf::from(p).join("Judgify").join("judgify.db"))
-            .unwrap_or_else(|_| std::path::PathBuf::from("judgify.db"));
-
-        let conn = rusqlite::Connection::open(&db_path)
-            .map_err(|e| anyhow::anyhow!("DB 연결 실패: {}", e))?;
-
-        // 3. SQL 실행 및 차트 데이터 생성
-        let mut chart_response = chart_service.execute_and_transform(&conn, &plan)?;
+        // 2~3. 메인 DB 읽기 연결로 SQL 실행 및 차트 데이터 생성 (인사이트 생성 전에 연결 반환)
+        let mut chart_response = {
+            let reader = Database::new()?.get_reader();
+            let conn = reader.lock()
+                .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+            chart_service.execute_and_transform(&conn, &plan)?
+        };
 
         // 4. 인사이트 생성
         let insight = chart_service.generate_insight(&chart_response, &user_request).await?;
@@ -1279,23 +1277,21 @@ Examples:
     /// # Returns
     /// * `Vec<RagSearchResult>` - 검색 결과 목록
     fn search_knowledge_base(&self, query: &str, limit: usize) -> Vec<RagSearchResult> {
-        // Judgify 메인 DB 경로
-        let db_path = std::env::var("APPDATA")
-            .or_else(|_| std::env::var("HOME"))
-            .map(|app_data| {
-                std::path::PathBuf::from(app_data)
-                    .join("Judgify")
-                    .join("judgify.db")
-            })
-            .unwrap_or_else(|_| std::path::PathBuf::from("judgify.db"));
-
-        let conn = match rusqlite::Connection::open(&db_path) {
-            Ok(c) => c,
+        // Judgify 메인 DB 읽기 연결
+        let reader = match Database::new() {
+            Ok(db) => db.get_reader(),
             Err(e) => {
                 eprintln!("⚠️ RAG DB 연결 실패: {}", e);
                 return vec![];
             }
         };
+        let conn = match reader.lock() {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("⚠️ RAG DB lock 실패: {}", e);
+                return vec![];
+            }
+        };
 
         // FTS5 검색 쿼리 (BM25 랭킹)
         let sql = r#"
@@ -1353,23 +1349,21 @@ Examples:
     /// # Returns
     /// * `Option<ErpQueryResult>` - ERP 조회 결과
     fn query_erp_data(&self, query_type: &str, time_filter: &str) -> Option<ErpQueryResult> {
-        // Judgify 메인 DB 경로
-        let db_path = std::env::var("APPDATA")
-            .or_else(|_| std::env::var("HOME"))
-            .map(|app_data| {
-                std::path::PathBuf::from(app_data)
-                    .join("Judgify")
-                    .join("judgify.db")
-            })
-            .unwrap_or_else(|_| std::path::PathBuf::from("judgify.db"));
-
-        let conn = match rusqlite::Connection::open(&db_path) {
-            Ok(c) => c,
+        // Judgify 메인 DB 읽기 연결
+        let reader = match Database::new() {
+            Ok(db) => db.get_reader(),
             Err(e) => {
                 eprintln!("⚠️ ERP DB 연결 실패: {}", e);
                 return None;
             }
         };
+        let conn = match reader.lock() {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("⚠️ ERP DB lock 실패: {}", e);
+                return None;
+            }
+        };
 
         // 시간 필터 조건 생성
         // 현재 연도 동적 계산
@@ -1696,22 +1690,43 @@ Examples:
 
     /// 수요/재고 예측 데이터 조회 및 통계 계산
     fn query_forecast_data(&self, forecast_type: &str, item_id: Option<&str>) -> Option<ForecastResult> {
-        // judgify_large.db 경로 (seed_data.py가 생성한 DB)
-        // 1. 프로젝트 루트 디렉토리에서 찾기
-        // 2. 현재 디렉토리에서 찾기
-        let possible_paths = [
-            std::path::PathBuf::from("c:/dev/Judgify-core/judgify_large.db"),
-            std::path::PathBuf::from("judgify_large.db"),
-            std::path::PathBuf::from("../judgify_large.db"),
-        ];
+        // 데이터 디렉토리의 judgify_large.db (seed_data.py가 생성한 DB), 없으면 메인 DB
+        let paths = match crate::utils::app_paths::AppPaths::resolve() {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!("⚠️ 예측 DB 경로 확인 실패: {}", e);
+                return None;
+            }
+        };
+        let db_path = paths.forecast_db_path();
 
-        let db_path = possible_paths
-            .iter()
-            .find(|p| p.exists())
-            .cloned()
-            .unwrap_or_else(|| std::path::PathBuf::from("c:/dev/Judgify-core/judgify_large.db"));
+        let forecast = |conn: &Connection| match forecast_type {
+            "demand" => self.calculate_demand_forecast(conn, item_id),
+            "inventory" => self.calculate_inventory_forecast(conn, item_id),
+            _ => None
+        };
 
-        let conn = match rusqlite::Connection::open(&db_path) {
+        // 메인 DB는 연결 풀의 읽기 연결 사용 (백업 복구 시 함께 닫힘)
+        if db_path == paths.db_path() {
+            let reader = match Database::new() {
+                Ok(db) => db.get_reader(),
+                Err(e) => {
+                    eprintln!("⚠️ 예측 DB 연결 실패: {} (경로: {:?})", e, db_path);
+                    return None;
+                }
+            };
+            let conn = match reader.lock() {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("⚠️ 예측 DB lock 실패: {}", e);
+                    return None;
+                }
+            };
+            return forecast(&conn);
+        }
+
+        // 별도 예측 DB (seed_data.py 생성 파일, 백업/복구 대상 아님)
+        let conn = match Connection::open(&db_path) {
             Ok(c) => c,
             Err(e) => {
                 eprintln!("⚠️ 예측 DB 연결 실패: {} (경로: {:?})", e, db_path);
@@ -1721,11 +1736,7 @@ Examples:
 
         println!("📊 [예측] DB 연결 성공: {:?}", db_path);
 
-        match forecast_type {
-            "demand" => self.calculate_demand_forecast(&conn, item_id),
-            "inventory" => self.calculate_inventory_forecast(&conn, item_id),
-            _ => None
-        }
+        forecast(&conn)
     }
 
     /// 수요 예측 계산 (이동평균 + 성장률)
@@ -0,0 +1,295 @@
+// services/few_shot_retriever.rs - 임베딩 유사도 기반 Few-shot 샘플 검색
+//
+// 저장시 훈련 샘플을 임베딩하고, 판단 입력과 가장 유사한 샘플을
+// 합격/불합격 라벨 균형을 맞춰 k개 선택한다.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use crate::database::{Database, TrainingSample};
+use crate::utils::embeddings::EmbeddingGenerator;
+
+/// Few-shot 후보로 사용할 최소 정확도
+const MIN_SAMPLE_ACCURACY: f64 = 0.8;
+
+/// 유사도 계산 대상 후보 풀 크기 (최근 샘플 기준)
+const CANDIDATE_POOL_SIZE: u32 = 200;
+
+/// 검색 요청 중 바로 임베딩할 최대 샘플 수 (원격 백엔드 호출 제한, 나머지는 백그라운드)
+const MAX_INLINE_BACKFILL: usize = 5;
+
+/// 백그라운드 재임베딩이 진행 중인 워크플로우 (중복 실행 방지)
+static BACKFILL_IN_PROGRESS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+pub struct FewShotRetriever {
+    db: Database,
+    embedder: EmbeddingGenerator,
+}
+
+impl FewShotRetriever {
+    pub fn new() -> anyhow::Result<Self> {
+        Self::from_database(Database::new()?)
+    }
+
+    pub fn from_database(db: Database) -> anyhow::Result<Self> {
+        Ok(Self {
+            db,
+            embedder: EmbeddingGenerator::new()?,
+        })
+    }
+
+    pub fn with_embedder(embedder: EmbeddingGenerator) -> anyhow::Result<Self> {
+        Ok(Self {
+            db: Database::new()?,
+            embedder,
+        })
+    }
+
+    /// 훈련 샘플 임베딩 생성 후 저장
+    pub async fn embed_sample(&self, sample: &TrainingSample) -> anyhow::Result<()> {
+        let embedding = self.embed_input_json(&sample.input_data).await?;
+        self.db.save_training_sample_embedding(
+            &sample.id,
+            &sample.workflow_id,
+            &self.embedder.model_id(),
+            &embedding,
+        )?;
+        Ok(())
+    }
+
+    /// 입력 데이터와 가장 유사한 고정확도 샘플 k개 (라벨 균형)
+    ///
+    /// 임베딩 생성에 실패하면 최근 샘플 순으로 대체한다.
+    pub async fn retrieve(
+        &self,
+        workflow_id: &str,
+        input_data: &serde_json::Value,
+        limit: u32,
+    ) -> anyhow::Result<Vec<TrainingSample>> {
+        let candidates: Vec<TrainingSample> = self
+            .db
+            .get_training_samples(workflow_id, CANDIDATE_POOL_SIZE)?
+            .into_iter()
+            .filter(|s| s.accuracy.unwrap_or(0.0) >= MIN_SAMPLE_ACCURACY)
+            .collect();
+
+        if candidates.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let query_embedding = match self
+            .embedder
+            .generate(&EmbeddingGenerator::canonical_text(input_data))
+            .await
+        {
+            Ok(embedding) => embedding,
+            Err(e) => {
+                eprintln!("⚠️ 입력 임베딩 실패, 최근 샘플로 대체: {}", e);
+                return Ok(candidates.into_iter().take(limit as usize).collect());
+            }
+        };
+
+        // 저장된 임베딩 로드, 없는 샘플은 보충 (이전 버전 데이터/백엔드 변경 대비)
+        // 원격 백엔드는 몇 개만 바로 임베딩하고 나머지는 백그라운드로 넘긴다 (이번 검색에서는 제외)
+        let model = self.embedder.model_id();
+        let mut stored: HashMap<String, Vec<f32>> = self
+            .db
+            .get_training_sample_embeddings(workflow_id, &model)?
+            .into_iter()
+            .collect();
+
+        let missing: Vec<&TrainingSample> = candidates
+            .iter()
+            .filter(|s| !stored.contains_key(&s.id))
+            .collect();
+        let inline_count = if self.embedder.is_local() {
+            missing.len()
+        } else {
+            missing.len().min(MAX_INLINE_BACKFILL)
+        };
+        if missing.len() > inline_count {
+            self.spawn_backfill(workflow_id, missing[inline_count..].iter().map(|s| (*s).clone()).collect());
+        }
+
+        for sample in missing.into_iter().take(inline_count) {
+            match self.embed_input_json(&sample.input_data).await {
+                Ok(embedding) => {
+                    self.db
+                        .save_training_sample_embedding(&sample.id, workflow_id, &model, &embedding)?;
+                    stored.insert(sample.id.clone(), embedding);
+                }
+                Err(e) => eprintln!("⚠️ 샘플 {} 임베딩 실패: {}", sample.id, e),
+            }
+        }
+
+        let ranked_ids = self.embedder.find_most_similar(
+            &query_embedding,
+            candidates
+                .iter()
+                .filter_map(|s| stored.get(&s.id).map(|e| (s.id.clone(), e.clone())))
+                .collect(),
+            candidates.len(),
+        );
+
+        let mut by_id: HashMap<String, TrainingSample> =
+            candidates.into_iter().map(|s| (s.id.clone(), s)).collect();
+        let ranked: Vec<TrainingSample> = ranked_ids
+            .into_iter()
+            .filter_map(|(id, _)| by_id.remove(&id))
+            .collect();
+
+        Ok(Self::balance_by_label(ranked, limit as usize))
+    }
+
+    /// 임베딩이 없는 샘플을 백그라운드에서 임베딩 (워크플로우당 하나만 실행)
+    ///
+    /// 새 검색기는 환경변수 기준 백엔드를 사용하므로 `with_embedder`로 바꾼 백엔드와 다를 수 있다.
+    fn spawn_backfill(&self, workflow_id: &str, samples: Vec<TrainingSample>) {
+        let started = BACKFILL_IN_PROGRESS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(workflow_id.to_string());
+        if !started {
+            return;
+        }
+
+        let db = self.db.clone();
+        let workflow_id = workflow_id.to_string();
+        tokio::spawn(async move {
+            match FewShotRetriever::from_database(db) {
+                Ok(retriever) => {
+                    for sample in &samples {
+                        if let Err(e) = retriever.embed_sample(sample).await {
+                            eprintln!("⚠️ 샘플 {} 백그라운드 임베딩 실패: {}", sample.id, e);
+                        }
+                    }
+                    println!("📚 Few-shot 샘플 {}개 백그라운드 임베딩 완료", samples.len());
+                }
+                Err(e) => eprintln!("⚠️ 백그라운드 임베딩 준비 실패: {}", e),
+            }
+            BACKFILL_IN_PROGRESS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&workflow_id);
+        });
+    }
+
+    async fn embed_input_json(&self, input_json: &str) -> anyhow::Result<Vec<f32>> {
+        let value: serde_json::Value = serde_json::from_str(input_json)
+            .unwrap_or_else(|_| serde_json::Value::String(input_json.to_string()));
+        self.embedder
+            .generate(&EmbeddingGenerator::canonical_text(&value))
+            .await
+    }
+
+    /// 유사도 순 샘플에서 합격/불합격을 교대로 선택, 한쪽이 부족하면 나머지로 채움
+    fn balance_by_label(ranked: Vec<TrainingSample>, limit: usize) -> Vec<TrainingSample> {
+        let (mut positives, mut negatives): (Vec<_>, Vec<_>) =
+            ranked.into_iter().partition(|s| s.expected_result);
+        positives.reverse();
+        negatives.reverse();
+
+        let mut selected = Vec::with_capacity(limit);
+        while selected.len() < limit {
+            let take_positive = selected.len() % 2 == 0;
+            let next = if take_positive {
+                positives.pop().or_else(|| negatives.pop())
+            } else {
+                negatives.pop().or_else(|| positives.pop())
+            };
+            match next {
+                Some(sample) => selected.push(sample),
+                None => break,
+            }
+        }
+
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Workflow;
+    use crate::utils::embeddings::EmbeddingBackend;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn sample(workflow_id: &str, input: &str, expected: bool) -> TrainingSample {
+        TrainingSample {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: workflow_id.to_string(),
+            input_data: input.to_string(),
+            expected_result: expected,
+            actual_result: Some(expected),
+            accuracy: Some(0.9),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_balance_by_label_alternates() {
+        let ranked = vec![
+            sample("w", "{}", true),
+            sample("w", "{}", true),
+            sample("w", "{}", true),
+            sample("w", "{}", false),
+        ];
+        let first_positive = ranked[0].id.clone();
+        let negative = ranked[3].id.clone();
+
+        let selected = FewShotRetriever::balance_by_label(ranked, 3);
+
+        assert_eq!(selected.len(), 3);
+        assert_eq!(selected[0].id, first_positive);
+        assert_eq!(selected[1].id, negative);
+        assert!(selected[2].expected_result); // 불합격 부족분은 합격으로 채움
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_most_similar_samples() {
+        let retriever = FewShotRetriever::with_embedder(EmbeddingGenerator::with_backend(
+            EmbeddingBackend::Hashing { dimensions: 256 },
+        ))
+        .unwrap();
+        let workflow_id = Uuid::new_v4().to_string();
+
+        retriever
+            .db
+            .save_workflow(&Workflow {
+                id: workflow_id.clone(),
+                name: "Few-shot Retrieval Test".to_string(),
+                definition: "{}".to_string(),
+                rule_expression: None,
+                version: 1,
+                is_active: true,
+                created_at: Utc::now(),
+            })
+            .unwrap();
+
+        let near_pass = sample(&workflow_id, r#"{"temperature": 88, "vibration": 42}"#, true);
+        let far_pass = sample(&workflow_id, r#"{"temperature": 40, "vibration": 5}"#, true);
+        let near_fail = sample(&workflow_id, r#"{"temperature": 88, "vibration": 70}"#, false);
+
+        for s in [&near_pass, &far_pass, &near_fail] {
+            retriever.db.save_training_sample(s).unwrap();
+        }
+        // 하나만 미리 임베딩, 나머지는 검색시 보충
+        retriever.embed_sample(&near_pass).await.unwrap();
+
+        let selected = retriever
+            .retrieve(&workflow_id, &serde_json::json!({"temperature": 88, "vibration": 42}), 2)
+            .await
+            .unwrap();
+
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].id, near_pass.id);
+        assert_eq!(selected[1].id, near_fail.id);
+
+        let stored = retriever
+            .db
+            .get_training_sample_embeddings(&workflow_id, "hashing-v1:256")
+            .unwrap();
+        assert_eq!(stored.len(), 3);
+    }
+}
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 use chrono::Utc;
+use std::time::Instant;
 use crate::database::{Database, Judgment};
-use crate::services::{rule_engine::RuleEngine, llm_engine::LLMEngine, learning_service::LearningService};
+use crate::services::{rule_engine::RuleEngine, llm_engine::{ClaudeUsage, LLMEngine}, learning_service::LearningService};
+use crate::services::lot_disposition::LotDispositionStore;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JudgmentInput {
@@ -19,6 +20,11 @@ pub struct JudgmentResult {
     pub method_used: String,
     pub explanation: String,
     pub created_at: String,
+    /// A/B 테스트로 선택된 프롬프트 템플릿 (Rule 판단은 None)
+    #[serde(default)]
+    pub template_id: Option<String>,
+    #[serde(default)]
+    pub template_version: Option<i32>,
 }
 
 pub struct JudgmentEngine {
@@ -30,19 +36,27 @@ pub struct JudgmentEngine {
 
 impl JudgmentEngine {
     pub fn new() -> anyhow::Result<Self> {
+        Self::from_database(Database::new()?)
+    }
+
+    /// 공유 연결 풀로 생성 (하위 엔진도 같은 풀 사용)
+    pub fn from_database(db: Database) -> anyhow::Result<Self> {
         Ok(Self {
-            rule_engine: RuleEngine::new()?,
-            llm_engine: LLMEngine::new()?,
-            learning_service: LearningService::new()?,
-            db: Database::new()?,
+            rule_engine: RuleEngine::from_database(db.clone()),
+            llm_engine: LLMEngine::from_database(db.clone())?,
+            learning_service: LearningService::from_database(db.clone())?,
+            db,
         })
     }
 
     /// Few-shot 학습을 포함한 하이브리드 판단 (새로운 기본 메서드!)
     pub async fn judge_with_few_shot(&self, input: JudgmentInput) -> anyhow::Result<JudgmentResult> {
-        // 1. Few-shot 샘플 검색 (Learning Service)
+        let started = Instant::now();
+
+        // 1. Few-shot 샘플 검색 (Learning Service, 입력 유사도 기반)
         let few_shot_samples = self.learning_service
-            .get_few_shot_samples(input.workflow_id.clone(), 15)?;
+            .get_similar_few_shot_samples(&input.workflow_id, &input.input_data, 15)
+            .await?;
 
         println!("📚 Few-shot 샘플 개수: {}", few_shot_samples.len());
 
@@ -51,7 +65,7 @@ impl JudgmentEngine {
             Ok(rule_result) if rule_result.confidence >= 0.7 => {
                 // Rule 성공, Few-shot 불필요
                 println!("✅ Rule Engine 성공 (신뢰도: {:.1}%), Few-shot 생략", rule_result.confidence * 100.0);
-                self.save_result(&rule_result, &input)?;
+                self.save_result(&rule_result, &input, Some(started))?;
                 return Ok(rule_result);
             }
             Ok(rule_result) => {
@@ -59,14 +73,15 @@ impl JudgmentEngine {
                 println!("⚠️  Rule Engine 저신뢰도 ({:.1}%), LLM + Few-shot 실행", rule_result.confidence * 100.0);
 
                 match self.llm_engine.evaluate_with_few_shot(&input, &few_shot_samples).await {
-                    Ok(llm_result) => {
-                        let final_result = self.combine_results(rule_result, llm_result);
-                        self.save_result(&final_result, &input)?;
+                    Ok(llm) => {
+                        let final_result = self.combine_results(rule_result, llm.result);
+                        self.save_result(&final_result, &input, Some(started))?;
+                        self.record_token_usage(&final_result, llm.usage);
                         Ok(final_result)
                     }
                     Err(_) => {
                         // LLM 실패, Rule 결과 사용
-                        self.save_result(&rule_result, &input)?;
+                        self.save_result(&rule_result, &input, Some(started))?;
                         Ok(rule_result)
                     }
                 }
@@ -74,9 +89,10 @@ impl JudgmentEngine {
             Err(_) => {
                 // Rule 실패, LLM + Few-shot만 실행
                 println!("❌ Rule Engine 실패, LLM + Few-shot만 사용");
-                let llm_result = self.llm_engine.evaluate_with_few_shot(&input, &few_shot_samples).await?;
-                self.save_result(&llm_result, &input)?;
-                Ok(llm_result)
+                let llm = self.llm_engine.evaluate_with_few_shot(&input, &few_shot_samples).await?;
+                self.save_result(&llm.result, &input, Some(started))?;
+                self.record_token_usage(&llm.result, llm.usage);
+                Ok(llm.result)
             }
         }
     }
@@ -87,10 +103,12 @@ impl JudgmentEngine {
         self.judge_with_few_shot(input).await
     }
 
+    /// Rule/LLM 결과 결합
+    ///
+    /// LLM 결과가 채택된 경우에만 프롬프트 템플릿을 기록한다 (Rule 판단이 A/B 변형 성과로 집계되지 않도록).
     fn combine_results(&self, rule: JudgmentResult, llm: JudgmentResult) -> JudgmentResult {
         if llm.confidence > rule.confidence {
             JudgmentResult {
-                id: Uuid::new_v4().to_string(),
                 method_used: "hybrid".to_string(),
                 explanation: format!(
                     "하이브리드 판단 결과:\n\n[Rule Engine (신뢰도: {:.1}%)]\n{}\n\n[LLM Engine (신뢰도: {:.1}%)]\n{}",
@@ -106,7 +124,19 @@ impl JudgmentEngine {
         }
     }
 
-    fn save_result(&self, result: &JudgmentResult, input: &JudgmentInput) -> anyhow::Result<()> {
+    /// LLM 토큰 사용량을 최종 저장된 판단 id로 기록 (save_result 이후 호출)
+    fn record_token_usage(&self, result: &JudgmentResult, usage: Option<ClaudeUsage>) {
+        if let Some(usage) = usage {
+            self.llm_engine.record_token_usage(&result.id, &usage);
+        }
+    }
+
+    fn save_result(
+        &self,
+        result: &JudgmentResult,
+        input: &JudgmentInput,
+        started: Option<Instant>,
+    ) -> anyhow::Result<()> {
         let judgment = Judgment {
             id: result.id.clone(),
             workflow_id: result.workflow_id.clone(),
@@ -116,9 +146,21 @@ impl JudgmentEngine {
             method_used: result.method_used.clone(),
             explanation: result.explanation.clone(),
             created_at: Utc::now(),
+            template_id: result.template_id.clone(),
+            template_version: result.template_version,
+            latency_ms: started.map(|t| t.elapsed().as_millis() as i64),
         };
 
         self.db.save_judgment(&judgment)?;
+
+        // NG 판단이 LOT을 가리키면 출하 보류 (보류 실패는 판단 결과에 영향 없음)
+        if !result.result {
+            if let Err(e) = LotDispositionStore::from_database(self.db.clone())
+                .hold_from_judgment(&result.id, &input.input_data, &result.explanation)
+            {
+                eprintln!("⚠️  LOT 보류 실패: {}", e);
+            }
+        }
         Ok(())
     }
 
@@ -139,6 +181,8 @@ impl JudgmentEngine {
                 method_used: j.method_used,
                 explanation: j.explanation,
                 created_at: j.created_at.to_rfc3339(),
+                template_id: j.template_id,
+                template_version: j.template_version,
             })
             .collect())
     }
@@ -239,6 +283,8 @@ mod tests {
             method_used: "rule".to_string(),
             explanation: "Rule 판단".to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
         };
 
         let llm_result = JudgmentResult {
@@ -249,6 +295,8 @@ mod tests {
             method_used: "llm".to_string(),
             explanation: "LLM 판단".to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
         };
 
         let combined = engine.combine_results(rule_result, llm_result.clone());
@@ -259,6 +307,100 @@ mod tests {
         assert!(combined.explanation.contains("하이브리드 판단 결과"));
     }
 
+    #[test]
+    fn test_combine_results_drops_template_when_rule_wins() {
+        let engine = JudgmentEngine::new().unwrap();
+
+        let rule_result = JudgmentResult {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: "test".to_string(),
+            result: false,
+            confidence: 0.65,
+            method_used: "rule".to_string(),
+            explanation: "Rule 판단".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
+        };
+
+        let llm_result = JudgmentResult {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: "test".to_string(),
+            result: true,
+            confidence: 0.5,
+            method_used: "llm".to_string(),
+            explanation: "LLM 판단".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: Some("tpl-b".to_string()),
+            template_version: Some(2),
+        };
+
+        let combined = engine.combine_results(rule_result.clone(), llm_result);
+
+        // Rule 결과가 채택되면 A/B 변형 성과에 포함하지 않음
+        assert_eq!(combined.id, rule_result.id);
+        assert_eq!(combined.result, rule_result.result);
+        assert_eq!(combined.method_used, "rule");
+        assert!(combined.template_id.is_none());
+        assert!(combined.template_version.is_none());
+    }
+
+    #[test]
+    fn test_hybrid_token_usage_saved_with_foreign_keys() {
+        // token_usage.judgment_id -> judgments(id) 외래키를 켠 상태에서도 토큰 사용량이 기록되는지 검증
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::open(temp_dir.path().join("fk.db")).unwrap();
+        db.get_connection()
+            .lock()
+            .unwrap()
+            .execute_batch("PRAGMA foreign_keys = ON;")
+            .unwrap();
+        let engine = JudgmentEngine::from_database(db.clone()).unwrap();
+
+        let workflow_id = Uuid::new_v4().to_string();
+        db.save_workflow(&Workflow {
+            id: workflow_id.clone(),
+            name: "FK Workflow".to_string(),
+            definition: "{}".to_string(),
+            rule_expression: Some("temperature > 85".to_string()),
+            version: 1,
+            is_active: true,
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+        let input = JudgmentInput {
+            workflow_id: workflow_id.clone(),
+            input_data: serde_json::json!({"temperature": 90}),
+        };
+        let rule_result = JudgmentResult {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: workflow_id.clone(),
+            result: true,
+            confidence: 0.6,
+            method_used: "rule".to_string(),
+            explanation: "Rule 판단".to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
+        };
+        let llm_result = JudgmentResult {
+            id: Uuid::new_v4().to_string(),
+            method_used: "llm_few_shot".to_string(),
+            confidence: 0.9,
+            explanation: "LLM 판단".to_string(),
+            ..rule_result.clone()
+        };
+
+        let combined = engine.combine_results(rule_result, llm_result);
+        engine.save_result(&combined, &input, None).unwrap();
+        engine.record_token_usage(&combined, Some(ClaudeUsage { input_tokens: 1200, output_tokens: 300 }));
+
+        let usage = db.get_token_usage_by_judgment(&combined.id).unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].tokens_used, 1500);
+    }
+
     #[tokio::test]
     async fn test_get_history() {
         let engine = JudgmentEngine::new().unwrap();
@@ -278,9 +420,11 @@ mod tests {
             method_used: "rule".to_string(),
             explanation: "Test".to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
         };
 
-        engine.save_result(&result, &input).unwrap();
+        engine.save_result(&result, &input, None).unwrap();
 
         // 히스토리 조회
         let history = engine.get_history(Some(workflow_id.clone()), 10).await.unwrap();
@@ -507,6 +651,8 @@ mod tests {
             method_used: "llm_few_shot".to_string(),
             explanation: "📚 Few-shot 학습: 10 개 유사 사례 참조".to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
         };
 
         let result_without_few_shot = JudgmentResult {
@@ -517,6 +663,8 @@ mod tests {
             method_used: "llm".to_string(),
             explanation: "LLM 판단".to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
         };
 
         // 검증
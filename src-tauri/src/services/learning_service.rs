@@ -1,25 +1,140 @@
 use uuid::Uuid;
 use chrono::Utc;
-use crate::database::{Database, TrainingSample, Feedback, Workflow};
+use crate::database::{Database, TrainingSample, Feedback, Workflow, RuleEvaluationRecord};
+use crate::services::few_shot_retriever::FewShotRetriever;
+use crate::services::audit_log::{self, AuditLogStore, NewAuditEntry};
 use crate::algorithms::{
+    decision_tree_converter::DecisionTreeConverter,
     frequency_analyzer::FrequencyAnalyzer,
     llm_pattern_discoverer::LLMPatternDiscoverer,
+    rule_evaluator::{Fold, RuleEvaluator},
     rule_integrator::RuleIntegrator,
-    FeedbackData,
+    ExtractedRule, FeedbackData, RuleMetrics, ValidatedRule,
 };
+use serde::{Deserialize, Serialize};
+
+/// 홀드아웃 테스트 세트 비율 (라벨별 층화)
+const HOLDOUT_TEST_RATIO: f64 = 0.2;
+
+/// Rule 승격 최소 F1 기본값 (JUDGIFY_RULE_MIN_F1 환경변수로 변경)
+const DEFAULT_MIN_PROMOTION_F1: f64 = 0.8;
+
+/// 알고리즘별 Rule 추출 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractorReport {
+    /// "frequency" | "decision_tree" | "llm"
+    pub method: String,
+
+    /// 전체 샘플에서 추출한 후보 Rule과 교차검증 점수
+    pub candidates: Vec<ValidatedRule>,
+
+    /// fold별 holdout 정확도 평균 (교차검증 불가시 None)
+    pub holdout_accuracy: Option<f64>,
+
+    /// 실행 실패/건너뜀 사유
+    pub error: Option<String>,
+}
+
+impl ExtractorReport {
+    fn skipped(method: &str, reason: &str) -> Self {
+        ExtractorReport {
+            method: method.to_string(),
+            candidates: Vec::new(),
+            holdout_accuracy: None,
+            error: Some(reason.to_string()),
+        }
+    }
+}
+
+/// Rule 추출 리포트 (extract_rules 결과, rule_evaluation_reports에 저장)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleExtractionReport {
+    pub workflow_id: String,
+    pub sample_count: usize,
+    pub train_size: usize,
+    pub test_size: usize,
+    pub folds: usize,
+    pub extractors: Vec<ExtractorReport>,
+
+    /// 교차검증 점수로 선택된 Rule
+    pub selected_rule: Option<ExtractedRule>,
+
+    /// 선택된 Rule의 테스트 세트 평가 결과
+    pub selected_metrics: Option<RuleMetrics>,
+
+    /// 승격 최소 F1 (테스트 세트 기준)
+    pub min_f1: f64,
+
+    /// Workflow에 저장되었는지 여부 (min_f1 미달시 거부)
+    pub promoted: bool,
+
+    /// 승격시 새 Rule 버전
+    pub rule_version: Option<i32>,
+
+    /// 저장된 리포트 ID
+    pub report_id: Option<String>,
+    pub message: String,
+}
+
+/// Rule 추출 알고리즘 (교차검증시 fold마다 재실행)
+enum RuleExtractor {
+    Frequency(FrequencyAnalyzer),
+    DecisionTree(DecisionTreeConverter),
+    Llm(LLMPatternDiscoverer),
+}
+
+impl RuleExtractor {
+    fn method(&self) -> &'static str {
+        match self {
+            RuleExtractor::Frequency(_) => "frequency",
+            RuleExtractor::DecisionTree(_) => "decision_tree",
+            RuleExtractor::Llm(_) => "llm",
+        }
+    }
+
+    async fn extract(&self, feedback_data: Vec<FeedbackData>) -> anyhow::Result<Vec<ExtractedRule>> {
+        match self {
+            RuleExtractor::Frequency(analyzer) => analyzer.extract_rules(feedback_data),
+            RuleExtractor::DecisionTree(converter) => converter.extract_rules(feedback_data),
+            RuleExtractor::Llm(discoverer) => discoverer.extract_rules(feedback_data).await,
+        }
+    }
+}
 
 pub struct LearningService {
     db: Database,
+    retriever: FewShotRetriever,
+    min_promotion_f1: f64,
 }
 
 impl LearningService {
     pub fn new() -> anyhow::Result<Self> {
+        Self::from_database(Database::new()?)
+    }
+
+    pub fn from_database(db: Database) -> anyhow::Result<Self> {
+        let min_promotion_f1 = std::env::var("JUDGIFY_RULE_MIN_F1")
+            .ok()
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(DEFAULT_MIN_PROMOTION_F1);
+
         Ok(Self {
-            db: Database::new()?,
+            retriever: FewShotRetriever::from_database(db.clone())?,
+            db,
+            min_promotion_f1,
         })
     }
 
-    pub fn save_feedback(
+    /// Rule 승격 최소 F1 지정 (0.0 ~ 1.0)
+    pub fn with_min_promotion_f1(mut self, min_f1: f64) -> anyhow::Result<Self> {
+        if !(0.0..=1.0).contains(&min_f1) {
+            return Err(anyhow::anyhow!("min_f1 must be between 0.0 and 1.0: {}", min_f1));
+        }
+        self.min_promotion_f1 = min_f1;
+        Ok(self)
+    }
+
+    pub async fn save_feedback(
         &self,
         judgment_id: String,
         feedback_type: String,
@@ -55,6 +170,11 @@ impl LearningService {
 
                 self.db.save_training_sample(&training_sample)
                     .map_err(|e| anyhow::anyhow!("Failed to save training sample: {}", e))?;
+
+                // 유사도 검색용 임베딩 (실패해도 피드백 저장은 유지, 검색시 재시도)
+                if let Err(e) = self.retriever.embed_sample(&training_sample).await {
+                    eprintln!("⚠️ 훈련 샘플 임베딩 실패: {}", e);
+                }
             }
         }
 
@@ -77,13 +197,23 @@ impl LearningService {
             .collect())
     }
 
-    /// 추출된 Rule을 Workflow에 저장
+    /// 판단 입력과 유사한 Few-shot 샘플 (임베딩 유사도 + 라벨 균형)
+    pub async fn get_similar_few_shot_samples(
+        &self,
+        workflow_id: &str,
+        input_data: &serde_json::Value,
+        limit: u32,
+    ) -> anyhow::Result<Vec<TrainingSample>> {
+        self.retriever.retrieve(workflow_id, input_data, limit).await
+    }
+
+    /// 추출된 Rule을 Workflow에 저장 (새 버전 반환)
     pub fn save_extracted_rule(
         &self,
         workflow_id: String,
         rule_expression: String,
         confidence: f64,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<i32> {
         // 1. Workflow 로드
         let workflow = self.db.get_workflow(&workflow_id)?
             .ok_or_else(|| anyhow::anyhow!("Workflow not found: {}", workflow_id))?;
@@ -95,8 +225,24 @@ impl LearningService {
             ..workflow
         };
 
-        // 3. DB 저장
-        self.db.save_workflow(&updated_workflow)?;
+        // 3. DB 저장 + 감사 기록 (룰 변경, 한 트랜잭션)
+        let entry = NewAuditEntry {
+            actor: &audit_log::current_actor(),
+            action: audit_log::ACTION_RULE_UPDATE,
+            entity_type: "workflow",
+            entity_id: &workflow_id,
+            before: Some(serde_json::json!({
+                "rule_expression": workflow.rule_expression,
+                "version": workflow.version,
+            })),
+            after: Some(serde_json::json!({
+                "rule_expression": updated_workflow.rule_expression,
+                "version": updated_workflow.version,
+                "confidence": confidence,
+            })),
+        };
+        AuditLogStore::from_database(self.db.clone())
+            .record_change(&entry, |conn| Ok(Database::upsert_workflow(conn, &updated_workflow)?))?;
 
         println!(
             "✅ Rule saved to workflow {}: {} (confidence: {:.2}, version: {} → {})",
@@ -104,62 +250,209 @@ impl LearningService {
             workflow.version, updated_workflow.version
         );
 
-        Ok(())
+        Ok(updated_workflow.version)
     }
 
-    pub async fn extract_rules(&self, workflow_id: String) -> anyhow::Result<String> {
+    /// 워크플로우의 Rule 평가 리포트 이력 (최신순)
+    pub fn get_rule_evaluation_reports(
+        &self,
+        workflow_id: &str,
+        limit: u32,
+    ) -> anyhow::Result<Vec<RuleEvaluationRecord>> {
+        Ok(self.db.get_rule_evaluation_reports(workflow_id, limit)?)
+    }
+
+    /// 세 가지 알고리즘으로 Rule 추출 → 학습 세트 k-fold 교차검증 → 검증 점수 기준 통합
+    /// → 테스트 세트 평가 후 min_f1 이상일 때만 Workflow에 승격, 리포트는 항상 저장
+    pub async fn extract_rules(&self, workflow_id: String) -> anyhow::Result<RuleExtractionReport> {
         // 1. Load feedback data from database
         let samples = self.db.get_training_samples(&workflow_id, 100)?;
 
+        let mut report = RuleExtractionReport {
+            workflow_id: workflow_id.clone(),
+            sample_count: samples.len(),
+            train_size: 0,
+            test_size: 0,
+            folds: 0,
+            extractors: Vec::new(),
+            selected_rule: None,
+            selected_metrics: None,
+            min_f1: self.min_promotion_f1,
+            promoted: false,
+            rule_version: None,
+            report_id: None,
+            message: String::new(),
+        };
+
         if samples.is_empty() {
-            return Ok("No training samples available".to_string());
+            report.message = "No training samples available".to_string();
+            return Ok(report);
         }
 
+        let workflow = self.db.get_workflow(&workflow_id)?
+            .ok_or_else(|| anyhow::anyhow!("Workflow not found: {}", workflow_id))?;
+
         // Convert TrainingSample to FeedbackData
+        // 훈련 샘플은 긍정 피드백으로 확인된 판단이므로 expected_result가 정답 라벨
         let feedback_data: Vec<FeedbackData> = samples
             .into_iter()
-            .map(|sample| {
-                let is_positive = sample.accuracy.unwrap_or(0.0) >= 0.7;
-                FeedbackData {
-                    input_json: sample.input_data,
-                    is_positive,
-                    judgment_result: sample.expected_result,
-                    judgment_id: sample.id.clone(),
-                }
+            .map(|sample| FeedbackData {
+                input_json: sample.input_data,
+                is_positive: sample.expected_result,
+                judgment_result: sample.expected_result,
+                judgment_id: sample.id.clone(),
             })
             .collect();
 
-        // 2. Run Algorithm 1: Frequency Analysis
-        let freq_analyzer = FrequencyAnalyzer::new(0.80);
-        let freq_rules = freq_analyzer.extract_rules(feedback_data.clone())?;
+        // 2. 학습/테스트 분할 (테스트 세트는 추출과 교차검증에 사용하지 않음)
+        let evaluator = RuleEvaluator::default();
+        let Fold { train, test } = evaluator.holdout_split(&feedback_data, HOLDOUT_TEST_RATIO);
+        report.train_size = train.len();
+        report.test_size = test.len();
+
+        // 3. 알고리즘 구성 (LLM 패턴 발견은 Claude API 키가 있을 때만)
+        let mut extractors = vec![
+            RuleExtractor::Frequency(FrequencyAnalyzer::new(0.80)),
+            RuleExtractor::DecisionTree(DecisionTreeConverter::new()),
+        ];
+        let llm_skipped = match std::env::var("ANTHROPIC_API_KEY") {
+            Ok(api_key) => {
+                extractors.push(RuleExtractor::Llm(LLMPatternDiscoverer::new(api_key)));
+                false
+            }
+            Err(_) => true,
+        };
+
+        // 4. 알고리즘별 후보 Rule + 교차검증 점수 + 테스트 세트 지표
+        let folds = evaluator.split_folds(&train);
+        report.folds = folds.len();
 
-        // 3. Run Algorithm 3: LLM Pattern Discovery (parallel execution possible)
-        let llm_rules = if let Ok(api_key) = std::env::var("OPENAI_API_KEY") {
-            let llm_discoverer = LLMPatternDiscoverer::new(api_key);
-            llm_discoverer.extract_rules(feedback_data).await?
-        } else {
-            Vec::new()
+        for extractor in &extractors {
+            let mut extractor_report = Self::cross_validate(extractor, &train, &folds).await;
+            if !test.is_empty() {
+                for candidate in &mut extractor_report.candidates {
+                    candidate.holdout = Some(RuleEvaluator::evaluate(&candidate.rule.expression, &test));
+                }
+            }
+            report.extractors.push(extractor_report);
+        }
+        if llm_skipped {
+            report.extractors.push(ExtractorReport::skipped(
+                "llm",
+                "ANTHROPIC_API_KEY not configured",
+            ));
+        }
+
+        // 5. 교차검증 점수 기준 통합 (자체 보고 신뢰도는 사용하지 않음)
+        let candidates: Vec<ValidatedRule> = report
+            .extractors
+            .iter()
+            .flat_map(|r| r.candidates.iter().cloned())
+            .collect();
+        report.selected_rule = RuleIntegrator::new().select_validated(&candidates);
+        report.selected_metrics = match (&report.selected_rule, test.is_empty()) {
+            (Some(rule), false) => Some(RuleEvaluator::evaluate(&rule.expression, &test)),
+            _ => None,
+        };
+
+        // 6. 테스트 세트 F1이 임계값 이상일 때만 승격
+        report.message = match (&report.selected_rule, &report.selected_metrics) {
+            (None, _) => "No rules passed cross-validation".to_string(),
+            (Some(_), None) => "Not enough samples for a holdout test set - rule not promoted".to_string(),
+            (Some(rule), Some(metrics)) if metrics.f1 < self.min_promotion_f1 => format!(
+                "Rule {} rejected: holdout F1 {:.2} < {:.2}",
+                rule.expression, metrics.f1, self.min_promotion_f1
+            ),
+            (Some(rule), Some(metrics)) => {
+                let version = self.save_extracted_rule(
+                    workflow_id.clone(),
+                    rule.expression.clone(),
+                    rule.confidence,
+                )?;
+                report.promoted = true;
+                report.rule_version = Some(version);
+                format!(
+                    "Extracted Rule: {} (cv score: {:.2}, holdout F1: {:.2}) - Saved to workflow {} v{}",
+                    rule.expression, rule.confidence, metrics.f1, workflow_id, version
+                )
+            }
+        };
+
+        // 7. 리포트 저장 (거부된 경우도 평가 당시 버전으로 기록)
+        let report_id = Uuid::new_v4().to_string();
+        report.report_id = Some(report_id.clone());
+        self.db.save_rule_evaluation_report(&RuleEvaluationRecord {
+            id: report_id,
+            workflow_id: workflow_id.clone(),
+            workflow_version: report.rule_version.unwrap_or(workflow.version),
+            rule_expression: report.selected_rule.as_ref().map(|r| r.expression.clone()),
+            promoted: report.promoted,
+            f1_score: report.selected_metrics.as_ref().map(|m| m.f1),
+            min_f1: self.min_promotion_f1,
+            report_json: serde_json::to_string(&report)?,
+            created_at: Utc::now(),
+        })?;
+
+        Ok(report)
+    }
+
+    /// 알고리즘 하나를 전체 샘플로 실행하고 k-fold로 검증
+    ///
+    /// fold마다 학습 fold로 재추출하여 (1) 최고 신뢰도 Rule의 검증 fold 정확도 평균을
+    /// holdout 정확도로, (2) 각 후보 Rule이 재추출된 fold의 검증 정확도 합 / k를 cv 점수로 사용
+    async fn cross_validate(
+        extractor: &RuleExtractor,
+        feedback_data: &[FeedbackData],
+        folds: &[Fold],
+    ) -> ExtractorReport {
+        let method = extractor.method();
+
+        let rules = match extractor.extract(feedback_data.to_vec()).await {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("⚠️ {} Rule 추출 실패: {}", method, e);
+                return ExtractorReport::skipped(method, &e.to_string());
+            }
         };
 
-        // 4. Integrate rules from both algorithms
-        let integrator = RuleIntegrator::new();
-        let best_rule = integrator.integrate_rules(vec![freq_rules, llm_rules])?;
-
-        // 5. Save rule to workflow and return result
-        if let Some(rule) = best_rule {
-            // 자동으로 Workflow에 저장
-            self.save_extracted_rule(
-                workflow_id.clone(),
-                rule.expression.clone(),
-                rule.confidence,
-            )?;
-
-            Ok(format!(
-                "Extracted Rule: {} (confidence: {:.2}, method: {}) - Saved to workflow {}",
-                rule.expression, rule.confidence, rule.method, workflow_id
-            ))
-        } else {
-            Ok("No confident rules found".to_string())
+        let mut score_sums = vec![0.0; rules.len()];
+        let mut rediscovered = vec![0usize; rules.len()];
+        let mut holdout_scores = Vec::with_capacity(folds.len());
+
+        for fold in folds {
+            // fold 추출 실패는 0점 처리 (불안정한 알고리즘에 불리하게)
+            let fold_rules = extractor.extract(fold.train.clone()).await.unwrap_or_else(|e| {
+                eprintln!("⚠️ {} fold Rule 추출 실패: {}", method, e);
+                Vec::new()
+            });
+
+            holdout_scores.push(RuleEvaluator::holdout_accuracy(&fold_rules, &fold.test));
+
+            for (i, rule) in rules.iter().enumerate() {
+                if let Some(score) = RuleEvaluator::fold_score(&rule.expression, &fold_rules, &fold.test) {
+                    score_sums[i] += score;
+                    rediscovered[i] += 1;
+                }
+            }
+        }
+
+        let candidates = rules
+            .into_iter()
+            .enumerate()
+            .map(|(i, rule)| ValidatedRule {
+                rule,
+                cv_score: if folds.is_empty() { 0.0 } else { score_sums[i] / folds.len() as f64 },
+                folds_rediscovered: rediscovered[i],
+                holdout: None,
+            })
+            .collect();
+
+        ExtractorReport {
+            method: method.to_string(),
+            candidates,
+            holdout_accuracy: (!holdout_scores.is_empty())
+                .then(|| holdout_scores.iter().sum::<f64>() / holdout_scores.len() as f64),
+            error: None,
         }
     }
 }
@@ -255,4 +548,143 @@ mod tests {
         assert_eq!(workflow_v3.version, 3);
         assert_eq!(workflow_v3.rule_expression, Some("temperature > 90 && vibration > 40".to_string()));
     }
+
+    fn feedback(positive: bool, temperature: f64) -> FeedbackData {
+        FeedbackData {
+            input_json: format!(r#"{{"temperature": {}}}"#, temperature),
+            is_positive: positive,
+            judgment_result: positive,
+            judgment_id: Uuid::new_v4().to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cross_validate_scores_rules_on_unseen_folds() {
+        let data: Vec<FeedbackData> = (0..10)
+            .map(|i| feedback(true, 90.0 + i as f64 * 0.1))
+            .chain((0..10).map(|i| feedback(false, 70.0 + i as f64 * 0.1)))
+            .collect();
+        let folds = RuleEvaluator::default().split_folds(&data);
+
+        let report = LearningService::cross_validate(
+            &RuleExtractor::Frequency(FrequencyAnalyzer::new(0.80)),
+            &data,
+            &folds,
+        )
+        .await;
+
+        assert_eq!(report.method, "frequency");
+        assert!(report.error.is_none());
+        assert!(report.holdout_accuracy.unwrap() > 0.9);
+
+        // 합격 샘플 90.x → "temperature > 85" (5단위 반올림) 매 fold 재추출
+        let candidate = report
+            .candidates
+            .iter()
+            .find(|c| c.rule.expression == "temperature > 85")
+            .unwrap();
+        assert_eq!(candidate.folds_rediscovered, folds.len());
+        assert!((candidate.cv_score - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_cross_validate_reports_extractor_failure() {
+        let data = vec![FeedbackData {
+            input_json: "not json".to_string(),
+            is_positive: true,
+            judgment_result: true,
+            judgment_id: Uuid::new_v4().to_string(),
+        }; 12];
+
+        let report = LearningService::cross_validate(
+            &RuleExtractor::DecisionTree(DecisionTreeConverter::new()),
+            &data,
+            &RuleEvaluator::default().split_folds(&data),
+        )
+        .await;
+
+        assert_eq!(report.method, "decision_tree");
+        assert!(report.error.is_some());
+        assert!(report.candidates.is_empty());
+    }
+
+    fn seed_workflow_with_samples(service: &LearningService, samples: &[(bool, f64)]) -> String {
+        let workflow_id = Uuid::new_v4().to_string();
+        service.db.save_workflow(&Workflow {
+            id: workflow_id.clone(),
+            name: "Rule Holdout Test".to_string(),
+            definition: "{}".to_string(),
+            rule_expression: None,
+            version: 1,
+            is_active: true,
+            created_at: Utc::now(),
+        }).unwrap();
+
+        for (expected, temperature) in samples {
+            service.db.save_training_sample(&TrainingSample {
+                id: Uuid::new_v4().to_string(),
+                workflow_id: workflow_id.clone(),
+                input_data: format!(r#"{{"temperature": {}}}"#, temperature),
+                expected_result: *expected,
+                actual_result: Some(*expected),
+                accuracy: Some(0.9),
+                created_at: Utc::now(),
+            }).unwrap();
+        }
+
+        workflow_id
+    }
+
+    #[tokio::test]
+    async fn test_extract_rules_promotes_and_persists_report() {
+        let service = LearningService::new().unwrap().with_min_promotion_f1(0.8).unwrap();
+        let samples: Vec<(bool, f64)> = (0..15)
+            .map(|i| (true, 90.0 + i as f64 * 0.1))
+            .chain((0..15).map(|i| (false, 70.0 + i as f64 * 0.1)))
+            .collect();
+        let workflow_id = seed_workflow_with_samples(&service, &samples);
+
+        let report = service.extract_rules(workflow_id.clone()).await.unwrap();
+
+        assert_eq!(report.train_size + report.test_size, 30);
+        assert_eq!(report.test_size, 6);
+        assert!(report.promoted, "{}", report.message);
+        assert_eq!(report.rule_version, Some(2));
+        let metrics = report.selected_metrics.as_ref().unwrap();
+        assert_eq!(metrics.sample_count(), 6);
+        assert!(metrics.f1 >= 0.8);
+
+        let records = service.get_rule_evaluation_reports(&workflow_id, 10).unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].promoted);
+        assert_eq!(records[0].workflow_version, 2);
+        assert_eq!(records[0].id, report.report_id.clone().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_extract_rules_refuses_promotion_below_threshold() {
+        let service = LearningService::new().unwrap().with_min_promotion_f1(0.95).unwrap();
+        // 고온인데 불합격인 샘플 섞임 → temperature Rule의 precision 저하
+        let samples: Vec<(bool, f64)> = (0..10)
+            .map(|i| (true, 90.0 + i as f64 * 0.1))
+            .chain((0..5).map(|i| (false, 90.0 + i as f64 * 0.1)))
+            .chain((0..5).map(|i| (false, 70.0 + i as f64 * 0.1)))
+            .collect();
+        let workflow_id = seed_workflow_with_samples(&service, &samples);
+
+        let report = service.extract_rules(workflow_id.clone()).await.unwrap();
+
+        assert!(!report.promoted);
+        assert!(report.rule_version.is_none());
+        let workflow = service.db.get_workflow(&workflow_id).unwrap().unwrap();
+        assert_eq!(workflow.version, 1);
+        assert!(workflow.rule_expression.is_none());
+
+        let records = service.get_rule_evaluation_reports(&workflow_id, 10).unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(!records[0].promoted);
+        assert_eq!(records[0].workflow_version, 1);
+
+        assert!(LearningService::new().unwrap().with_min_promotion_f1(1.5).is_err());
+    }
 }
@@ -1,11 +1,27 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 use crate::services::judgment_engine::{JudgmentInput, JudgmentResult};
-use crate::database::Database;
+use crate::services::prompt_engine::PromptEngine;
+use crate::services::few_shot_retriever::FewShotRetriever;
+use crate::services::complexity_analyzer::JudgmentComplexity;
+use crate::database::{Database, PromptTemplate, TokenUsage};
 use crate::utils::security::{sanitize_for_xml, validate_llm_response};
 
-#[derive(Serialize, Deserialize)]
+const CLAUDE_MODEL: &str = "claude-sonnet-4-5-20250929";
+
+/// 구조화된 판단 결과 제출용 도구 이름 (tool_choice로 강제)
+const JUDGMENT_TOOL_NAME: &str = "submit_judgment";
+
+/// 스키마 불일치 응답에 대한 재시도 횟수
+const MAX_PARSE_RETRIES: usize = 1;
+
+/// Claude Sonnet 4.5 단가 (USD / 1M tokens)
+const INPUT_COST_PER_MTOK: f64 = 3.0;
+const OUTPUT_COST_PER_MTOK: f64 = 15.0;
+
+#[derive(Serialize, Deserialize, Clone)]
 struct Message {
     role: String,
     content: String,
@@ -14,23 +30,75 @@ struct Message {
 #[derive(Deserialize)]
 struct ClaudeResponse {
     content: Vec<ClaudeContent>,
+    #[serde(default)]
+    usage: Option<ClaudeUsage>,
+}
+
+/// LLM 호출 토큰 사용량 (재시도 포함 합계)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct ClaudeUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
 }
 
 #[derive(Deserialize)]
 struct ClaudeContent {
     #[serde(rename = "type")]
     content_type: String,
-    text: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    input: Option<serde_json::Value>,
+}
+
+/// 스키마 검증을 통과한 LLM 판단 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredJudgment {
+    pub result: bool,
+    pub confidence: f64,
+    pub reasons: Vec<String>,
+    pub cited_fields: Vec<String>,
+}
+
+/// LLM 판단 결과 + 토큰 사용량
+///
+/// 토큰 사용량은 최종 판단이 저장된 뒤 그 id로 기록한다 (`LLMEngine::record_token_usage`).
+/// 하이브리드 판단에서는 LLM 결과가 아닌 Rule 결과가 저장될 수 있기 때문.
+#[derive(Debug, Clone)]
+pub struct LlmJudgment {
+    pub result: JudgmentResult,
+    pub usage: Option<ClaudeUsage>,
+}
+
+/// LLM 판단 응답 파싱 에러
+#[derive(Debug, thiserror::Error)]
+pub enum JudgmentParseError {
+    #[error("LLM 응답 내용이 비어있습니다")]
+    EmptyContent,
+    #[error("LLM 응답에서 JSON 판단 결과를 찾을 수 없습니다")]
+    MissingJson,
+    #[error("LLM 응답 JSON 파싱 실패: {0}")]
+    InvalidJson(String),
+    #[error("LLM 응답이 판단 스키마와 맞지 않습니다 ({field}): {reason}")]
+    SchemaViolation { field: &'static str, reason: String },
 }
 
 pub struct LLMEngine {
     client: Client,
     api_key: String,
     db: Database,
+    prompt_engine: PromptEngine,
+    few_shot_retriever: FewShotRetriever,
 }
 
 impl LLMEngine {
     pub fn new() -> anyhow::Result<Self> {
+        Self::from_database(Database::new()?)
+    }
+
+    pub fn from_database(db: Database) -> anyhow::Result<Self> {
         let api_key = std::env::var("ANTHROPIC_API_KEY")
             .map_err(|_| anyhow::anyhow!("Claude API 키가 설정되지 않았습니다. Settings 페이지에서 API 키를 설정해주세요."))?;
 
@@ -42,7 +110,9 @@ impl LLMEngine {
         Ok(Self {
             client: Client::new(),
             api_key,
-            db: Database::new()?,
+            prompt_engine: PromptEngine::from_database(db.clone()),
+            few_shot_retriever: FewShotRetriever::from_database(db.clone())?,
+            db,
         })
     }
 
@@ -51,14 +121,17 @@ impl LLMEngine {
         &self,
         input: &JudgmentInput,
         few_shot_samples: &[crate::database::TrainingSample],
-    ) -> anyhow::Result<JudgmentResult> {
+    ) -> anyhow::Result<LlmJudgment> {
         self.evaluate_internal(input, few_shot_samples).await
     }
 
     /// 기존 evaluate() 메서드 (내부적으로 Few-shot 샘플 검색)
-    pub async fn evaluate(&self, input: &JudgmentInput) -> anyhow::Result<JudgmentResult> {
-        // Few-shot 학습 샘플 가져오기 (10-20개)
-        let few_shot_samples = self.get_few_shot_samples(&input.workflow_id, 15)?;
+    pub async fn evaluate(&self, input: &JudgmentInput) -> anyhow::Result<LlmJudgment> {
+        // 입력과 유사한 Few-shot 학습 샘플 가져오기 (10-20개)
+        let few_shot_samples = self
+            .few_shot_retriever
+            .retrieve(&input.workflow_id, &input.input_data, 15)
+            .await?;
         self.evaluate_internal(input, &few_shot_samples).await
     }
 
@@ -67,18 +140,28 @@ impl LLMEngine {
         &self,
         input: &JudgmentInput,
         few_shot_samples: &[crate::database::TrainingSample],
-    ) -> anyhow::Result<JudgmentResult> {
+    ) -> anyhow::Result<LlmJudgment> {
 
-        let prompt = self.build_prompt(input, &few_shot_samples)?;
+        // A/B 테스트 중인 judgment 템플릿이 있으면 가중치에 따라 선택
+        let variant = self.prompt_engine.select_variant("judgment").unwrap_or_else(|e| {
+            eprintln!("⚠️ 프롬프트 변형 선택 실패, 기본 프롬프트 사용: {}", e);
+            None
+        });
 
-        let mut messages = vec![
-            Message {
-                role: "system".to_string(),
-                content: "당신은 제조 품질 판단 전문가입니다. 주어진 데이터를 분석하여 합격/불합격을 판단하고, 그 이유를 명확하게 설명하세요.\n\n응답 형식:\n판단: [합격/불합격]\n이유: [상세 설명]\n신뢰도: [0.0-1.0]".to_string(),
+        let (prompt, template) = match variant {
+            Some(template) => match self.render_variant_prompt(&template, input, few_shot_samples) {
+                Ok(rendered) => (rendered, Some(template)),
+                Err(e) => {
+                    eprintln!("⚠️ 프롬프트 변형 {} 렌더링 실패, 기본 프롬프트 사용: {}", template.id, e);
+                    (self.build_prompt(input, few_shot_samples)?, None)
+                }
             },
-        ];
+            None => (self.build_prompt(input, few_shot_samples)?, None),
+        };
 
-        // Few-shot 예시를 메시지에 추가
+        let mut messages = Vec::new();
+
+        // Few-shot 예시를 메시지에 추가 (응답은 판단 스키마 JSON 형식)
         for sample in few_shot_samples {
             messages.push(Message {
                 role: "user".to_string(),
@@ -86,10 +169,13 @@ impl LLMEngine {
             });
             messages.push(Message {
                 role: "assistant".to_string(),
-                content: format!(
-                    "판단: {}\n이유: 이전 사례를 기반으로 한 판단입니다.",
-                    if sample.expected_result { "합격" } else { "불합격" }
-                ),
+                content: serde_json::json!({
+                    "result": sample.expected_result,
+                    "confidence": sample.accuracy.unwrap_or(0.8),
+                    "reasons": ["이전 사례를 기반으로 한 판단입니다."],
+                    "cited_fields": [],
+                })
+                .to_string(),
             });
         }
 
@@ -99,9 +185,81 @@ impl LLMEngine {
             content: prompt,
         });
 
+        // 스키마에 맞지 않는 응답은 에러를 알려주고 재시도
+        let mut attempt = 0;
+        let mut input_tokens = 0;
+        let mut output_tokens = 0;
+        let parsed = loop {
+            let response = self.send_judgment_request(&messages).await?;
+            if let Some(usage) = &response.usage {
+                input_tokens += usage.input_tokens;
+                output_tokens += usage.output_tokens;
+            }
+
+            match Self::parse_structured_judgment(&response.content, &input.input_data) {
+                Ok(parsed) => break parsed,
+                Err(e) if attempt < MAX_PARSE_RETRIES => {
+                    attempt += 1;
+                    eprintln!("⚠️ LLM 판단 응답 스키마 불일치, 재시도 {}/{}: {}", attempt, MAX_PARSE_RETRIES, e);
+                    if let Some(last) = messages.last_mut() {
+                        last.content.push_str(&format!(
+                            "\n\n<format_error>이전 응답이 판단 스키마와 맞지 않았습니다: {}. {} 도구로 result, confidence(0.0-1.0), reasons, cited_fields를 모두 채워 다시 응답하세요.</format_error>",
+                            e, JUDGMENT_TOOL_NAME
+                        ));
+                    }
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        // LLM 응답 보안 검증
+        if !validate_llm_response(&parsed.reasons.join("\n")) {
+            eprintln!("⚠️ LLM 응답에서 위험한 패턴 감지됨");
+            return Err(anyhow::anyhow!("보안 정책에 의해 응답이 차단되었습니다"));
+        }
+
+        let explanation = Self::format_explanation(&parsed);
+        let (result, confidence) = (parsed.result, parsed.confidence);
+
+        // Few-shot 샘플 수에 따라 신뢰도 보정
+        let adjusted_confidence = if few_shot_samples.len() >= 10 {
+            (confidence * 1.1).min(1.0) // 10개 이상 샘플이 있으면 신뢰도 향상
+        } else if few_shot_samples.len() >= 5 {
+            confidence
+        } else {
+            confidence * 0.9 // 샘플이 부족하면 신뢰도 감소
+        };
+
+        let result = JudgmentResult {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: input.workflow_id.clone(),
+            result,
+            confidence: adjusted_confidence,
+            method_used: if few_shot_samples.is_empty() { "llm".to_string() } else { "llm_few_shot".to_string() },
+            explanation: format!(
+                "{}\n\n📚 Few-shot 학습: {} 개 유사 사례 참조",
+                explanation,
+                few_shot_samples.len()
+            ),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: template.as_ref().map(|t| t.id.clone()),
+            template_version: template.as_ref().map(|t| t.version),
+        };
+
+        Ok(LlmJudgment {
+            result,
+            usage: (input_tokens + output_tokens > 0).then_some(ClaudeUsage { input_tokens, output_tokens }),
+        })
+    }
+
+    /// 판단 요청 전송 (submit_judgment 도구 사용 강제)
+    async fn send_judgment_request(&self, messages: &[Message]) -> anyhow::Result<ClaudeResponse> {
         let request = serde_json::json!({
-            "model": "claude-sonnet-4-5-20250929",
+            "model": CLAUDE_MODEL,
+            "system": "당신은 제조 품질 판단 전문가입니다. 주어진 데이터를 분석하여 합격/불합격을 판단하고, 반드시 submit_judgment 도구로 결과를 제출하세요. reasons에는 판단 근거를, cited_fields에는 근거로 사용한 입력 데이터 필드명을 넣으세요.",
             "messages": messages,
+            "tools": [Self::judgment_tool()],
+            "tool_choice": { "type": "tool", "name": JUDGMENT_TOOL_NAME },
             "temperature": 0.3,
             "max_tokens": 8192,
         });
@@ -132,54 +290,274 @@ impl LLMEngine {
             ));
         }
 
-        let response = http_response
+        http_response
             .json::<ClaudeResponse>()
             .await
-            .map_err(|e| anyhow::anyhow!("Claude API 응답 파싱 실패: {}", e))?;
+            .map_err(|e| anyhow::anyhow!("Claude API 응답 파싱 실패: {}", e))
+    }
 
-        let llm_response = &response.content[0].text;
+    /// 판단 결과 JSON 스키마 (result, confidence, reasons, cited_fields)
+    fn judgment_tool() -> serde_json::Value {
+        serde_json::json!({
+            "name": JUDGMENT_TOOL_NAME,
+            "description": "품질 판단 결과를 구조화된 형식으로 제출합니다.",
+            "input_schema": {
+                "type": "object",
+                "properties": {
+                    "result": {
+                        "type": "boolean",
+                        "description": "합격이면 true, 불합격이면 false"
+                    },
+                    "confidence": {
+                        "type": "number",
+                        "minimum": 0.0,
+                        "maximum": 1.0,
+                        "description": "판단 신뢰도 (0.0-1.0)"
+                    },
+                    "reasons": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "minItems": 1,
+                        "description": "판단 근거"
+                    },
+                    "cited_fields": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "판단 근거로 사용한 입력 데이터 필드명"
+                    }
+                },
+                "required": ["result", "confidence", "reasons", "cited_fields"]
+            }
+        })
+    }
 
-        // LLM 응답 보안 검증
-        if !validate_llm_response(llm_response) {
-            eprintln!("⚠️ LLM 응답에서 위험한 패턴 감지됨");
-            return Err(anyhow::anyhow!("보안 정책에 의해 응답이 차단되었습니다"));
+    /// LLM 응답에서 판단 결과 추출 및 스키마 검증
+    ///
+    /// tool_use 블록을 우선 사용하고, 없으면 텍스트 블록의 JSON(코드펜스 포함)을 파싱한다.
+    /// 복구 가능한 형식 차이(문자열 신뢰도, 백분율, "합격"/"불합격" 문자열 등)는 보정한다.
+    fn parse_structured_judgment(
+        content: &[ClaudeContent],
+        input_data: &serde_json::Value,
+    ) -> Result<StructuredJudgment, JudgmentParseError> {
+        if content.is_empty() {
+            return Err(JudgmentParseError::EmptyContent);
         }
 
-        let (result, confidence, explanation) = self.parse_llm_response(llm_response)?;
+        let tool_input = content.iter().find_map(|c| {
+            (c.content_type == "tool_use" && c.name.as_deref() == Some(JUDGMENT_TOOL_NAME))
+                .then(|| c.input.clone())
+                .flatten()
+        });
 
-        // Few-shot 샘플 수에 따라 신뢰도 보정
-        let adjusted_confidence = if few_shot_samples.len() >= 10 {
-            (confidence * 1.1).min(1.0) // 10개 이상 샘플이 있으면 신뢰도 향상
-        } else if few_shot_samples.len() >= 5 {
-            confidence
+        let value = match tool_input {
+            Some(value) => value,
+            None => {
+                let text = content
+                    .iter()
+                    .filter_map(|c| c.text.as_deref())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if text.trim().is_empty() {
+                    return Err(JudgmentParseError::EmptyContent);
+                }
+                let json_text = Self::extract_json_object(&text).ok_or(JudgmentParseError::MissingJson)?;
+                serde_json::from_str(json_text)
+                    .map_err(|e| JudgmentParseError::InvalidJson(e.to_string()))?
+            }
+        };
+
+        Self::validate_judgment_value(&value, input_data)
+    }
+
+    /// 텍스트에서 첫 '{' ~ 마지막 '}' 구간 추출 (```json 코드펜스, 앞뒤 설명문 제거)
+    fn extract_json_object(text: &str) -> Option<&str> {
+        let start = text.find('{')?;
+        let end = text.rfind('}')?;
+        (end > start).then(|| &text[start..=end])
+    }
+
+    fn validate_judgment_value(
+        value: &serde_json::Value,
+        input_data: &serde_json::Value,
+    ) -> Result<StructuredJudgment, JudgmentParseError> {
+        use serde_json::Value;
+
+        let obj = value.as_object().ok_or_else(|| JudgmentParseError::SchemaViolation {
+            field: "root",
+            reason: "JSON 객체가 아닙니다".to_string(),
+        })?;
+
+        let result = match obj.get("result") {
+            Some(Value::Bool(b)) => *b,
+            Some(Value::String(s)) => match s.trim().to_lowercase().as_str() {
+                "true" | "pass" | "합격" => true,
+                "false" | "fail" | "불합격" => false,
+                other => {
+                    return Err(JudgmentParseError::SchemaViolation {
+                        field: "result",
+                        reason: format!("알 수 없는 값: {}", other),
+                    })
+                }
+            },
+            _ => {
+                return Err(JudgmentParseError::SchemaViolation {
+                    field: "result",
+                    reason: "boolean 값이 필요합니다".to_string(),
+                })
+            }
+        };
+
+        let raw_confidence = match obj.get("confidence") {
+            Some(Value::Number(n)) => n.as_f64(),
+            Some(Value::String(s)) => s.trim().trim_end_matches('%').trim().parse::<f64>().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| JudgmentParseError::SchemaViolation {
+            field: "confidence",
+            reason: "숫자 값이 필요합니다".to_string(),
+        })?;
+
+        // 백분율(예: 85)로 응답한 경우 0.0-1.0으로 보정
+        let confidence = if raw_confidence > 1.0 && raw_confidence <= 100.0 {
+            raw_confidence / 100.0
         } else {
-            confidence * 0.9 // 샘플이 부족하면 신뢰도 감소
+            raw_confidence
         };
+        if !(0.0..=1.0).contains(&confidence) {
+            return Err(JudgmentParseError::SchemaViolation {
+                field: "confidence",
+                reason: format!("0.0-1.0 범위를 벗어났습니다: {}", raw_confidence),
+            });
+        }
 
-        Ok(JudgmentResult {
-            id: Uuid::new_v4().to_string(),
-            workflow_id: input.workflow_id.clone(),
+        // 템플릿 응답 형식의 "reasoning" 단일 문자열도 허용
+        let reasons: Vec<String> = match obj.get("reasons").or_else(|| obj.get("reasoning")) {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            Some(Value::String(s)) if !s.trim().is_empty() => vec![s.trim().to_string()],
+            _ => Vec::new(),
+        };
+        if reasons.is_empty() {
+            return Err(JudgmentParseError::SchemaViolation {
+                field: "reasons",
+                reason: "판단 근거가 최소 1개 필요합니다".to_string(),
+            });
+        }
+
+        // 입력 데이터에 없는 필드 인용은 제거 (환각 방지)
+        let cited_fields = match obj.get("cited_fields") {
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .filter(|field| {
+                    input_data
+                        .as_object()
+                        .map(|input| input.contains_key(*field))
+                        .unwrap_or(false)
+                })
+                .map(|s| s.to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(StructuredJudgment {
             result,
-            confidence: adjusted_confidence,
-            method_used: if few_shot_samples.is_empty() { "llm".to_string() } else { "llm_few_shot".to_string() },
-            explanation: format!(
-                "{}\n\n📚 Few-shot 학습: {} 개 유사 사례 참조",
-                explanation,
-                few_shot_samples.len()
-            ),
-            created_at: chrono::Utc::now().to_rfc3339(),
+            confidence,
+            reasons,
+            cited_fields,
         })
     }
 
-    fn get_few_shot_samples(&self, workflow_id: &str, limit: u32) -> anyhow::Result<Vec<crate::database::TrainingSample>> {
-        // 정확도가 높은 훈련 샘플만 가져오기 (accuracy >= 0.8)
-        let samples = self.db.get_training_samples(workflow_id, limit)
-            .map_err(|e| anyhow::anyhow!("Failed to retrieve training samples: {}", e))?;
+    fn format_explanation(judgment: &StructuredJudgment) -> String {
+        let mut explanation = format!(
+            "판단: {}\n\n근거:\n{}",
+            if judgment.result { "합격" } else { "불합격" },
+            judgment
+                .reasons
+                .iter()
+                .map(|r| format!("- {}", r))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+
+        if !judgment.cited_fields.is_empty() {
+            explanation.push_str(&format!("\n\n참조 필드: {}", judgment.cited_fields.join(", ")));
+        }
 
-        Ok(samples
-            .into_iter()
-            .filter(|s| s.accuracy.unwrap_or(0.0) >= 0.8)
-            .collect())
+        explanation
+    }
+
+    /// A/B 변형 템플릿 렌더링 (workflow_context / input_data / few_shot_samples)
+    fn render_variant_prompt(
+        &self,
+        template: &PromptTemplate,
+        input: &JudgmentInput,
+        few_shot_samples: &[crate::database::TrainingSample],
+    ) -> anyhow::Result<String> {
+        let workflow = self.db.get_workflow(&input.workflow_id)?;
+
+        let few_shot_text = if few_shot_samples.is_empty() {
+            "없음 (첫 판단)".to_string()
+        } else {
+            few_shot_samples
+                .iter()
+                .take(5)
+                .enumerate()
+                .map(|(idx, sample)| {
+                    format!(
+                        "사례 {}:\n입력: {}\n결과: {}",
+                        idx + 1,
+                        sanitize_for_xml(&sample.input_data),
+                        if sample.expected_result { "합격" } else { "불합격" }
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        };
+
+        let mut variables = HashMap::new();
+        variables.insert(
+            "workflow_context".to_string(),
+            serde_json::json!({
+                "name": workflow.as_ref().map(|w| w.name.clone()).unwrap_or_default(),
+                "rule": workflow
+                    .and_then(|w| w.rule_expression)
+                    .unwrap_or_else(|| "없음".to_string()),
+            }),
+        );
+        variables.insert(
+            "input_data".to_string(),
+            serde_json::json!(sanitize_for_xml(&serde_json::to_string_pretty(&input.input_data)?)),
+        );
+        variables.insert("few_shot_samples".to_string(), serde_json::json!(few_shot_text));
+
+        self.prompt_engine.render_template(template, variables)
+    }
+
+    /// 판단별 토큰 사용량 기록 (A/B 변형 비용 비교용, 실패해도 판단은 계속)
+    ///
+    /// token_usage.judgment_id가 judgments를 참조하므로 판단 저장 후 호출해야 한다.
+    pub fn record_token_usage(&self, judgment_id: &str, usage: &ClaudeUsage) {
+        let cost_usd = usage.input_tokens as f64 / 1_000_000.0 * INPUT_COST_PER_MTOK
+            + usage.output_tokens as f64 / 1_000_000.0 * OUTPUT_COST_PER_MTOK;
+
+        let token_usage = TokenUsage {
+            id: Uuid::new_v4().to_string(),
+            judgment_id: judgment_id.to_string(),
+            service: "judgment".to_string(),
+            tokens_used: (usage.input_tokens + usage.output_tokens) as i32,
+            cost_usd,
+            complexity: JudgmentComplexity::Medium.as_str().to_string(),
+            created_at: chrono::Utc::now(),
+        };
+
+        if let Err(e) = self.db.save_token_usage(&token_usage) {
+            eprintln!("⚠️ 토큰 사용량 기록 실패: {}", e);
+        }
     }
 
     fn build_prompt(&self, input: &JudgmentInput, few_shot_samples: &[crate::database::TrainingSample]) -> anyhow::Result<String> {
@@ -223,7 +601,7 @@ impl LLMEngine {
         }];
 
         let request = serde_json::json!({
-            "model": "claude-sonnet-4-5-20250929",
+            "model": CLAUDE_MODEL,
             "messages": messages,
             "temperature": 0.7,
             "max_tokens": 8192,
@@ -268,8 +646,8 @@ impl LLMEngine {
 
         let text = response_body
             .content
-            .first()
-            .map(|c| c.text.clone())
+            .into_iter()
+            .find_map(|c| c.text)
             .ok_or_else(|| anyhow::anyhow!("응답 내용이 비어있습니다"))?;
 
         // LLM 응답 보안 검증
@@ -280,22 +658,112 @@ impl LLMEngine {
 
         Ok(text)
     }
+}
 
-    fn parse_llm_response(&self, response: &str) -> anyhow::Result<(bool, f64, String)> {
-        let result = response.contains("합격") && !response.contains("불합격");
-
-        // 신뢰도 파싱 시도
-        let confidence = if let Some(conf_str) = response.split("신뢰도:").nth(1) {
-            conf_str
-                .trim()
-                .split_whitespace()
-                .next()
-                .and_then(|s| s.parse::<f64>().ok())
-                .unwrap_or(0.8)
-        } else {
-            0.8 // 기본 신뢰도
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn content(blocks: serde_json::Value) -> Vec<ClaudeContent> {
+        serde_json::from_value(blocks).unwrap()
+    }
+
+    #[test]
+    fn test_parse_tool_use_judgment() {
+        let input = json!({"temperature": 92, "vibration": 41});
+        let blocks = content(json!([{
+            "type": "tool_use",
+            "id": "toolu_1",
+            "name": JUDGMENT_TOOL_NAME,
+            "input": {
+                "result": false,
+                "confidence": 0.87,
+                "reasons": ["온도가 기준 90도를 초과했습니다."],
+                "cited_fields": ["temperature", "pressure"]
+            }
+        }]));
+
+        let parsed = LLMEngine::parse_structured_judgment(&blocks, &input).unwrap();
+
+        assert!(!parsed.result);
+        assert_eq!(parsed.confidence, 0.87);
+        assert_eq!(parsed.reasons.len(), 1);
+        // 입력에 없는 pressure 필드 인용은 제거
+        assert_eq!(parsed.cited_fields, vec!["temperature".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_repairs_fenced_text_json() {
+        let input = json!({"temperature": 85});
+        let blocks = content(json!([{
+            "type": "text",
+            "text": "판단 결과입니다.\n```json\n{\"result\": \"합격\", \"confidence\": \"85%\", \"reasoning\": \"모든 조건 충족\"}\n```"
+        }]));
+
+        let parsed = LLMEngine::parse_structured_judgment(&blocks, &input).unwrap();
+
+        assert!(parsed.result);
+        assert!((parsed.confidence - 0.85).abs() < f64::EPSILON);
+        assert_eq!(parsed.reasons, vec!["모든 조건 충족".to_string()]);
+        assert!(parsed.cited_fields.is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors_are_typed() {
+        let input = json!({});
+
+        assert!(matches!(
+            LLMEngine::parse_structured_judgment(&[], &input),
+            Err(JudgmentParseError::EmptyContent)
+        ));
+
+        let free_text = content(json!([{"type": "text", "text": "판단: 합격\n이유: 정상"}]));
+        assert!(matches!(
+            LLMEngine::parse_structured_judgment(&free_text, &input),
+            Err(JudgmentParseError::MissingJson)
+        ));
+
+        let broken = content(json!([{"type": "text", "text": "{\"result\": true,"}]));
+        assert!(matches!(
+            LLMEngine::parse_structured_judgment(&broken, &input),
+            Err(JudgmentParseError::MissingJson) | Err(JudgmentParseError::InvalidJson(_))
+        ));
+
+        let out_of_range = content(json!([{
+            "type": "tool_use",
+            "name": JUDGMENT_TOOL_NAME,
+            "input": {"result": true, "confidence": 150, "reasons": ["x"], "cited_fields": []}
+        }]));
+        assert!(matches!(
+            LLMEngine::parse_structured_judgment(&out_of_range, &input),
+            Err(JudgmentParseError::SchemaViolation { field: "confidence", .. })
+        ));
+
+        let no_reasons = content(json!([{
+            "type": "tool_use",
+            "name": JUDGMENT_TOOL_NAME,
+            "input": {"result": true, "confidence": 0.9, "reasons": [], "cited_fields": []}
+        }]));
+        assert!(matches!(
+            LLMEngine::parse_structured_judgment(&no_reasons, &input),
+            Err(JudgmentParseError::SchemaViolation { field: "reasons", .. })
+        ));
+    }
+
+    #[test]
+    fn test_format_explanation() {
+        let judgment = StructuredJudgment {
+            result: true,
+            confidence: 0.9,
+            reasons: vec!["온도 정상".to_string(), "진동 정상".to_string()],
+            cited_fields: vec!["temperature".to_string()],
         };
 
-        Ok((result, confidence, response.to_string()))
+        let explanation = LLMEngine::format_explanation(&judgment);
+
+        assert!(explanation.starts_with("판단: 합격"));
+        assert!(explanation.contains("- 진동 정상"));
+        assert!(explanation.contains("참조 필드: temperature"));
     }
 }
@@ -0,0 +1,605 @@
+// services/lot_disposition.rs - LOT 출하 보류/해제
+//
+// 판단 결과(NG)나 CCP 시정조치의 보류 결정이 batch_lot_no / fg_lot_no를 가리키면 LOT을 보류하고,
+// 보류 이후의 qc_test / qc_inspection 합격 결과와 승인(approval_requests)이 있어야 해제한다.
+// 보류 중인 완제품 LOT(배치 LOT 보류 시 해당 배치의 완제품 포함)은 v_lot_hold_status 뷰로 조회되며
+// outbound_dtl 트리거가 출고 등록을 막는다.
+//
+// 상태: HOLD → RELEASE_PENDING → RELEASED (승인 거부/만료 시 RELEASE_PENDING → HOLD)
+
+use crate::database::Database;
+use crate::services::approval_request::{self, NewApprovalRequest};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// LOT 출하 승인 요청의 workflow_id (approval_requests)
+pub const LOT_RELEASE_WORKFLOW_ID: &str = "lot-release";
+const LOT_RELEASE_WORKFLOW_NAME: &str = "LOT 출하 보류 해제";
+const RELEASE_STEP_NAME: &str = "보류 해제 승인";
+
+/// 해제 승인 기본 만료 시간 (3일)
+pub const DEFAULT_RELEASE_TIMEOUT_MINUTES: i64 = 3 * 24 * 60;
+
+pub const HOLD_STATUS_HOLD: &str = "HOLD";
+pub const HOLD_STATUS_RELEASE_PENDING: &str = "RELEASE_PENDING";
+pub const HOLD_STATUS_RELEASED: &str = "RELEASED";
+
+/// 판단 입력에서 LOT 번호로 인식하는 키
+const LOT_INPUT_KEYS: &[&str] = &["batch_lot_no", "fg_lot_no", "fg_lot"];
+
+/// 보류 대상 LOT 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum LotType {
+    /// 배합 LOT (batch_lot) - 해당 배치의 완제품 LOT 출고도 보류
+    Batch,
+    /// 완제품 LOT (fg_lot)
+    Fg,
+}
+
+impl LotType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LotType::Batch => "BATCH",
+            LotType::Fg => "FG",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "BATCH" => Some(LotType::Batch),
+            "FG" => Some(LotType::Fg),
+            _ => None,
+        }
+    }
+}
+
+/// LOT 보류
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LotHold {
+    pub id: String,
+    pub lot_no: String,
+    pub lot_type: String,
+    pub status: String,
+    pub reason: String,
+    pub source_type: String, // "judgment" | "corrective_action" | "manual"
+    pub source_ref: Option<String>,
+    pub held_by: String,
+    pub held_at: String,
+    pub approval_request_id: Option<String>,
+    pub qc_ref: Option<String>,
+    pub released_by: Option<String>,
+    pub released_at: Option<String>,
+    pub release_comment: Option<String>,
+}
+
+/// 보류 해제 근거 품질검사 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QcEvidence {
+    pub source: String, // "qc_test" | "qc_inspection"
+    pub ref_no: String,
+    pub result: String,
+    pub tested_at: String,
+}
+
+/// 출고 가능 여부 (완제품 LOT 기준)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboundCheck {
+    pub fg_lot_no: String,
+    pub shippable: bool,
+    /// 출고를 막는 보류 (완제품 LOT 자체 또는 상위 배치 LOT)
+    pub blocking_holds: Vec<LotHold>,
+}
+
+/// LOT 보류 저장소
+pub struct LotDispositionStore {
+    db: Database,
+}
+
+impl LotDispositionStore {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// LOT 보류 (lot_type이 None이면 fg_lot → batch_lot 순으로 판별)
+    pub fn place_hold(
+        &self,
+        lot_no: &str,
+        lot_type: Option<LotType>,
+        reason: &str,
+        source_type: &str,
+        source_ref: Option<&str>,
+        held_by: &str,
+    ) -> anyhow::Result<LotHold> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        place_hold(&conn, lot_no, lot_type, reason, source_type, source_ref, held_by)
+    }
+
+    /// 판단 결과가 NG이면 입력 데이터의 LOT(batch_lot_no, fg_lot_no, fg_lot)을 보류
+    ///
+    /// 존재하지 않는 LOT 번호는 건너뛴다. 보류한 LOT 목록 반환.
+    pub fn hold_from_judgment(
+        &self,
+        judgment_id: &str,
+        input_data: &serde_json::Value,
+        explanation: &str,
+    ) -> anyhow::Result<Vec<LotHold>> {
+        let lots = lot_refs(input_data);
+        if lots.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let reason: String = format!("판단 NG: {}", explanation).chars().take(500).collect();
+        let mut holds = Vec::new();
+        for (lot_no, lot_type) in lots {
+            if resolve_lot_type(&conn, &lot_no, lot_type)?.is_none() {
+                println!("⚠️  보류 대상 LOT 없음 (건너뜀): {}", lot_no);
+                continue;
+            }
+            holds.push(place_hold(&conn, &lot_no, lot_type, &reason, "judgment", Some(judgment_id), "system")?);
+        }
+        Ok(holds)
+    }
+
+    pub fn get(&self, id: &str) -> anyhow::Result<Option<LotHold>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        load_hold(&conn, id)
+    }
+
+    /// 보류 목록 (status 지정 시 해당 상태만, 최신순)
+    pub fn list(&self, status: Option<&str>) -> anyhow::Result<Vec<LotHold>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE (?1 IS NULL OR status = ?1) ORDER BY held_at DESC, id",
+            HOLD_SELECT
+        ))?;
+        let holds = stmt
+            .query_map([status], row_to_hold)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(holds)
+    }
+
+    /// LOT의 보류 이력 (해제된 보류 포함, 최신순)
+    pub fn history(&self, lot_no: &str) -> anyhow::Result<Vec<LotHold>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut stmt = conn.prepare(&format!("{} WHERE lot_no = ?1 ORDER BY held_at DESC, id", HOLD_SELECT))?;
+        let holds = stmt
+            .query_map([lot_no], row_to_hold)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(holds)
+    }
+
+    /// 완제품 LOT 출고 가능 여부
+    pub fn check_outbound(&self, fg_lot_no: &str) -> anyhow::Result<OutboundCheck> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let hold_ids: Vec<String> = {
+            let mut stmt = conn.prepare("SELECT hold_id FROM v_lot_hold_status WHERE fg_lot_no = ?1 ORDER BY held_at")?;
+            let rows = stmt.query_map([fg_lot_no], |row| row.get(0))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        let mut blocking_holds = Vec::with_capacity(hold_ids.len());
+        for id in hold_ids {
+            if let Some(hold) = load_hold(&conn, &id)? {
+                blocking_holds.push(hold);
+            }
+        }
+
+        Ok(OutboundCheck {
+            fg_lot_no: fg_lot_no.to_string(),
+            shippable: blocking_holds.is_empty(),
+            blocking_holds,
+        })
+    }
+
+    /// 보류 해제 승인 요청
+    ///
+    /// 보류 이후 가장 최근 품질검사(qc_test / qc_inspection)가 PASS여야 한다.
+    /// 승인/거부는 process_approval 커맨드로 처리된다.
+    pub fn request_release(
+        &self,
+        id: &str,
+        approvers: &str,
+        timeout_minutes: Option<i64>,
+    ) -> anyhow::Result<LotHold> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let hold = require_hold(&conn, id)?;
+        if hold.status != HOLD_STATUS_HOLD {
+            return Err(anyhow::anyhow!("해제를 요청할 수 없는 상태입니다: {}", hold.status));
+        }
+        let evidence = latest_qc_since(&conn, &hold)?.ok_or_else(|| {
+            anyhow::anyhow!("보류({}) 이후 품질검사 결과가 없어 해제를 요청할 수 없습니다: {}", hold.held_at, hold.lot_no)
+        })?;
+        if evidence.result != "PASS" {
+            return Err(anyhow::anyhow!(
+                "최근 품질검사가 합격이 아닙니다: {} {} ({})",
+                evidence.source,
+                evidence.ref_no,
+                evidence.result
+            ));
+        }
+
+        let request_id = approval_request::create_request(
+            &conn,
+            &NewApprovalRequest {
+                workflow_id: LOT_RELEASE_WORKFLOW_ID,
+                workflow_name: LOT_RELEASE_WORKFLOW_NAME,
+                step_id: id,
+                step_name: RELEASE_STEP_NAME,
+                approvers,
+                input_data: &serde_json::json!({ "hold": hold, "qc": evidence }),
+                timeout_minutes: timeout_minutes.unwrap_or(DEFAULT_RELEASE_TIMEOUT_MINUTES),
+            },
+        )?;
+        conn.execute(
+            "UPDATE lot_holds SET status = ?2, approval_request_id = ?3, qc_ref = ?4, updated_at = ?5 WHERE id = ?1",
+            rusqlite::params![
+                id,
+                HOLD_STATUS_RELEASE_PENDING,
+                request_id,
+                format!("{}:{}", evidence.source, evidence.ref_no),
+                now_string()
+            ],
+        )?;
+
+        require_hold(&conn, id)
+    }
+
+    /// 승인 요청 결과를 보류에 반영
+    ///
+    /// - approved: RELEASED (완제품 LOT은 qc_status = PASSED, release_date 기록)
+    /// - rejected/expired: HOLD로 되돌림
+    /// - pending: 변화 없음
+    pub fn sync_release(&self, id: &str) -> anyhow::Result<LotHold> {
+        let db_conn = self.db.get_connection();
+        let mut conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let hold = require_hold(&conn, id)?;
+        if hold.status != HOLD_STATUS_RELEASE_PENDING {
+            return Ok(hold);
+        }
+        let request_id = hold
+            .approval_request_id
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("해제 승인 요청이 없습니다: {}", id))?;
+
+        let outcome = approval_request::load_outcome(&conn, &request_id)?;
+        let now = now_string();
+
+        match outcome.status.as_str() {
+            "approved" => {
+                let released_at = outcome.decided_at.unwrap_or_else(|| now.clone());
+                let tx = conn.transaction()?;
+                tx.execute(
+                    "UPDATE lot_holds SET status = ?2, released_by = ?3, released_at = ?4, release_comment = ?5, updated_at = ?6
+                     WHERE id = ?1",
+                    rusqlite::params![id, HOLD_STATUS_RELEASED, outcome.decided_by, released_at, outcome.comment, now],
+                )?;
+                if hold.lot_type == LotType::Fg.as_str() {
+                    tx.execute(
+                        "UPDATE fg_lot SET qc_status = 'PASSED', release_date = ?2 WHERE fg_lot_no = ?1",
+                        rusqlite::params![hold.lot_no, &released_at[..10.min(released_at.len())]],
+                    )?;
+                }
+                tx.commit()?;
+                println!("✅ LOT 보류 해제: {} ({})", hold.lot_no, id);
+            }
+            "rejected" | "expired" => {
+                conn.execute(
+                    "UPDATE lot_holds SET status = ?2, release_comment = ?3, updated_at = ?4 WHERE id = ?1",
+                    rusqlite::params![id, HOLD_STATUS_HOLD, outcome.comment, now],
+                )?;
+            }
+            _ => {}
+        }
+
+        require_hold(&conn, id)
+    }
+}
+
+/// LOT 보류 (이미 해제되지 않은 보류가 있으면 그 보류를 반환)
+pub fn place_hold(
+    conn: &Connection,
+    lot_no: &str,
+    lot_type: Option<LotType>,
+    reason: &str,
+    source_type: &str,
+    source_ref: Option<&str>,
+    held_by: &str,
+) -> anyhow::Result<LotHold> {
+    let lot_type = resolve_lot_type(conn, lot_no, lot_type)?
+        .ok_or_else(|| anyhow::anyhow!("LOT을 찾을 수 없습니다: {}", lot_no))?;
+
+    let active = conn
+        .query_row(
+            &format!("{} WHERE lot_no = ?1 AND status != ?2", HOLD_SELECT),
+            rusqlite::params![lot_no, HOLD_STATUS_RELEASED],
+            row_to_hold,
+        )
+        .optional()?;
+    if let Some(hold) = active {
+        return Ok(hold);
+    }
+
+    let id = format!("hold-{}", uuid::Uuid::new_v4());
+    let now = now_string();
+    conn.execute(
+        "INSERT INTO lot_holds (id, lot_no, lot_type, status, reason, source_type, source_ref, held_by, held_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)",
+        rusqlite::params![id, lot_no, lot_type.as_str(), HOLD_STATUS_HOLD, reason, source_type, source_ref, held_by, now],
+    )?;
+    if lot_type == LotType::Fg {
+        conn.execute("UPDATE fg_lot SET qc_status = 'HOLD' WHERE fg_lot_no = ?1", [lot_no])?;
+    }
+
+    println!("⛔ LOT 보류: {} ({}, {})", lot_no, lot_type.as_str(), source_type);
+    require_hold(conn, &id)
+}
+
+/// 판단 입력 데이터에서 LOT 번호 추출 (문자열 또는 문자열 배열)
+pub fn lot_refs(input_data: &serde_json::Value) -> Vec<(String, Option<LotType>)> {
+    let mut lots: Vec<(String, Option<LotType>)> = Vec::new();
+    for key in LOT_INPUT_KEYS {
+        let lot_type = if *key == "batch_lot_no" { LotType::Batch } else { LotType::Fg };
+        let values: Vec<&str> = match input_data.get(*key) {
+            Some(serde_json::Value::String(value)) => vec![value.as_str()],
+            Some(serde_json::Value::Array(values)) => values.iter().filter_map(|v| v.as_str()).collect(),
+            _ => Vec::new(),
+        };
+        for value in values {
+            let value = value.trim();
+            if !value.is_empty() && !lots.iter().any(|(lot, _)| lot == value) {
+                lots.push((value.to_string(), Some(lot_type)));
+            }
+        }
+    }
+    lots
+}
+
+/// LOT 종류 판별 (지정값이 있으면 해당 테이블에 존재하는지만 확인)
+fn resolve_lot_type(conn: &Connection, lot_no: &str, lot_type: Option<LotType>) -> anyhow::Result<Option<LotType>> {
+    let exists = |sql: &str| -> anyhow::Result<bool> { Ok(conn.prepare(sql)?.exists([lot_no])?) };
+    let is_fg = || exists("SELECT 1 FROM fg_lot WHERE fg_lot_no = ?1");
+    let is_batch = || exists("SELECT 1 FROM batch_lot WHERE batch_lot_no = ?1");
+
+    Ok(match lot_type {
+        Some(LotType::Fg) => is_fg()?.then_some(LotType::Fg),
+        Some(LotType::Batch) => is_batch()?.then_some(LotType::Batch),
+        None if is_fg()? => Some(LotType::Fg),
+        None if is_batch()? => Some(LotType::Batch),
+        None => None,
+    })
+}
+
+/// 보류 시점 이후 가장 최근 품질검사 결과 (qc_test, qc_inspection)
+pub fn latest_qc_since(conn: &Connection, hold: &LotHold) -> anyhow::Result<Option<QcEvidence>> {
+    let ref_type = if hold.lot_type == LotType::Fg.as_str() { "FG" } else { "BATCH" };
+    // qc_test.test_date는 날짜만 기록되는 경우가 있어 보류일 당일 검사부터 인정
+    let held_date = &hold.held_at[..10.min(hold.held_at.len())];
+
+    let evidence = conn
+        .query_row(
+            "SELECT source, ref_no, result, tested_at FROM (
+                SELECT 'qc_test' AS source, qc_no AS ref_no, result, test_date AS tested_at, created_at AS recorded_at
+                FROM qc_test
+                WHERE (lot_no = ?1 OR (ref_type = ?2 AND ref_no = ?1)) AND result IS NOT NULL AND test_date >= ?3
+                UNION ALL
+                SELECT 'qc_inspection', inspection_no, result, inspection_time, created_at
+                FROM qc_inspection
+                WHERE lot_no = ?1 AND result IS NOT NULL AND inspection_time >= ?4
+             )
+             ORDER BY tested_at DESC, recorded_at DESC
+             LIMIT 1",
+            rusqlite::params![hold.lot_no, ref_type, held_date, hold.held_at],
+            |row| {
+                Ok(QcEvidence {
+                    source: row.get(0)?,
+                    ref_no: row.get(1)?,
+                    result: row.get(2)?,
+                    tested_at: row.get(3)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(evidence)
+}
+
+fn require_hold(conn: &Connection, id: &str) -> anyhow::Result<LotHold> {
+    load_hold(conn, id)?.ok_or_else(|| anyhow::anyhow!("LOT 보류를 찾을 수 없습니다: {}", id))
+}
+
+fn load_hold(conn: &Connection, id: &str) -> anyhow::Result<Option<LotHold>> {
+    Ok(conn
+        .query_row(&format!("{} WHERE id = ?1", HOLD_SELECT), [id], row_to_hold)
+        .optional()?)
+}
+
+const HOLD_SELECT: &str = "SELECT id, lot_no, lot_type, status, reason, source_type, source_ref, held_by, held_at,
+        approval_request_id, qc_ref, released_by, released_at, release_comment
+     FROM lot_holds";
+
+fn row_to_hold(row: &rusqlite::Row) -> rusqlite::Result<LotHold> {
+    Ok(LotHold {
+        id: row.get(0)?,
+        lot_no: row.get(1)?,
+        lot_type: row.get(2)?,
+        status: row.get(3)?,
+        reason: row.get(4)?,
+        source_type: row.get(5)?,
+        source_ref: row.get(6)?,
+        held_by: row.get(7)?,
+        held_at: row.get(8)?,
+        approval_request_id: row.get(9)?,
+        qc_ref: row.get(10)?,
+        released_by: row.get(11)?,
+        released_at: row.get(12)?,
+        release_comment: row.get(13)?,
+    })
+}
+
+fn now_string() -> String {
+    chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lot_refs() {
+        let input = serde_json::json!({
+            "batch_lot_no": "B-001",
+            "fg_lot": ["FG-001", "FG-002", ""],
+            "fg_lot_no": "FG-001",
+            "temperature": 72.0
+        });
+
+        let lots = lot_refs(&input);
+        assert_eq!(
+            lots,
+            vec![
+                ("B-001".to_string(), Some(LotType::Batch)),
+                ("FG-001".to_string(), Some(LotType::Fg)),
+                ("FG-002".to_string(), Some(LotType::Fg)),
+            ]
+        );
+        assert!(lot_refs(&serde_json::json!({ "temperature": 72.0 })).is_empty());
+    }
+
+    #[test]
+    fn test_hold_blocks_outbound_until_release() {
+        let store = LotDispositionStore::new().unwrap();
+        let suffix = uuid::Uuid::new_v4().to_string()[..8].to_string();
+        let batch = format!("TEST-B-{}", suffix);
+        let filling = format!("TEST-FL-{}", suffix);
+        let fg = format!("TEST-FG-{}", suffix);
+        // 시드 데이터의 생산지시/품목/출고를 참조 (외래키)
+        let (item_cd, outbound_no) = {
+            let db_conn = store.db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            let refs = conn
+                .query_row(
+                    "SELECT (SELECT item_cd FROM item_mst LIMIT 1), (SELECT outbound_no FROM outbound LIMIT 1),
+                            (SELECT prod_order_no FROM production_order LIMIT 1), (SELECT bom_cd FROM bom_mst LIMIT 1)",
+                    [],
+                    |row| {
+                        Ok((
+                            row.get::<_, Option<String>>(0)?,
+                            row.get::<_, Option<String>>(1)?,
+                            row.get::<_, Option<String>>(2)?,
+                            row.get::<_, Option<String>>(3)?,
+                        ))
+                    },
+                )
+                .unwrap();
+            let (Some(item_cd), Some(outbound_no), Some(prod_order_no), Some(bom_cd)) = refs else {
+                println!("⚠️  Seed 데이터 없음 - 테스트 스킵");
+                return;
+            };
+
+            conn.execute(
+                "INSERT INTO batch_lot (batch_lot_no, prod_order_no, bom_cd, batch_date, batch_size) VALUES (?1, ?2, ?3, '2025-11-01', 1000)",
+                rusqlite::params![batch, prod_order_no, bom_cd],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO filling_lot (filling_lot_no, batch_lot_no, filling_date, line_cd, pkg_item_cd, plan_qty) VALUES (?1, ?2, '2025-11-01', 'LINE-B', ?3, 100)",
+                rusqlite::params![filling, batch, item_cd],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO fg_lot (fg_lot_no, filling_lot_no, fg_item_cd, qty, mfg_date, exp_date) VALUES (?1, ?2, ?3, 100, '2025-11-01', '2026-05-01')",
+                rusqlite::params![fg, filling, item_cd],
+            )
+            .unwrap();
+            (item_cd, outbound_no)
+        };
+
+        // 판단 NG → 배치 LOT 보류 → 하위 완제품 LOT 출고 차단
+        let holds = store
+            .hold_from_judgment("judgment-test", &serde_json::json!({ "batch_lot_no": batch, "fg_lot_no": "NO-SUCH-LOT" }), "살균 온도 미달")
+            .unwrap();
+        assert_eq!(holds.len(), 1);
+        let hold = &holds[0];
+        assert_eq!(hold.lot_type, "BATCH");
+        assert!(!store.check_outbound(&fg).unwrap().shippable);
+
+        {
+            let db_conn = store.db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            let blocked = conn.execute(
+                "INSERT INTO outbound_dtl (outbound_no, seq, item_cd, fg_lot_no, qty) VALUES (?1, 9999, ?2, ?3, 10)",
+                rusqlite::params![outbound_no, item_cd, fg],
+            );
+            assert!(blocked.unwrap_err().to_string().contains("출하 보류"));
+        }
+
+        // 품질검사 없이 해제 요청 불가, 불합격 후에도 불가
+        assert!(store.request_release(&hold.id, "qa@example.com", None).is_err());
+        let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+        {
+            let db_conn = store.db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO qc_test (qc_no, test_type, ref_type, ref_no, item_cd, lot_no, test_date, result, created_at)
+                 VALUES (?1, 'HOLD_RELEASE', 'BATCH', ?2, ?4, ?2, ?3, 'FAIL', datetime('now', '-1 second'))",
+                rusqlite::params![format!("QC-F-{}", suffix), batch, today, item_cd],
+            )
+            .unwrap();
+        }
+        assert!(store.request_release(&hold.id, "qa@example.com", None).is_err());
+        {
+            let db_conn = store.db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO qc_test (qc_no, test_type, ref_type, ref_no, item_cd, lot_no, test_date, result)
+                 VALUES (?1, 'HOLD_RELEASE', 'BATCH', ?2, ?4, ?2, ?3, 'PASS')",
+                rusqlite::params![format!("QC-P-{}", suffix), batch, today, item_cd],
+            )
+            .unwrap();
+        }
+
+        let pending = store.request_release(&hold.id, "qa@example.com", None).unwrap();
+        assert_eq!(pending.status, HOLD_STATUS_RELEASE_PENDING);
+        // 승인 전에는 여전히 출고 차단
+        assert!(!store.check_outbound(&fg).unwrap().shippable);
+
+        {
+            let db_conn = store.db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            conn.execute(
+                "UPDATE approval_requests SET status = 'approved', decided_by = 'qa@example.com', decided_at = ?2 WHERE id = ?1",
+                rusqlite::params![pending.approval_request_id, chrono::Utc::now().to_rfc3339()],
+            )
+            .unwrap();
+        }
+        let released = store.sync_release(&hold.id).unwrap();
+        assert_eq!(released.status, HOLD_STATUS_RELEASED);
+        assert!(store.check_outbound(&fg).unwrap().shippable);
+        assert_eq!(store.history(&batch).unwrap().len(), 1);
+    }
+}
@@ -1,13 +1,15 @@
 use serde_json;
 use crate::database::Database;
 use crate::services::llm_engine::LLMEngine;
+use crate::services::mes_ingest;
+use crate::services::mes_query::{self, MesQueryService};
 use crate::utils::security::{sanitize_for_xml, detect_injection_attempt};
 
 /// Generic MES/ERP RAG 서비스
 ///
 /// 기능:
-/// 1. CSV 파일 업로드 및 SQLite 저장
-/// 2. FTS5 BM25 기반 데이터 검색
+/// 1. CSV/xlsx/Parquet 파일 업로드 및 SQLite 저장 (적재는 mes_ingest 공용)
+/// 2. 구조화 질의(mes_query) + FTS5 BM25 기반 데이터 검색
 /// 3. LLM 자연어 질의응답
 /// 4. 세션 기반 데이터 격리
 pub struct MesDataService {
@@ -17,103 +19,43 @@ pub struct MesDataService {
 
 impl MesDataService {
     pub fn new() -> anyhow::Result<Self> {
+        Self::from_database(Database::new()?)
+    }
+
+    pub fn from_database(db: Database) -> anyhow::Result<Self> {
         Ok(Self {
-            db: Database::new()?,
-            llm_engine: LLMEngine::new()?,
+            llm_engine: LLMEngine::from_database(db.clone())?,
+            db,
         })
     }
 
-    /// CSV 파일 업로드 및 SQLite 저장
+    /// CSV/xlsx/Parquet 파일 업로드 및 SQLite 저장
     ///
     /// Parameters:
     /// - session_id: 세션 UUID (사용자 격리용)
-    /// - file_name: 원본 파일명 (예: "mes_data_2025-01.csv")
-    /// - file_content: CSV 파일 내용 (Vec<u8>)
+    /// - file_name: 원본 파일명 (예: "mes_data_2025-01.csv", 확장자로 형식 판별)
+    /// - file_content: 파일 내용 (CSV는 UTF-8 또는 CP949, xlsx는 데이터가 있는 첫 시트)
     ///
     /// Returns: 저장된 행 수
     ///
     /// Process:
-    /// 1. 기존 세션 데이터 삭제 (덮어쓰기)
-    /// 2. CSV 파싱 (헤더 포함)
-    /// 3. 각 행을 "컬럼명: 값" 형식으로 변환
-    /// 4. mes_data_logs 테이블에 삽입
-    /// 5. FTS5 자동 인덱싱 (트리거)
+    /// 1. 인코딩 감지 및 컬럼 타입 추론 (mes_ingest)
+    /// 2. 각 행을 타입 값 raw_json + "컬럼명: 값" content로 변환
+    /// 3. 배치 단위로 mes_data_logs 테이블에 삽입 (데이터는 누적 저장)
+    /// 4. FTS5 자동 인덱싱 (트리거), 업로드 스키마는 mes_uploads에 기록
+    ///
+    /// 대용량 파일은 파일 경로 기반 MesIngestService::ingest_file 사용
     pub fn upload_mes_data(
         &self,
         session_id: &str,
         file_name: &str,
         file_content: &[u8],
     ) -> anyhow::Result<usize> {
-        let db_conn = self.db.get_connection();
-        let mut conn = db_conn.lock()
-            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
-
-        // CSV 파싱
-        let mut reader = csv::Reader::from_reader(file_content);
-        let headers = reader.headers()
-            .map_err(|e| anyhow::anyhow!("CSV 헤더 파싱 실패: {}", e))?
-            .clone();
-
-        // 트랜잭션 시작
-        let tx = conn.transaction()?;
+        let upload = mes_ingest::ingest_bytes(&self.db, session_id, file_name, file_content, None, &mut |_| {})?;
 
-        // 데이터 누적을 위해 기존 데이터 삭제하지 않음
-        // 모든 업로드된 데이터는 누적되어 저장됨
+        println!("[MES RAG] ✅ 파일 업로드 완료: {} ({} 행)", file_name, upload.row_count);
 
-        // CSV 행 삽입
-        let mut row_count = 0;
-        const MAX_ROWS: usize = 10_000; // 대용량 파일 제한
-
-        for (row_index, result) in reader.records().enumerate() {
-            if row_index >= MAX_ROWS {
-                return Err(anyhow::anyhow!(
-                    "파일이 너무 큽니다. 최대 {}행까지 지원합니다.",
-                    MAX_ROWS
-                ));
-            }
-
-            let record = result.map_err(|e| anyhow::anyhow!("CSV 행 파싱 실패 (행 {}): {}", row_index + 1, e))?;
-
-            // raw_json: {"온도": "90", "습도": "45", ...}
-            let mut json_map = serde_json::Map::new();
-            for (i, field) in record.iter().enumerate() {
-                if let Some(header) = headers.get(i) {
-                    json_map.insert(header.to_string(), serde_json::Value::String(field.to_string()));
-                }
-            }
-            let raw_json = serde_json::to_string(&json_map)?;
-
-            // content: "온도: 90, 습도: 45, 판정: NG" (검색용)
-            let content = headers
-                .iter()
-                .enumerate()
-                .filter_map(|(i, header)| {
-                    record.get(i).map(|value| format!("{}: {}", header, value))
-                })
-                .collect::<Vec<_>>()
-                .join(", ");
-
-            tx.execute(
-                "INSERT INTO mes_data_logs (session_id, file_name, row_index, raw_json, content)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                rusqlite::params![
-                    session_id,
-                    file_name,
-                    row_index as i64,
-                    raw_json,
-                    content,
-                ],
-            )?;
-
-            row_count += 1;
-        }
-
-        // 트랜잭션 커밋 (FTS5 트리거 자동 실행)
-        tx.commit()?;
-
-        println!("[MES RAG] ✅ 파일 업로드 완료: {} ({} 행)", file_name, row_count);
-
-        Ok(row_count)
+        Ok(upload.row_count as usize)
     }
 
     /// MES/ERP 데이터 자연어 질의
@@ -123,20 +65,34 @@ impl MesDataService {
     /// - question: 자연어 질문 (예: "온도가 90도 이상인 데이터는?")
     /// - top_k: 검색할 최대 행 수
     ///
-    /// Returns: LLM 생성 답변 (데이터 없으면 None)
+    /// Returns: 구조화 질의 답변 또는 LLM 생성 답변 (데이터 없으면 None)
     ///
     /// Process:
-    /// 1. 세션 데이터 존재 여부 확인
-    /// 2. 쿼리 전처리 (숫자 추출, 키워드 매핑)
-    /// 3. FTS5 BM25 검색 (개선된 검색어)
-    /// 4. 검색 실패시 LIKE 검색 또는 전체 데이터 샘플링
-    /// 5. LLM API 호출하여 자연어 답변 생성
+    /// 1. 세션 컬럼 스키마로 구조화 질의(비교/범위/그룹/상위 N) 계획 → SQL 실행 후 바로 답변
+    /// 2. 계획을 만들 수 없으면 세션 데이터 존재 여부 확인
+    /// 3. 쿼리 전처리 (키워드 매핑, 타입 스키마가 없는 데이터만 숫자 토큰 사용)
+    /// 4. FTS5 BM25 검색 (개선된 검색어)
+    /// 5. 검색 실패시 LIKE 검색 또는 전체 데이터 샘플링
+    /// 6. LLM API 호출하여 자연어 답변 생성
     pub async fn query_mes_data(
         &self,
-        _session_id: &str,  // 현재는 사용하지 않지만 API 호환성을 위해 유지
+        session_id: &str,
         question: &str,
         top_k: usize,
     ) -> anyhow::Result<Option<String>> {
+        // 1차: 구조화 질의 (숫자 비교는 FTS 토큰이 아닌 SQL 조건으로 처리)
+        let query_service = MesQueryService::from_database(self.db.clone());
+        match query_service.plan(session_id, question) {
+            Ok(Some(plan)) => {
+                let outcome = query_service.execute(session_id, plan)?;
+                println!("[MES RAG] 📐 구조화 질의 결과: {}건", outcome.total_matched);
+                return Ok(Some(mes_query::format_answer(&outcome)));
+            }
+            Ok(None) => {}
+            Err(e) => println!("[MES RAG] ℹ️  구조화 질의 불가, FTS 검색으로 전환: {}", e),
+        }
+        let has_typed_schema = !query_service.session_columns(session_id)?.is_empty();
+
         // 데이터베이스 쿼리 결과를 저장할 변수
         let mut results: Vec<(String, String, f64)>;
 
@@ -146,10 +102,10 @@ impl MesDataService {
             let conn = db_conn.lock()
                 .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
 
-            // 전체 데이터 존재 확인 (모든 세션의 누적 데이터)
+            // 세션 데이터 존재 확인
             let data_exists: i64 = conn.query_row(
-                "SELECT COUNT(*) FROM mes_data_logs",
-                rusqlite::params![],
+                "SELECT COUNT(*) FROM mes_data_logs WHERE session_id = ?1",
+                rusqlite::params![session_id],
                 |row| row.get(0),
             )?;
 
@@ -158,15 +114,16 @@ impl MesDataService {
                 return Ok(None);
             }
 
-            println!("[MES RAG] 📊 전체 누적 데이터: {}건", data_exists);
+            println!("[MES RAG] 📊 세션 누적 데이터: {}건", data_exists);
 
             // 쿼리 전처리: 숫자 추출 및 키워드 매핑
             let mut search_terms: Vec<String> = Vec::new();
 
             for word in question.split_whitespace() {
                 // 숫자 추출 (예: "90도" → "90", "45.5" → "45.5")
+                // 타입 스키마가 있으면 숫자 컬럼은 구조화 질의 대상이므로 텍스트 검색어에서 제외
                 let cleaned = word.trim_matches(|c: char| !c.is_numeric() && c != '.');
-                if !cleaned.is_empty() && cleaned.chars().any(|c| c.is_numeric()) {
+                if !has_typed_schema && !cleaned.is_empty() && cleaned.chars().any(|c| c.is_numeric()) {
                     search_terms.push(cleaned.to_string());
                 }
 
@@ -204,14 +161,14 @@ impl MesDataService {
                 SELECT m.raw_json, m.content, bm25(mes_data_logs_fts) AS score
                 FROM mes_data_logs m
                 JOIN mes_data_logs_fts f ON m.id = f.rowid
-                WHERE f.content MATCH ?1
+                WHERE f.content MATCH ?1 AND m.session_id = ?3
                 ORDER BY score
                 LIMIT ?2
             "#;
 
             let mut stmt = conn.prepare(sql)?;
             let rows = stmt.query_map(
-                rusqlite::params![search_query, top_k as i64],
+                rusqlite::params![search_query, top_k as i64, session_id],
                 |row| {
                     Ok((
                         row.get::<_, String>(0)?, // raw_json
@@ -232,13 +189,13 @@ impl MesDataService {
                 let sql_like = r#"
                     SELECT raw_json, content, 0.5 AS score
                     FROM mes_data_logs
-                    WHERE content LIKE ?1
+                    WHERE content LIKE ?1 AND session_id = ?3
                     LIMIT ?2
                 "#;
 
                 let mut stmt = conn.prepare(sql_like)?;
                 let rows = stmt.query_map(
-                    rusqlite::params![like_pattern, top_k as i64],
+                    rusqlite::params![like_pattern, top_k as i64, session_id],
                     |row| {
                         Ok((
                             row.get::<_, String>(0)?,
@@ -258,13 +215,14 @@ impl MesDataService {
                 let sql_all = r#"
                     SELECT raw_json, content, 0.0 AS score
                     FROM mes_data_logs
+                    WHERE session_id = ?2
                     ORDER BY id DESC
                     LIMIT ?1
                 "#;
 
                 let mut stmt = conn.prepare(sql_all)?;
                 let rows = stmt.query_map(
-                    rusqlite::params![std::cmp::min(top_k * 2, 30) as i64],
+                    rusqlite::params![std::cmp::min(top_k * 2, 30) as i64, session_id],
                     |row| {
                         Ok((
                             row.get::<_, String>(0)?,
@@ -416,6 +374,10 @@ Example for "온도가 90도 이상인 데이터":
             "DELETE FROM mes_data_logs WHERE session_id = ?1",
             rusqlite::params![session_id],
         )?;
+        conn.execute(
+            "DELETE FROM mes_uploads WHERE session_id = ?1",
+            rusqlite::params![session_id],
+        )?;
 
         println!("[MES RAG] 🗑️  세션 데이터 삭제: {} ({} 행)", session_id, deleted);
 
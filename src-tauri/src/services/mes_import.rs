@@ -0,0 +1,439 @@
+// services/mes_import.rs - MES xlsx/Parquet 읽기
+//
+// 라인 감독자의 xlsx 통합문서와 데이터팀의 Parquet 내보내기를 셀 문자열 행으로 변환해
+// mes_ingest 적재 파이프라인(타입 추론, 배치 적재, mes_uploads 기록)에 그대로 공급한다.
+// - xlsx: 시트 선택, 상단 제목/결재란을 건너뛰는 헤더 행 감지, 병합 셀 값 채우기
+// - Parquet: 최상위 필드를 컬럼으로, 행 그룹 단위 스트리밍 (arrow 미사용)
+
+use crate::services::mes_ingest::{
+    parse_datetime, parse_numeric, RowSource, SourceFormat, SourceInfo, TIMESTAMP_FORMAT,
+};
+use calamine::{open_workbook_auto, open_workbook_auto_from_rs, Data, Dimensions, Range, Reader, Sheets};
+use chrono::{DateTime, NaiveDate};
+use parquet::file::reader::{ChunkReader, FileReader, SerializedFileReader};
+use parquet::record::reader::RowIter;
+use parquet::record::Field;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+
+/// 헤더 행 탐색 범위 (시트 상단 제목/작성일/결재란 행)
+const HEADER_SCAN_ROWS: usize = 20;
+
+/// 통합문서 시트 요약 (시트 선택용)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheetSummary {
+    pub name: String,
+    pub row_count: usize,
+    pub column_count: usize,
+    /// 감지된 헤더 행 (0부터, 데이터 없으면 None)
+    pub header_row: Option<usize>,
+    pub headers: Vec<String>,
+}
+
+/// 통합문서 시트 목록 (시트별 헤더 감지 결과 포함)
+pub fn list_sheets(path: &Path) -> anyhow::Result<Vec<SheetSummary>> {
+    let mut workbook = open_workbook_auto(path)
+        .map_err(|e| anyhow::anyhow!("통합문서 열기 실패 ({}): {}", path.display(), e))?;
+
+    workbook
+        .sheet_names()
+        .into_iter()
+        .map(|name| {
+            let grid = load_sheet_grid(&mut workbook, &name)?;
+            let header_row = detect_header_row(&grid);
+            Ok(SheetSummary {
+                row_count: grid.len(),
+                column_count: grid.iter().map(Vec::len).max().unwrap_or(0),
+                headers: header_row.map(|i| grid[i].clone()).unwrap_or_default(),
+                header_row,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// xlsx 시트 행 공급자 (시트는 메모리에 적재 후 헤더 아래 행을 순서대로 공급)
+pub struct XlsxSource {
+    sheet_name: String,
+    headers: Vec<String>,
+    rows: std::vec::IntoIter<Vec<String>>,
+    total_rows: u64,
+}
+
+impl XlsxSource {
+    /// sheet: 시트 이름 (None이면 데이터가 있는 첫 시트)
+    pub fn open_path(path: &Path, sheet: Option<&str>) -> anyhow::Result<Self> {
+        let workbook = open_workbook_auto(path)
+            .map_err(|e| anyhow::anyhow!("통합문서 열기 실패 ({}): {}", path.display(), e))?;
+        Self::from_workbook(workbook, sheet)
+    }
+
+    pub fn from_bytes(content: &[u8], sheet: Option<&str>) -> anyhow::Result<Self> {
+        let workbook = open_workbook_auto_from_rs(Cursor::new(content))
+            .map_err(|e| anyhow::anyhow!("통합문서 열기 실패: {}", e))?;
+        Self::from_workbook(workbook, sheet)
+    }
+
+    fn from_workbook<RS: Read + Seek>(mut workbook: Sheets<RS>, sheet: Option<&str>) -> anyhow::Result<Self> {
+        let sheet_names = workbook.sheet_names();
+        let (sheet_name, grid) = match sheet {
+            Some(sheet) => {
+                let name = sheet_names
+                    .iter()
+                    .find(|name| name.as_str() == sheet.trim())
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("시트를 찾을 수 없습니다: {} (시트: {})", sheet, sheet_names.join(", "))
+                    })?
+                    .clone();
+                let grid = load_sheet_grid(&mut workbook, &name)?;
+                (name, grid)
+            }
+            None => {
+                let mut found = None;
+                for name in &sheet_names {
+                    let grid = load_sheet_grid(&mut workbook, name)?;
+                    if detect_header_row(&grid).is_some() {
+                        found = Some((name.clone(), grid));
+                        break;
+                    }
+                }
+                found.ok_or_else(|| anyhow::anyhow!("데이터가 있는 시트가 없습니다"))?
+            }
+        };
+
+        let header_row = detect_header_row(&grid)
+            .ok_or_else(|| anyhow::anyhow!("시트 '{}'에서 헤더 행을 찾을 수 없습니다", sheet_name))?;
+        let mut rows = grid.into_iter().skip(header_row);
+        let headers = rows.next().unwrap_or_default();
+        // 중간 빈 행(소계 구분 등)은 건너뜀
+        let data: Vec<Vec<String>> = rows
+            .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()))
+            .collect();
+        println!(
+            "[MES IMPORT] 📗 시트 '{}': 헤더 {}행, 데이터 {}행",
+            sheet_name,
+            header_row + 1,
+            data.len()
+        );
+
+        Ok(Self {
+            sheet_name,
+            headers,
+            total_rows: data.len() as u64,
+            rows: data.into_iter(),
+        })
+    }
+
+    pub fn sheet_name(&self) -> &str {
+        &self.sheet_name
+    }
+
+    pub fn info(&self, source_path: Option<String>, total_bytes: Option<u64>) -> SourceInfo {
+        SourceInfo {
+            format: SourceFormat::Xlsx,
+            source_path,
+            encoding: "UTF-8".to_string(),
+            sheet_name: Some(self.sheet_name.clone()),
+            total_bytes,
+            total_rows: Some(self.total_rows),
+        }
+    }
+}
+
+impl RowSource for XlsxSource {
+    fn headers(&mut self) -> anyhow::Result<Vec<String>> {
+        Ok(self.headers.clone())
+    }
+
+    fn next_row(&mut self) -> Option<anyhow::Result<Vec<String>>> {
+        self.rows.next().map(Ok)
+    }
+}
+
+/// 시트를 셀 문자열 격자로 변환하고 병합 셀을 채운다
+fn load_sheet_grid<RS: Read + Seek>(workbook: &mut Sheets<RS>, name: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    let range = workbook
+        .worksheet_range(name)
+        .map_err(|e| anyhow::anyhow!("시트 읽기 실패 ({}): {}", name, e))?;
+    let merges = match workbook {
+        Sheets::Xlsx(xlsx) => xlsx
+            .worksheet_merge_cells(name)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("병합 셀 읽기 실패 ({}): {}", name, e))?
+            .unwrap_or_default(),
+        Sheets::Xls(xls) => xls.worksheet_merge_cells(name).unwrap_or_default(),
+        Sheets::Xlsb(_) | Sheets::Ods(_) => Vec::new(),
+    };
+    Ok(sheet_grid(&range, &merges))
+}
+
+/// Range → 문자열 격자, 병합 영역은 좌상단 값을 영역 전체에 채움
+///
+/// 감독자 일지는 날짜/라인/설비 셀을 세로로 병합하는 경우가 많아 채우지 않으면 해당 행 값이 비게 된다.
+pub fn sheet_grid(range: &Range<Data>, merges: &[Dimensions]) -> Vec<Vec<String>> {
+    let Some((start_row, start_col)) = range.start() else {
+        return Vec::new();
+    };
+    let mut grid: Vec<Vec<String>> = range
+        .rows()
+        .map(|row| row.iter().map(cell_to_string).collect())
+        .collect();
+
+    for merge in merges {
+        let value = range.get_value(merge.start).map(cell_to_string).unwrap_or_default();
+        if value.is_empty() {
+            continue;
+        }
+        for row in merge.start.0..=merge.end.0 {
+            for col in merge.start.1..=merge.end.1 {
+                let (Some(r), Some(c)) = (row.checked_sub(start_row), col.checked_sub(start_col)) else {
+                    continue;
+                };
+                if let Some(cell) = grid.get_mut(r as usize).and_then(|cells| cells.get_mut(c as usize)) {
+                    cell.clone_from(&value);
+                }
+            }
+        }
+    }
+    grid
+}
+
+/// 헤더 행 감지: 상단 HEADER_SCAN_ROWS 행 중 숫자/날짜가 아닌 고유 문자열이 가장 많은 행 (동률이면 위쪽)
+///
+/// 병합된 제목 행은 같은 값이 채워져 고유값이 1개이므로 헤더로 선택되지 않는다.
+pub fn detect_header_row(grid: &[Vec<String>]) -> Option<usize> {
+    grid.iter()
+        .take(HEADER_SCAN_ROWS)
+        .enumerate()
+        .map(|(i, row)| {
+            let labels: BTreeSet<&str> = row
+                .iter()
+                .map(|cell| cell.trim())
+                .filter(|cell| !cell.is_empty() && parse_numeric(cell).is_none() && parse_datetime(cell).is_none())
+                .collect();
+            (i, labels.len())
+        })
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(i, count)| (*count, std::cmp::Reverse(*i)))
+        .map(|(i, _)| i)
+}
+
+/// 셀 값 → 문자열 (날짜 셀은 "YYYY-MM-DD HH:MM:SS", 오류 셀은 "#DIV/0!" 등 원문)
+fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => value.clone(),
+        Data::Int(value) => value.to_string(),
+        Data::Float(value) => value.to_string(),
+        Data::Bool(value) => value.to_string(),
+        Data::DateTime(value) => value
+            .as_datetime()
+            .map(|dt| dt.format(TIMESTAMP_FORMAT).to_string())
+            .unwrap_or_else(|| value.as_f64().to_string()),
+        Data::Error(error) => error.to_string(),
+    }
+}
+
+/// Parquet 행 공급자 (최상위 필드 = 컬럼)
+pub struct ParquetSource {
+    headers: Vec<String>,
+    rows: RowIter<'static>,
+    total_rows: u64,
+}
+
+impl ParquetSource {
+    pub fn open_path(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| anyhow::anyhow!("파일 열기 실패 ({}): {}", path.display(), e))?;
+        Self::from_chunk_reader(file)
+    }
+
+    pub fn from_bytes(content: &[u8]) -> anyhow::Result<Self> {
+        Self::from_chunk_reader(bytes::Bytes::copy_from_slice(content))
+    }
+
+    fn from_chunk_reader<R: ChunkReader + 'static>(input: R) -> anyhow::Result<Self> {
+        let reader = SerializedFileReader::new(input)
+            .map_err(|e| anyhow::anyhow!("Parquet 파일 열기 실패: {}", e))?;
+        let metadata = reader.metadata().file_metadata();
+        let headers = metadata
+            .schema_descr()
+            .root_schema()
+            .get_fields()
+            .iter()
+            .map(|field| field.name().to_string())
+            .collect();
+        let total_rows = metadata.num_rows().max(0) as u64;
+
+        Ok(Self {
+            headers,
+            total_rows,
+            rows: RowIter::from_file_into(Box::new(reader)),
+        })
+    }
+
+    pub fn info(&self, source_path: Option<String>, total_bytes: Option<u64>) -> SourceInfo {
+        SourceInfo {
+            format: SourceFormat::Parquet,
+            source_path,
+            encoding: "UTF-8".to_string(),
+            sheet_name: None,
+            total_bytes,
+            total_rows: Some(self.total_rows),
+        }
+    }
+}
+
+impl RowSource for ParquetSource {
+    fn headers(&mut self) -> anyhow::Result<Vec<String>> {
+        Ok(self.headers.clone())
+    }
+
+    fn next_row(&mut self) -> Option<anyhow::Result<Vec<String>>> {
+        self.rows.next().map(|row| {
+            row.map(|row| row.get_column_iter().map(|(_, field)| field_to_string(field)).collect())
+                .map_err(|e| anyhow::anyhow!("Parquet 행 읽기 실패: {}", e))
+        })
+    }
+}
+
+/// Parquet 필드 → 문자열 (날짜/타임스탬프는 UTC 기준 "YYYY-MM-DD HH:MM:SS", 중첩 필드는 원문 표기)
+fn field_to_string(field: &Field) -> String {
+    match field {
+        Field::Null => String::new(),
+        Field::Str(value) => value.clone(),
+        Field::Bytes(value) => value
+            .as_utf8()
+            .map(str::to_string)
+            .unwrap_or_else(|_| field.to_string()),
+        // 1970-01-01 기준 일수
+        Field::Date(days) => NaiveDate::from_num_days_from_ce_opt(days + 719_163)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| days.to_string()),
+        Field::TimestampMillis(millis) => DateTime::from_timestamp_millis(*millis)
+            .map(|dt| dt.naive_utc().format(TIMESTAMP_FORMAT).to_string())
+            .unwrap_or_else(|| millis.to_string()),
+        Field::TimestampMicros(micros) => DateTime::from_timestamp_micros(*micros)
+            .map(|dt| dt.naive_utc().format(TIMESTAMP_FORMAT).to_string())
+            .unwrap_or_else(|| micros.to_string()),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::services::mes_ingest::{self, ColumnType, UPLOAD_STATUS_COMPLETED};
+
+    #[test]
+    fn test_sheet_grid_header_detection_and_merged_cells() {
+        // 0: 제목 (A1:D1 병합), 1: 작성일, 2: 헤더, 3~5: 데이터 (일자 A4:A5 세로 병합)
+        let mut range: Range<Data> = Range::new((0, 0), (5, 3));
+        range.set_value((0, 0), Data::String("3월 1라인 생산일지".to_string()));
+        range.set_value((1, 0), Data::String("작성일".to_string()));
+        range.set_value((1, 1), Data::String("2025-03-02".to_string()));
+        for (col, header) in ["일자", "설비ID", "온도", "판정"].iter().enumerate() {
+            range.set_value((2, col as u32), Data::String(header.to_string()));
+        }
+        range.set_value((3, 0), Data::String("2025-03-01".to_string()));
+        range.set_value((3, 1), Data::String("EQ-001".to_string()));
+        range.set_value((3, 2), Data::Float(90.5));
+        range.set_value((3, 3), Data::String("정상".to_string()));
+        range.set_value((4, 1), Data::String("EQ-002".to_string()));
+        range.set_value((4, 2), Data::Int(88));
+        range.set_value((4, 3), Data::String("불량".to_string()));
+        let merges = vec![
+            Dimensions { start: (0, 0), end: (0, 3) },
+            Dimensions { start: (3, 0), end: (4, 0) },
+        ];
+
+        let grid = sheet_grid(&range, &merges);
+        assert_eq!(grid[0], vec!["3월 1라인 생산일지"; 4]);
+        assert_eq!(detect_header_row(&grid), Some(2));
+        assert_eq!(grid[4], vec!["2025-03-01", "EQ-002", "88", "불량"]);
+        assert_eq!(grid[3][2], "90.5");
+        assert!(grid[5].iter().all(String::is_empty));
+    }
+
+    #[test]
+    fn test_ingest_parquet() {
+        use parquet::data_type::{ByteArray, ByteArrayType, DoubleType};
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+        use std::sync::Arc;
+
+        let Ok(db) = Database::new() else {
+            println!("⚠️  DB 초기화 실패 - 테스트 스킵");
+            return;
+        };
+        let session_id = format!("test-parquet-{}", uuid::Uuid::new_v4());
+
+        let schema = Arc::new(
+            parse_message_type(
+                "message mes { REQUIRED BINARY equipment_id (UTF8); OPTIONAL DOUBLE temperature; REQUIRED BINARY judgment (UTF8); }",
+            )
+            .unwrap(),
+        );
+        let mut content = Vec::new();
+        {
+            let mut writer = SerializedFileWriter::new(&mut content, schema, Arc::new(WriterProperties::builder().build())).unwrap();
+            let mut row_group = writer.next_row_group().unwrap();
+            let equipment: Vec<ByteArray> = (0..4).map(|i| ByteArray::from(format!("EQ-00{}", i % 2).as_str())).collect();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column.typed::<ByteArrayType>().write_batch(&equipment, None, None).unwrap();
+            column.close().unwrap();
+            // 3번째 행 온도 누락
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column.typed::<DoubleType>().write_batch(&[90.5, 88.0, 92.5], Some(&[1, 1, 0, 1]), None).unwrap();
+            column.close().unwrap();
+            let judgments: Vec<ByteArray> = ["정상", "정상", "불량", "정상"].iter().map(|j| ByteArray::from(*j)).collect();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column.typed::<ByteArrayType>().write_batch(&judgments, None, None).unwrap();
+            column.close().unwrap();
+            row_group.close().unwrap();
+            writer.close().unwrap();
+        }
+
+        let mut events = Vec::new();
+        let upload = mes_ingest::ingest_bytes(
+            &db,
+            &session_id,
+            "mes_export.parquet",
+            &content,
+            None,
+            &mut |progress| events.push(progress.clone()),
+        )
+        .unwrap();
+
+        assert_eq!(upload.status, UPLOAD_STATUS_COMPLETED);
+        assert_eq!(upload.source_format, "parquet");
+        assert_eq!(upload.row_count, 4);
+        assert_eq!(events.last().unwrap().total_rows, Some(4));
+        let temperature = upload.columns.iter().find(|c| c.name == "temperature").unwrap();
+        assert_eq!(temperature.column_type, ColumnType::Numeric);
+        assert_eq!(temperature.null_count, 1);
+        assert_eq!(temperature.max, Some(serde_json::json!(92.5)));
+
+        {
+            let db_conn = db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            let raw_json: String = conn
+                .query_row(
+                    "SELECT raw_json FROM mes_data_logs WHERE upload_id = ?1 AND row_index = 2",
+                    [&upload.id],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            let raw: serde_json::Value = serde_json::from_str(&raw_json).unwrap();
+            assert_eq!(raw["temperature"], serde_json::Value::Null);
+            assert_eq!(raw["judgment"], "불량");
+
+            conn.execute("DELETE FROM mes_data_logs WHERE session_id = ?1", [&session_id]).unwrap();
+            conn.execute("DELETE FROM mes_uploads WHERE session_id = ?1", [&session_id]).unwrap();
+        }
+    }
+}
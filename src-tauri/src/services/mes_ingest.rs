@@ -0,0 +1,861 @@
+// services/mes_ingest.rs - MES CSV/xlsx/Parquet 스트리밍 적재
+//
+// 일일 MES 내보내기(20만 행 이상)를 파일 경로에서 스트리밍으로 읽어 mes_data_logs에 적재한다.
+// - 원본 형식은 확장자로 판별, xlsx/Parquet 읽기는 mes_import (RowSource 공용 파이프라인)
+// - CSV 인코딩 감지: BOM → UTF-8 검증 → 실패 시 CP949(EUC-KR)로 디코딩
+// - 앞부분 샘플로 컬럼 타입(numeric / datetime / categorical / text) 추론 후 raw_json에 타입 값 저장
+// - INGEST_BATCH_ROWS 단위 트랜잭션 커밋 + 진행 콜백 (DB lock은 배치마다 해제)
+// - 업로드별 스키마/인코딩/상태를 mes_uploads에 기록, 실패 시 해당 업로드 행 삭제
+
+use crate::database::Database;
+use crate::services::mes_import::{ParquetSource, XlsxSource};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use encoding_rs::{Decoder, Encoding, EUC_KR, UTF_8};
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// 트랜잭션 1회당 적재 행 수
+pub const INGEST_BATCH_ROWS: usize = 5_000;
+/// 컬럼 타입 추론에 쓰는 앞부분 행 수
+pub const INFER_SAMPLE_ROWS: usize = 1_000;
+/// 인코딩 감지용 앞부분 바이트 수
+const ENCODING_SNIFF_BYTES: usize = 64 * 1024;
+/// categorical 판정 최대 고유값 수 (스키마에도 이 수까지만 기록)
+const MAX_CATEGORIES: usize = 50;
+/// numeric/datetime 판정 최소 변환 비율 ("측정불가", "N/A" 같은 일부 값 허용)
+const INFER_MIN_MATCH_RATIO: f64 = 0.95;
+
+pub(crate) const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y/%m/%d %H:%M",
+    "%Y.%m.%d %H:%M:%S",
+];
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d"];
+
+pub const UPLOAD_STATUS_IN_PROGRESS: &str = "IN_PROGRESS";
+pub const UPLOAD_STATUS_COMPLETED: &str = "COMPLETED";
+pub const UPLOAD_STATUS_FAILED: &str = "FAILED";
+
+/// 원본 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Csv,
+    /// xlsx/xlsm/xls/xlsb/ods 통합문서
+    Xlsx,
+    Parquet,
+}
+
+impl SourceFormat {
+    /// 확장자로 형식 판별 (알 수 없으면 CSV)
+    pub fn from_file_name(file_name: &str) -> Self {
+        let extension = Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("xlsx" | "xlsm" | "xls" | "xlsb" | "ods") => SourceFormat::Xlsx,
+            Some("parquet" | "parq") => SourceFormat::Parquet,
+            _ => SourceFormat::Csv,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceFormat::Csv => "csv",
+            SourceFormat::Xlsx => "xlsx",
+            SourceFormat::Parquet => "parquet",
+        }
+    }
+}
+
+/// 적재 행 공급자 (CSV 스트림 / xlsx 시트 / Parquet 파일)
+pub trait RowSource {
+    /// 헤더 원문 (빈 이름/중복은 적재 시 정규화)
+    fn headers(&mut self) -> anyhow::Result<Vec<String>>;
+
+    /// 다음 데이터 행의 셀 문자열, 끝이면 None
+    fn next_row(&mut self) -> Option<anyhow::Result<Vec<String>>>;
+
+    /// 원본에서 읽은 바이트 수 (행 단위 원본은 0, 진행률은 total_rows 기준)
+    fn bytes_read(&self) -> u64 {
+        0
+    }
+}
+
+/// mes_uploads에 기록할 원본 정보
+#[derive(Debug, Clone)]
+pub struct SourceInfo {
+    pub format: SourceFormat,
+    pub source_path: Option<String>,
+    /// CSV: 감지 인코딩, xlsx/Parquet: "UTF-8"
+    pub encoding: String,
+    pub sheet_name: Option<String>,
+    pub total_bytes: Option<u64>,
+    pub total_rows: Option<u64>,
+}
+
+/// 컬럼 타입
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Numeric,
+    Datetime,
+    /// 고유값이 적은 문자열 (설비ID, 판정 등)
+    Categorical,
+    Text,
+}
+
+/// 업로드 컬럼 스키마 (적재 중 전체 행 기준 통계)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub non_null_count: u64,
+    pub null_count: u64,
+    /// 추론 타입으로 변환되지 않아 문자열로 저장된 값 수
+    pub invalid_count: u64,
+    /// numeric: 숫자, datetime: "YYYY-MM-DD HH:MM:SS"
+    pub min: Option<serde_json::Value>,
+    pub max: Option<serde_json::Value>,
+    /// categorical 고유값 (최대 MAX_CATEGORIES개)
+    pub categories: Vec<String>,
+}
+
+/// 적재 진행 상황 (mes-ingest-progress 이벤트 payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestProgress {
+    pub upload_id: String,
+    pub file_name: String,
+    pub rows_processed: u64,
+    pub bytes_read: u64,
+    pub total_bytes: Option<u64>,
+    /// xlsx/Parquet 전체 데이터 행 수 (CSV는 None)
+    pub total_rows: Option<u64>,
+    pub done: bool,
+}
+
+/// 업로드 기록 (mes_uploads)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MesUpload {
+    pub id: String,
+    pub session_id: String,
+    pub file_name: String,
+    pub source_path: Option<String>,
+    pub encoding: String,
+    pub source_format: String,
+    pub sheet_name: Option<String>,
+    pub row_count: u64,
+    pub columns: Vec<ColumnSchema>,
+    pub status: String,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+}
+
+/// MES 파일 적재 서비스
+pub struct MesIngestService {
+    db: Database,
+}
+
+impl MesIngestService {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// 파일 경로에서 스트리밍 적재
+    ///
+    /// 형식은 확장자로 판별하며 sheet는 xlsx 시트 이름 (None이면 데이터가 있는 첫 시트)
+    pub fn ingest_file(
+        &self,
+        session_id: &str,
+        path: &Path,
+        sheet: Option<&str>,
+        on_progress: &mut dyn FnMut(&IngestProgress),
+    ) -> anyhow::Result<MesUpload> {
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let source_path = Some(path.display().to_string());
+        let total_bytes = std::fs::metadata(path)
+            .map_err(|e| anyhow::anyhow!("파일 열기 실패 ({}): {}", path.display(), e))?
+            .len();
+
+        match SourceFormat::from_file_name(&file_name) {
+            SourceFormat::Csv => {
+                let file = std::fs::File::open(path)
+                    .map_err(|e| anyhow::anyhow!("파일 열기 실패 ({}): {}", path.display(), e))?;
+                ingest_reader(&self.db, session_id, &file_name, source_path.as_deref(), file, Some(total_bytes), on_progress)
+            }
+            SourceFormat::Xlsx => {
+                let mut source = XlsxSource::open_path(path, sheet)?;
+                let info = source.info(source_path, Some(total_bytes));
+                ingest_source(&self.db, session_id, &file_name, info, &mut source, on_progress)
+            }
+            SourceFormat::Parquet => {
+                let mut source = ParquetSource::open_path(path)?;
+                let info = source.info(source_path, Some(total_bytes));
+                ingest_source(&self.db, session_id, &file_name, info, &mut source, on_progress)
+            }
+        }
+    }
+
+    pub fn get_upload(&self, upload_id: &str) -> anyhow::Result<Option<MesUpload>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        Ok(conn
+            .query_row(&format!("{} WHERE id = ?1", UPLOAD_SELECT), [upload_id], row_to_upload)
+            .optional()?)
+    }
+
+    /// 업로드 목록 (session_id 지정 시 해당 세션만, 최신순)
+    pub fn list_uploads(&self, session_id: Option<&str>) -> anyhow::Result<Vec<MesUpload>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE (?1 IS NULL OR session_id = ?1) ORDER BY started_at DESC, id",
+            UPLOAD_SELECT
+        ))?;
+        let uploads = stmt
+            .query_map([session_id], row_to_upload)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(uploads)
+    }
+}
+
+/// 메모리 파일 적재 (upload_mes_data), 형식은 file_name 확장자로 판별
+pub fn ingest_bytes(
+    db: &Database,
+    session_id: &str,
+    file_name: &str,
+    content: &[u8],
+    sheet: Option<&str>,
+    on_progress: &mut dyn FnMut(&IngestProgress),
+) -> anyhow::Result<MesUpload> {
+    let total_bytes = Some(content.len() as u64);
+    match SourceFormat::from_file_name(file_name) {
+        SourceFormat::Csv => ingest_reader(db, session_id, file_name, None, content, total_bytes, on_progress),
+        SourceFormat::Xlsx => {
+            let mut source = XlsxSource::from_bytes(content, sheet)?;
+            let info = source.info(None, total_bytes);
+            ingest_source(db, session_id, file_name, info, &mut source, on_progress)
+        }
+        SourceFormat::Parquet => {
+            let mut source = ParquetSource::from_bytes(content)?;
+            let info = source.info(None, total_bytes);
+            ingest_source(db, session_id, file_name, info, &mut source, on_progress)
+        }
+    }
+}
+
+/// CSV 스트림 적재 (파일/메모리 공용)
+pub fn ingest_reader<R: Read>(
+    db: &Database,
+    session_id: &str,
+    file_name: &str,
+    source_path: Option<&str>,
+    reader: R,
+    total_bytes: Option<u64>,
+    on_progress: &mut dyn FnMut(&IngestProgress),
+) -> anyhow::Result<MesUpload> {
+    let mut buffered = BufReader::with_capacity(ENCODING_SNIFF_BYTES, reader);
+    let encoding = detect_encoding(buffered.fill_buf()?);
+    let info = SourceInfo {
+        format: SourceFormat::Csv,
+        source_path: source_path.map(str::to_string),
+        encoding: encoding.name().to_string(),
+        sheet_name: None,
+        total_bytes,
+        total_rows: None,
+    };
+
+    let mut source = CsvSource {
+        reader: csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(DecodingReader::new(buffered, encoding)),
+        record: csv::StringRecord::new(),
+        row_index: 0,
+    };
+    ingest_source(db, session_id, file_name, info, &mut source, on_progress)
+}
+
+/// 행 공급자 적재 (형식 공용)
+///
+/// 이미 커밋된 배치가 있어도 중간에 실패하면 해당 업로드의 행을 삭제하고 FAILED로 기록한다.
+pub fn ingest_source(
+    db: &Database,
+    session_id: &str,
+    file_name: &str,
+    info: SourceInfo,
+    source: &mut dyn RowSource,
+    on_progress: &mut dyn FnMut(&IngestProgress),
+) -> anyhow::Result<MesUpload> {
+    let upload_id = format!("upl-{}", uuid::Uuid::new_v4());
+
+    {
+        let db_conn = db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        conn.execute(
+            "INSERT INTO mes_uploads (id, session_id, file_name, source_path, encoding, source_format, sheet_name, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                upload_id,
+                session_id,
+                file_name,
+                info.source_path,
+                info.encoding,
+                info.format.as_str(),
+                info.sheet_name,
+                UPLOAD_STATUS_IN_PROGRESS
+            ],
+        )?;
+    }
+    println!(
+        "[MES INGEST] 📥 적재 시작: {} ({}, {}, {})",
+        file_name, info.format.as_str(), info.encoding, upload_id
+    );
+
+    let result = stream_rows(db, session_id, file_name, &upload_id, source, &info, on_progress);
+
+    let db_conn = db.get_connection();
+    let conn = db_conn.lock()
+        .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+    match result {
+        Ok((row_count, columns)) => {
+            conn.execute(
+                "UPDATE mes_uploads SET status = ?2, row_count = ?3, schema_json = ?4, completed_at = datetime('now')
+                 WHERE id = ?1",
+                rusqlite::params![upload_id, UPLOAD_STATUS_COMPLETED, row_count as i64, serde_json::to_string(&columns)?],
+            )?;
+            println!("[MES INGEST] ✅ 적재 완료: {} ({} 행, {} 컬럼)", file_name, row_count, columns.len());
+            Ok(conn.query_row(&format!("{} WHERE id = ?1", UPLOAD_SELECT), [&upload_id], row_to_upload)?)
+        }
+        Err(e) => {
+            conn.execute("DELETE FROM mes_data_logs WHERE upload_id = ?1", [&upload_id])?;
+            conn.execute(
+                "UPDATE mes_uploads SET status = ?2, error = ?3, completed_at = datetime('now') WHERE id = ?1",
+                rusqlite::params![upload_id, UPLOAD_STATUS_FAILED, e.to_string()],
+            )?;
+            eprintln!("[MES INGEST] ❌ 적재 실패: {} ({})", file_name, e);
+            Err(e)
+        }
+    }
+}
+
+/// 헤더 → 샘플 추론 → 배치 적재, (행 수, 컬럼 스키마) 반환
+fn stream_rows(
+    db: &Database,
+    session_id: &str,
+    file_name: &str,
+    upload_id: &str,
+    source: &mut dyn RowSource,
+    info: &SourceInfo,
+    on_progress: &mut dyn FnMut(&IngestProgress),
+) -> anyhow::Result<(u64, Vec<ColumnSchema>)> {
+    let headers = normalize_headers(&source.headers()?);
+    if headers.is_empty() {
+        return Err(anyhow::anyhow!("헤더가 없습니다"));
+    }
+
+    let mut sample = Vec::new();
+    while sample.len() < INFER_SAMPLE_ROWS {
+        match source.next_row() {
+            Some(record) => sample.push(record?),
+            None => break,
+        }
+    }
+    let mut columns: Vec<ColumnStats> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let values = sample.iter().filter_map(|r| r.get(i)).map(String::as_str);
+            ColumnStats::new(name, infer_column_type(values))
+        })
+        .collect();
+
+    let mut progress = IngestProgress {
+        upload_id: upload_id.to_string(),
+        file_name: file_name.to_string(),
+        rows_processed: 0,
+        bytes_read: 0,
+        total_bytes: info.total_bytes,
+        total_rows: info.total_rows,
+        done: false,
+    };
+    let mut batch: Vec<(u64, String, String)> = Vec::with_capacity(INGEST_BATCH_ROWS);
+    let mut row_index: u64 = 0;
+    let mut sample_rows = sample.into_iter();
+
+    loop {
+        let record = match sample_rows.next() {
+            Some(record) => record,
+            None => match source.next_row() {
+                Some(record) => record?,
+                None => break,
+            },
+        };
+
+        // raw_json: {"온도": 90.5, "설비ID": "EQ-001", "측정시각": "2025-01-01 10:00:00", ...}
+        let mut json_map = serde_json::Map::with_capacity(columns.len());
+        for (i, column) in columns.iter_mut().enumerate() {
+            json_map.insert(column.schema.name.clone(), column.accept(record.get(i).map_or("", String::as_str)));
+        }
+        // content: "온도: 90.5, 설비ID: EQ-001, 판정: NG" (검색용)
+        let content = headers
+            .iter()
+            .zip(record.iter())
+            .map(|(header, value)| format!("{}: {}", header, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        batch.push((row_index, serde_json::to_string(&json_map)?, content));
+        row_index += 1;
+
+        if batch.len() >= INGEST_BATCH_ROWS {
+            insert_batch(db, session_id, file_name, upload_id, &mut batch)?;
+            progress.rows_processed = row_index;
+            progress.bytes_read = source.bytes_read();
+            on_progress(&progress);
+        }
+    }
+    insert_batch(db, session_id, file_name, upload_id, &mut batch)?;
+
+    progress.rows_processed = row_index;
+    progress.bytes_read = source.bytes_read();
+    progress.done = true;
+    on_progress(&progress);
+
+    Ok((row_index, columns.into_iter().map(ColumnStats::finish).collect()))
+}
+
+/// 헤더 정규화 (빈 이름 → column_N, 중복 → 이름_2, 이름_3 ...)
+///
+/// xlsx 병합 헤더는 병합 범위 전체에 같은 이름이 채워지므로 중복 처리가 필요하다.
+fn normalize_headers(raw: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    raw.iter()
+        .enumerate()
+        .map(|(i, header)| {
+            let base = match header.trim() {
+                "" => format!("column_{}", i + 1),
+                trimmed => trimmed.to_string(),
+            };
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while !seen.insert(name.clone()) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+/// 배치 1회 트랜잭션 적재 (FTS5 인덱스는 트리거로 갱신)
+fn insert_batch(
+    db: &Database,
+    session_id: &str,
+    file_name: &str,
+    upload_id: &str,
+    batch: &mut Vec<(u64, String, String)>,
+) -> anyhow::Result<()> {
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    let db_conn = db.get_connection();
+    let mut conn = db_conn.lock()
+        .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO mes_data_logs (session_id, file_name, row_index, raw_json, content, upload_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for (row_index, raw_json, content) in batch.drain(..) {
+            stmt.execute(rusqlite::params![session_id, file_name, row_index as i64, raw_json, content, upload_id])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// 인코딩 감지 (BOM → UTF-8 → CP949/EUC-KR)
+///
+/// 샘플 끝에서 잘린 멀티바이트 문자는 UTF-8 오류로 보지 않는다.
+pub fn detect_encoding(sample: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(sample) {
+        return encoding;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => UTF_8,
+        Err(e) if e.error_len().is_none() => UTF_8,
+        Err(_) => EUC_KR,
+    }
+}
+
+/// 컬럼 타입 추론 (빈 값 제외, numeric → datetime → categorical → text 순)
+pub fn infer_column_type<'a>(values: impl Iterator<Item = &'a str>) -> ColumnType {
+    let values: Vec<&str> = values.map(str::trim).filter(|v| !v.is_empty()).collect();
+    if values.is_empty() {
+        return ColumnType::Text;
+    }
+    let mostly = |matches: usize| matches as f64 >= values.len() as f64 * INFER_MIN_MATCH_RATIO;
+    if mostly(values.iter().filter(|v| parse_numeric(v).is_some()).count()) {
+        return ColumnType::Numeric;
+    }
+    if mostly(values.iter().filter(|v| parse_datetime(v).is_some()).count()) {
+        return ColumnType::Datetime;
+    }
+
+    let distinct: BTreeSet<&str> = values.iter().copied().collect();
+    if distinct.len() <= MAX_CATEGORIES && distinct.len() * 2 <= values.len() {
+        ColumnType::Categorical
+    } else {
+        ColumnType::Text
+    }
+}
+
+/// 숫자 변환 (천 단위 쉼표 허용, NaN/inf 제외)
+pub fn parse_numeric(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let parsed = if value.contains(',') {
+        value.replace(',', "").parse::<f64>().ok()
+    } else {
+        value.parse::<f64>().ok()
+    };
+    parsed.filter(|v| v.is_finite())
+}
+
+/// 날짜/시각 변환 → "YYYY-MM-DD HH:MM:SS"
+pub fn parse_datetime(value: &str) -> Option<String> {
+    let value = value.trim();
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok().map(|dt| dt.naive_utc()))
+        .map(|dt| dt.format(TIMESTAMP_FORMAT).to_string())
+}
+
+/// 적재 중 컬럼 통계 누적
+struct ColumnStats {
+    schema: ColumnSchema,
+    min_numeric: Option<f64>,
+    max_numeric: Option<f64>,
+    min_datetime: Option<String>,
+    max_datetime: Option<String>,
+    categories: BTreeSet<String>,
+}
+
+impl ColumnStats {
+    fn new(name: &str, column_type: ColumnType) -> Self {
+        Self {
+            schema: ColumnSchema {
+                name: name.to_string(),
+                column_type,
+                non_null_count: 0,
+                null_count: 0,
+                invalid_count: 0,
+                min: None,
+                max: None,
+                categories: Vec::new(),
+            },
+            min_numeric: None,
+            max_numeric: None,
+            min_datetime: None,
+            max_datetime: None,
+            categories: BTreeSet::new(),
+        }
+    }
+
+    /// 값 1개를 타입에 맞춰 변환하고 통계 갱신 (변환 실패 시 원문 문자열 유지)
+    fn accept(&mut self, raw: &str) -> serde_json::Value {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            self.schema.null_count += 1;
+            return serde_json::Value::Null;
+        }
+        self.schema.non_null_count += 1;
+
+        match self.schema.column_type {
+            ColumnType::Numeric => match parse_numeric(trimmed) {
+                Some(number) => {
+                    self.min_numeric = Some(self.min_numeric.map_or(number, |m| m.min(number)));
+                    self.max_numeric = Some(self.max_numeric.map_or(number, |m| m.max(number)));
+                    serde_json::json!(number)
+                }
+                None => self.invalid(raw),
+            },
+            ColumnType::Datetime => match parse_datetime(trimmed) {
+                Some(timestamp) => {
+                    if self.min_datetime.as_ref().is_none_or(|m| &timestamp < m) {
+                        self.min_datetime = Some(timestamp.clone());
+                    }
+                    if self.max_datetime.as_ref().is_none_or(|m| &timestamp > m) {
+                        self.max_datetime = Some(timestamp.clone());
+                    }
+                    serde_json::Value::String(timestamp)
+                }
+                None => self.invalid(raw),
+            },
+            ColumnType::Categorical => {
+                if self.categories.len() < MAX_CATEGORIES {
+                    self.categories.insert(trimmed.to_string());
+                }
+                serde_json::Value::String(trimmed.to_string())
+            }
+            ColumnType::Text => serde_json::Value::String(raw.to_string()),
+        }
+    }
+
+    fn invalid(&mut self, raw: &str) -> serde_json::Value {
+        self.schema.invalid_count += 1;
+        serde_json::Value::String(raw.to_string())
+    }
+
+    fn finish(self) -> ColumnSchema {
+        let mut schema = self.schema;
+        match schema.column_type {
+            ColumnType::Numeric => {
+                schema.min = self.min_numeric.map(|v| serde_json::json!(v));
+                schema.max = self.max_numeric.map(|v| serde_json::json!(v));
+            }
+            ColumnType::Datetime => {
+                schema.min = self.min_datetime.map(serde_json::Value::String);
+                schema.max = self.max_datetime.map(serde_json::Value::String);
+            }
+            ColumnType::Categorical => schema.categories = self.categories.into_iter().collect(),
+            ColumnType::Text => {}
+        }
+        schema
+    }
+}
+
+/// CSV 행 공급자
+struct CsvSource<R: BufRead> {
+    reader: csv::Reader<DecodingReader<R>>,
+    record: csv::StringRecord,
+    row_index: u64,
+}
+
+impl<R: BufRead> RowSource for CsvSource<R> {
+    fn headers(&mut self) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .reader
+            .headers()
+            .map_err(|e| anyhow::anyhow!("CSV 헤더 파싱 실패: {}", e))?
+            .iter()
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn next_row(&mut self) -> Option<anyhow::Result<Vec<String>>> {
+        self.row_index += 1;
+        match self.reader.read_record(&mut self.record) {
+            Ok(true) => Some(Ok(self.record.iter().map(str::to_string).collect())),
+            Ok(false) => None,
+            Err(e) => Some(Err(anyhow::anyhow!("CSV 행 파싱 실패 (행 {}): {}", self.row_index, e))),
+        }
+    }
+
+    fn bytes_read(&self) -> u64 {
+        self.reader.get_ref().bytes_read
+    }
+}
+
+/// 바이트 스트림을 UTF-8로 디코딩하는 Read 어댑터 (BOM 제거)
+struct DecodingReader<R: BufRead> {
+    inner: R,
+    decoder: Decoder,
+    buffer: Vec<u8>,
+    pos: usize,
+    finished: bool,
+    /// 원본에서 읽은 바이트 수 (진행률)
+    bytes_read: u64,
+}
+
+impl<R: BufRead> DecodingReader<R> {
+    fn new(inner: R, encoding: &'static Encoding) -> Self {
+        Self {
+            inner,
+            decoder: encoding.new_decoder_with_bom_removal(),
+            buffer: Vec::new(),
+            pos: 0,
+            finished: false,
+            bytes_read: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Read for DecodingReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            if self.pos < self.buffer.len() {
+                let n = out.len().min(self.buffer.len() - self.pos);
+                out[..n].copy_from_slice(&self.buffer[self.pos..self.pos + n]);
+                self.pos += n;
+                return Ok(n);
+            }
+            if self.finished {
+                return Ok(0);
+            }
+
+            let input = self.inner.fill_buf()?;
+            let last = input.is_empty();
+            let capacity = self
+                .decoder
+                .max_utf8_buffer_length(input.len())
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "디코딩 버퍼 크기 초과"))?;
+            self.buffer.resize(capacity, 0);
+            let (_, read, written, _) = self.decoder.decode_to_utf8(input, &mut self.buffer, last);
+            self.buffer.truncate(written);
+            self.pos = 0;
+            self.inner.consume(read);
+            self.bytes_read += read as u64;
+            self.finished = last;
+        }
+    }
+}
+
+const UPLOAD_SELECT: &str = "SELECT id, session_id, file_name, source_path, encoding, row_count, schema_json, status,
+        error, started_at, completed_at, source_format, sheet_name
+     FROM mes_uploads";
+
+fn row_to_upload(row: &rusqlite::Row) -> rusqlite::Result<MesUpload> {
+    let schema_json: String = row.get(6)?;
+    Ok(MesUpload {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        file_name: row.get(2)?,
+        source_path: row.get(3)?,
+        encoding: row.get(4)?,
+        source_format: row.get(11)?,
+        sheet_name: row.get(12)?,
+        row_count: row.get::<_, i64>(5)? as u64,
+        columns: serde_json::from_str(&schema_json).unwrap_or_default(),
+        status: row.get(7)?,
+        error: row.get(8)?,
+        started_at: row.get(9)?,
+        completed_at: row.get(10)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_encoding_cp949() {
+        let (encoded, _, _) = EUC_KR.encode("설비ID,온도,판정\nEQ-001,90.5,정상\n");
+        assert_eq!(detect_encoding(&encoded), EUC_KR);
+        assert_eq!(detect_encoding("설비ID,온도\n".as_bytes()), UTF_8);
+        assert_eq!(detect_encoding(b"\xEF\xBB\xBFa,b\n"), UTF_8);
+        // 샘플 끝에서 잘린 UTF-8 문자
+        let truncated = &"온도".as_bytes()[..4];
+        assert_eq!(detect_encoding(truncated), UTF_8);
+    }
+
+    #[test]
+    fn test_infer_column_type() {
+        assert_eq!(infer_column_type(["90.5", "1,234", " 12 ", ""].into_iter()), ColumnType::Numeric);
+        assert_eq!(
+            infer_column_type(["2025-01-01 10:00:00", "2025/01/02", "2025-01-03T08:30:00"].into_iter()),
+            ColumnType::Datetime
+        );
+        assert_eq!(infer_column_type(["OK", "NG", "OK", "OK"].into_iter()), ColumnType::Categorical);
+        assert_eq!(infer_column_type(["LOT-1", "LOT-2", "LOT-3"].into_iter()), ColumnType::Text);
+        assert_eq!(infer_column_type(["", " "].into_iter()), ColumnType::Text);
+        let mostly_numeric: Vec<String> = (0..40).map(|i| if i == 7 { "N/A".to_string() } else { i.to_string() }).collect();
+        assert_eq!(infer_column_type(mostly_numeric.iter().map(String::as_str)), ColumnType::Numeric);
+        assert_eq!(parse_datetime("2025.03.04"), Some("2025-03-04 00:00:00".to_string()));
+        assert_eq!(parse_numeric("NaN"), None);
+    }
+
+    #[test]
+    fn test_ingest_cp949_in_batches() {
+        let Ok(db) = Database::new() else {
+            println!("⚠️  DB 초기화 실패 - 테스트 스킵");
+            return;
+        };
+        let session_id = format!("test-ingest-{}", uuid::Uuid::new_v4());
+
+        let rows = INGEST_BATCH_ROWS + 10;
+        let mut csv_text = String::from("설비ID,온도,측정시각,판정\n");
+        for i in 0..rows {
+            let judgment = if i % 10 == 0 { "불량" } else { "정상" };
+            let temp = if i == 3 { "측정불가".to_string() } else { format!("{}.5", 80 + i % 20) };
+            csv_text.push_str(&format!("EQ-{:03},{},2025-01-01 10:{:02}:00,{}\n", i % 5, temp, i % 60, judgment));
+        }
+        let (encoded, _, _) = EUC_KR.encode(&csv_text);
+
+        let mut events = Vec::new();
+        let upload = ingest_reader(
+            &db,
+            &session_id,
+            "mes_cp949.csv",
+            None,
+            encoded.as_ref(),
+            Some(encoded.len() as u64),
+            &mut |progress| events.push(progress.clone()),
+        )
+        .unwrap();
+
+        assert_eq!(upload.status, UPLOAD_STATUS_COMPLETED);
+        assert_eq!(upload.encoding, "EUC-KR");
+        assert_eq!(upload.row_count, rows as u64);
+        assert_eq!(events.len(), 2);
+        assert!(events.last().unwrap().done);
+        assert_eq!(events.last().unwrap().bytes_read, encoded.len() as u64);
+
+        let column = |name: &str| upload.columns.iter().find(|c| c.name == name).unwrap();
+        assert_eq!(column("설비ID").column_type, ColumnType::Categorical);
+        assert_eq!(column("판정").categories, vec!["불량".to_string(), "정상".to_string()]);
+        assert_eq!(column("측정시각").column_type, ColumnType::Datetime);
+        let temp = column("온도");
+        assert_eq!(temp.column_type, ColumnType::Numeric);
+        assert_eq!(temp.invalid_count, 1);
+        assert_eq!(temp.max, Some(serde_json::json!(99.5)));
+
+        {
+            let db_conn = db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            let raw_json: String = conn
+                .query_row(
+                    "SELECT raw_json FROM mes_data_logs WHERE upload_id = ?1 AND row_index = 0",
+                    [&upload.id],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            let raw: serde_json::Value = serde_json::from_str(&raw_json).unwrap();
+            assert_eq!(raw["온도"], serde_json::json!(80.5));
+            assert_eq!(raw["판정"], "불량");
+
+            conn.execute("DELETE FROM mes_data_logs WHERE session_id = ?1", [&session_id]).unwrap();
+            conn.execute("DELETE FROM mes_uploads WHERE session_id = ?1", [&session_id]).unwrap();
+        }
+    }
+}
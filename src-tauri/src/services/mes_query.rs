@@ -0,0 +1,898 @@
+// services/mes_query.rs - 업로드 MES 데이터 구조화 질의
+//
+// mes_uploads에 기록된 세션 컬럼 스키마를 기준으로 질문을 필터/집계 계획(QueryPlan)으로 변환하고,
+// 검증 후 mes_data_logs.raw_json에 대한 SQL(json_extract)로 실행한다.
+// 예: "온도가 90도 이상" → 온도 >= 90, "설비ID별 평균 온도" → GROUP BY 설비ID, AVG(온도)
+// 계획을 만들 수 없는 질문은 MesDataService의 FTS 검색으로 넘어간다.
+
+use crate::database::Database;
+use crate::services::mes_ingest::{parse_datetime, parse_numeric, ColumnSchema, ColumnType, UPLOAD_STATUS_COMPLETED};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rusqlite::types::Value as SqlValue;
+use serde::{Deserialize, Serialize};
+
+/// 행 조회 기본 건수
+pub const DEFAULT_LIMIT: usize = 50;
+/// 행/그룹 조회 최대 건수
+pub const MAX_LIMIT: usize = 500;
+
+static NUMBER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"-?\d+(?:,\d{3})*(?:\.\d+)?").unwrap());
+static DATE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d{4}[-/.]\d{1,2}[-/.]\d{1,2}").unwrap());
+static TOP_N_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)(상위|하위|top)\s*(\d+)\s*(?:개|건)?|(\d+)\s*(?:개|건)").unwrap());
+
+/// 필터 연산자
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    /// value ~ value_to (양끝 포함)
+    Between,
+    /// 문자열 포함 (categorical/text)
+    Contains,
+}
+
+impl FilterOp {
+    fn symbol(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "=",
+            FilterOp::Ne => "!=",
+            FilterOp::Gt => ">",
+            FilterOp::Gte => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Lte => "<=",
+            FilterOp::Between => "BETWEEN",
+            FilterOp::Contains => "포함",
+        }
+    }
+}
+
+/// 컬럼 필터
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Filter {
+    pub column: String,
+    pub op: FilterOp,
+    pub value: serde_json::Value,
+    #[serde(default)]
+    pub value_to: Option<serde_json::Value>,
+}
+
+/// 집계 함수
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateFunc {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFunc {
+    fn sql(&self) -> &'static str {
+        match self {
+            AggregateFunc::Count => "COUNT",
+            AggregateFunc::Sum => "SUM",
+            AggregateFunc::Avg => "AVG",
+            AggregateFunc::Min => "MIN",
+            AggregateFunc::Max => "MAX",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            AggregateFunc::Count => "건수",
+            AggregateFunc::Sum => "합계",
+            AggregateFunc::Avg => "평균",
+            AggregateFunc::Min => "최소",
+            AggregateFunc::Max => "최대",
+        }
+    }
+}
+
+/// 집계 (Count는 column 생략 가능)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Aggregate {
+    pub func: AggregateFunc,
+    #[serde(default)]
+    pub column: Option<String>,
+}
+
+/// 정렬 (group_by 사용 시 집계값 기준)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderBy {
+    pub column: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// 구조화 질의 계획
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueryPlan {
+    #[serde(default)]
+    pub filters: Vec<Filter>,
+    #[serde(default)]
+    pub group_by: Option<String>,
+    #[serde(default)]
+    pub aggregate: Option<Aggregate>,
+    #[serde(default)]
+    pub order_by: Option<OrderBy>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl QueryPlan {
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty() && self.group_by.is_none() && self.aggregate.is_none() && self.order_by.is_none()
+    }
+
+    /// 필터 조건 설명 (예: "온도 >= 90, 판정 = NG")
+    pub fn describe_filters(&self) -> String {
+        self.filters
+            .iter()
+            .map(|f| match (&f.op, &f.value_to) {
+                (FilterOp::Between, Some(to)) => format!("{} {} ~ {}", f.column, display_value(&f.value), display_value(to)),
+                _ => format!("{} {} {}", f.column, f.op.symbol(), display_value(&f.value)),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// 질의 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryOutcome {
+    pub plan: QueryPlan,
+    /// 필터 조건에 맞는 전체 행 수
+    pub total_matched: i64,
+    /// 행 조회: raw_json 객체, 집계: {"group", "value", "count"}
+    pub rows: Vec<serde_json::Value>,
+}
+
+/// MES 데이터 구조화 질의 서비스
+pub struct MesQueryService {
+    db: Database,
+}
+
+impl MesQueryService {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// 세션 컬럼 스키마 (완료된 업로드 합집합, 타입이 다르면 text)
+    pub fn session_columns(&self, session_id: &str) -> anyhow::Result<Vec<ColumnSchema>> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT schema_json FROM mes_uploads WHERE session_id = ?1 AND status = ?2 ORDER BY started_at, id",
+        )?;
+        let schemas = stmt
+            .query_map([session_id, UPLOAD_STATUS_COMPLETED], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut columns: Vec<ColumnSchema> = Vec::new();
+        for schema_json in schemas {
+            let upload_columns: Vec<ColumnSchema> = serde_json::from_str(&schema_json).unwrap_or_default();
+            for column in upload_columns {
+                match columns.iter_mut().find(|c| c.name == column.name) {
+                    Some(existing) if existing.column_type != column.column_type => existing.column_type = ColumnType::Text,
+                    Some(existing) => {
+                        for category in column.categories {
+                            if !existing.categories.contains(&category) {
+                                existing.categories.push(category);
+                            }
+                        }
+                    }
+                    None => columns.push(column),
+                }
+            }
+        }
+        Ok(columns)
+    }
+
+    /// 질문 → 검증된 계획 (구조화할 수 없으면 None)
+    pub fn plan(&self, session_id: &str, question: &str) -> anyhow::Result<Option<QueryPlan>> {
+        let columns = self.session_columns(session_id)?;
+        match plan_from_question(question, &columns) {
+            Some(plan) => Ok(Some(validate_plan(plan, &columns)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 계획 검증 후 SQL 실행
+    pub fn execute(&self, session_id: &str, plan: QueryPlan) -> anyhow::Result<QueryOutcome> {
+        let columns = self.session_columns(session_id)?;
+        let plan = validate_plan(plan, &columns)?;
+
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let mut params = vec![SqlValue::Text(session_id.to_string())];
+        let where_clause = build_where(&plan, &mut params);
+        let total_matched: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM mes_data_logs WHERE {}", where_clause),
+            rusqlite::params_from_iter(params.iter()),
+            |row| row.get(0),
+        )?;
+        let limit = plan.limit.unwrap_or(DEFAULT_LIMIT) as i64;
+
+        let rows = if plan.group_by.is_some() || plan.aggregate.is_some() {
+            let aggregate = plan.aggregate.clone().unwrap_or(Aggregate { func: AggregateFunc::Count, column: None });
+            let value_expr = match &aggregate.column {
+                Some(column) if aggregate.func != AggregateFunc::Count => {
+                    format!("{}({})", aggregate.func.sql(), json_expr(column, &mut params))
+                }
+                _ => "COUNT(*)".to_string(),
+            };
+            let (group_expr, tail) = match &plan.group_by {
+                Some(group) => {
+                    let direction = if plan.order_by.as_ref().is_some_and(|o| !o.descending) { "ASC" } else { "DESC" };
+                    (json_expr(group, &mut params), format!("GROUP BY 1 ORDER BY 2 {}, 1 LIMIT {}", direction, limit))
+                }
+                None => ("NULL".to_string(), String::new()),
+            };
+            let sql = format!(
+                "SELECT {}, {}, COUNT(*) FROM mes_data_logs WHERE {} {}",
+                group_expr, value_expr, where_clause, tail
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt
+                .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                    Ok(serde_json::json!({
+                        "group": sql_to_json(row.get::<_, SqlValue>(0)?),
+                        "value": sql_to_json(row.get::<_, SqlValue>(1)?),
+                        "count": row.get::<_, i64>(2)?,
+                    }))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        } else {
+            let order = match &plan.order_by {
+                Some(order) => {
+                    let expr = json_expr(&order.column, &mut params);
+                    format!("{} IS NULL, {} {}, row_index", expr, expr, if order.descending { "DESC" } else { "ASC" })
+                }
+                None => "id".to_string(),
+            };
+            let sql = format!(
+                "SELECT raw_json FROM mes_data_logs WHERE {} ORDER BY {} LIMIT {}",
+                where_clause, order, limit
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt
+                .query_map(rusqlite::params_from_iter(params.iter()), |row| row.get::<_, String>(0))?
+                .map(|raw| raw.map(|raw| serde_json::from_str(&raw).unwrap_or(serde_json::Value::String(raw))))
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        println!("[MES QUERY] 📐 구조화 질의: {} ({}건 일치)", plan.describe_filters(), total_matched);
+        Ok(QueryOutcome { plan, total_matched, rows })
+    }
+}
+
+/// 계획 검증: 컬럼 존재/타입, 값 타입, limit 범위 (datetime 값은 "YYYY-MM-DD HH:MM:SS"로 정규화)
+pub fn validate_plan(mut plan: QueryPlan, columns: &[ColumnSchema]) -> anyhow::Result<QueryPlan> {
+    let column = |name: &str| -> anyhow::Result<&ColumnSchema> {
+        if name.contains('"') {
+            return Err(anyhow::anyhow!("컬럼명에 큰따옴표를 사용할 수 없습니다: {}", name));
+        }
+        columns
+            .iter()
+            .find(|c| c.name == name)
+            .ok_or_else(|| anyhow::anyhow!("업로드 데이터에 없는 컬럼: {}", name))
+    };
+
+    for filter in &mut plan.filters {
+        let schema = column(&filter.column)?;
+        let ordered = matches!(filter.op, FilterOp::Gt | FilterOp::Gte | FilterOp::Lt | FilterOp::Lte | FilterOp::Between);
+        match schema.column_type {
+            ColumnType::Numeric if filter.op == FilterOp::Contains => {
+                return Err(anyhow::anyhow!("숫자 컬럼에는 포함 검색을 쓸 수 없습니다: {}", filter.column));
+            }
+            ColumnType::Categorical | ColumnType::Text if ordered => {
+                return Err(anyhow::anyhow!("문자 컬럼에는 크기 비교를 쓸 수 없습니다: {}", filter.column));
+            }
+            _ => {}
+        }
+
+        filter.value = normalize_value(schema, &filter.value)?;
+        filter.value_to = match (filter.op, filter.value_to.take()) {
+            (FilterOp::Between, Some(to)) => Some(normalize_value(schema, &to)?),
+            (FilterOp::Between, None) => {
+                return Err(anyhow::anyhow!("범위 조건에는 value_to가 필요합니다: {}", filter.column));
+            }
+            (_, _) => None,
+        };
+        if let Some(to) = filter.value_to.as_mut() {
+            if compare_values(&filter.value, to) == Some(std::cmp::Ordering::Greater) {
+                std::mem::swap(&mut filter.value, to);
+            }
+        }
+    }
+
+    if let Some(group) = &plan.group_by {
+        if column(group)?.column_type == ColumnType::Numeric {
+            return Err(anyhow::anyhow!("숫자 컬럼으로는 그룹화할 수 없습니다: {}", group));
+        }
+    }
+    if let Some(aggregate) = &plan.aggregate {
+        match (&aggregate.func, &aggregate.column) {
+            (AggregateFunc::Count, Some(name)) => {
+                column(name)?;
+            }
+            (AggregateFunc::Count, None) => {}
+            (func, Some(name)) => {
+                let column_type = column(name)?.column_type;
+                let allowed = column_type == ColumnType::Numeric
+                    || (column_type == ColumnType::Datetime && matches!(func, AggregateFunc::Min | AggregateFunc::Max));
+                if !allowed {
+                    return Err(anyhow::anyhow!("{} 집계는 숫자 컬럼에만 쓸 수 있습니다: {}", func.label(), name));
+                }
+            }
+            (func, None) => return Err(anyhow::anyhow!("{} 집계에는 컬럼이 필요합니다", func.label())),
+        }
+    }
+    if let Some(order) = &plan.order_by {
+        column(&order.column)?;
+    }
+    if let Some(limit) = plan.limit {
+        if limit == 0 || limit > MAX_LIMIT {
+            return Err(anyhow::anyhow!("limit은 1~{} 사이여야 합니다: {}", MAX_LIMIT, limit));
+        }
+    }
+    Ok(plan)
+}
+
+/// 컬럼 타입에 맞춘 값 변환 (숫자 문자열 → 숫자, 날짜 → 정규화 문자열)
+fn normalize_value(schema: &ColumnSchema, value: &serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    let invalid = || anyhow::anyhow!("{} 컬럼({:?})에 맞지 않는 값: {}", schema.name, schema.column_type, value);
+    match schema.column_type {
+        ColumnType::Numeric => {
+            let number = match value {
+                serde_json::Value::Number(n) => n.as_f64(),
+                serde_json::Value::String(s) => parse_numeric(s),
+                _ => None,
+            };
+            number.map(|n| serde_json::json!(n)).ok_or_else(invalid)
+        }
+        ColumnType::Datetime => value
+            .as_str()
+            .and_then(parse_datetime)
+            .map(serde_json::Value::String)
+            .ok_or_else(invalid),
+        ColumnType::Categorical | ColumnType::Text => match value {
+            serde_json::Value::String(s) => Ok(serde_json::Value::String(s.trim().to_string())),
+            serde_json::Value::Number(n) => Ok(serde_json::Value::String(n.to_string())),
+            _ => Err(invalid()),
+        },
+    }
+}
+
+fn compare_values(a: &serde_json::Value, b: &serde_json::Value) -> Option<std::cmp::Ordering> {
+    match (a, b) {
+        (serde_json::Value::Number(x), serde_json::Value::Number(y)) => x.as_f64()?.partial_cmp(&y.as_f64()?),
+        (serde_json::Value::String(x), serde_json::Value::String(y)) => Some(x.cmp(y)),
+        _ => None,
+    }
+}
+
+/// json_extract 식 (JSON 경로는 바인딩 파라미터)
+fn json_expr(column: &str, params: &mut Vec<SqlValue>) -> String {
+    params.push(SqlValue::Text(format!("$.\"{}\"", column)));
+    format!("json_extract(raw_json, ?{})", params.len())
+}
+
+fn push_param(value: &serde_json::Value, params: &mut Vec<SqlValue>) -> String {
+    params.push(match value {
+        serde_json::Value::Number(n) => SqlValue::Real(n.as_f64().unwrap_or_default()),
+        serde_json::Value::String(s) => SqlValue::Text(s.clone()),
+        other => SqlValue::Text(other.to_string()),
+    });
+    format!("?{}", params.len())
+}
+
+/// WHERE 절 (session_id = ?1 + 필터), 크기 비교는 숫자로 적재된 값만 대상
+fn build_where(plan: &QueryPlan, params: &mut Vec<SqlValue>) -> String {
+    let mut conditions = vec!["session_id = ?1".to_string()];
+    for filter in &plan.filters {
+        let expr = json_expr(&filter.column, params);
+        if filter.value.is_number() {
+            conditions.push(format!("typeof({}) IN ('integer', 'real')", expr));
+        }
+        let condition = match filter.op {
+            FilterOp::Between => {
+                let low = push_param(&filter.value, params);
+                let high = push_param(filter.value_to.as_ref().unwrap_or(&filter.value), params);
+                format!("{} BETWEEN {} AND {}", expr, low, high)
+            }
+            FilterOp::Contains => {
+                let pattern = serde_json::Value::String(format!("%{}%", display_value(&filter.value)));
+                format!("{} LIKE {}", expr, push_param(&pattern, params))
+            }
+            op => format!("{} {} {}", expr, op.symbol(), push_param(&filter.value, params)),
+        };
+        conditions.push(condition);
+    }
+    if let Some(Aggregate { func, column: Some(column) }) = &plan.aggregate {
+        if matches!(func, AggregateFunc::Sum | AggregateFunc::Avg) {
+            conditions.push(format!("typeof({}) IN ('integer', 'real')", json_expr(column, params)));
+        } else if *func != AggregateFunc::Count {
+            conditions.push(format!("{} IS NOT NULL", json_expr(column, params)));
+        }
+    }
+    conditions.join(" AND ")
+}
+
+fn sql_to_json(value: SqlValue) -> serde_json::Value {
+    match value {
+        SqlValue::Null => serde_json::Value::Null,
+        SqlValue::Integer(i) => serde_json::json!(i),
+        SqlValue::Real(f) => serde_json::json!(f),
+        SqlValue::Text(s) => serde_json::Value::String(s),
+        SqlValue::Blob(_) => serde_json::Value::Null,
+    }
+}
+
+fn display_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => match n.as_f64() {
+            Some(f) if f.fract() == 0.0 && f.abs() < 1e15 => format!("{}", f as i64),
+            Some(f) => format!("{:.2}", f).trim_end_matches('0').trim_end_matches('.').to_string(),
+            None => n.to_string(),
+        },
+        other => other.to_string(),
+    }
+}
+
+/// 컬럼명 비교 키 (괄호 단위 제거, 공백 제거, 소문자)
+fn column_key(name: &str) -> String {
+    let base = name.split(['(', '[']).next().unwrap_or(name);
+    base.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase()
+}
+
+/// 질문 → 계획 (규칙 기반)
+///
+/// - 컬럼명 뒤의 숫자 + 이상/초과/이하/미만 → 비교, 두 숫자 + 사이/~/에서 → 범위
+/// - categorical 값 언급 → 일치 (뒤에 제외/아닌 → 불일치)
+/// - "X별" → 그룹화, 평균/합계/최대/최소/건수 → 집계, 상위/하위 N → 정렬 + limit
+pub fn plan_from_question(question: &str, columns: &[ColumnSchema]) -> Option<QueryPlan> {
+    if columns.is_empty() {
+        return None;
+    }
+    let lower = question.to_lowercase();
+
+    // 상위/하위 N 표현은 숫자 비교에서 제외하도록 공백으로 가림
+    // (N, Some(내림차순 여부)): 상위/하위/top 명시, (N, None): "N개/N건"만 있음
+    let mut top_n: Option<(usize, Option<bool>)> = None;
+    let masked = TOP_N_RE.replace_all(&lower, |caps: &regex::Captures| {
+        let n = caps.get(2).or_else(|| caps.get(3)).and_then(|m| m.as_str().parse::<usize>().ok());
+        let descending = caps.get(1).map(|m| m.as_str() != "하위");
+        if let Some(n) = n {
+            top_n.get_or_insert((n, descending));
+        }
+        " ".repeat(caps[0].len())
+    });
+    let masked = masked.as_ref();
+
+    // 컬럼 언급 위치 (긴 이름 우선, 겹치지 않게)
+    let mut keyed: Vec<(String, &ColumnSchema)> = columns
+        .iter()
+        .map(|c| (column_key(&c.name), c))
+        .filter(|(key, _)| !key.is_empty())
+        .collect();
+    keyed.sort_by_key(|(key, _)| std::cmp::Reverse(key.len()));
+    let mut mentions: Vec<(usize, usize, &ColumnSchema)> = Vec::new();
+    for (key, schema) in keyed {
+        let mut from = 0;
+        while let Some(offset) = masked[from..].find(&key) {
+            let start = from + offset;
+            let end = start + key.len();
+            if !mentions.iter().any(|(s, e, _)| start < *e && *s < end) {
+                mentions.push((start, end, schema));
+                break;
+            }
+            from = end;
+        }
+    }
+    mentions.sort_by_key(|(start, _, _)| *start);
+
+    let mut plan = QueryPlan::default();
+    for (i, (_, end, schema)) in mentions.iter().enumerate() {
+        let segment_end = mentions.get(i + 1).map(|(s, _, _)| *s).unwrap_or(masked.len());
+        let segment = &masked[*end..segment_end];
+
+        if masked[*end..].starts_with('별') && schema.column_type != ColumnType::Numeric {
+            plan.group_by.get_or_insert_with(|| schema.name.clone());
+            continue;
+        }
+        match schema.column_type {
+            ColumnType::Numeric => {
+                let without_dates = DATE_RE.replace_all(segment, " ");
+                let numbers: Vec<f64> = NUMBER_RE
+                    .find_iter(&without_dates)
+                    .filter_map(|m| parse_numeric(m.as_str()))
+                    .collect();
+                if let Some(filter) = comparison_filter(&schema.name, segment, &numbers) {
+                    plan.filters.push(filter);
+                }
+            }
+            ColumnType::Datetime => {
+                let dates: Vec<String> = DATE_RE.find_iter(segment).filter_map(|m| parse_datetime(m.as_str())).collect();
+                if let Some(filter) = date_filter(&schema.name, segment, &dates) {
+                    plan.filters.push(filter);
+                }
+            }
+            ColumnType::Categorical | ColumnType::Text => {}
+        }
+    }
+
+    // 날짜 컬럼이 하나뿐이면 컬럼명 없이 날짜만 있어도 적용
+    let datetime_columns: Vec<&ColumnSchema> = columns.iter().filter(|c| c.column_type == ColumnType::Datetime).collect();
+    if let [only] = datetime_columns.as_slice() {
+        if !plan.filters.iter().any(|f| f.column == only.name) {
+            let dates: Vec<String> = DATE_RE.find_iter(masked).filter_map(|m| parse_datetime(m.as_str())).collect();
+            if let Some(filter) = date_filter(&only.name, masked, &dates) {
+                plan.filters.push(filter);
+            }
+        }
+    }
+
+    // categorical 값 언급
+    for schema in columns.iter().filter(|c| c.column_type == ColumnType::Categorical) {
+        let matched = schema.categories.iter().find_map(|category| {
+            let needle = category.to_lowercase();
+            if needle.chars().count() < 2 {
+                return None;
+            }
+            lower.find(&needle).map(|pos| (category, pos + needle.len()))
+        });
+        if let Some((category, end)) = matched {
+            let after: String = lower[end..].chars().take(6).collect();
+            let op = if after.contains("제외") || after.contains("아닌") { FilterOp::Ne } else { FilterOp::Eq };
+            plan.filters.push(Filter {
+                column: schema.name.clone(),
+                op,
+                value: serde_json::Value::String(category.clone()),
+                value_to: None,
+            });
+        }
+    }
+
+    // 집계 대상: 필터가 없는 숫자 컬럼 우선
+    let numeric_mentions: Vec<&ColumnSchema> = mentions
+        .iter()
+        .map(|(_, _, schema)| *schema)
+        .filter(|schema| schema.column_type == ColumnType::Numeric)
+        .collect();
+    let measure = numeric_mentions
+        .iter()
+        .find(|schema| !plan.filters.iter().any(|f| f.column == schema.name))
+        .or_else(|| numeric_mentions.first())
+        .map(|schema| schema.name.clone());
+
+    const AGGREGATE_KEYWORDS: &[(&[&str], AggregateFunc)] = &[
+        (&["평균"], AggregateFunc::Avg),
+        (&["합계", "총합", "합산"], AggregateFunc::Sum),
+        (&["최대", "최고", "최댓값"], AggregateFunc::Max),
+        (&["최소", "최저", "최솟값"], AggregateFunc::Min),
+        (&["건수", "개수", "몇 건", "몇건", "몇 개", "몇개"], AggregateFunc::Count),
+    ];
+    let aggregate_func = AGGREGATE_KEYWORDS
+        .iter()
+        .find(|(keywords, _)| keywords.iter().any(|k| lower.contains(k)))
+        .map(|(_, func)| *func);
+    match aggregate_func {
+        Some(AggregateFunc::Count) => plan.aggregate = Some(Aggregate { func: AggregateFunc::Count, column: None }),
+        Some(func) => {
+            if let Some(column) = &measure {
+                plan.aggregate = Some(Aggregate { func, column: Some(column.clone()) });
+            }
+        }
+        None => {}
+    }
+
+    // 정렬/상위 N
+    let high = ["높은", "큰", "많은"].iter().any(|k| lower.contains(k));
+    let low = ["낮은", "작은", "적은"].iter().any(|k| lower.contains(k));
+    let top = match top_n {
+        Some((n, Some(descending))) => Some((n, descending)),
+        // "N개"는 높은/낮은 표현이 함께 있을 때만 정렬 조건
+        Some((n, None)) if high || low => Some((n, !low)),
+        _ if lower.contains("가장") && (high || low) => Some((1, high)),
+        _ => None,
+    };
+    if let Some((n, descending)) = top {
+        plan.limit = Some(n.clamp(1, MAX_LIMIT));
+        let order_column = if plan.group_by.is_some() {
+            plan.aggregate.as_ref().and_then(|a| a.column.clone()).or_else(|| plan.group_by.clone())
+        } else {
+            numeric_mentions.first().map(|schema| schema.name.clone())
+        };
+        if let Some(column) = order_column {
+            plan.order_by = Some(OrderBy { column, descending });
+        }
+    }
+
+    (!plan.is_empty()).then_some(plan)
+}
+
+/// 숫자 비교 필터 (segment: 컬럼명 뒤 ~ 다음 컬럼명 앞)
+fn comparison_filter(column: &str, segment: &str, numbers: &[f64]) -> Option<Filter> {
+    let has = |keywords: &[&str]| keywords.iter().any(|k| segment.contains(k));
+    let to_json = |value: f64| serde_json::json!(value);
+    match numbers {
+        [] => None,
+        [low, high, ..] if has(&["사이", "~", "에서", "부터"]) || (has(&["이상", "초과"]) && has(&["이하", "미만"])) => {
+            Some(Filter {
+                column: column.to_string(),
+                op: FilterOp::Between,
+                value: to_json(low.min(*high)),
+                value_to: Some(to_json(low.max(*high))),
+            })
+        }
+        [value, ..] => {
+            let op = if has(&["이상", "넘거나"]) {
+                FilterOp::Gte
+            } else if has(&["초과", "넘는", "넘은", "보다 큰", "보다 높은", "보다 많은"]) {
+                FilterOp::Gt
+            } else if has(&["이하"]) {
+                FilterOp::Lte
+            } else if has(&["미만", "보다 작은", "보다 낮은", "보다 적은"]) {
+                FilterOp::Lt
+            } else if has(&["아닌", "제외"]) {
+                FilterOp::Ne
+            } else {
+                FilterOp::Eq
+            };
+            Some(Filter { column: column.to_string(), op, value: to_json(*value), value_to: None })
+        }
+    }
+}
+
+/// 날짜 필터 (이후/이전, 두 날짜는 범위, 하루만 있으면 그날 전체)
+fn date_filter(column: &str, segment: &str, dates: &[String]) -> Option<Filter> {
+    let end_of_day = |date: &str| format!("{} 23:59:59", &date[..10]);
+    let filter = |op, value: String, value_to: Option<String>| Filter {
+        column: column.to_string(),
+        op,
+        value: serde_json::Value::String(value),
+        value_to: value_to.map(serde_json::Value::String),
+    };
+    match dates {
+        [] => None,
+        [from, to, ..] => Some(filter(FilterOp::Between, from.clone(), Some(end_of_day(to)))),
+        [date] if segment.contains("이후") || segment.contains("부터") => Some(filter(FilterOp::Gte, date.clone(), None)),
+        [date] if segment.contains("이전") || segment.contains("까지") => Some(filter(FilterOp::Lte, end_of_day(date), None)),
+        [date] => Some(filter(FilterOp::Between, date.clone(), Some(end_of_day(date)))),
+    }
+}
+
+/// 질의 결과 → 답변 문장
+pub fn format_answer(outcome: &QueryOutcome) -> String {
+    let plan = &outcome.plan;
+    let condition = if plan.filters.is_empty() { "전체".to_string() } else { format!("[{}] 조건", plan.describe_filters()) };
+
+    if plan.group_by.is_some() || plan.aggregate.is_some() {
+        let (label, measure) = match &plan.aggregate {
+            Some(Aggregate { func: AggregateFunc::Count, .. }) | None => ("건수", String::new()),
+            Some(Aggregate { func, column }) => (func.label(), column.clone().unwrap_or_default()),
+        };
+        let title = format!("{} {}", measure, label).trim().to_string();
+        return match &plan.group_by {
+            Some(group) => {
+                let lines = outcome
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .map(|(i, row)| {
+                        format!(
+                            "{}. {}: {} ({}건)",
+                            i + 1,
+                            display_value(&row["group"]),
+                            display_value(&row["value"]),
+                            row["count"]
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("{} {}별 {}:\n\n{}\n\n총 {}건의 데이터를 집계했습니다.", condition, group, title, lines, outcome.total_matched)
+            }
+            None => {
+                let row = outcome.rows.first().cloned().unwrap_or_default();
+                format!(
+                    "{} {}: {} ({}건 기준)",
+                    condition,
+                    title,
+                    display_value(&row["value"]),
+                    row["count"].as_i64().unwrap_or(0)
+                )
+            }
+        };
+    }
+
+    if outcome.rows.is_empty() {
+        return format!("{}에 맞는 데이터가 없습니다.", condition);
+    }
+    let lines = outcome
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let fields = row
+                .as_object()
+                .map(|obj| obj.iter().map(|(k, v)| format!("{}: {}", k, display_value(v))).collect::<Vec<_>>().join(", "))
+                .unwrap_or_else(|| row.to_string());
+            format!("{}. {}", i + 1, fields)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let shown = if (outcome.rows.len() as i64) < outcome.total_matched {
+        format!(" (상위 {}건 표시)", outcome.rows.len())
+    } else {
+        String::new()
+    };
+    format!(
+        "{}에 맞는 데이터는 다음과 같습니다:\n\n{}\n\n총 {}건의 데이터가 발견되었습니다{}.",
+        condition, lines, outcome.total_matched, shown
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::mes_ingest::ingest_reader;
+
+    fn column(name: &str, column_type: ColumnType, categories: &[&str]) -> ColumnSchema {
+        ColumnSchema {
+            name: name.to_string(),
+            column_type,
+            non_null_count: 0,
+            null_count: 0,
+            invalid_count: 0,
+            min: None,
+            max: None,
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    fn sample_columns() -> Vec<ColumnSchema> {
+        vec![
+            column("설비ID", ColumnType::Categorical, &["EQ-001", "EQ-002"]),
+            column("온도(℃)", ColumnType::Numeric, &[]),
+            column("압력", ColumnType::Numeric, &[]),
+            column("측정시각", ColumnType::Datetime, &[]),
+            column("판정", ColumnType::Categorical, &["OK", "NG"]),
+        ]
+    }
+
+    #[test]
+    fn test_plan_comparisons_and_ranges() {
+        let columns = sample_columns();
+
+        let plan = plan_from_question("온도가 90도 이상인 데이터는?", &columns).unwrap();
+        assert_eq!(plan.filters, vec![Filter {
+            column: "온도(℃)".to_string(),
+            op: FilterOp::Gte,
+            value: serde_json::json!(90.0),
+            value_to: None,
+        }]);
+
+        let plan = plan_from_question("압력 1,000에서 1,200 사이이고 판정 NG", &columns).unwrap();
+        assert_eq!(plan.filters[0].op, FilterOp::Between);
+        assert_eq!(plan.filters[0].value, serde_json::json!(1000.0));
+        assert_eq!(plan.filters[0].value_to, Some(serde_json::json!(1200.0)));
+        assert_eq!(plan.filters[1].column, "판정");
+        assert_eq!(plan.filters[1].value, "NG");
+
+        let plan = plan_from_question("2025-01-02 이후 온도 80 미만", &columns).unwrap();
+        assert!(plan.filters.iter().any(|f| f.column == "측정시각" && f.op == FilterOp::Gte));
+        assert!(plan.filters.iter().any(|f| f.column == "온도(℃)" && f.op == FilterOp::Lt));
+
+        assert!(plan_from_question("오늘 특이사항 알려줘", &columns).is_none());
+    }
+
+    #[test]
+    fn test_plan_group_by_and_top_n() {
+        let columns = sample_columns();
+
+        let plan = plan_from_question("설비ID별 평균 온도", &columns).unwrap();
+        assert_eq!(plan.group_by.as_deref(), Some("설비ID"));
+        assert_eq!(plan.aggregate, Some(Aggregate { func: AggregateFunc::Avg, column: Some("온도(℃)".to_string()) }));
+
+        let plan = plan_from_question("온도가 가장 높은 상위 3개", &columns).unwrap();
+        assert_eq!(plan.limit, Some(3));
+        assert_eq!(plan.order_by, Some(OrderBy { column: "온도(℃)".to_string(), descending: true }));
+        assert!(plan.filters.is_empty());
+
+        let plan = plan_from_question("판정 NG 건수", &columns).unwrap();
+        assert_eq!(plan.aggregate.unwrap().func, AggregateFunc::Count);
+    }
+
+    #[test]
+    fn test_validate_plan_rejects_invalid() {
+        let columns = sample_columns();
+        let bad_column = QueryPlan {
+            filters: vec![Filter { column: "습도".into(), op: FilterOp::Gt, value: serde_json::json!(1), value_to: None }],
+            ..Default::default()
+        };
+        assert!(validate_plan(bad_column, &columns).is_err());
+
+        let text_compare = QueryPlan {
+            filters: vec![Filter { column: "판정".into(), op: FilterOp::Gt, value: serde_json::json!("OK"), value_to: None }],
+            ..Default::default()
+        };
+        assert!(validate_plan(text_compare, &columns).is_err());
+
+        let avg_text = QueryPlan {
+            aggregate: Some(Aggregate { func: AggregateFunc::Avg, column: Some("판정".into()) }),
+            ..Default::default()
+        };
+        assert!(validate_plan(avg_text, &columns).is_err());
+
+        let swapped = QueryPlan {
+            filters: vec![Filter {
+                column: "압력".into(),
+                op: FilterOp::Between,
+                value: serde_json::json!("20"),
+                value_to: Some(serde_json::json!(10)),
+            }],
+            ..Default::default()
+        };
+        let plan = validate_plan(swapped, &columns).unwrap();
+        assert_eq!(plan.filters[0].value, serde_json::json!(10.0));
+        assert_eq!(plan.filters[0].value_to, Some(serde_json::json!(20.0)));
+    }
+
+    #[test]
+    fn test_execute_numeric_filter_scoped_to_session() {
+        let Ok(db) = Database::new() else {
+            println!("⚠️  DB 초기화 실패 - 테스트 스킵");
+            return;
+        };
+        let session_id = format!("test-query-{}", uuid::Uuid::new_v4());
+        let other_session = format!("test-query-{}", uuid::Uuid::new_v4());
+        let mut csv_text = String::from("설비ID,온도,판정\nEQ-001,89.5,OK\nEQ-001,90,NG\nEQ-002,95.5,NG\nEQ-002,190,NG\nEQ-001,측정불가,OK\nEQ-002,70,OK\n");
+        for _ in 0..20 {
+            csv_text.push_str("EQ-001,10,OK\n");
+        }
+        for session in [&session_id, &other_session] {
+            ingest_reader(&db, session, "query.csv", None, csv_text.as_bytes(), None, &mut |_| {}).unwrap();
+        }
+
+        let service = MesQueryService::from_database(db.clone());
+        let plan = service.plan(&session_id, "온도가 90도 이상인 데이터는?").unwrap().unwrap();
+        let outcome = service.execute(&session_id, plan).unwrap();
+        // "190"은 포함, 토큰 "90"만 맞는 89.5/측정불가는 제외, 다른 세션 데이터 제외
+        assert_eq!(outcome.total_matched, 3);
+        assert!(format_answer(&outcome).contains("총 3건"));
+
+        let plan = service.plan(&session_id, "설비ID별 평균 온도").unwrap().unwrap();
+        let outcome = service.execute(&session_id, plan).unwrap();
+        assert_eq!(outcome.rows.len(), 2);
+        assert_eq!(outcome.rows[0]["group"], "EQ-002");
+        assert_eq!(outcome.rows[0]["value"], serde_json::json!((95.5 + 190.0 + 70.0) / 3.0));
+
+        {
+            let db_conn = db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            for session in [&session_id, &other_session] {
+                conn.execute("DELETE FROM mes_data_logs WHERE session_id = ?1", [session]).unwrap();
+                conn.execute("DELETE FROM mes_uploads WHERE session_id = ?1", [session]).unwrap();
+            }
+        }
+    }
+}
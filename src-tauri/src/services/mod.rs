@@ -2,6 +2,7 @@ pub mod judgment_engine;
 pub mod rule_engine;
 pub mod llm_engine;
 pub mod learning_service;
+pub mod few_shot_retriever;
 pub mod bi_service;
 pub mod workflow_service;
 pub mod chat_service;
@@ -10,6 +11,21 @@ pub mod prompt_engine;
 pub mod complexity_analyzer;
 pub mod context7_cache;
 pub mod ccp_service;
+pub mod ccp_doc_ingestion;
+pub mod ccp_check_evaluator;
+pub mod ccp_policy;
+pub mod approval_request;
+pub mod audit_log;
+pub mod auth;  // 로컬 사용자 / 로그인 세션 / 역할 기반 권한
+pub mod ccp_corrective_action;
+pub mod lot_disposition;
+pub mod traceability;
 pub mod mes_data_service;
+pub mod mes_import;
+pub mod mes_ingest;
+pub mod mes_query;
 pub mod chart_service;
+pub mod spc_service;
 pub mod prompt_router;
+pub mod backup_scheduler;
+pub mod retention_scheduler;
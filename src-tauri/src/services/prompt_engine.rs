@@ -2,7 +2,7 @@ use handlebars::Handlebars;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use crate::database::Database;
-use crate::database::models::PromptTemplate;
+use crate::database::models::{PromptTemplate, PromptVariantStats};
 
 /// Prompt Template Engine with Handlebars variable system
 pub struct PromptEngine {
@@ -12,10 +12,14 @@ pub struct PromptEngine {
 
 impl PromptEngine {
     pub fn new() -> anyhow::Result<Self> {
-        Ok(Self {
-            db: Database::new()?,
+        Ok(Self::from_database(Database::new()?))
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self {
+            db,
             handlebars: Handlebars::new(),
-        })
+        }
     }
 
     /// Render prompt template with variables
@@ -30,8 +34,17 @@ impl PromptEngine {
             .get_active_template_by_type(template_type)?
             .ok_or_else(|| anyhow::anyhow!("No active template found for type: {}", template_type))?;
 
+        self.render_template(&template, variables)
+    }
+
+    /// Render a specific template (e.g. an A/B variant) with variables
+    pub fn render_template(
+        &self,
+        template: &PromptTemplate,
+        variables: HashMap<String, Value>,
+    ) -> anyhow::Result<String> {
         // Validate variables against template requirements
-        self.validate_variables(&template, &variables)?;
+        self.validate_variables(template, &variables)?;
 
         // Render with Handlebars
         let rendered = self
@@ -42,6 +55,77 @@ impl PromptEngine {
         Ok(rendered)
     }
 
+    /// Pick an A/B test variant for template_type by traffic weight
+    ///
+    /// Returns None when no experiment is running for the type.
+    pub fn select_variant(&self, template_type: &str) -> anyhow::Result<Option<PromptTemplate>> {
+        let variants = self.db.get_prompt_variants(template_type)?;
+        let roll = rand::random::<f64>();
+
+        Ok(Self::pick_weighted(&variants, roll).cloned())
+    }
+
+    /// Weighted pick; `roll` is uniform in [0, 1)
+    fn pick_weighted(variants: &[(PromptTemplate, f64)], roll: f64) -> Option<&PromptTemplate> {
+        let total: f64 = variants.iter().map(|(_, weight)| weight.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut threshold = roll * total;
+        for (template, weight) in variants {
+            let weight = weight.max(0.0);
+            if threshold < weight {
+                return Some(template);
+            }
+            threshold -= weight;
+        }
+
+        // 부동소수점 오차로 끝까지 온 경우 마지막 양수 가중치 변형
+        variants
+            .iter()
+            .rev()
+            .find(|(_, weight)| *weight > 0.0)
+            .map(|(template, _)| template)
+    }
+
+    /// Add a template to its type's A/B experiment (or change its weight)
+    pub fn set_variant_weight(&self, template_id: &str, weight: f64) -> anyhow::Result<()> {
+        if !weight.is_finite() || weight < 0.0 {
+            return Err(anyhow::anyhow!("Variant weight must be a non-negative number: {}", weight));
+        }
+
+        self.db
+            .get_prompt_template(template_id)?
+            .ok_or_else(|| anyhow::anyhow!("Template not found: {}", template_id))?;
+
+        self.db.set_prompt_variant_weight(template_id, weight)?;
+        Ok(())
+    }
+
+    pub fn remove_variant(&self, template_id: &str) -> anyhow::Result<()> {
+        self.db.remove_prompt_variant(template_id)?;
+        Ok(())
+    }
+
+    /// Compare variants of template_type (accuracy from feedbacks, confidence, latency, token cost)
+    pub fn get_variant_report(&self, template_type: &str) -> anyhow::Result<Vec<PromptVariantStats>> {
+        Ok(self.db.get_prompt_variant_stats(template_type)?)
+    }
+
+    /// Promote the winning variant to is_active and end the experiment
+    pub fn promote_variant(&self, template_id: &str) -> anyhow::Result<PromptTemplate> {
+        self.db
+            .get_prompt_template(template_id)?
+            .ok_or_else(|| anyhow::anyhow!("Template not found: {}", template_id))?;
+
+        self.db.promote_prompt_template(template_id)?;
+
+        self.db
+            .get_prompt_template(template_id)?
+            .ok_or_else(|| anyhow::anyhow!("Template not found: {}", template_id))
+    }
+
     /// Render judgment prompt with Few-shot samples
     pub async fn render_judgment_prompt(
         &self,
@@ -245,4 +329,70 @@ mod tests {
         invalid_vars.insert("var1".to_string(), json!("value1"));
         assert!(engine.validate_variables(&template, &invalid_vars).is_err());
     }
+
+    fn test_template(id: &str, version: i32) -> PromptTemplate {
+        PromptTemplate {
+            id: id.to_string(),
+            name: format!("Variant {}", id),
+            template_type: "judgment".to_string(),
+            content: "{{input_data}}".to_string(),
+            variables: r#"["input_data"]"#.to_string(),
+            version,
+            is_active: false,
+            token_limit: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_pick_weighted_splits_by_weight() {
+        let variants = vec![
+            (test_template("a", 1), 3.0),
+            (test_template("b", 2), 1.0),
+        ];
+
+        // 누적 가중치 [0, 0.75) → a, [0.75, 1) → b
+        assert_eq!(PromptEngine::pick_weighted(&variants, 0.0).unwrap().id, "a");
+        assert_eq!(PromptEngine::pick_weighted(&variants, 0.74).unwrap().id, "a");
+        assert_eq!(PromptEngine::pick_weighted(&variants, 0.76).unwrap().id, "b");
+        assert_eq!(PromptEngine::pick_weighted(&variants, 0.999).unwrap().id, "b");
+    }
+
+    #[test]
+    fn test_pick_weighted_no_experiment() {
+        assert!(PromptEngine::pick_weighted(&[], 0.5).is_none());
+
+        let zero_weights = vec![(test_template("a", 1), 0.0)];
+        assert!(PromptEngine::pick_weighted(&zero_weights, 0.5).is_none());
+    }
+
+    #[test]
+    fn test_promote_variant_ends_experiment() {
+        let engine = PromptEngine::new().unwrap();
+        let template_type = format!("ab-test-{}", uuid::Uuid::new_v4());
+
+        let mut control = test_template(&uuid::Uuid::new_v4().to_string(), 1);
+        control.template_type = template_type.clone();
+        control.is_active = true;
+        let mut challenger = test_template(&uuid::Uuid::new_v4().to_string(), 2);
+        challenger.template_type = template_type.clone();
+
+        engine.db.save_prompt_template(&control).unwrap();
+        engine.db.save_prompt_template(&challenger).unwrap();
+        engine.set_variant_weight(&control.id, 1.0).unwrap();
+        engine.set_variant_weight(&challenger.id, 1.0).unwrap();
+        assert!(engine.set_variant_weight(&challenger.id, -1.0).is_err());
+
+        let report = engine.get_variant_report(&template_type).unwrap();
+        assert_eq!(report.len(), 2);
+        assert!(report.iter().all(|s| s.weight == Some(1.0) && s.judgment_count == 0));
+
+        let promoted = engine.promote_variant(&challenger.id).unwrap();
+        assert!(promoted.is_active);
+
+        let active = engine.db.get_active_template_by_type(&template_type).unwrap().unwrap();
+        assert_eq!(active.id, challenger.id);
+        assert!(engine.select_variant(&template_type).unwrap().is_none());
+    }
 }
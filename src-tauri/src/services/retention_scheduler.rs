@@ -0,0 +1,92 @@
+// services/retention_scheduler.rs - 데이터 보존 백그라운드 작업
+//
+// 앱 시작 후 STARTUP_DELAY 뒤부터 CHECK_INTERVAL마다 마지막 실행 이후 하루가 지났으면
+// 보존 정책(retention_policy.json)을 적용한다 (정책 enabled가 false면 건너뜀).
+// 실패 시 `retention-alert` 이벤트(RetentionAlert)를 발생시킨다.
+
+use crate::database::retention::{self, DataRetentionPolicy, RetentionStatus};
+use crate::database::Database;
+use crate::utils::app_paths::AppPaths;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// 앱 시작 직후 백업/초기 조회와 겹치지 않도록 대기
+const STARTUP_DELAY: Duration = Duration::from_secs(10 * 60);
+/// 실행 시점 점검 주기
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 보존 작업 실패 알림 (retention-alert 이벤트 payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionAlert {
+    pub message: String,
+    pub occurred_at: String,
+}
+
+/// 스케줄러 시작 (Tauri setup에서 1회 호출)
+pub fn start(app_handle: AppHandle) {
+    let paths = match AppPaths::resolve() {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("⚠️  [RETENTION] 데이터 경로 확인 실패, 보존 작업 비활성화: {}", e);
+            return;
+        }
+    };
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+
+        loop {
+            if is_due(&paths) {
+                run(&app_handle, &paths).await;
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+    println!("⏰ [RETENTION] 데이터 보존 스케줄러 시작");
+}
+
+fn is_due(paths: &AppPaths) -> bool {
+    let data_dir = paths.data_dir();
+    let enabled = DataRetentionPolicy::load(&data_dir)
+        .map(|policy| policy.enabled)
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️  [RETENTION] 보존 정책 로드 실패: {}", e);
+            false
+        });
+    enabled
+        && RetentionStatus::load(&data_dir)
+            .unwrap_or_default()
+            .is_due(Utc::now().naive_utc())
+}
+
+async fn run(app_handle: &AppHandle, paths: &AppPaths) {
+    let paths = paths.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let database = Database::new()?;
+        retention::run_for_app(&database, &paths)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(report)) => println!(
+            "🗄️  [RETENTION] 보존 작업 완료: {}건 정리 (VACUUM {})",
+            report.removed_rows(),
+            if report.vacuumed { "완료" } else { "생략" }
+        ),
+        Ok(Err(e)) => emit_alert(app_handle, format!("데이터 보존 작업 실패: {:#}", e)),
+        Err(e) => emit_alert(app_handle, format!("데이터 보존 작업 실행 실패: {}", e)),
+    }
+}
+
+fn emit_alert(app_handle: &AppHandle, message: String) {
+    eprintln!("🚨 [RETENTION] {}", message);
+    let alert = RetentionAlert {
+        message,
+        occurred_at: Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = app_handle.emit_all("retention-alert", alert) {
+        eprintln!("⚠️  보존 작업 알림 이벤트 전송 실패: {}", e);
+    }
+}
@@ -20,10 +20,14 @@ pub struct RuleEngine {
 
 impl RuleEngine {
     pub fn new() -> anyhow::Result<Self> {
-        Ok(Self {
-            db: Database::new()?,
+        Ok(Self::from_database(Database::new()?))
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self {
+            db,
             rule_cache: Arc::new(Mutex::new(HashMap::new())),
-        })
+        }
     }
 
     // Engine 생성 헬퍼 (매 호출시 새로 생성하여 Send 트레잇 문제 해결)
@@ -95,11 +99,64 @@ impl RuleEngine {
             }
         }
 
+        let (mut scope, registered_vars) = Self::build_scope(&input.input_data);
+
+        // Execute rule with detailed error handling (매번 새 Engine 생성)
+        let engine = Self::create_engine();
+        let result: bool = engine
+            .eval_with_scope(&mut scope, &rule_expression)
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Rule evaluation failed\n\nRule: {}\n\nVariables:\n{}\n\nError: {}",
+                    rule_expression,
+                    registered_vars.join("\n"),
+                    e
+                )
+            })?;
+
+        // 신뢰도 계산 (Rule 기반은 높은 신뢰도)
+        let confidence = if registered_vars.len() >= 3 {
+            0.95 // 충분한 데이터
+        } else if registered_vars.len() >= 1 {
+            0.85 // 일부 데이터
+        } else {
+            0.7 // 데이터 부족
+        };
+
+        Ok(JudgmentResult {
+            id: Uuid::new_v4().to_string(),
+            workflow_id: input.workflow_id.clone(),
+            result,
+            confidence,
+            method_used: "rule".to_string(),
+            explanation: format!(
+                "Rule 기반 판단 완료\n\n📋 Rule: {}\n\n📊 입력 데이터:\n{}\n\n✅ 결과: {}\n💯 신뢰도: {:.1}%",
+                rule_expression,
+                registered_vars.join("\n"),
+                if result { "합격 (통과)" } else { "불합격 (거부)" },
+                confidence * 100.0
+            ),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            template_id: None,
+            template_version: None,
+        })
+    }
+
+    /// 저장 없이 Rule 표현식을 입력 데이터에 적용 (추출 Rule 검증용)
+    pub fn evaluate_expression(expression: &str, input_data: &serde_json::Value) -> anyhow::Result<bool> {
+        let (mut scope, _) = Self::build_scope(input_data);
+        Self::create_engine()
+            .eval_with_scope::<bool>(&mut scope, expression)
+            .map_err(|e| anyhow::anyhow!("Rule evaluation failed: {}: {}", expression, e))
+    }
+
+    /// 입력 JSON의 최상위 키를 Rhai 변수로 등록 (디버깅용 변수 목록 포함)
+    fn build_scope(input_data: &serde_json::Value) -> (Scope<'static>, Vec<String>) {
         let mut scope = Scope::new();
         let mut registered_vars = Vec::new();
 
         // Register input data as variables (Array/Object 지원 확장)
-        if let Some(obj) = input.input_data.as_object() {
+        if let Some(obj) = input_data.as_object() {
             for (key, value) in obj {
                 match value {
                     serde_json::Value::Number(n) => {
@@ -123,7 +180,7 @@ impl RuleEngine {
                         // Array를 Rhai Array로 변환
                         let rhai_array: Array = arr
                             .iter()
-                            .filter_map(|v| self.json_to_dynamic(v))
+                            .filter_map(Self::json_to_dynamic)
                             .collect();
                         scope.push(key.clone(), rhai_array);
                         registered_vars.push(format!("{} = [array with {} items]", key, arr.len()));
@@ -133,7 +190,7 @@ impl RuleEngine {
                         let rhai_map: Map = obj
                             .iter()
                             .filter_map(|(k, v)| {
-                                self.json_to_dynamic(v).map(|d| (k.clone().into(), d))
+                                Self::json_to_dynamic(v).map(|d| (k.clone().into(), d))
                             })
                             .collect();
                         scope.push(key.clone(), rhai_map);
@@ -146,47 +203,11 @@ impl RuleEngine {
             }
         }
 
-        // Execute rule with detailed error handling (매번 새 Engine 생성)
-        let engine = Self::create_engine();
-        let result: bool = engine
-            .eval_with_scope(&mut scope, &rule_expression)
-            .map_err(|e| {
-                anyhow::anyhow!(
-                    "Rule evaluation failed\n\nRule: {}\n\nVariables:\n{}\n\nError: {}",
-                    rule_expression,
-                    registered_vars.join("\n"),
-                    e
-                )
-            })?;
-
-        // 신뢰도 계산 (Rule 기반은 높은 신뢰도)
-        let confidence = if registered_vars.len() >= 3 {
-            0.95 // 충분한 데이터
-        } else if registered_vars.len() >= 1 {
-            0.85 // 일부 데이터
-        } else {
-            0.7 // 데이터 부족
-        };
-
-        Ok(JudgmentResult {
-            id: Uuid::new_v4().to_string(),
-            workflow_id: input.workflow_id.clone(),
-            result,
-            confidence,
-            method_used: "rule".to_string(),
-            explanation: format!(
-                "Rule 기반 판단 완료\n\n📋 Rule: {}\n\n📊 입력 데이터:\n{}\n\n✅ 결과: {}\n💯 신뢰도: {:.1}%",
-                rule_expression,
-                registered_vars.join("\n"),
-                if result { "합격 (통과)" } else { "불합격 (거부)" },
-                confidence * 100.0
-            ),
-            created_at: chrono::Utc::now().to_rfc3339(),
-        })
+        (scope, registered_vars)
     }
 
     // JSON Value를 Rhai Dynamic으로 변환하는 헬퍼 함수
-    fn json_to_dynamic(&self, value: &serde_json::Value) -> Option<Dynamic> {
+    fn json_to_dynamic(value: &serde_json::Value) -> Option<Dynamic> {
         match value {
             serde_json::Value::Number(n) => {
                 if let Some(i) = n.as_i64() {
@@ -202,7 +223,7 @@ impl RuleEngine {
             serde_json::Value::Array(arr) => {
                 let rhai_array: Array = arr
                     .iter()
-                    .filter_map(|v| self.json_to_dynamic(v))
+                    .filter_map(Self::json_to_dynamic)
                     .collect();
                 Some(Dynamic::from(rhai_array))
             }
@@ -210,7 +231,7 @@ impl RuleEngine {
                 let rhai_map: Map = obj
                     .iter()
                     .filter_map(|(k, v)| {
-                        self.json_to_dynamic(v).map(|d| (k.clone().into(), d))
+                        Self::json_to_dynamic(v).map(|d| (k.clone().into(), d))
                     })
                     .collect();
                 Some(Dynamic::from(rhai_map))
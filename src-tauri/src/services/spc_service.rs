@@ -0,0 +1,804 @@
+// services/spc_service.rs - 통계적 공정 관리 (SPC)
+//
+// sensor_log / process_param_log / ccp_check_log 측정값으로 관리도(I-MR, X̄-R)를 계산하고
+// 공정능력(Cp/Cpk)과 Western Electric/Nelson 규칙 위반을 판정한다.
+// 결과는 JUDGMENT 노드 입력(judgment_input)과 ChartService 차트 데이터로 사용된다.
+
+use crate::database::Database;
+use crate::services::ccp_check_evaluator::CcpType;
+use crate::services::ccp_doc_ingestion::normalize_timestamp;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// 표준편차 0 판정 기준 (모든 측정값이 같으면 규칙 판정 불가)
+const SIGMA_EPSILON: f64 = 1e-12;
+
+/// X̄-R 관리도 계수 (부분군 크기 2~10): (n, A2, D3, D4, d2)
+const XBAR_R_CONSTANTS: [(usize, f64, f64, f64, f64); 9] = [
+    (2, 1.880, 0.0, 3.267, 1.128),
+    (3, 1.023, 0.0, 2.574, 1.693),
+    (4, 0.729, 0.0, 2.282, 2.059),
+    (5, 0.577, 0.0, 2.114, 2.326),
+    (6, 0.483, 0.0, 2.004, 2.534),
+    (7, 0.419, 0.076, 1.924, 2.704),
+    (8, 0.373, 0.136, 1.864, 2.847),
+    (9, 0.337, 0.184, 1.816, 2.970),
+    (10, 0.308, 0.223, 1.777, 3.078),
+];
+
+/// I-MR 관리도 계수 (이동범위 n=2)
+const IMR_E2: f64 = 2.660;
+const IMR_D4: f64 = 3.267;
+const IMR_D2: f64 = 1.128;
+
+/// process_param_log 측정 컬럼 (SQL 인젝션 방지 화이트리스트)
+const PROCESS_PARAM_COLUMNS: &[&str] = &[
+    "sterilization_temp",
+    "holding_time_sec",
+    "homogenizer_pressure",
+    "tank_temp",
+    "tank_level",
+    "cip_temp",
+    "cip_conductivity",
+    "fill_speed",
+    "fill_volume",
+    "fill_temp",
+    "cooling_temp",
+    "glycol_temp",
+];
+
+/// ccp_check_log 측정 필드 → SQL 식
+const CCP_CHECK_FIELDS: &[(&str, &str)] = &[
+    ("actual_temp", "COALESCE(actual_cool_temp, actual_temp)"),
+    ("actual_time_sec", "actual_time_sec"),
+    ("cool_time_sec", "cool_time_sec"),
+    ("sensitivity_fe", "sensitivity_fe"),
+    ("sensitivity_sus", "sensitivity_sus"),
+];
+
+/// SPC 데이터 소스
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum SpcSource {
+    /// sensor_log의 파라미터 (param_mst.param_cd)
+    SensorLog { param_cd: String, equip_cd: Option<String> },
+    /// process_param_log의 측정 컬럼 (예: sterilization_temp)
+    ProcessParamLog { column: String, equip_cd: Option<String> },
+    /// ccp_check_log의 CCP 유형별 측정 필드 (예: PASTEURIZATION / actual_temp)
+    CcpCheckLog { ccp_type: String, field: String },
+}
+
+impl SpcSource {
+    /// 표시용 측정 항목 이름
+    pub fn measure_name(&self) -> String {
+        match self {
+            SpcSource::SensorLog { param_cd, .. } => format!("sensor_log.{}", param_cd),
+            SpcSource::ProcessParamLog { column, .. } => format!("process_param_log.{}", column),
+            SpcSource::CcpCheckLog { ccp_type, field } => format!("ccp_check_log.{}.{}", ccp_type, field),
+        }
+    }
+}
+
+/// SPC 분석 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpcRequest {
+    #[serde(flatten)]
+    pub source: SpcSource,
+    pub batch_lot_no: Option<String>,
+    /// 기간 (YYYY-MM-DD 또는 YYYY-MM-DD HH:MM:SS, 종료일 포함)
+    pub from: String,
+    pub to: String,
+    /// 부분군 크기 (None 또는 1 = I-MR, 2~10 = X̄-R)
+    pub subgroup_size: Option<usize>,
+    /// 규격을 가져올 작업지시 (operation_param_target, 없으면 해당 파라미터의 최근 목표)
+    pub wo_no: Option<String>,
+    /// 규격 직접 지정 (DB 규격보다 우선)
+    pub lsl: Option<f64>,
+    pub usl: Option<f64>,
+}
+
+/// 관리도 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlChartType {
+    #[serde(rename = "I-MR")]
+    IndividualMovingRange,
+    #[serde(rename = "XBAR-R")]
+    XbarR,
+}
+
+/// 관리한계
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlLimits {
+    pub chart_type: ControlChartType,
+    pub subgroup_size: usize,
+    /// 중심선 (X̄ 또는 X̿)
+    pub center_line: f64,
+    pub ucl: f64,
+    pub lcl: f64,
+    /// 범위 관리도 중심선 (MR̄ 또는 R̄)
+    pub range_center: f64,
+    pub range_ucl: f64,
+    pub range_lcl: f64,
+    /// 군내 표준편차 추정값 (MR̄/d2 또는 R̄/d2)
+    pub sigma_within: f64,
+}
+
+impl ControlLimits {
+    /// 관리도에 찍히는 통계량(개별값 또는 부분군 평균)의 표준편차
+    pub fn plotted_sigma(&self) -> f64 {
+        (self.ucl - self.center_line) / 3.0
+    }
+}
+
+/// 규격 한계
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecLimits {
+    pub lsl: Option<f64>,
+    pub usl: Option<f64>,
+    pub target: Option<f64>,
+    /// "request" | "operation_param_target" | "param_mst"
+    pub source: String,
+}
+
+/// 공정능력 지수 (한쪽 규격만 있으면 Cp는 None)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessCapability {
+    pub cp: Option<f64>,
+    pub cpk: f64,
+    pub cpu: Option<f64>,
+    pub cpl: Option<f64>,
+}
+
+/// Nelson 규칙 위반 (point_index = 패턴이 완성된 점)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleViolation {
+    pub rule: u8,
+    pub name: String,
+    pub point_index: usize,
+}
+
+/// 관리도 점
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpcPoint {
+    pub index: usize,
+    /// 측정 시각 (부분군은 첫 측정 시각)
+    pub label: String,
+    /// 개별값 또는 부분군 평균
+    pub value: f64,
+    /// 이동범위 또는 부분군 범위 (첫 개별값은 None)
+    pub range: Option<f64>,
+    pub rules: Vec<u8>,
+}
+
+/// SPC 분석 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpcAnalysis {
+    pub measure: String,
+    pub sample_count: usize,
+    /// 부분군을 채우지 못해 제외된 마지막 측정값 수
+    pub dropped_samples: usize,
+    pub mean: f64,
+    pub limits: ControlLimits,
+    pub spec: Option<SpecLimits>,
+    pub capability: Option<ProcessCapability>,
+    pub points: Vec<SpcPoint>,
+    pub violations: Vec<RuleViolation>,
+    pub in_control: bool,
+}
+
+impl SpcAnalysis {
+    /// JUDGMENT 노드 입력 변수 (Rule 표현식 예: `spc_violation_count == 0 && spc_cpk >= 1.33`)
+    ///
+    /// 규격이 없으면 spc_cp/spc_cpk 변수는 생략된다.
+    pub fn judgment_input(&self) -> serde_json::Value {
+        let mut input = serde_json::json!({
+            "spc_in_control": self.in_control,
+            "spc_violation_count": self.violations.len(),
+            "spc_mean": self.mean,
+            "spc_ucl": self.limits.ucl,
+            "spc_lcl": self.limits.lcl,
+            "spc_sigma": self.limits.sigma_within,
+            "spc_sample_count": self.sample_count,
+        });
+        let fields = input.as_object_mut().expect("json object");
+
+        if let Some(last) = self.points.last() {
+            fields.insert("spc_last_value".to_string(), serde_json::json!(last.value));
+        }
+        for rule in 1..=8u8 {
+            let hit = self.violations.iter().any(|v| v.rule == rule);
+            fields.insert(format!("spc_rule_{}", rule), serde_json::json!(hit));
+        }
+        if let Some(capability) = &self.capability {
+            fields.insert("spc_cpk".to_string(), serde_json::json!(capability.cpk));
+            if let Some(cp) = capability.cp {
+                fields.insert("spc_cp".to_string(), serde_json::json!(cp));
+            }
+        }
+        input
+    }
+}
+
+/// 시계열 측정값
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub label: String,
+    pub value: f64,
+}
+
+/// SPC 서비스
+pub struct SpcService {
+    db: Database,
+}
+
+impl SpcService {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    /// 요청 데이터 소스에서 측정값을 읽어 SPC 분석
+    pub fn analyze(&self, request: &SpcRequest) -> anyhow::Result<SpcAnalysis> {
+        let from = normalize_timestamp(&request.from, false)?;
+        let to = normalize_timestamp(&request.to, true)?;
+
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+
+        let measurements = load_measurements(&conn, &request.source, request.batch_lot_no.as_deref(), &from, &to)?;
+        let spec = resolve_spec(&conn, request)?;
+        drop(conn);
+
+        analyze_measurements(
+            request.source.measure_name(),
+            &measurements,
+            request.subgroup_size.unwrap_or(1),
+            spec,
+        )
+    }
+}
+
+/// 측정값 조회 (시간순)
+pub fn load_measurements(
+    conn: &Connection,
+    source: &SpcSource,
+    batch_lot_no: Option<&str>,
+    from: &str,
+    to: &str,
+) -> anyhow::Result<Vec<Measurement>> {
+    // 바인딩: ?1 = 소스 키, ?2 = 배치 LOT, ?3/?4 = 기간, ?5 = 설비 (소스별로 쓰지 않는 자리는 NULL)
+    let (sql, key, equip): (String, Option<String>, Option<String>) = match source {
+        SpcSource::SensorLog { param_cd, equip_cd } => (
+            "SELECT recorded_at, value FROM sensor_log
+             WHERE param_cd = ?1 AND (?2 IS NULL OR batch_lot_no = ?2)
+               AND recorded_at BETWEEN ?3 AND ?4 AND (?5 IS NULL OR equip_cd = ?5)
+             ORDER BY recorded_at, id"
+                .to_string(),
+            Some(param_cd.clone()),
+            equip_cd.clone(),
+        ),
+        SpcSource::ProcessParamLog { column, equip_cd } => {
+            if !PROCESS_PARAM_COLUMNS.contains(&column.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "지원하지 않는 process_param_log 컬럼: {} (가능: {})",
+                    column,
+                    PROCESS_PARAM_COLUMNS.join(", ")
+                ));
+            }
+            (
+                format!(
+                    "SELECT recorded_at, {col} FROM process_param_log
+                     WHERE ?1 IS NULL AND {col} IS NOT NULL AND (?2 IS NULL OR batch_lot_no = ?2)
+                       AND recorded_at BETWEEN ?3 AND ?4 AND (?5 IS NULL OR equip_cd = ?5)
+                     ORDER BY recorded_at, id",
+                    col = column
+                ),
+                None,
+                equip_cd.clone(),
+            )
+        }
+        SpcSource::CcpCheckLog { ccp_type, field } => {
+            let ccp_type = CcpType::parse(ccp_type)
+                .ok_or_else(|| anyhow::anyhow!("지원하지 않는 CCP 유형: {}", ccp_type))?;
+            let expr = CCP_CHECK_FIELDS
+                .iter()
+                .find(|(name, _)| *name == field)
+                .map(|(_, expr)| *expr)
+                .ok_or_else(|| anyhow::anyhow!("지원하지 않는 ccp_check_log 필드: {}", field))?;
+            (
+                format!(
+                    "SELECT check_time, {expr} FROM ccp_check_log
+                     WHERE ccp_type = ?1 AND {expr} IS NOT NULL AND (?2 IS NULL OR batch_lot_no = ?2)
+                       AND check_time BETWEEN ?3 AND ?4 AND ?5 IS NULL
+                     ORDER BY check_time, id",
+                    expr = expr
+                ),
+                Some(ccp_type.as_str().to_string()),
+                None,
+            )
+        }
+    };
+
+    let mut stmt = conn.prepare(&sql)?;
+    let measurements = stmt
+        .query_map(rusqlite::params![key, batch_lot_no, from, to, equip], |row| {
+            Ok(Measurement {
+                label: row.get(0)?,
+                value: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(measurements)
+}
+
+/// 규격 결정: 요청 지정값 → operation_param_target → param_mst (sensor_log만)
+///
+/// operation_param_target의 tolerance_min/max는 규격 하한/상한 절대값으로 해석한다.
+pub fn resolve_spec(conn: &Connection, request: &SpcRequest) -> anyhow::Result<Option<SpecLimits>> {
+    if request.lsl.is_some() || request.usl.is_some() {
+        return Ok(Some(SpecLimits {
+            lsl: request.lsl,
+            usl: request.usl,
+            target: None,
+            source: "request".to_string(),
+        }));
+    }
+
+    let SpcSource::SensorLog { param_cd, .. } = &request.source else {
+        return Ok(None);
+    };
+
+    let target = conn
+        .query_row(
+            "SELECT tolerance_min, tolerance_max, target_val FROM operation_param_target
+             WHERE param_cd = ?1 AND (?2 IS NULL OR wo_no = ?2)
+             ORDER BY id DESC LIMIT 1",
+            rusqlite::params![param_cd, request.wo_no],
+            |row| Ok((row.get::<_, Option<f64>>(0)?, row.get::<_, Option<f64>>(1)?, row.get::<_, Option<f64>>(2)?)),
+        )
+        .optional()?;
+    if let Some((lsl, usl, target)) = target.filter(|(lsl, usl, _)| lsl.is_some() || usl.is_some()) {
+        return Ok(Some(SpecLimits {
+            lsl,
+            usl,
+            target,
+            source: "operation_param_target".to_string(),
+        }));
+    }
+
+    let master = conn
+        .query_row(
+            "SELECT min_val, max_val, target_val FROM param_mst WHERE param_cd = ?1",
+            [param_cd],
+            |row| Ok((row.get::<_, Option<f64>>(0)?, row.get::<_, Option<f64>>(1)?, row.get::<_, Option<f64>>(2)?)),
+        )
+        .optional()?;
+    Ok(master
+        .filter(|(lsl, usl, _)| lsl.is_some() || usl.is_some())
+        .map(|(lsl, usl, target)| SpecLimits {
+            lsl,
+            usl,
+            target,
+            source: "param_mst".to_string(),
+        }))
+}
+
+/// 측정값 시계열 분석 (관리한계 + 공정능력 + 규칙 위반)
+pub fn analyze_measurements(
+    measure: String,
+    measurements: &[Measurement],
+    subgroup_size: usize,
+    spec: Option<SpecLimits>,
+) -> anyhow::Result<SpcAnalysis> {
+    let values: Vec<f64> = measurements.iter().map(|m| m.value).collect();
+
+    let (limits, mut points, dropped_samples) = if subgroup_size <= 1 {
+        let limits = individual_limits(&values)?;
+        let points = measurements
+            .iter()
+            .enumerate()
+            .map(|(i, m)| SpcPoint {
+                index: i,
+                label: m.label.clone(),
+                value: m.value,
+                range: (i > 0).then(|| (m.value - values[i - 1]).abs()),
+                rules: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        (limits, points, 0)
+    } else {
+        let limits = xbar_r_limits(&values, subgroup_size)?;
+        let points = measurements
+            .chunks_exact(subgroup_size)
+            .enumerate()
+            .map(|(i, group)| {
+                let group_values: Vec<f64> = group.iter().map(|m| m.value).collect();
+                SpcPoint {
+                    index: i,
+                    label: group[0].label.clone(),
+                    value: mean(&group_values),
+                    range: Some(range(&group_values)),
+                    rules: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+        (limits, points, values.len() % subgroup_size)
+    };
+
+    let plotted: Vec<f64> = points.iter().map(|p| p.value).collect();
+    let violations = nelson_rules(&plotted, limits.center_line, limits.plotted_sigma());
+    for violation in &violations {
+        let rules = &mut points[violation.point_index].rules;
+        if !rules.contains(&violation.rule) {
+            rules.push(violation.rule);
+        }
+    }
+
+    let capability = spec
+        .as_ref()
+        .and_then(|spec| process_capability(mean(&values), limits.sigma_within, spec.lsl, spec.usl));
+
+    Ok(SpcAnalysis {
+        measure,
+        sample_count: values.len(),
+        dropped_samples,
+        mean: mean(&values),
+        in_control: violations.is_empty(),
+        limits,
+        spec,
+        capability,
+        points,
+        violations,
+    })
+}
+
+/// I-MR 관리한계
+pub fn individual_limits(values: &[f64]) -> anyhow::Result<ControlLimits> {
+    if values.len() < 2 {
+        return Err(anyhow::anyhow!("I-MR 관리도는 측정값이 2개 이상 필요합니다 (현재 {}개)", values.len()));
+    }
+
+    let center = mean(values);
+    let moving_ranges: Vec<f64> = values.windows(2).map(|w| (w[1] - w[0]).abs()).collect();
+    let mr_bar = mean(&moving_ranges);
+
+    Ok(ControlLimits {
+        chart_type: ControlChartType::IndividualMovingRange,
+        subgroup_size: 1,
+        center_line: center,
+        ucl: center + IMR_E2 * mr_bar,
+        lcl: center - IMR_E2 * mr_bar,
+        range_center: mr_bar,
+        range_ucl: IMR_D4 * mr_bar,
+        range_lcl: 0.0,
+        sigma_within: mr_bar / IMR_D2,
+    })
+}
+
+/// X̄-R 관리한계 (마지막 미완성 부분군은 제외)
+pub fn xbar_r_limits(values: &[f64], subgroup_size: usize) -> anyhow::Result<ControlLimits> {
+    let &(_, a2, d3, d4, d2) = XBAR_R_CONSTANTS
+        .iter()
+        .find(|(n, ..)| *n == subgroup_size)
+        .ok_or_else(|| anyhow::anyhow!("부분군 크기는 2~10이어야 합니다 (요청: {})", subgroup_size))?;
+
+    let groups: Vec<&[f64]> = values.chunks_exact(subgroup_size).collect();
+    if groups.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "X̄-R 관리도는 부분군이 2개 이상 필요합니다 (측정값 {}개, 부분군 크기 {})",
+            values.len(),
+            subgroup_size
+        ));
+    }
+
+    let means: Vec<f64> = groups.iter().map(|g| mean(g)).collect();
+    let ranges: Vec<f64> = groups.iter().map(|g| range(g)).collect();
+    let center = mean(&means);
+    let r_bar = mean(&ranges);
+
+    Ok(ControlLimits {
+        chart_type: ControlChartType::XbarR,
+        subgroup_size,
+        center_line: center,
+        ucl: center + a2 * r_bar,
+        lcl: center - a2 * r_bar,
+        range_center: r_bar,
+        range_ucl: d4 * r_bar,
+        range_lcl: d3 * r_bar,
+        sigma_within: r_bar / d2,
+    })
+}
+
+/// 공정능력 지수 (σ = 군내 표준편차, 규격이 없거나 σ가 0이면 None)
+pub fn process_capability(mean: f64, sigma: f64, lsl: Option<f64>, usl: Option<f64>) -> Option<ProcessCapability> {
+    if sigma <= SIGMA_EPSILON {
+        return None;
+    }
+
+    let cpu = usl.map(|usl| (usl - mean) / (3.0 * sigma));
+    let cpl = lsl.map(|lsl| (mean - lsl) / (3.0 * sigma));
+    let cpk = match (cpu, cpl) {
+        (Some(u), Some(l)) => u.min(l),
+        (Some(u), None) => u,
+        (None, Some(l)) => l,
+        (None, None) => return None,
+    };
+    let cp = match (lsl, usl) {
+        (Some(lsl), Some(usl)) => Some((usl - lsl) / (6.0 * sigma)),
+        _ => None,
+    };
+
+    Some(ProcessCapability { cp, cpk, cpu, cpl })
+}
+
+/// Nelson 규칙 이름 (1~4는 Western Electric 규칙과 동일 계열)
+pub fn rule_name(rule: u8) -> &'static str {
+    match rule {
+        1 => "3σ 이탈",
+        2 => "9점 연속 중심선 한쪽 (shift)",
+        3 => "6점 연속 증가/감소 (trend)",
+        4 => "14점 연속 교대 증감",
+        5 => "3점 중 2점 2σ 초과 (같은 쪽)",
+        6 => "5점 중 4점 1σ 초과 (같은 쪽)",
+        7 => "15점 연속 1σ 이내 (층화)",
+        8 => "8점 연속 1σ 밖 (혼합)",
+        _ => "알 수 없는 규칙",
+    }
+}
+
+/// Nelson 8규칙 판정 (sigma = 관리도 통계량의 표준편차)
+pub fn nelson_rules(points: &[f64], center: f64, sigma: f64) -> Vec<RuleViolation> {
+    let mut violations = Vec::new();
+    if sigma <= SIGMA_EPSILON {
+        return violations;
+    }
+
+    let z: Vec<f64> = points.iter().map(|p| (p - center) / sigma).collect();
+    let side = |v: f64| if v > 0.0 { 1 } else if v < 0.0 { -1 } else { 0 };
+    let mut push = |rule: u8, point_index: usize| {
+        violations.push(RuleViolation {
+            rule,
+            name: rule_name(rule).to_string(),
+            point_index,
+        })
+    };
+
+    for i in 0..z.len() {
+        // 규칙 1: 3σ 밖
+        if z[i].abs() > 3.0 {
+            push(1, i);
+        }
+
+        // 규칙 2: 9점 연속 같은 쪽
+        if i >= 8 {
+            let s = side(z[i]);
+            if s != 0 && z[i - 8..=i].iter().all(|v| side(*v) == s) {
+                push(2, i);
+            }
+        }
+
+        // 규칙 3: 6점 연속 증가 또는 감소
+        if i >= 5 {
+            let window = &points[i - 5..=i];
+            if window.windows(2).all(|w| w[1] > w[0]) || window.windows(2).all(|w| w[1] < w[0]) {
+                push(3, i);
+            }
+        }
+
+        // 규칙 4: 14점 연속 교대 증감
+        if i >= 13 {
+            let diffs: Vec<i32> = points[i - 13..=i].windows(2).map(|w| side(w[1] - w[0])).collect();
+            if diffs.iter().all(|d| *d != 0) && diffs.windows(2).all(|d| d[0] != d[1]) {
+                push(4, i);
+            }
+        }
+
+        // 규칙 5: 3점 중 2점 2σ 초과 (같은 쪽)
+        if i >= 2 {
+            let window = &z[i - 2..=i];
+            for s in [1.0, -1.0] {
+                if z[i] * s > 2.0 && window.iter().filter(|v| **v * s > 2.0).count() >= 2 {
+                    push(5, i);
+                }
+            }
+        }
+
+        // 규칙 6: 5점 중 4점 1σ 초과 (같은 쪽)
+        if i >= 4 {
+            let window = &z[i - 4..=i];
+            for s in [1.0, -1.0] {
+                if z[i] * s > 1.0 && window.iter().filter(|v| **v * s > 1.0).count() >= 4 {
+                    push(6, i);
+                }
+            }
+        }
+
+        // 규칙 7: 15점 연속 1σ 이내
+        if i >= 14 && z[i - 14..=i].iter().all(|v| v.abs() < 1.0) {
+            push(7, i);
+        }
+
+        // 규칙 8: 8점 연속 1σ 밖 (양쪽 모두 포함)
+        if i >= 7 {
+            let window = &z[i - 7..=i];
+            if window.iter().all(|v| v.abs() > 1.0) && window.iter().any(|v| *v > 0.0) && window.iter().any(|v| *v < 0.0) {
+                push(8, i);
+            }
+        }
+    }
+
+    violations
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+fn range(values: &[f64]) -> f64 {
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    max - min
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurements(values: &[f64]) -> Vec<Measurement> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| Measurement {
+                label: format!("2025-11-01 09:{:02}:00", i),
+                value: *v,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_individual_limits() {
+        let limits = individual_limits(&[10.0, 12.0, 11.0, 13.0, 12.0]).unwrap();
+
+        // MR = [2, 1, 2, 1] → MR̄ = 1.5
+        assert!((limits.center_line - 11.6).abs() < 1e-9);
+        assert!((limits.range_center - 1.5).abs() < 1e-9);
+        assert!((limits.ucl - (11.6 + 2.66 * 1.5)).abs() < 1e-9);
+        assert!((limits.range_ucl - 3.267 * 1.5).abs() < 1e-9);
+        assert!((limits.sigma_within - 1.5 / 1.128).abs() < 1e-9);
+        assert!(individual_limits(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_xbar_r_limits() {
+        let values = [10.0, 12.0, 11.0, 11.0, 13.0, 12.0, 9.0, 10.0, 11.0, 99.0];
+        let limits = xbar_r_limits(&values, 3).unwrap();
+
+        // 부분군 평균 [11, 12, 10], 범위 [2, 2, 2] (마지막 1개 제외)
+        assert!((limits.center_line - 11.0).abs() < 1e-9);
+        assert!((limits.range_center - 2.0).abs() < 1e-9);
+        assert!((limits.ucl - (11.0 + 1.023 * 2.0)).abs() < 1e-9);
+        assert!((limits.sigma_within - 2.0 / 1.693).abs() < 1e-9);
+        assert!(xbar_r_limits(&values, 11).is_err());
+        assert!(xbar_r_limits(&values[..4], 3).is_err());
+    }
+
+    #[test]
+    fn test_process_capability() {
+        let capability = process_capability(85.0, 1.0, Some(80.0), Some(95.0)).unwrap();
+        assert!((capability.cp.unwrap() - 2.5).abs() < 1e-9);
+        assert!((capability.cpk - 5.0 / 3.0).abs() < 1e-9);
+
+        // 한쪽 규격: Cp 없음
+        let upper_only = process_capability(25.0, 2.0, None, Some(30.0)).unwrap();
+        assert!(upper_only.cp.is_none());
+        assert!((upper_only.cpk - 5.0 / 6.0).abs() < 1e-9);
+
+        assert!(process_capability(1.0, 0.0, Some(0.0), Some(2.0)).is_none());
+        assert!(process_capability(1.0, 1.0, None, None).is_none());
+    }
+
+    #[test]
+    fn test_nelson_rules() {
+        let rules_hit = |points: &[f64]| {
+            let mut rules: Vec<u8> = nelson_rules(points, 0.0, 1.0).iter().map(|v| v.rule).collect();
+            rules.dedup();
+            rules
+        };
+
+        assert_eq!(rules_hit(&[0.5, -0.5, 3.5]), vec![1]);
+        assert!(rules_hit(&[0.5; 9]).contains(&2));
+        assert!(rules_hit(&[-0.9, -0.5, -0.2, 0.1, 0.4, 0.8]).contains(&3));
+        assert_eq!(rules_hit(&[0.0, 2.5, 0.0, 2.2]), vec![5]);
+        assert_eq!(rules_hit(&[1.5, 1.2, 0.0, 1.8, 1.1]), vec![6]);
+
+        let alternating: Vec<f64> = (0..14).map(|i| if i % 2 == 0 { 0.5 } else { -0.5 }).collect();
+        assert!(rules_hit(&alternating).contains(&4));
+
+        let hugging: Vec<f64> = (0..15).map(|i| if i % 3 == 0 { 0.2 } else { -0.1 * (i % 3) as f64 }).collect();
+        assert!(rules_hit(&hugging).contains(&7));
+
+        let mixture = [1.5, -1.5, 1.6, -1.4, 1.3, -1.7, 1.2, -1.2];
+        assert!(rules_hit(&mixture).contains(&8));
+
+        // 표준편차 0이면 판정 불가
+        assert!(nelson_rules(&[1.0, 1.0], 1.0, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_measurements_marks_points() {
+        let mut values: Vec<f64> = (0..20).map(|i| 85.0 + if i % 2 == 0 { 0.3 } else { -0.3 }).collect();
+        values.push(95.0);
+        let spec = SpecLimits {
+            lsl: Some(80.0),
+            usl: Some(95.0),
+            target: Some(85.0),
+            source: "request".to_string(),
+        };
+
+        let analysis = analyze_measurements("sensor_log.PARAM-PAST-TEMP".to_string(), &measurements(&values), 1, Some(spec)).unwrap();
+
+        assert!(!analysis.in_control);
+        assert!(analysis.points[20].rules.contains(&1));
+        assert!(analysis.points[0].range.is_none());
+        let capability = analysis.capability.clone().unwrap();
+        assert!(capability.cp.unwrap() > capability.cpk);
+
+        let input = analysis.judgment_input();
+        assert_eq!(input["spc_in_control"], false);
+        assert_eq!(input["spc_rule_1"], true);
+        assert_eq!(input["spc_last_value"], 95.0);
+        assert!(input["spc_cpk"].is_number());
+    }
+
+    #[test]
+    fn test_spc_request_deserialize() {
+        let request: SpcRequest = serde_json::from_value(serde_json::json!({
+            "source": "ccp_check_log",
+            "ccp_type": "PASTEURIZATION",
+            "field": "actual_temp",
+            "from": "2024-09-01",
+            "to": "2024-11-30",
+            "subgroup_size": null
+        }))
+        .unwrap();
+
+        assert_eq!(
+            request.source,
+            SpcSource::CcpCheckLog {
+                ccp_type: "PASTEURIZATION".to_string(),
+                field: "actual_temp".to_string()
+            }
+        );
+        assert_eq!(request.source.measure_name(), "ccp_check_log.PASTEURIZATION.actual_temp");
+    }
+
+    #[test]
+    fn test_load_measurements_from_seed() {
+        // 시드 데이터 사용 (마이그레이션 007: 살균 CCP 점검)
+        let db = Database::new().unwrap();
+        let db_conn = db.get_connection();
+        let conn = db_conn.lock().unwrap();
+
+        let source = SpcSource::CcpCheckLog {
+            ccp_type: "PASTEURIZATION".to_string(),
+            field: "actual_temp".to_string(),
+        };
+        let measurements = load_measurements(&conn, &source, None, "2024-09-01 00:00:00", "2024-11-30 23:59:59").unwrap();
+        if measurements.len() < 2 {
+            println!("⚠️  Seed 데이터 없음 - 테스트 스킵");
+            return;
+        }
+        assert!(measurements.windows(2).all(|w| w[0].label <= w[1].label));
+
+        let invalid = SpcSource::ProcessParamLog {
+            column: "value; DROP TABLE sensor_log".to_string(),
+            equip_cd: None,
+        };
+        assert!(load_measurements(&conn, &invalid, None, "2024-01-01", "2024-12-31").is_err());
+    }
+}
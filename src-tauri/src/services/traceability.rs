@@ -0,0 +1,555 @@
+// services/traceability.rs - LOT 추적 (정방향/역방향)
+//
+// 원료 LOT → 배합 LOT(batch_lot) → 충진 LOT(filling_lot) → 완제품 LOT(fg_lot) → 출고(outbound_dtl)
+// 연결을 따라 리콜 대상 범위를 조회한다.
+// - 정방향(forward): 원료 LOT이 들어간 배치, 완제품 LOT, 출고처
+// - 역방향(backward): 완제품 LOT에 투입된 원료 LOT과 입고처(inbound_dtl)
+//
+// 원료 투입은 material_issue(lot_no)와 material_input_log(material_lot_no)를 모두 본다.
+
+use crate::database::Database;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// 추적 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceDirection {
+    /// 원료 → 완제품/출고
+    Forward,
+    /// 완제품 → 원료/입고
+    Backward,
+}
+
+impl TraceDirection {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "forward" => Some(TraceDirection::Forward),
+            "backward" => Some(TraceDirection::Backward),
+            _ => None,
+        }
+    }
+}
+
+/// 시작 LOT 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TraceLotKind {
+    Material,
+    Batch,
+    Filling,
+    Fg,
+}
+
+/// 원료 LOT (입고 이력 포함)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialLot {
+    pub lot_no: String,
+    pub item_cd: Option<String>,
+    pub item_nm: Option<String>,
+    pub receipts: Vec<InboundReceipt>,
+}
+
+/// 원료 입고 (inbound_dtl)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboundReceipt {
+    pub inbound_no: String,
+    pub inbound_date: String,
+    pub vendor_cd: String,
+    pub vendor_nm: Option<String>,
+    pub qty: f64,
+    pub inspect_result: Option<String>,
+}
+
+/// 원료 투입 (material_issue / material_input_log)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialUsage {
+    pub material_lot_no: String,
+    pub item_cd: String,
+    pub batch_lot_no: String,
+    pub qty: Option<f64>,
+    pub used_at: Option<String>,
+    pub source: String, // "material_issue" | "material_input_log"
+}
+
+/// 배합 LOT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchLotNode {
+    pub batch_lot_no: String,
+    pub prod_order_no: String,
+    pub bom_cd: String,
+    pub batch_date: String,
+    pub status: Option<String>,
+}
+
+/// 충진 LOT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillingLotNode {
+    pub filling_lot_no: String,
+    pub batch_lot_no: String,
+    pub filling_date: String,
+    pub line_cd: String,
+    pub good_qty: Option<i64>,
+}
+
+/// 완제품 LOT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FgLotNode {
+    pub fg_lot_no: String,
+    pub filling_lot_no: String,
+    pub fg_item_cd: String,
+    pub item_nm: Option<String>,
+    pub qty: i64,
+    pub mfg_date: String,
+    pub exp_date: String,
+    pub qc_status: Option<String>,
+    /// 출하 보류 중 여부 (v_lot_hold_status)
+    pub on_hold: bool,
+}
+
+/// 고객 출고 (outbound_dtl)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shipment {
+    pub outbound_no: String,
+    pub seq: i64,
+    pub fg_lot_no: String,
+    pub item_cd: String,
+    pub qty: i64,
+    pub ship_date: String,
+    pub status: Option<String>,
+    pub cust_cd: String,
+    pub cust_nm: Option<String>,
+}
+
+/// 추적 요약
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TraceSummary {
+    pub material_lot_count: usize,
+    pub batch_lot_count: usize,
+    pub filling_lot_count: usize,
+    pub fg_lot_count: usize,
+    pub fg_qty: i64,
+    pub held_fg_lot_count: usize,
+    pub shipment_count: usize,
+    pub shipped_qty: i64,
+    pub customer_count: usize,
+}
+
+/// LOT 추적 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceReport {
+    pub lot_no: String,
+    pub lot_kind: TraceLotKind,
+    pub direction: TraceDirection,
+    pub material_lots: Vec<MaterialLot>,
+    pub material_usages: Vec<MaterialUsage>,
+    pub batch_lots: Vec<BatchLotNode>,
+    pub filling_lots: Vec<FillingLotNode>,
+    pub fg_lots: Vec<FgLotNode>,
+    pub shipments: Vec<Shipment>,
+    pub summary: TraceSummary,
+}
+
+impl TraceReport {
+    /// JUDGMENT 노드 입력용 필드 (trace_ 접두사)
+    pub fn judgment_input(&self) -> serde_json::Value {
+        serde_json::json!({
+            "trace_lot_no": self.lot_no,
+            "trace_direction": self.direction,
+            "trace_material_lot_count": self.summary.material_lot_count,
+            "trace_batch_lot_count": self.summary.batch_lot_count,
+            "trace_fg_lot_count": self.summary.fg_lot_count,
+            "trace_fg_qty": self.summary.fg_qty,
+            "trace_held_fg_lot_count": self.summary.held_fg_lot_count,
+            "trace_shipment_count": self.summary.shipment_count,
+            "trace_shipped_qty": self.summary.shipped_qty,
+            "trace_customer_count": self.summary.customer_count,
+        })
+    }
+}
+
+/// LOT 추적 서비스
+pub struct TraceabilityService {
+    db: Database,
+}
+
+impl TraceabilityService {
+    pub fn new() -> anyhow::Result<Self> {
+        Ok(Self { db: Database::new()? })
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// LOT 추적 (시작 LOT 종류는 fg_lot → filling_lot → batch_lot → 원료 순으로 판별)
+    pub fn trace(&self, lot_no: &str, direction: TraceDirection) -> anyhow::Result<TraceReport> {
+        let db_conn = self.db.get_connection();
+        let conn = db_conn.lock()
+            .map_err(|e| anyhow::anyhow!("DB lock 실패: {}", e))?;
+        trace_lot(&conn, lot_no, direction)
+    }
+}
+
+/// LOT 추적
+pub fn trace_lot(conn: &Connection, lot_no: &str, direction: TraceDirection) -> anyhow::Result<TraceReport> {
+    let lot_no = lot_no.trim();
+    let lot_kind = resolve_lot_kind(conn, lot_no)?
+        .ok_or_else(|| anyhow::anyhow!("추적 대상 LOT을 찾을 수 없습니다: {}", lot_no))?;
+
+    let mut materials = BTreeSet::new();
+    let mut batches = BTreeSet::new();
+    let mut fillings = BTreeSet::new();
+    let mut fgs = BTreeSet::new();
+    match lot_kind {
+        TraceLotKind::Material => materials.insert(lot_no.to_string()),
+        TraceLotKind::Batch => batches.insert(lot_no.to_string()),
+        TraceLotKind::Filling => fillings.insert(lot_no.to_string()),
+        TraceLotKind::Fg => fgs.insert(lot_no.to_string()),
+    };
+
+    let mut usages = Vec::new();
+    match direction {
+        TraceDirection::Forward => {
+            for material in &materials {
+                usages.extend(load_usages(conn, "material_lot_no", material)?);
+            }
+            batches.extend(usages.iter().map(|u| u.batch_lot_no.clone()));
+            for batch in &batches {
+                fillings.extend(column_values(conn, "SELECT filling_lot_no FROM filling_lot WHERE batch_lot_no = ?1", batch)?);
+            }
+            for filling in &fillings {
+                fgs.extend(column_values(conn, "SELECT fg_lot_no FROM fg_lot WHERE filling_lot_no = ?1", filling)?);
+            }
+        }
+        TraceDirection::Backward => {
+            for fg in &fgs {
+                fillings.extend(column_values(conn, "SELECT filling_lot_no FROM fg_lot WHERE fg_lot_no = ?1", fg)?);
+            }
+            for filling in &fillings {
+                batches.extend(column_values(conn, "SELECT batch_lot_no FROM filling_lot WHERE filling_lot_no = ?1", filling)?);
+            }
+            for batch in &batches {
+                usages.extend(load_usages(conn, "batch_lot_no", batch)?);
+            }
+            materials.extend(usages.iter().map(|u| u.material_lot_no.clone()));
+        }
+    }
+
+    let material_lots = materials
+        .iter()
+        .map(|lot| load_material_lot(conn, lot, &usages))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let batch_lots = batches
+        .iter()
+        .filter_map(|lot| load_batch_lot(conn, lot).transpose())
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let filling_lots = fillings
+        .iter()
+        .filter_map(|lot| load_filling_lot(conn, lot).transpose())
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let fg_lots = fgs
+        .iter()
+        .filter_map(|lot| load_fg_lot(conn, lot).transpose())
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut shipments = Vec::new();
+    for fg in &fgs {
+        shipments.extend(load_shipments(conn, fg)?);
+    }
+
+    let customers: BTreeSet<&str> = shipments.iter().map(|s| s.cust_cd.as_str()).collect();
+    let summary = TraceSummary {
+        material_lot_count: material_lots.len(),
+        batch_lot_count: batch_lots.len(),
+        filling_lot_count: filling_lots.len(),
+        fg_lot_count: fg_lots.len(),
+        fg_qty: fg_lots.iter().map(|f| f.qty).sum(),
+        held_fg_lot_count: fg_lots.iter().filter(|f| f.on_hold).count(),
+        shipment_count: shipments.len(),
+        shipped_qty: shipments.iter().map(|s| s.qty).sum(),
+        customer_count: customers.len(),
+    };
+
+    println!(
+        "🔎 [TRACE] {} ({:?}, {:?}): 원료 {} / 배치 {} / 완제품 {} / 출고 {}건",
+        lot_no, lot_kind, direction, summary.material_lot_count, summary.batch_lot_count,
+        summary.fg_lot_count, summary.shipment_count
+    );
+
+    Ok(TraceReport {
+        lot_no: lot_no.to_string(),
+        lot_kind,
+        direction,
+        material_lots,
+        material_usages: usages,
+        batch_lots,
+        filling_lots,
+        fg_lots,
+        shipments,
+        summary,
+    })
+}
+
+fn resolve_lot_kind(conn: &Connection, lot_no: &str) -> anyhow::Result<Option<TraceLotKind>> {
+    let exists = |sql: &str| -> anyhow::Result<bool> { Ok(conn.prepare(sql)?.exists([lot_no])?) };
+
+    Ok(if exists("SELECT 1 FROM fg_lot WHERE fg_lot_no = ?1")? {
+        Some(TraceLotKind::Fg)
+    } else if exists("SELECT 1 FROM filling_lot WHERE filling_lot_no = ?1")? {
+        Some(TraceLotKind::Filling)
+    } else if exists("SELECT 1 FROM batch_lot WHERE batch_lot_no = ?1")? {
+        Some(TraceLotKind::Batch)
+    } else if exists(
+        "SELECT 1 FROM inbound_dtl WHERE lot_no = ?1
+         UNION ALL SELECT 1 FROM material_issue WHERE lot_no = ?1
+         UNION ALL SELECT 1 FROM material_input_log WHERE material_lot_no = ?1",
+    )? {
+        Some(TraceLotKind::Material)
+    } else {
+        None
+    })
+}
+
+fn column_values(conn: &Connection, sql: &str, key: &str) -> anyhow::Result<Vec<String>> {
+    let mut stmt = conn.prepare(sql)?;
+    let values = stmt
+        .query_map([key], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(values)
+}
+
+/// 원료 투입 이력 (key_column: "material_lot_no" | "batch_lot_no")
+fn load_usages(conn: &Connection, key_column: &str, key: &str) -> anyhow::Result<Vec<MaterialUsage>> {
+    let sql = format!(
+        "SELECT material_lot_no, item_cd, batch_lot_no, qty, used_at, source FROM (
+            SELECT lot_no AS material_lot_no, item_cd, batch_lot_no, COALESCE(actual_qty, plan_qty) AS qty,
+                   issue_time AS used_at, 'material_issue' AS source
+            FROM material_issue
+            UNION ALL
+            SELECT material_lot_no, item_cd, batch_lot_no, input_qty, input_time, 'material_input_log'
+            FROM material_input_log
+         ) WHERE {} = ?1
+         ORDER BY used_at, source",
+        match key_column {
+            "batch_lot_no" => "batch_lot_no",
+            _ => "material_lot_no",
+        }
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let usages = stmt
+        .query_map([key], |row| {
+            Ok(MaterialUsage {
+                material_lot_no: row.get(0)?,
+                item_cd: row.get(1)?,
+                batch_lot_no: row.get(2)?,
+                qty: row.get(3)?,
+                used_at: row.get(4)?,
+                source: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(usages)
+}
+
+/// 원료 LOT (입고 이력이 없으면 투입 이력의 품목코드 사용)
+fn load_material_lot(conn: &Connection, lot_no: &str, usages: &[MaterialUsage]) -> anyhow::Result<MaterialLot> {
+    let mut stmt = conn.prepare(
+        "SELECT d.inbound_no, i.inbound_date, i.vendor_cd, v.vendor_nm, d.qty, d.inspect_result, d.item_cd
+         FROM inbound_dtl d
+         JOIN inbound i ON i.inbound_no = d.inbound_no
+         LEFT JOIN vendor_mst v ON v.vendor_cd = i.vendor_cd
+         WHERE d.lot_no = ?1
+         ORDER BY i.inbound_date, d.inbound_no, d.seq",
+    )?;
+    let mut item_cd = None;
+    let receipts = stmt
+        .query_map([lot_no], |row| {
+            Ok((
+                InboundReceipt {
+                    inbound_no: row.get(0)?,
+                    inbound_date: row.get(1)?,
+                    vendor_cd: row.get(2)?,
+                    vendor_nm: row.get(3)?,
+                    qty: row.get(4)?,
+                    inspect_result: row.get(5)?,
+                },
+                row.get::<_, String>(6)?,
+            ))
+        })?
+        .map(|row| {
+            row.map(|(receipt, receipt_item)| {
+                item_cd.get_or_insert(receipt_item);
+                receipt
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let item_cd = item_cd.or_else(|| {
+        usages
+            .iter()
+            .find(|u| u.material_lot_no == lot_no)
+            .map(|u| u.item_cd.clone())
+    });
+    let item_nm = match &item_cd {
+        Some(code) => column_values(conn, "SELECT item_nm FROM item_mst WHERE item_cd = ?1", code)?
+            .into_iter()
+            .next(),
+        None => None,
+    };
+
+    Ok(MaterialLot {
+        lot_no: lot_no.to_string(),
+        item_cd,
+        item_nm,
+        receipts,
+    })
+}
+
+fn load_batch_lot(conn: &Connection, lot_no: &str) -> anyhow::Result<Option<BatchLotNode>> {
+    let mut stmt = conn.prepare(
+        "SELECT batch_lot_no, prod_order_no, bom_cd, batch_date, status FROM batch_lot WHERE batch_lot_no = ?1",
+    )?;
+    let mut rows = stmt.query_map([lot_no], |row| {
+        Ok(BatchLotNode {
+            batch_lot_no: row.get(0)?,
+            prod_order_no: row.get(1)?,
+            bom_cd: row.get(2)?,
+            batch_date: row.get(3)?,
+            status: row.get(4)?,
+        })
+    })?;
+    Ok(rows.next().transpose()?)
+}
+
+fn load_filling_lot(conn: &Connection, lot_no: &str) -> anyhow::Result<Option<FillingLotNode>> {
+    let mut stmt = conn.prepare(
+        "SELECT filling_lot_no, batch_lot_no, filling_date, line_cd, good_qty FROM filling_lot WHERE filling_lot_no = ?1",
+    )?;
+    let mut rows = stmt.query_map([lot_no], |row| {
+        Ok(FillingLotNode {
+            filling_lot_no: row.get(0)?,
+            batch_lot_no: row.get(1)?,
+            filling_date: row.get(2)?,
+            line_cd: row.get(3)?,
+            good_qty: row.get(4)?,
+        })
+    })?;
+    Ok(rows.next().transpose()?)
+}
+
+fn load_fg_lot(conn: &Connection, lot_no: &str) -> anyhow::Result<Option<FgLotNode>> {
+    let mut stmt = conn.prepare(
+        "SELECT f.fg_lot_no, f.filling_lot_no, f.fg_item_cd, m.item_nm, f.qty, f.mfg_date, f.exp_date, f.qc_status,
+                EXISTS (SELECT 1 FROM v_lot_hold_status h WHERE h.fg_lot_no = f.fg_lot_no)
+         FROM fg_lot f
+         LEFT JOIN item_mst m ON m.item_cd = f.fg_item_cd
+         WHERE f.fg_lot_no = ?1",
+    )?;
+    let mut rows = stmt.query_map([lot_no], |row| {
+        Ok(FgLotNode {
+            fg_lot_no: row.get(0)?,
+            filling_lot_no: row.get(1)?,
+            fg_item_cd: row.get(2)?,
+            item_nm: row.get(3)?,
+            qty: row.get(4)?,
+            mfg_date: row.get(5)?,
+            exp_date: row.get(6)?,
+            qc_status: row.get(7)?,
+            on_hold: row.get(8)?,
+        })
+    })?;
+    Ok(rows.next().transpose()?)
+}
+
+fn load_shipments(conn: &Connection, fg_lot_no: &str) -> anyhow::Result<Vec<Shipment>> {
+    let mut stmt = conn.prepare(
+        "SELECT d.outbound_no, d.seq, d.fg_lot_no, d.item_cd, d.qty, o.ship_date, o.status, o.cust_cd, c.cust_nm
+         FROM outbound_dtl d
+         JOIN outbound o ON o.outbound_no = d.outbound_no
+         LEFT JOIN customer_mst c ON c.cust_cd = o.cust_cd
+         WHERE d.fg_lot_no = ?1
+         ORDER BY o.ship_date, d.outbound_no, d.seq",
+    )?;
+    let shipments = stmt
+        .query_map([fg_lot_no], |row| {
+            Ok(Shipment {
+                outbound_no: row.get(0)?,
+                seq: row.get(1)?,
+                fg_lot_no: row.get(2)?,
+                item_cd: row.get(3)?,
+                qty: row.get(4)?,
+                ship_date: row.get(5)?,
+                status: row.get(6)?,
+                cust_cd: row.get(7)?,
+                cust_nm: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(shipments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::OptionalExtension;
+
+    #[test]
+    fn test_trace_direction_parse() {
+        assert_eq!(TraceDirection::parse("Forward"), Some(TraceDirection::Forward));
+        assert_eq!(TraceDirection::parse(" backward "), Some(TraceDirection::Backward));
+        assert_eq!(TraceDirection::parse("sideways"), None);
+    }
+
+    #[test]
+    fn test_forward_and_backward_trace_are_consistent() {
+        let Ok(service) = TraceabilityService::new() else {
+            println!("⚠️  DB 초기화 실패 - 테스트 스킵");
+            return;
+        };
+
+        // 시드 데이터에서 출고까지 이어지는 원료 LOT 선택
+        let start = {
+            let db_conn = service.db.get_connection();
+            let conn = db_conn.lock().unwrap();
+            conn.query_row(
+                "SELECT mi.lot_no FROM material_issue mi
+                 JOIN filling_lot fl ON fl.batch_lot_no = mi.batch_lot_no
+                 JOIN fg_lot f ON f.filling_lot_no = fl.filling_lot_no
+                 JOIN outbound_dtl d ON d.fg_lot_no = f.fg_lot_no
+                 ORDER BY mi.lot_no LIMIT 1",
+                [],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .unwrap()
+        };
+        let Some(material_lot) = start else {
+            println!("⚠️  Seed 데이터 없음 - 테스트 스킵");
+            return;
+        };
+
+        let forward = service.trace(&material_lot, TraceDirection::Forward).unwrap();
+        assert_eq!(forward.lot_kind, TraceLotKind::Material);
+        assert!(!forward.batch_lots.is_empty());
+        assert!(!forward.fg_lots.is_empty());
+        assert!(!forward.shipments.is_empty());
+        assert_eq!(forward.summary.shipment_count, forward.shipments.len());
+        assert!(forward.summary.customer_count >= 1);
+
+        // 정방향으로 찾은 완제품 LOT을 역추적하면 시작 원료 LOT이 나와야 함
+        let fg_lot = &forward.shipments[0].fg_lot_no;
+        let backward = service.trace(fg_lot, TraceDirection::Backward).unwrap();
+        assert_eq!(backward.lot_kind, TraceLotKind::Fg);
+        assert!(backward.material_lots.iter().any(|m| m.lot_no == material_lot));
+        assert!(backward
+            .batch_lots
+            .iter()
+            .any(|b| forward.batch_lots.iter().any(|f| f.batch_lot_no == b.batch_lot_no)));
+
+        let input = forward.judgment_input();
+        assert_eq!(input["trace_shipment_count"], serde_json::json!(forward.shipments.len()));
+
+        assert!(service.trace("NO-SUCH-LOT", TraceDirection::Forward).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::database::{Database, Workflow};
+use crate::services::audit_log::{self, AuditLogStore, NewAuditEntry};
 use uuid::Uuid;
 use chrono::Utc;
 
@@ -8,9 +9,11 @@ pub struct WorkflowService {
 
 impl WorkflowService {
     pub fn new() -> anyhow::Result<Self> {
-        Ok(Self {
-            db: Database::new()?,
-        })
+        Ok(Self::from_database(Database::new()?))
+    }
+
+    pub fn from_database(db: Database) -> Self {
+        Self { db }
     }
 
     pub fn create_workflow(
@@ -29,7 +32,7 @@ impl WorkflowService {
             created_at: Utc::now(),
         };
 
-        self.db.save_workflow(&workflow)?;
+        self.save_with_audit(audit_log::ACTION_WORKFLOW_CREATE, None, &workflow)?;
         Ok(workflow)
     }
 
@@ -53,6 +56,7 @@ impl WorkflowService {
             .db
             .get_workflow(&id)?
             .ok_or_else(|| anyhow::anyhow!("Workflow not found"))?;
+        let before = workflow.clone();
 
         if let Some(n) = name {
             workflow.name = n;
@@ -68,7 +72,7 @@ impl WorkflowService {
         }
 
         workflow.version += 1;
-        self.db.save_workflow(&workflow)?;
+        self.save_with_audit(audit_log::ACTION_WORKFLOW_UPDATE, Some(&before), &workflow)?;
         Ok(workflow)
     }
 
@@ -78,12 +82,27 @@ impl WorkflowService {
             .db
             .get_workflow(id)?
             .ok_or_else(|| anyhow::anyhow!("Workflow not found"))?;
+        let before = workflow.clone();
 
         workflow.is_active = false;
-        self.db.save_workflow(&workflow)?;
+        self.save_with_audit(audit_log::ACTION_WORKFLOW_DELETE, Some(&before), &workflow)?;
         Ok(())
     }
 
+    /// 워크플로우 저장 + 감사 기록 (변경 전/후 전체 스냅샷, 한 트랜잭션)
+    fn save_with_audit(&self, action: &str, before: Option<&Workflow>, after: &Workflow) -> anyhow::Result<()> {
+        let entry = NewAuditEntry {
+            actor: &audit_log::current_actor(),
+            action,
+            entity_type: "workflow",
+            entity_id: &after.id,
+            before: before.map(serde_json::to_value).transpose()?,
+            after: Some(serde_json::to_value(after)?),
+        };
+        AuditLogStore::from_database(self.db.clone())
+            .record_change(&entry, |conn| Ok(Database::upsert_workflow(conn, after)?))
+    }
+
     pub fn validate_workflow(&self, definition: &serde_json::Value) -> anyhow::Result<bool> {
         // Basic validation
         if !definition.is_object() {
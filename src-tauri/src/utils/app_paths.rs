@@ -0,0 +1,217 @@
+// 앱 데이터 경로 (DB, 백업, 예측용 DB 등) 단일 기준
+//
+// 데이터 루트 우선순위:
+// 1. JUDGIFY_DATA_DIR 환경 변수 (공유 폴더/테스트용)
+// 2. 기존 설치 경로 (`$HOME/Judgify` 등)에 DB가 있으면 그대로 사용 (업그레이드 호환)
+// 3. OS 기본 데이터 디렉토리 / Judgify (Windows: %APPDATA%\Judgify, Linux: ~/.local/share/Judgify)
+//
+// 프로필: JUDGIFY_PROFILE (기본 "default")
+// - default  → <루트>
+// - 그 외    → <루트>/profiles/<이름> (공장/라인별 DB 분리, 백업도 프로필별)
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DATA_DIR_ENV: &str = "JUDGIFY_DATA_DIR";
+pub const PROFILE_ENV: &str = "JUDGIFY_PROFILE";
+pub const DEFAULT_PROFILE: &str = "default";
+
+const APP_DIR_NAME: &str = "Judgify";
+const DB_FILE_NAME: &str = "judgify.db";
+/// 채팅 세션/메시지 DB (이전 버전은 작업 디렉토리에 생성)
+const CHAT_DB_FILE_NAME: &str = "chat_service.db";
+/// seed_data.py가 생성하는 대용량 예측 데이터 DB
+const FORECAST_DB_FILE_NAME: &str = "judgify_large.db";
+
+/// 확인된 앱 경로 (루트 + 프로필)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppPaths {
+    root: PathBuf,
+    profile: String,
+}
+
+impl AppPaths {
+    /// 환경 변수/OS 기본값으로 경로 결정 (디렉토리는 만들지 않음)
+    pub fn resolve() -> Result<Self> {
+        let profile = std::env::var(PROFILE_ENV)
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        Self::with_root(Self::resolve_root()?, &profile)
+    }
+
+    /// 루트/프로필 지정 (프로필명은 영문/숫자/`-`/`_`만 허용)
+    pub fn with_root(root: PathBuf, profile: &str) -> Result<Self> {
+        let profile = profile.trim();
+        let valid = !profile.is_empty()
+            && profile.len() <= 64
+            && profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(anyhow::anyhow!(
+                "프로필 이름이 올바르지 않습니다 (영문/숫자/-/_ 64자 이내): {:?}",
+                profile
+            ));
+        }
+        Ok(Self { root, profile: profile.to_string() })
+    }
+
+    fn resolve_root() -> Result<PathBuf> {
+        if let Some(dir) = std::env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            return Ok(PathBuf::from(dir));
+        }
+
+        let default_root = dirs::data_dir().map(|dir| dir.join(APP_DIR_NAME));
+        if let Some(root) = &default_root {
+            if root.join(DB_FILE_NAME).exists() {
+                return Ok(root.clone());
+            }
+        }
+
+        // 이전 버전은 $APPDATA 또는 $HOME 바로 아래 Judgify 폴더를 사용
+        let legacy_root = std::env::var_os("APPDATA")
+            .or_else(|| std::env::var_os("HOME"))
+            .map(|dir| PathBuf::from(dir).join(APP_DIR_NAME));
+        if let Some(root) = legacy_root.filter(|root| root.join(DB_FILE_NAME).exists()) {
+            return Ok(root);
+        }
+
+        default_root
+            .or_else(|| dirs::home_dir().map(|dir| dir.join(APP_DIR_NAME)))
+            .context("앱 데이터 디렉토리를 찾을 수 없습니다 (JUDGIFY_DATA_DIR을 설정하세요)")
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// 현재 프로필 데이터 디렉토리
+    pub fn data_dir(&self) -> PathBuf {
+        if self.profile == DEFAULT_PROFILE {
+            self.root.clone()
+        } else {
+            self.root.join("profiles").join(&self.profile)
+        }
+    }
+
+    pub fn db_path(&self) -> PathBuf {
+        self.data_dir().join(DB_FILE_NAME)
+    }
+
+    /// 백업 디렉토리 (BackupManager 기본값과 동일: DB 옆 backups)
+    pub fn backup_dir(&self) -> PathBuf {
+        self.data_dir().join("backups")
+    }
+
+    pub fn chat_db_path(&self) -> PathBuf {
+        self.data_dir().join(CHAT_DB_FILE_NAME)
+    }
+
+    /// 보존 정책으로 옮긴 월별 아카이브 디렉토리
+    pub fn archive_dir(&self) -> PathBuf {
+        self.data_dir().join("archives")
+    }
+
+    /// 예측용 대용량 DB (없으면 메인 DB 사용)
+    pub fn forecast_db_path(&self) -> PathBuf {
+        let forecast_db = self.data_dir().join(FORECAST_DB_FILE_NAME);
+        if forecast_db.exists() {
+            forecast_db
+        } else {
+            self.db_path()
+        }
+    }
+
+    /// 프로필 데이터 디렉토리 생성
+    pub fn ensure_data_dir(&self) -> Result<PathBuf> {
+        let dir = self.data_dir();
+        fs::create_dir_all(&dir)
+            .context(format!("데이터 디렉토리 생성 실패: {}", dir.display()))?;
+        Ok(dir)
+    }
+
+    /// 루트 아래 프로필 목록 (default 포함)
+    pub fn list_profiles(&self) -> Result<Vec<String>> {
+        let mut profiles = vec![DEFAULT_PROFILE.to_string()];
+        let profiles_dir = self.root.join("profiles");
+        if profiles_dir.is_dir() {
+            for entry in fs::read_dir(&profiles_dir).context("프로필 디렉토리 읽기 실패")? {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        if name != DEFAULT_PROFILE {
+                            profiles.push(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+        profiles[1..].sort();
+        Ok(profiles)
+    }
+}
+
+/// 현재 프로필 데이터 디렉토리 (없으면 생성)
+pub fn data_dir() -> Result<PathBuf> {
+    AppPaths::resolve()?.ensure_data_dir()
+}
+
+/// 현재 프로필 메인 DB 경로 (디렉토리 생성 포함)
+pub fn db_path() -> Result<PathBuf> {
+    let paths = AppPaths::resolve()?;
+    paths.ensure_data_dir()?;
+    Ok(paths.db_path())
+}
+
+/// 현재 프로필 채팅 DB 경로 (작업 디렉토리에 남은 이전 버전 DB는 최초 1회 복사)
+pub fn chat_db_path() -> Result<PathBuf> {
+    let paths = AppPaths::resolve()?;
+    paths.ensure_data_dir()?;
+    let path = paths.chat_db_path();
+
+    let legacy = PathBuf::from(CHAT_DB_FILE_NAME);
+    if !path.exists() && legacy.is_file() {
+        fs::copy(&legacy, &path)
+            .context(format!("이전 채팅 DB 복사 실패: {}", legacy.display()))?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_profile_directories() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path().to_path_buf();
+
+        let default = AppPaths::with_root(root.clone(), DEFAULT_PROFILE)?;
+        assert_eq!(default.db_path(), root.join("judgify.db"));
+        assert_eq!(default.backup_dir(), root.join("backups"));
+        assert_eq!(default.chat_db_path(), root.join("chat_service.db"));
+
+        let line2 = AppPaths::with_root(root.clone(), "line-2")?;
+        assert_eq!(line2.db_path(), root.join("profiles").join("line-2").join("judgify.db"));
+        line2.ensure_data_dir()?;
+        AppPaths::with_root(root.clone(), "a_plant")?.ensure_data_dir()?;
+        assert_eq!(default.list_profiles()?, vec!["default", "a_plant", "line-2"]);
+
+        // 예측 DB가 없으면 메인 DB
+        assert_eq!(line2.forecast_db_path(), line2.db_path());
+        fs::write(line2.data_dir().join("judgify_large.db"), b"")?;
+        assert_eq!(line2.forecast_db_path(), line2.data_dir().join("judgify_large.db"));
+
+        // 경로 탈출/빈 이름 거부
+        assert!(AppPaths::with_root(root.clone(), "../other").is_err());
+        assert!(AppPaths::with_root(root, " ").is_err());
+
+        Ok(())
+    }
+}
@@ -1,18 +1,199 @@
 use crate::utils::openai::OpenAIClient;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 오프라인 해시 임베딩 기본 차원
+const DEFAULT_HASHING_DIMENSIONS: usize = 256;
+
+/// 임베딩 백엔드 (JUDGIFY_EMBEDDING_BACKEND 환경변수로 선택)
+///
+/// - `openai`: OpenAI text-embedding-3-small (OPENAI_API_KEY 필요)
+/// - `ollama`: 로컬 Ollama 임베딩 모델 (JUDGIFY_OLLAMA_URL, JUDGIFY_EMBEDDING_MODEL)
+/// - `hashing`: 네트워크 없이 동작하는 피처 해싱 임베딩
+pub enum EmbeddingBackend {
+    OpenAI(OpenAIClient),
+    Ollama {
+        client: Client,
+        base_url: String,
+        model: String,
+    },
+    Hashing {
+        dimensions: usize,
+    },
+}
+
+impl EmbeddingBackend {
+    /// 환경변수 기반 백엔드 선택 (미지정시 OpenAI 키가 있으면 openai, 없으면 hashing)
+    pub fn from_env() -> anyhow::Result<Self> {
+        let backend = std::env::var("JUDGIFY_EMBEDDING_BACKEND").unwrap_or_else(|_| {
+            if std::env::var("OPENAI_API_KEY").is_ok() {
+                "openai".to_string()
+            } else {
+                "hashing".to_string()
+            }
+        });
+
+        match backend.to_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAI(OpenAIClient::new()?)),
+            "ollama" => Ok(Self::Ollama {
+                client: Client::new(),
+                base_url: std::env::var("JUDGIFY_OLLAMA_URL")
+                    .unwrap_or_else(|_| "http://localhost:11434".to_string()),
+                model: std::env::var("JUDGIFY_EMBEDDING_MODEL")
+                    .unwrap_or_else(|_| "nomic-embed-text".to_string()),
+            }),
+            "hashing" => Ok(Self::Hashing {
+                dimensions: DEFAULT_HASHING_DIMENSIONS,
+            }),
+            other => Err(anyhow::anyhow!("Unknown embedding backend: {}", other)),
+        }
+    }
+
+    /// 저장된 임베딩 구분용 모델 식별자 (백엔드가 바뀌면 재임베딩)
+    ///
+    /// 해시 임베딩은 해시 함수/토큰화가 바뀌면 버전을 올려 기존 벡터를 재임베딩한다.
+    pub fn model_id(&self) -> String {
+        match self {
+            Self::OpenAI(_) => "openai:text-embedding-3-small".to_string(),
+            Self::Ollama { model, .. } => format!("ollama:{}", model),
+            Self::Hashing { dimensions } => format!("hashing-v1:{}", dimensions),
+        }
+    }
+
+    /// 네트워크 호출 없이 계산되는 백엔드인지 (대량 재임베딩을 요청 중에 해도 되는지)
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Hashing { .. })
+    }
+
+    pub async fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        match self {
+            Self::OpenAI(client) => client.create_embedding(text).await,
+            Self::Ollama { client, base_url, model } => {
+                #[derive(Serialize)]
+                struct OllamaRequest<'a> {
+                    model: &'a str,
+                    prompt: &'a str,
+                }
+
+                #[derive(Deserialize)]
+                struct OllamaResponse {
+                    embedding: Vec<f32>,
+                }
+
+                let response = client
+                    .post(format!("{}/api/embeddings", base_url.trim_end_matches('/')))
+                    .json(&OllamaRequest { model, prompt: text })
+                    .send()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Ollama embedding request failed: {}", e))?
+                    .error_for_status()?
+                    .json::<OllamaResponse>()
+                    .await?;
+
+                if response.embedding.is_empty() {
+                    return Err(anyhow::anyhow!("Ollama returned an empty embedding"));
+                }
+                Ok(response.embedding)
+            }
+            Self::Hashing { dimensions } => Ok(hashing_embedding(text, *dimensions)),
+        }
+    }
+}
+
+/// 피처 해싱 임베딩: "key: value" 줄 단위 토큰을 고정 차원 벡터로 투영
+///
+/// 숫자 값은 유효숫자 2자리로 구간화하여 가까운 값끼리 같은 버킷에 들어가도록 한다.
+/// 저장된 벡터와 비교하므로 툴체인과 무관하게 고정된 SHA-256을 해시로 사용한다.
+fn hashing_embedding(text: &str, dimensions: usize) -> Vec<f32> {
+    let mut vector = vec![0.0f32; dimensions.max(1)];
+
+    let mut add = |feature: &str, weight: f32| {
+        let digest = Sha256::digest(feature.as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        let hash = u64::from_le_bytes(bytes);
+        let index = (hash % vector.len() as u64) as usize;
+        // 상위 비트로 부호 결정 (해시 충돌 상쇄)
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[index] += sign * weight;
+    };
+
+    for line in text.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => ("", line.trim()),
+        };
+
+        if !key.is_empty() {
+            add(&format!("k:{}", key), 1.0);
+        }
+
+        if let Ok(number) = value.parse::<f64>() {
+            add(&format!("n:{}={}", key, bucket_number(number)), 2.0);
+        } else {
+            for token in value.split_whitespace() {
+                add(&format!("t:{}={}", key, token.to_lowercase()), 1.0);
+            }
+        }
+    }
+
+    vector
+}
+
+/// 유효숫자 2자리 구간 (예: 87.3 → 87, 1234 → 1200)
+fn bucket_number(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return "0".to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let scale = 10f64.powi(magnitude - 1);
+    format!("{}", (value / scale).round() * scale)
+}
 
 pub struct EmbeddingGenerator {
-    client: OpenAIClient,
+    backend: EmbeddingBackend,
 }
 
 impl EmbeddingGenerator {
     pub fn new() -> anyhow::Result<Self> {
         Ok(Self {
-            client: OpenAIClient::new()?,
+            backend: EmbeddingBackend::from_env()?,
         })
     }
 
+    pub fn with_backend(backend: EmbeddingBackend) -> Self {
+        Self { backend }
+    }
+
+    pub fn model_id(&self) -> String {
+        self.backend.model_id()
+    }
+
+    pub fn is_local(&self) -> bool {
+        self.backend.is_local()
+    }
+
     pub async fn generate(&self, text: &str) -> anyhow::Result<Vec<f32>> {
-        self.client.create_embedding(text).await
+        self.backend.embed(text).await
+    }
+
+    /// JSON 입력 데이터를 임베딩용 텍스트로 정규화 ("key: value" 줄, 키 정렬)
+    pub fn canonical_text(input_data: &serde_json::Value) -> String {
+        match input_data {
+            serde_json::Value::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                keys.into_iter()
+                    .map(|key| match &map[key] {
+                        serde_json::Value::String(s) => format!("{}: {}", key, s),
+                        other => format!("{}: {}", key, other),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            other => other.to_string(),
+        }
     }
 
     pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
@@ -45,7 +226,63 @@ impl EmbeddingGenerator {
             })
             .collect();
 
-        similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        similarities.sort_by(|a, b| b.1.total_cmp(&a.1));
         similarities.into_iter().take(top_k).collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_text_sorts_keys() {
+        let text = EmbeddingGenerator::canonical_text(&json!({"vibration": 42, "temperature": 88, "line": "A"}));
+        assert_eq!(text, "line: A\ntemperature: 88\nvibration: 42");
+    }
+
+    #[test]
+    fn test_hashing_embedding_prefers_closer_inputs() {
+        let query = hashing_embedding(&EmbeddingGenerator::canonical_text(&json!({"temperature": 88, "vibration": 42})), 256);
+        let near = hashing_embedding(&EmbeddingGenerator::canonical_text(&json!({"temperature": 88.2, "vibration": 42})), 256);
+        let far = hashing_embedding(&EmbeddingGenerator::canonical_text(&json!({"temperature": 60, "vibration": 12})), 256);
+
+        assert_eq!(query.len(), 256);
+        assert!(
+            EmbeddingGenerator::cosine_similarity(&query, &near)
+                > EmbeddingGenerator::cosine_similarity(&query, &far)
+        );
+    }
+
+    #[test]
+    fn test_hashing_embedding_is_stable() {
+        // 저장된 벡터와 호환되어야 하므로 해시 결과가 바뀌면 실패해야 함 (바뀌면 model_id 버전을 올릴 것)
+        let embedding = hashing_embedding("temperature: 88", 8);
+        assert_eq!(embedding, vec![0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_bucket_number() {
+        assert_eq!(bucket_number(87.3), "87");
+        assert_eq!(bucket_number(1234.0), "1200");
+        assert_eq!(bucket_number(0.0), "0");
+    }
+
+    #[test]
+    fn test_find_most_similar_orders_by_similarity() {
+        let generator = EmbeddingGenerator::with_backend(EmbeddingBackend::Hashing { dimensions: 4 });
+        let ranked = generator.find_most_similar(
+            &[1.0, 0.0, 0.0, 0.0],
+            vec![
+                ("orthogonal".to_string(), vec![0.0, 1.0, 0.0, 0.0]),
+                ("same".to_string(), vec![2.0, 0.0, 0.0, 0.0]),
+            ],
+            1,
+        );
+
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].0, "same");
+        assert_eq!(generator.model_id(), "hashing-v1:4");
+    }
+}
@@ -3,3 +3,4 @@ pub mod openai;
 pub mod embeddings;
 pub mod claude;
 pub mod security;
+pub mod app_paths;
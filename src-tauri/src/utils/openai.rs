@@ -52,6 +52,11 @@ impl OpenAIClient {
             .json::<EmbeddingResponse>()
             .await?;
 
-        Ok(response.data[0].embedding.clone())
+        response
+            .data
+            .into_iter()
+            .next()
+            .map(|d| d.embedding)
+            .ok_or_else(|| anyhow::anyhow!("OpenAI returned no embedding data"))
     }
 }
@@ -20,6 +20,7 @@ import { ErrorBoundary } from './components/ErrorBoundary'
 import { Toaster } from './components/ui/toaster'
 import { Skeleton } from './components/ui/skeleton'
 import OfflineDetector from './components/OfflineDetector'
+import { toast } from './components/ui/use-toast'
 
 const queryClient = new QueryClient({
   defaultOptions: {
@@ -85,6 +86,27 @@ function AnimatedRoutes() {
     }
   }, [navigate])
 
+  // 자동 백업 실패 알림 (backup_scheduler의 backup-alert 이벤트)
+  useEffect(() => {
+    const listenForBackupAlerts = async () => {
+      const { listen } = await import('@tauri-apps/api/event')
+      return listen<{ trigger: string | null; message: string }>('backup-alert', (event) => {
+        toast({
+          variant: 'destructive',
+          title: '자동 백업 실패',
+          description: event.payload.message,
+        })
+      })
+    }
+
+    let unlisten: (() => void) | null = null
+    listenForBackupAlerts().then((fn) => { unlisten = fn })
+
+    return () => {
+      if (unlisten) unlisten()
+    }
+  }, [])
+
   return (
     <AnimatePresence mode="wait">
       <Routes location={location} key={location.pathname}>
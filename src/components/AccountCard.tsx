@@ -0,0 +1,190 @@
+import { useState } from 'react';
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
+import { getAuthStatus, createInitialAdmin, login, logout } from '@/lib/tauri-api-wrapper';
+import type { UserRole } from '@/lib/tauri-api';
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/components/ui/card';
+import { Label } from '@/components/ui/label';
+import { Input } from '@/components/ui/input';
+import { Button } from '@/components/ui/button';
+import { Badge } from '@/components/ui/badge';
+import { LogIn, LogOut, UserCircle } from 'lucide-react';
+
+const ROLE_LABELS: Record<UserRole, string> = {
+  operator: '작업자',
+  qa: '품질 담당',
+  supervisor: '관리 감독자',
+  admin: '관리자',
+};
+
+/**
+ * 계정 카드
+ *
+ * 사용자가 없으면 첫 관리자 생성, 있으면 로그인/로그아웃.
+ * 승인, 워크플로우 삭제, 백업 복구 등 권한이 필요한 기능은 로그인 세션이 있어야 동작한다.
+ */
+export default function AccountCard() {
+  const queryClient = useQueryClient();
+  const [username, setUsername] = useState('');
+  const [displayName, setDisplayName] = useState('');
+  const [password, setPassword] = useState('');
+  const [passwordConfirm, setPasswordConfirm] = useState('');
+
+  const { data: authStatus, isLoading } = useQuery({
+    queryKey: ['auth-status'],
+    queryFn: getAuthStatus,
+    // 세션 만료(유휴 시간 초과)를 반영
+    refetchInterval: 60000,
+  });
+
+  const resetForm = () => {
+    setUsername('');
+    setDisplayName('');
+    setPassword('');
+    setPasswordConfirm('');
+  };
+
+  const onAuthChanged = () => {
+    resetForm();
+    queryClient.invalidateQueries({ queryKey: ['auth-status'] });
+  };
+
+  const createAdminMutation = useMutation({
+    mutationFn: () => createInitialAdmin(username.trim(), displayName.trim(), password),
+    onSuccess: () => {
+      onAuthChanged();
+      alert('✅ 관리자 계정이 생성되었습니다. 로그인된 상태입니다.');
+    },
+    onError: (error) => {
+      alert('❌ ' + error);
+    },
+  });
+
+  const loginMutation = useMutation({
+    mutationFn: () => login(username.trim(), password),
+    onSuccess: onAuthChanged,
+    onError: (error) => {
+      setPassword('');
+      alert('❌ 로그인 실패: ' + error);
+    },
+  });
+
+  const logoutMutation = useMutation({
+    mutationFn: logout,
+    onSuccess: onAuthChanged,
+    onError: (error) => {
+      alert('❌ 로그아웃 실패: ' + error);
+    },
+  });
+
+  const handleCreateAdmin = () => {
+    if (password !== passwordConfirm) {
+      alert('❌ 비밀번호 확인이 일치하지 않습니다.');
+      return;
+    }
+    createAdminMutation.mutate();
+  };
+
+  const isSetup = authStatus !== undefined && !authStatus.has_users;
+  const user = authStatus?.user ?? null;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <UserCircle className="w-5 h-5" />
+          계정
+        </CardTitle>
+        <CardDescription>
+          {isSetup
+            ? '등록된 사용자가 없습니다. 첫 관리자 계정을 만드세요.'
+            : '승인, 워크플로우 삭제, 백업 복구 등은 권한이 있는 계정으로 로그인해야 합니다.'}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        {isLoading ? (
+          <p className="text-sm text-muted-foreground">계정 정보를 불러오는 중...</p>
+        ) : user ? (
+          <div className="flex items-center justify-between p-3 rounded-lg border">
+            <div className="flex items-center gap-2">
+              <span className="text-sm font-medium">{user.display_name || user.username}</span>
+              <span className="text-xs text-muted-foreground font-mono">{user.username}</span>
+              <Badge variant="secondary">{ROLE_LABELS[user.role]}</Badge>
+            </div>
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => logoutMutation.mutate()}
+              disabled={logoutMutation.isPending}
+            >
+              <LogOut className="w-4 h-4 mr-2" />
+              로그아웃
+            </Button>
+          </div>
+        ) : (
+          <form
+            className="space-y-3"
+            onSubmit={(e) => {
+              e.preventDefault();
+              if (isSetup) {
+                handleCreateAdmin();
+              } else {
+                loginMutation.mutate();
+              }
+            }}
+          >
+            <div>
+              <Label htmlFor="account-username">아이디</Label>
+              <Input
+                id="account-username"
+                value={username}
+                onChange={(e) => setUsername(e.target.value)}
+                autoComplete="username"
+              />
+            </div>
+            {isSetup && (
+              <div>
+                <Label htmlFor="account-display-name">이름</Label>
+                <Input
+                  id="account-display-name"
+                  value={displayName}
+                  onChange={(e) => setDisplayName(e.target.value)}
+                />
+              </div>
+            )}
+            <div>
+              <Label htmlFor="account-password">비밀번호</Label>
+              <Input
+                id="account-password"
+                type="password"
+                value={password}
+                onChange={(e) => setPassword(e.target.value)}
+                autoComplete={isSetup ? 'new-password' : 'current-password'}
+              />
+            </div>
+            {isSetup && (
+              <div>
+                <Label htmlFor="account-password-confirm">비밀번호 확인</Label>
+                <Input
+                  id="account-password-confirm"
+                  type="password"
+                  value={passwordConfirm}
+                  onChange={(e) => setPasswordConfirm(e.target.value)}
+                  autoComplete="new-password"
+                />
+              </div>
+            )}
+            <Button
+              type="submit"
+              disabled={
+                !username.trim() || !password || createAdminMutation.isPending || loginMutation.isPending
+              }
+            >
+              <LogIn className="w-4 h-4 mr-2" />
+              {isSetup ? '관리자 생성' : '로그인'}
+            </Button>
+          </form>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -11,12 +11,16 @@ import { Textarea } from '@/components/ui/textarea'
  * - 불량률 통계 조회
  * - 재고 현황 조회
  * - 작업 이력 조회
+ * - LOT 추적 (원료 → 완제품/출고, 리콜 모의훈련)
  */
 
 interface QueryFormProps {
   config: {
-    dataSource?: 'database' | 'api' | 'sensor' | 'file'
+    dataSource?: 'database' | 'api' | 'sensor' | 'file' | 'traceability'
     queryType?: 'sql' | 'rest' | 'graphql'
+    direction?: 'forward' | 'backward'
+    lotNo?: string
+    lotField?: string
     query?: string
     parameters?: string
     resultMapping?: string
@@ -46,10 +50,55 @@ export default function QueryForm({ config, onChange }: QueryFormProps) {
             <SelectItem value="api">외부 API</SelectItem>
             <SelectItem value="sensor">센서 데이터</SelectItem>
             <SelectItem value="file">파일 시스템</SelectItem>
+            <SelectItem value="traceability">LOT 추적</SelectItem>
           </SelectContent>
         </Select>
       </div>
 
+      {/* LOT 추적 설정 (traceability인 경우) */}
+      {config.dataSource === 'traceability' && (
+        <>
+          <div className="space-y-2">
+            <Label htmlFor="direction">추적 방향</Label>
+            <Select
+              value={config.direction || 'forward'}
+              onValueChange={(value) => updateConfig('direction', value)}
+            >
+              <SelectTrigger id="direction">
+                <SelectValue placeholder="추적 방향 선택" />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="forward">정방향 (원료 → 완제품/출고처)</SelectItem>
+                <SelectItem value="backward">역방향 (완제품 → 원료/입고처)</SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
+
+          <div className="space-y-2">
+            <Label htmlFor="lotNo">LOT 번호</Label>
+            <Input
+              id="lotNo"
+              placeholder="예: RM001-240805-01"
+              value={config.lotNo || ''}
+              onChange={(e) => updateConfig('lotNo', e.target.value)}
+            />
+          </div>
+
+          <div className="space-y-2">
+            <Label htmlFor="lotField">LOT 입력 필드 (LOT 번호 미지정 시)</Label>
+            <Input
+              id="lotField"
+              placeholder="lot_no"
+              value={config.lotField || ''}
+              onChange={(e) => updateConfig('lotField', e.target.value)}
+            />
+            <p className="text-xs text-muted-foreground">
+              결과의 trace_fg_lot_count, trace_shipment_count 등을 판단 규칙에 사용
+            </p>
+          </div>
+        </>
+      )}
+
       {/* 쿼리 타입 (database인 경우) */}
       {config.dataSource === 'database' && (
         <div className="space-y-2">
@@ -71,23 +120,25 @@ export default function QueryForm({ config, onChange }: QueryFormProps) {
       )}
 
       {/* 쿼리 */}
-      <div className="space-y-2">
-        <Label htmlFor="query">쿼리</Label>
-        <Textarea
-          id="query"
-          placeholder={
-            config.dataSource === 'database'
-              ? "SELECT * FROM equipment WHERE temperature > {threshold}"
-              : "https://api.example.com/sensors/{sensorId}/data"
-          }
-          value={config.query || ''}
-          onChange={(e) => updateConfig('query', e.target.value)}
-          className="min-h-[100px] font-mono text-sm"
-        />
-        <p className="text-xs text-muted-foreground">
-          파라미터는 &#123;변수명&#125; 형식으로 사용
-        </p>
-      </div>
+      {config.dataSource !== 'traceability' && (
+        <div className="space-y-2">
+          <Label htmlFor="query">쿼리</Label>
+          <Textarea
+            id="query"
+            placeholder={
+              config.dataSource === 'database'
+                ? "SELECT * FROM equipment WHERE temperature > {threshold}"
+                : "https://api.example.com/sensors/{sensorId}/data"
+            }
+            value={config.query || ''}
+            onChange={(e) => updateConfig('query', e.target.value)}
+            className="min-h-[100px] font-mono text-sm"
+          />
+          <p className="text-xs text-muted-foreground">
+            파라미터는 &#123;변수명&#125; 형식으로 사용
+          </p>
+        </div>
+      )}
 
       {/* 파라미터 */}
       <div className="space-y-2">
@@ -156,18 +156,31 @@ describe('tauri-api', () => {
     });
 
     it('extractRules - Rule 추출 성공', async () => {
-      const mockRules = [
-        'if temperature > 80 then alert',
-        'if vibration > 50 then warning',
-      ];
+      const mockReport = {
+        workflow_id: 'workflow-123',
+        sample_count: 20,
+        folds: 5,
+        extractors: [
+          { method: 'frequency', candidates: [], holdout_accuracy: 0.9, error: null },
+          { method: 'decision_tree', candidates: [], holdout_accuracy: 0.7, error: null },
+        ],
+        selected_rule: {
+          expression: 'temperature > 80',
+          confidence: 0.9,
+          method: 'integrated',
+          support_count: 18,
+          total_count: 20,
+        },
+        message: 'Extracted Rule: temperature > 80',
+      };
 
-      vi.mocked(invoke).mockResolvedValue(mockRules);
+      vi.mocked(invoke).mockResolvedValue(mockReport);
 
       const result = await extractRules('workflow-123');
 
-      expect(invoke).toHaveBeenCalledWith('extract_rules', { workflowId: 'workflow-123' });
-      expect(result).toHaveLength(2);
-      expect(result[0]).toContain('temperature');
+      expect(invoke).toHaveBeenCalledWith('extract_rules', { workflowId: 'workflow-123', minF1: undefined });
+      expect(result.extractors).toHaveLength(2);
+      expect(result.selected_rule?.expression).toContain('temperature');
     });
   });
 
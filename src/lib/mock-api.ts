@@ -18,8 +18,10 @@ import type {
   CreateWorkflowRequest,
   WorkflowResponse,
   SystemStatus,
+  AuthStatus,
   SystemStats,
   TokenMetrics,
+  RuleExtractionReport,
 } from './tauri-api';
 
 // ===========================
@@ -100,14 +102,54 @@ export const getFewShotSamples = async (
   return mockSamples;
 };
 
-export const extractRules = async (workflowId: string): Promise<string[]> => {
+export const extractRules = async (workflowId: string): Promise<RuleExtractionReport> => {
   console.warn('[Mock API] extractRules called for:', workflowId);
 
-  return [
-    'temperature > 85 AND vibration > 45 → result = true',
-    'temperature < 70 → result = false',
-    'vibration > 50 → result = true (confidence: 0.8)',
-  ];
+  const rule = {
+    expression: 'temperature > 85 && vibration > 45',
+    confidence: 0.86,
+    method: 'integrated',
+    feature_importance: null,
+    support_count: 42,
+    total_count: 50,
+  };
+
+  const metrics = {
+    true_positive: 5,
+    false_positive: 1,
+    true_negative: 4,
+    false_negative: 0,
+    errors: 0,
+    accuracy: 0.9,
+    precision: 0.83,
+    recall: 1.0,
+    f1: 0.91,
+  };
+
+  return {
+    workflow_id: workflowId,
+    sample_count: 50,
+    train_size: 40,
+    test_size: 10,
+    folds: 5,
+    extractors: [
+      {
+        method: 'frequency',
+        candidates: [{ rule: { ...rule, method: 'frequency', confidence: 0.81 }, cv_score: 0.86, folds_rediscovered: 5, holdout: metrics }],
+        holdout_accuracy: 0.84,
+        error: null,
+      },
+      { method: 'decision_tree', candidates: [], holdout_accuracy: 0.62, error: null },
+      { method: 'llm', candidates: [], holdout_accuracy: null, error: 'ANTHROPIC_API_KEY not configured' },
+    ],
+    selected_rule: rule,
+    selected_metrics: metrics,
+    min_f1: 0.8,
+    promoted: true,
+    rule_version: 2,
+    report_id: 'mock-report-1',
+    message: `Extracted Rule: ${rule.expression} (cv score: 0.86, holdout F1: 0.91) - Saved to workflow ${workflowId} v2`,
+  };
 };
 
 // ===========================
@@ -357,6 +399,16 @@ export const exportDatabase = async (exportPath: string): Promise<void> => {
   await new Promise(resolve => setTimeout(resolve, 100));
 };
 
+// ===========================
+// Auth API Mocks
+// ===========================
+
+export const getAuthStatus = async (): Promise<AuthStatus> => {
+  console.warn('[Mock API] getAuthStatus called');
+  // Mock 환경에서는 계정 기능 없음 (로그인 폼만 표시)
+  return { has_users: true, user: null };
+};
+
 // ===========================
 // Token Metrics API Mocks
 // ===========================
@@ -89,6 +89,12 @@ export const getSystemStats = api.getSystemStats;
 export const getDataDirectory = api.getDataDirectory;
 export const exportDatabase = api.exportDatabase;
 
+// Auth API
+export const getAuthStatus = api.getAuthStatus;
+export const createInitialAdmin = api.createInitialAdmin;
+export const login = api.login;
+export const logout = api.logout;
+
 // Token Metrics API
 export const getTokenMetrics = api.getTokenMetrics;
 
@@ -108,6 +114,8 @@ export type {
   WorkflowResponse,
   SystemStatus,
   SystemStats,
+  AuthStatus,
+  UserAccount,
   TokenMetrics,
   // Chart Types
   ChartType,
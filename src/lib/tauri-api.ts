@@ -43,8 +43,77 @@ export const getFewShotSamples = (
 ): Promise<any[]> =>
   invoke('get_few_shot_samples', { request: { workflow_id: workflowId, limit } });
 
-export const extractRules = (workflowId: string): Promise<string[]> =>
-  invoke('extract_rules', { workflowId });
+export interface ExtractedRule {
+  expression: string;
+  confidence: number;
+  method: string;
+  feature_importance?: Record<string, number> | null;
+  support_count: number;
+  total_count: number;
+}
+
+export interface RuleMetrics {
+  true_positive: number;
+  false_positive: number;
+  true_negative: number;
+  false_negative: number;
+  errors: number;
+  accuracy: number;
+  precision: number;
+  recall: number;
+  f1: number;
+}
+
+export interface ValidatedRule {
+  rule: ExtractedRule;
+  cv_score: number;
+  folds_rediscovered: number;
+  holdout?: RuleMetrics | null;
+}
+
+export interface ExtractorReport {
+  method: 'frequency' | 'decision_tree' | 'llm';
+  candidates: ValidatedRule[];
+  holdout_accuracy: number | null;
+  error: string | null;
+}
+
+export interface RuleExtractionReport {
+  workflow_id: string;
+  sample_count: number;
+  train_size: number;
+  test_size: number;
+  folds: number;
+  extractors: ExtractorReport[];
+  selected_rule: ExtractedRule | null;
+  selected_metrics: RuleMetrics | null;
+  min_f1: number;
+  promoted: boolean;
+  rule_version: number | null;
+  report_id: string | null;
+  message: string;
+}
+
+export interface RuleEvaluationRecord {
+  id: string;
+  workflow_id: string;
+  workflow_version: number;
+  rule_expression: string | null;
+  promoted: boolean;
+  f1_score: number | null;
+  min_f1: number;
+  report_json: string;
+  created_at: string;
+}
+
+export const extractRules = (workflowId: string, minF1?: number): Promise<RuleExtractionReport> =>
+  invoke('extract_rules', { workflowId, minF1 });
+
+export const getRuleEvaluationReports = (
+  workflowId: string,
+  limit?: number
+): Promise<RuleEvaluationRecord[]> =>
+  invoke('get_rule_evaluation_reports', { workflowId, limit });
 
 // BI API
 export interface BiInsightResponse {
@@ -147,8 +216,198 @@ export const getSystemStats = (): Promise<SystemStats> =>
 export const getDataDirectory = (): Promise<string> =>
   invoke('get_data_directory');
 
-export const exportDatabase = (exportPath: string): Promise<void> =>
-  invoke('export_database', { exportPath });
+export const exportDatabase = (exportPath: string, passphrase?: string): Promise<void> =>
+  invoke('export_database', { exportPath, passphrase });
+
+export interface DemoDataReport {
+  loaded: string[];
+  already_loaded: string[];
+  conflicts: string[];
+}
+
+export const loadDemoData = (): Promise<DemoDataReport> =>
+  invoke('load_demo_data');
+
+// Data Retention API (테이블별 보존 기간 / 월별 압축 아카이브)
+export type RetentionAction = 'keep_all' | 'delete' | 'archive';
+
+export interface TableRetentionRule {
+  action: RetentionAction;
+  keep_days: number;
+}
+
+export interface DataRetentionPolicy {
+  enabled: boolean;
+  judgments: TableRetentionRule;
+  workflow_executions: TableRetentionRule;
+  chat_messages: TableRetentionRule;
+  sensor_log: TableRetentionRule;
+  mes_data_logs: TableRetentionRule;
+  haccp_retention_days: number;
+  vacuum_after_run: boolean;
+}
+
+export interface TableRetentionResult {
+  table: string;
+  action: RetentionAction | null;
+  cutoff: string | null;
+  archived: number;
+  deleted: number;
+  haccp_preserved: number;
+  archive_files: string[];
+}
+
+export interface RetentionReport {
+  ran_at: string;
+  tables: TableRetentionResult[];
+  vacuumed: boolean;
+}
+
+export interface RetentionStatus {
+  last_run_at: string | null;
+  last_report: RetentionReport | null;
+  last_error: string | null;
+}
+
+export interface ArchiveFile {
+  table: string;
+  month: string;
+  path: string;
+  size_bytes: number;
+}
+
+export interface ArchiveQueryResult {
+  columns: string[];
+  rows: unknown[][];
+  truncated: boolean;
+}
+
+export const getRetentionPolicy = (): Promise<DataRetentionPolicy> =>
+  invoke('get_retention_policy');
+
+export const updateRetentionPolicy = (policy: DataRetentionPolicy): Promise<DataRetentionPolicy> =>
+  invoke('update_retention_policy', { policy });
+
+export const getRetentionStatus = (): Promise<RetentionStatus> =>
+  invoke('get_retention_status');
+
+export const runRetentionNow = (): Promise<RetentionReport> =>
+  invoke('run_retention_now');
+
+export const listArchives = (): Promise<ArchiveFile[]> =>
+  invoke('list_archives');
+
+export const queryArchive = (table: string, month: string, sql: string): Promise<ArchiveQueryResult> =>
+  invoke('query_archive', { table, month, sql });
+
+// Audit Log API (추가 전용 해시 체인 감사 로그)
+export interface AuditEntry {
+  seq: number;
+  occurred_at: string;
+  actor: string;
+  action: string;
+  entity_type: string;
+  entity_id: string;
+  before_json: string | null;
+  after_json: string | null;
+  prev_hash: string;
+  hash: string;
+}
+
+export interface AuditQuery {
+  entity_type?: string;
+  entity_id?: string;
+  actor?: string;
+  from?: string;
+  to?: string;
+  limit?: number;
+}
+
+export interface AuditProblem {
+  seq: number;
+  message: string;
+}
+
+export interface AuditVerification {
+  valid: boolean;
+  checked: number;
+  last_seq: number | null;
+  last_hash: string | null;
+  problems: AuditProblem[];
+}
+
+export interface AuditExportReport {
+  path: string;
+  entries: number;
+  verification: AuditVerification;
+}
+
+export const listAuditLog = (query?: AuditQuery): Promise<AuditEntry[]> =>
+  invoke('list_audit_log', { query });
+
+export const verifyAuditLog = (): Promise<AuditVerification> =>
+  invoke('verify_audit_log');
+
+export const exportAuditLog = (exportPath: string): Promise<AuditExportReport> =>
+  invoke('export_audit_log', { exportPath });
+
+// Auth API (로컬 사용자 / 역할 기반 권한)
+export type UserRole = 'operator' | 'qa' | 'supervisor' | 'admin';
+
+export interface UserAccount {
+  id: string;
+  username: string;
+  display_name: string;
+  role: UserRole;
+  is_active: boolean;
+  last_login_at: string | null;
+  created_at: string;
+}
+
+export interface AuthStatus {
+  has_users: boolean;
+  user: UserAccount | null;
+}
+
+export interface NewUser {
+  username: string;
+  display_name: string;
+  role: UserRole;
+  password: string;
+}
+
+export interface UserUpdate {
+  display_name?: string;
+  role?: UserRole;
+  is_active?: boolean;
+}
+
+export const getAuthStatus = (): Promise<AuthStatus> =>
+  invoke('get_auth_status');
+
+export const createInitialAdmin = (username: string, displayName: string, password: string): Promise<UserAccount> =>
+  invoke('create_initial_admin', { username, displayName, password });
+
+export const login = (username: string, password: string): Promise<UserAccount> =>
+  invoke('login', { username, password });
+
+export const logout = (): Promise<void> =>
+  invoke('logout');
+
+export const changePassword = (currentPassword: string, newPassword: string): Promise<void> =>
+  invoke('change_password', { currentPassword, newPassword });
+
+export const listUsers = (): Promise<UserAccount[]> =>
+  invoke('list_users');
+
+export const createUser = (user: NewUser): Promise<UserAccount> =>
+  invoke('create_user', { user });
+
+export const updateUser = (userId: string, update: UserUpdate): Promise<UserAccount> =>
+  invoke('update_user', { userId, update });
+
+export const resetUserPassword = (userId: string, newPassword: string): Promise<void> =>
+  invoke('reset_user_password', { userId, newPassword });
 
 // Token Metrics API
 export interface TokenMetrics {
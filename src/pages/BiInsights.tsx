@@ -16,12 +16,10 @@ export default function BiInsights() {
     async function loadApiKey() {
       try {
         const { invoke } = await import('@tauri-apps/api/tauri');
-        const apiKey = await invoke<string>('load_api_key');
-        if (apiKey) {
+        // load_api_key가 Rust 환경변수까지 설정 (bi_service.rs가 사용)
+        const maskedKey = await invoke<string>('load_api_key');
+        if (maskedKey) {
           console.log('[BiInsights] API key loaded from system keychain');
-
-          // Rust 환경변수에도 설정 (bi_service.rs가 사용)
-          await invoke('save_api_key', { apiKey });
         }
       } catch (error) {
         console.error('[BiInsights] Failed to load API key from keychain:', error);
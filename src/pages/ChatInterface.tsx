@@ -48,6 +48,9 @@ interface Message {
   chartData?: ChartResponse;
 }
 
+// MES RAG 업로드 허용 확장자 (백엔드 mes_ingest::SourceFormat과 동일)
+const MES_UPLOAD_EXTENSIONS = ['.csv', '.xlsx', '.xls', '.parquet'];
+
 // Y축 숫자 압축 포맷터 (K: 천, M: 백만, B: 10억)
 const formatYAxisValue = (value: number): string => {
   if (value >= 1000000000) {
@@ -705,7 +708,14 @@ export default function ChatInterface() {
   const navigate = useNavigate();
 
   // MES RAG 상태
-  const [mesSessionId] = useState<string>(() => crypto.randomUUID()); // MES 세션 ID (고정)
+  // MES 세션 ID (업로드 데이터 조회 범위, 새로고침 후에도 유지)
+  const [mesSessionId] = useState<string>(() => {
+    const saved = localStorage.getItem('mes-session-id');
+    if (saved) return saved;
+    const created = crypto.randomUUID();
+    localStorage.setItem('mes-session-id', created);
+    return created;
+  });
   const [uploadedFile, setUploadedFile] = useState<{ name: string; rowCount: number } | null>(null);
   const [isUploading, setIsUploading] = useState(false);
   const fileInputRef = useRef<HTMLInputElement>(null);
@@ -730,14 +740,12 @@ export default function ChatInterface() {
     async function loadApiKey() {
       try {
         const { invoke } = await import('@tauri-apps/api/tauri');
-        const apiKey = await invoke<string>('load_api_key');
-        if (apiKey) {
+        // load_api_key가 Rust 환경변수까지 설정 (chat_service.rs가 사용), 반환값은 마스킹된 키
+        const maskedKey = await invoke<string>('load_api_key');
+        if (maskedKey) {
           console.log('[ChatInterface] API key loaded from system keychain');
-          setClaudeApiKey(apiKey);
+          setClaudeApiKey(maskedKey);
           setIsApiKeyConfigured(true); // API 키 로드 성공시 상태 업데이트
-
-          // Rust 환경변수에도 설정 (chat_service.rs가 사용)
-          await invoke('save_api_key', { apiKey });
         }
       } catch (error) {
         console.error('[ChatInterface] Failed to load API key from keychain:', error);
@@ -1227,13 +1235,13 @@ export default function ChatInterface() {
     }, 100);
   };
 
-  // MES RAG: CSV 파일 업로드 핸들러
+  // MES RAG: CSV/xlsx/Parquet 파일 업로드 핸들러
   const handleFileSelect = async (file: File) => {
-    if (!file.name.endsWith('.csv')) {
+    if (!MES_UPLOAD_EXTENSIONS.some((ext) => file.name.toLowerCase().endsWith(ext))) {
       toast({
         variant: 'destructive',
         title: '지원하지 않는 파일 형식',
-        description: 'CSV 파일만 업로드 가능합니다.',
+        description: 'CSV, Excel(xlsx/xls), Parquet 파일만 업로드 가능합니다.',
       });
       return;
     }
@@ -1426,7 +1434,7 @@ export default function ChatInterface() {
           {/* File Upload Button */}
           <input
             type="file"
-            accept=".csv"
+            accept={MES_UPLOAD_EXTENSIONS.join(',')}
             ref={fileInputRef}
             onChange={(e) => {
               const file = e.target.files?.[0];
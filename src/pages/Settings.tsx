@@ -7,6 +7,7 @@ import { Input } from '@/components/ui/input';
 import { Button } from '@/components/ui/button';
 import { Badge } from '@/components/ui/badge';
 import { Switch } from '@/components/ui/switch';
+import AccountCard from '@/components/AccountCard';
 import {
   Select,
   SelectContent,
@@ -133,12 +134,10 @@ export default function Settings() {
 
   const loadApiKeyFromSystem = async () => {
     try {
-      const apiKey = await invoke<string>('load_api_key');
-      if (apiKey) {
-        // 원본 API 키 보관
-        originalApiKeyRef.current = apiKey;
-        // 마스킹 처리 (보안)
-        const maskedKey = apiKey.substring(0, 10) + '...' + apiKey.substring(apiKey.length - 10);
+      // 백엔드는 마스킹된 값만 반환 (원본 키는 keychain에만 보관)
+      const maskedKey = await invoke<string>('load_api_key');
+      if (maskedKey) {
+        originalApiKeyRef.current = '';
         setClaudeKey(maskedKey);
         console.log('✅ API 키가 시스템 keychain에서 로드되었습니다.');
       }
@@ -154,6 +153,12 @@ export default function Settings() {
       const isAlreadyMasked = claudeKey.includes('...');
       const apiKeyToSave = isAlreadyMasked ? originalApiKeyRef.current : claudeKey;
 
+      // 저장된 키를 그대로 둔 경우 (마스킹 값은 다시 저장할 수 없음)
+      if (isAlreadyMasked && !apiKeyToSave) {
+        alert('ℹ️ 저장된 API 키가 그대로 유지됩니다. 변경하려면 새 키를 입력하세요.');
+        return;
+      }
+
       // 빈 값 체크
       if (!apiKeyToSave) {
         alert('❌ API 키를 입력해주세요.');
@@ -282,6 +287,9 @@ export default function Settings() {
         </CardContent>
       </Card>
 
+      {/* Account */}
+      <AccountCard />
+
       {/* Auto Update */}
       <Card>
         <CardHeader>